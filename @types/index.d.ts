@@ -9,23 +9,379 @@ declare global {
       lts: string | false;
       date: string;
       v8: string;
+      // missing from indexes of other sources that don't publish them
+      openssl?: string;
+      // the release fixes vulnerabilities
+      security?: boolean;
       files: string[];
     }
 
     type Versions = Array<Version>;
 
+    interface VersionQuery {
+      // beginning of the version (`20.1`) or part of the LTS codename
+      search?: string;
+      // LTS releases only
+      lts?: boolean;
+      sort?: "version" | "date";
+      order?: "asc" | "desc";
+      // from 1
+      page?: number;
+      pageSize?: number;
+    }
+
+    interface VersionPage {
+      items: Versions;
+      // matching the query, across all the pages
+      total: number;
+      page: number;
+      pageSize: number;
+    }
+
+    // The releases of a major
+    interface ReleaseLine {
+      major: number;
+      // LTS codename, e.g. `Iron`, once the line entered LTS
+      codename?: string;
+      latest: string;
+      latestLts?: string;
+      // newest first
+      versions: string[];
+    }
+
+    // An installed version with the toolchain it ships, from the release index
+    interface InstalledVersion {
+      version: string;
+      // the npm installed in the version, which differs from the bundled one once upgraded
+      npm?: string;
+      bundledNpm?: string;
+      v8?: string;
+      openssl?: string;
+      lts?: string | false;
+      security?: boolean;
+      date?: string;
+      // unpacked by hand and adopted by a scan
+      source?: "manual";
+    }
+
+    interface DownloadMetadata {
+      version: string;
+      arch: string;
+      // release file fetched for this platform, e.g. `node-v20.11.0-linux-x64.tar.gz`
+      file: string;
+      // in bytes
+      size?: number;
+      // sha256 from SHASUMS256.txt
+      checksum?: string;
+    }
+
+    type ProgressPhase =
+      | "downloading"
+      | "extracting"
+      // building from source
+      | "configuring"
+      | "building"
+      | "installing";
+
     interface ProgressData {
       percent: number;
       transferred: number;
       total: number;
+      // smoothed download speed in bytes per second
+      speed?: number;
+      // estimated seconds remaining, `-1` when unknown
+      eta?: number;
+      phase?: ProgressPhase;
+      // archive entries extracted so far, while `extracting`
+      entries?: number;
+      // `0` when the archive doesn't tell it up front
+      totalEntries?: number;
     }
 
+    interface VerifyResult {
+      version: string;
+      valid: boolean;
+      // expected files that are not present
+      missing: string[];
+      // files whose content no longer matches the cached checksum
+      mismatched: string[];
+      // whether a cached checksum was available for the integrity check
+      checked: boolean;
+    }
+
+    // Another Node.js version manager found on the machine
+    interface NodeManager {
+      name: string;
+      path: string;
+    }
+
+    type SetupStepId = "detect" | "mirror" | "directory" | "shims" | "shell" | "install-lts";
+
+    interface SetupStepResult {
+      step: SetupStepId;
+      // `skipped` when there was nothing to do, e.g. on a second run
+      status: "running" | "done" | "skipped" | "failed";
+      // what was found or changed: manager names, mirror URL, files edited, version
+      detail?: string;
+      error?: string;
+    }
+
+    interface SetupPlan {
+      managers: NodeManager[];
+      // the mirror presets from the fastest, none when the policy sets the mirror
+      mirrors: MirrorTest[];
+      // proposed mirror, the fastest that answered
+      mirror?: string;
+      directory: { path: string; exists: boolean };
+      // the shims directory is on the PATH of new shells
+      shellConfigured: boolean;
+      lts?: { version: string; installed: boolean };
+    }
+
+    // A version checked by the refresh of all installed versions
+    interface RefreshedVersion {
+      version: string;
+      reinstalled: boolean;
+      // why it was reinstalled
+      reason?: "missing-files" | "modified" | "release-checksum";
+    }
+
+    interface RefreshProgress {
+      version: string;
+      stage: "checking" | "reinstalling" | "done" | "failed";
+      // versions done (or failed) so far, out of `total`
+      completed: number;
+      total: number;
+    }
+
+    type ProxyProtocol = "http" | "socks5" | "socks5h";
+
     interface Proxy {
       enabled: boolean;
+      // `http` when not set
+      protocol?: ProxyProtocol;
       ip?: string;
       port?: string;
+      // hosts reached without the proxy, `no_proxy` style: `*.corp.com, 10.0.0.0/8`
+      noProxy?: string;
+    }
+
+    type ActivationMode = "shim" | "symlink";
+
+    type InstallStage =
+      | "queued"
+      // waiting for the network, see `pausedFor`
+      | "paused"
+      | "downloading"
+      | "extracting"
+      | "verifying"
+      | "linking"
+      | "done"
+      | "failed"
+      | "aborted";
+
+    interface InstallError {
+      // stage the install was in when it failed
+      stage: InstallStage;
+      code?: string;
+      message: string;
+    }
+
+    interface InstallEvent {
+      id: string;
+      version: string;
+      stage: InstallStage;
+      at: string;
+      // set when `stage` is `paused`
+      pausedFor?: NetworkPauseReason;
+      // set when `stage` is `failed` or `aborted`
+      error?: InstallError;
+    }
+
+    // why downloads and background fetches wait
+    type NetworkPauseReason = "offline" | "metered";
+
+    interface NetworkState {
+      online: boolean;
+      // as told by the OS, never on macOS
+      metered: boolean;
+      // a VPN tunnel is up
+      vpn: boolean;
+      // last change
+      at: string;
+      // the `pauseOnMetered` setting
+      pauseOnMetered?: boolean;
+    }
+
+    // An install the app quit in the middle of, as journaled
+    interface InterruptedInstall {
+      version: string;
+      arch: string;
+      source?: boolean;
+      location?: string;
+      // last stage reached
+      stage: InstallStage;
+      startAt: string;
+      // bytes downloaded by then, only shown: the archive is streamed into the
+      // extraction, so a retry downloads it again from the start
+      transferred?: number;
+      total?: number;
+    }
+
+    interface InstallState extends ActiveInstall {
+      // set once the install is over
+      endAt?: string;
+      error?: InstallError;
+    }
+
+    interface ActiveInstall {
+      id: string;
+      version: string;
+      arch: string;
+      stage: InstallStage;
+      startAt: string;
+      // last progress reported, undefined until the download starts
+      progress?: ProgressData;
+      pausedFor?: NetworkPauseReason;
+    }
+
+    interface AppInfo {
+      version: string;
+      // `date` of the bundle, ISO
+      build: { commit: string; date?: string };
+      packaged: boolean;
+      os: string;
+      osRelease: string;
+      arch: string;
+      // differs from `arch` when the app runs emulated
+      nativeArch: string;
+      electron: string;
+      chrome: string;
+      node: string;
+      paths: AppPaths & { install: string };
+      // version of the `nvmd` shim, absent when it's missing or doesn't answer
+      shim?: string;
+    }
+
+    interface AppPaths {
+      // whether the XDG base directories are used (Linux)
+      xdg: boolean;
+      // `~/.nvmd`, read by the shim
+      home: string;
+      bin: string;
+      config: string;
+      state: string;
+      cache: string;
+      logs: string;
+      downloads?: string;
+      setting: string;
+      projects: string;
+      groups: string;
+    }
+
+    interface ActivationInfo {
+      mode: ActivationMode;
+      // `~/.nvmd/current`, pointing to the global version in symlink mode
+      link: string;
+      // directory to add to PATH in symlink mode
+      binPath: string;
+      target?: string;
+      // whether links can be created in the app directory
+      linkable: boolean;
+    }
+
+    interface VersionUsage {
+      version: string;
+      // `group` is set for projects using the version through their group
+      projects: { name: string; path: string; group?: string }[];
+      groups: string[];
+    }
+
+    interface OrphanedVersion {
+      version: string;
+      path: string;
+      size: number;
+      // best effort, from the access time of the node executable
+      lastUsedAt?: string;
+      locked: boolean;
+    }
+
+    interface DiskUsage {
+      version: string;
+      // where the files are, outside of the install directory when `linked`
+      path: string;
+      size: number;
+      linked: boolean;
+    }
+
+    // moving the version folders, or linking them from the new directory
+    type MigrateMode = "move" | "link";
+
+    // A problem that didn't stop the operation
+    interface CommandWarning {
+      message: string;
+      // what it's about, e.g. a version or a path
+      subject?: string;
+    }
+
+    // Result of operations that can partially succeed
+    interface CommandResult<T> {
+      data: T;
+      warnings: CommandWarning[];
+    }
+
+    interface MigrateResult {
+      migrated: string[];
+      // already present in the new directory
+      skipped: string[];
+      failed: { version: string; message: string }[];
+      // whether the default version resolves in the new directory
+      current?: { version: string; valid: boolean };
+    }
+
+    type RollbackOperation = "cleanup" | "migration" | "relocation";
+
+    // Config files and installed versions before an operation, to roll it back
+    interface RollbackPoint {
+      id: string;
+      operation: RollbackOperation;
+      createAt: string;
+      // install directory at the time
+      directory: string;
+      // names of the config files copied, those missing didn't exist yet
+      files: string[];
+      // installed versions and where their files really were
+      versions: { version: string; location: string }[];
+      // default version
+      current?: string;
     }
 
+    interface RollbackResult {
+      operation: RollbackOperation;
+      createAt: string;
+      // config files put back or removed
+      restored: string[];
+      // versions linked back into the install directory
+      relinked: string[];
+      // versions whose files are nowhere to be found anymore
+      missing: string[];
+    }
+
+    type UpdateChannel = "stable" | "beta";
+
+    interface UpdateDownload {
+      // bytes of the installer
+      size: number;
+      // bytes fetched, fewer than `size` for a delta update
+      transferred: number;
+      // only the blocks that changed since the installed release were fetched
+      differential: boolean;
+      // the delta failed, the full installer was fetched instead
+      fallback: boolean;
+    }
+
+    type LogLevel = "error" | "warn" | "info" | "verbose" | "debug";
+
     interface Setting {
       locale: string;
       theme: Themes;
@@ -33,6 +389,415 @@ declare global {
       directory: string;
       mirror: string;
       proxy: Proxy;
+      logLevel?: LogLevel;
+      // consent to submit crash reports, they are always kept locally
+      crashReport?: boolean;
+      // anonymous usage events, recorded to a local queue only
+      telemetry?: boolean;
+      // move uninstalled versions and removed `.nvmdrc` files to the trash
+      trash?: boolean;
+      // read the nodejs entry of asdf/mise `.tool-versions` when there's no `.nvmdrc`
+      toolVersions?: boolean;
+      // also write the version to `engines.node` of the project's `package.json`
+      syncEngines?: boolean;
+      // project paths inside this folder (`~/dev`) are stored relative to it
+      projectsBase?: string;
+      // start with the OS session, in the tray
+      launchAtLogin?: boolean;
+      // don't show the main window on launch, only the tray icon
+      startHidden?: boolean;
+      updateChannel?: UpdateChannel;
+      // where archives are downloaded and extracted before being moved to `directory`
+      cacheDirectory?: string;
+      // maximum number of parallel file operations in batch updates and scans
+      concurrency?: number;
+      // editor executable, or a command line containing `{path}`
+      editor?: string;
+      // how the global version is activated: nvmd shims, or a `current` symlink on PATH
+      activation?: ActivationMode;
+      // per-user (home directory) or per-machine (system-wide, elevated) installs
+      installScope?: "user" | "machine";
+      // URL of the release index when the mirror doesn't serve `index.json` itself,
+      // e.g. when `mirror` is a URL template
+      indexUrl?: string;
+      // additional CA certificates trusted for downloads
+      tls?: {
+        // PEM file with one or more certificates
+        caFile?: string;
+        // trust the root certificates of the OS as well
+        systemStore?: boolean;
+      };
+      // build versions from the source tarball instead of downloading binaries
+      buildFromSource?: boolean;
+      shortcuts?: Shortcuts;
+      // periodic removal of leftover download and build folders from the cache
+      cacheCleanup?: CacheCleanup;
+      // download the archive of the newest LTS release to the cache while idle
+      prefetchLts?: boolean;
+      // don't download on a metered connection, installs wait for another one
+      pauseOnMetered?: boolean;
+      // how far the version of each group is behind the newest of its line
+      freshness?: FreshnessOptions;
+      // how often the install progress is reported
+      progress?: ProgressOptions;
+      // MB of free space under which the install volume is reported as low, 1024 by default
+      lowDiskThreshold?: number;
+      // other mirrors downloaded from after a checksum mismatch, 2 by default
+      checksumRetries?: number;
+      // npm cache folder shared by all the versions, written to their global npmrc
+      npmCache?: string;
+      // POST a signed JSON payload on version events, the secret is stored apart
+      webhook?: Webhook;
+      // additional places to list and download versions from
+      sources?: SourceManifest[];
+      // name of the source in use, the official index when unset
+      source?: string;
+    }
+
+    type CompletionShell = "bash" | "zsh" | "fish" | "powershell";
+
+    type PromptTool = "starship" | "oh-my-posh";
+
+    // where the version in effect in a folder comes from
+    type PromptSource = "project" | "folder" | "global" | "none";
+
+    interface PromptInfo {
+      cwd: string;
+      source: PromptSource;
+      // resolved version, `spec` when it can't be resolved from the cached data
+      version?: string;
+      spec?: string;
+      installed: boolean;
+      // name of the tracked project, for `project`
+      project?: string;
+      // what `nvmd prompt` prints, empty without any version
+      text: string;
+    }
+
+    // Machine-wide restrictions set by the administrators
+    interface Policy {
+      // the only mirror downloads are made from
+      mirror?: string;
+      // majors that can't be installed
+      forbiddenMajors?: number[];
+      // refuse downloads that can't be checked against a published checksum
+      enforceChecksums?: boolean;
+      disableTelemetry?: boolean;
+    }
+
+    interface SourceManifest {
+      name: string;
+      // URL of the release index
+      index: string;
+      // base URL or `{version}/{filename}` template of the release files, which
+      // must come with a `SHASUMS256.txt` like on nodejs.org
+      mirror: string;
+      // for indexes in another format
+      mapping?: SourceMapping;
+    }
+
+    // Where the releases and their fields are in an index, as dot separated paths
+    // (`data.releases`, `dist.tag`). Unset fields are read under their own name.
+    interface SourceMapping {
+      // the index itself when unset
+      releases?: string;
+      fields?: Partial<Record<keyof Version, string>>;
+    }
+
+    // `use` is a change of the global version, `project-version` is sent for each project
+    // whose `.nvmdrc` changed, also through its group, a bulk move or a relocation
+    type WebhookEvent = "install" | "uninstall" | "use" | "project-version" | "ping";
+
+    interface Webhook {
+      enabled?: boolean;
+      url?: string;
+      // all the events when empty
+      events?: WebhookEvent[];
+    }
+
+    interface FreshnessOptions {
+      // computed daily unless false
+      enabled?: boolean;
+      // a system notification when a group fell behind
+      notify?: boolean;
+    }
+
+    interface GroupFreshness {
+      group: string;
+      version: string;
+      // newest release of the same major
+      latest: string;
+      // releases of the line newer than `version`
+      behind: number;
+      // end of life date of the line, once passed
+      eol?: string;
+      message: string;
+    }
+
+    interface ReleaseLineSchedule {
+      major: number;
+      codename?: string;
+      // YYYY-MM-DD
+      start: string;
+      // active LTS, even majors only
+      lts?: string;
+      maintenance?: string;
+      end: string;
+      // at the time of the request
+      status: "pending" | "current" | "active" | "maintenance" | "end-of-life";
+    }
+
+    interface ReleaseSchedule {
+      fetchedAt: string;
+      // newest first
+      lines: ReleaseLineSchedule[];
+    }
+
+    interface FreshnessReport {
+      at: string;
+      groups: GroupFreshness[];
+    }
+
+    interface CacheCleanup {
+      enabled?: boolean;
+      // default 7 days
+      maxAgeDays?: number;
+      // oldest entries are removed first above this size
+      maxSizeMB?: number;
+    }
+
+    interface CacheCleanupRun {
+      at: string;
+      removed: string[];
+      // bytes
+      freed: number;
+    }
+
+    interface CacheCleanupStatus {
+      enabled: boolean;
+      nextRunAt?: string;
+      lastRun?: CacheCleanupRun;
+    }
+
+    interface PrefetchedArchive {
+      version: string;
+      path: string;
+      at: string;
+    }
+
+    interface PrefetchStatus {
+      enabled: boolean;
+      // a download is in progress
+      running: boolean;
+      // since launch
+      last?: PrefetchedArchive;
+    }
+
+    // milliseconds
+    interface BenchmarkTimes {
+      mean: number;
+      median: number;
+      min: number;
+      max: number;
+    }
+
+    interface BenchmarkResult {
+      version: string;
+      runs: number;
+      // `node -e ""`, from spawn to exit
+      startup?: BenchmarkTimes;
+      script?: BenchmarkTimes;
+      error?: string;
+    }
+
+    interface Sandbox {
+      // its version folder, `22.0.0-experiment`
+      version: string;
+      // the release it was installed from
+      base: string;
+      name: string;
+      createdAt: string;
+    }
+
+    // A folder moved out of the install directory by the launch scan
+    interface QuarantinedEntry {
+      // folder name in the quarantine
+      entry: string;
+      // folder name it had in the install directory
+      name: string;
+      // for version folders, what to reinstall
+      version?: string;
+      at: string;
+      reason: "incomplete" | "leftover" | "checksum";
+    }
+
+    // Versions unpacked by hand into the install directory, found by a scan
+    interface AdoptedInstalls {
+      // renamed after their version, `name` is the folder they had
+      adopted: { name: string; version: string; arch: string }[];
+      // left in place, out of the installed versions
+      failed: { name: string; version: string; message: string }[];
+    }
+
+    // A download didn't match its checksum
+    interface ChecksumMismatch {
+      // operation of the install
+      id: string;
+      version: string;
+      arch: string;
+      mirror: string;
+      // 1 for the first download
+      attempt: number;
+      // quarantine entry of the download
+      quarantined?: string;
+      // mirror downloaded from next, none when the install fails
+      retryMirror?: string;
+    }
+
+    interface ProgressOptions {
+      // milliseconds between two progress events, 300 by default
+      interval?: number;
+      // bytes downloaded between two progress events
+      minBytes?: number;
+      // every sample is reported and logged
+      verbose?: boolean;
+    }
+
+    type ErrorKind =
+      | "aborted"
+      | "network"
+      | "tls"
+      | "not-found"
+      | "permission"
+      | "disk-space"
+      // a config file was written by another program in between
+      | "conflict"
+      | "failed";
+
+    // What a command rejects with, `kind` and `code` for the UI to branch on
+    interface CommandError {
+      kind: ErrorKind;
+      // errno or library code, e.g. `ENOSPC`, `ERR_GOT_REQUEST_ERROR`
+      code?: string;
+      message: string;
+      // `channel` of the command, and `path` or `host` when known
+      context?: Record<string, string>;
+    }
+
+    interface NpmCacheInfo {
+      path: string;
+      // set in the settings, npm's default folder otherwise
+      shared: boolean;
+      // bytes
+      size: number;
+    }
+
+    // Global shortcuts, as Electron accelerators e.g. `CommandOrControl+Alt+N`
+    interface Shortcuts {
+      toggleWindow?: string;
+      quickSwitch?: string;
+      // each switching the global version to a favorite version
+      versions?: { accelerator: string; version: string }[];
+    }
+
+    interface TelemetryEvent {
+      name: "launch" | "install" | "uninstall" | "switch";
+      data: Record<string, string | number | boolean>;
+      platform: string;
+      arch: string;
+      createAt: string;
+    }
+
+    // `custom` has no `url` unless the mirror in use is not one of the presets
+    interface MirrorPreset {
+      id: string;
+      name: string;
+      url?: string;
+      active?: boolean;
+    }
+
+    interface MirrorTest {
+      url: string;
+      ok: boolean;
+      // milliseconds the mirror took to answer
+      time?: number;
+      error?: string;
+    }
+
+    interface MirrorSample {
+      at: string;
+      kind: "test" | "download";
+      ok: boolean;
+      // milliseconds the test or download took
+      time?: number;
+    }
+
+    // Health of a mirror over the last days, from its tests and downloads
+    interface MirrorStats {
+      url: string;
+      samples: number;
+      failures: number;
+      // from 0 to 1
+      successRate: number;
+      // milliseconds, of the requests that succeeded
+      medianTime?: number;
+      lastFailureAt?: string;
+      // enough requests failed to avoid it
+      flaky: boolean;
+    }
+
+    // Free space of the install volume, in bytes. `projected` is what the running
+    // installs will take once extracted, the volume is `low` when what's left after
+    // them is under `threshold`.
+    interface DiskStatus {
+      path: string;
+      free: number;
+      projected: number;
+      threshold: number;
+      low: boolean;
+    }
+
+    // Result of checking one setting, `message` says what was found either way
+    interface SettingCheck {
+      field: "mirror" | "proxy" | "directory" | "disk-space";
+      ok: boolean;
+      message: string;
+    }
+
+    type PathScope = "user" | "machine";
+
+    // A PATH rewritten by the repair, entries as stored (`%SystemRoot%` unexpanded)
+    interface PathChange {
+      scope: PathScope;
+      before: string[];
+      after: string[];
+      // empty, or repeating the machine PATH
+      removed: string[];
+      added: string[];
+    }
+
+    interface PathRepair {
+      changes: PathChange[];
+      // machine PATH entries with a node of their own, which win over the shims
+      shadowing: string[];
+    }
+
+    // One check of the diagnostics bundle, `detail` describes the problem found
+    interface DiagnosticCheck {
+      name: string;
+      ok: boolean;
+      detail?: string;
+    }
+
+    interface CrashReport {
+      id: string;
+      type: string;
+      message: string;
+      stack: string;
+      appVersion: string;
+      electron: string;
+      os: string;
+      createAt: string;
     }
 
     type UpdateInfo = ElectronUpdateInfo | "update-not-available";
@@ -44,6 +809,289 @@ declare global {
       active: boolean;
       createAt: string;
       updateAt: string;
+      // the project folder doesn't exist anymore, computed when listing
+      missing?: boolean;
+      // last time its version was switched
+      lastUsedAt?: string;
+      usageCount?: number;
+      // overrides the `syncEngines` setting for this project
+      syncEngines?: boolean;
+      // offer to restart the project's running node processes after a switch
+      restartProcesses?: boolean;
+      // versions of the other runtimes pinned for this project
+      runtimes?: Partial<Record<RuntimeName, string>>;
+      // replaces those of its group, field by field
+      network?: NetworkOverride;
+      // The settings below apply to the terminals, editors, scripts and audits the app
+      // starts in the project, not to commands run from other shells.
+      // PEM bundle given to node as `NODE_EXTRA_CA_CERTS` in this project only
+      caFile?: string;
+      // environment variables of the processes started in the project, over its group's
+      env?: Record<string, string>;
+      // over those of its group, a scope at a time
+      npm?: NpmRegistries;
+      // npm run by the app's scripts and audits instead of the bundled one
+      npmVersion?: string;
+      // pinned to the x64 build of its version, installed as `<version>-x64`, on ARM machines
+      emulateX64?: boolean;
+      // kept on its exact version by bulk switches, moves to the LTS and group changes
+      frozen?: boolean;
+    }
+
+    type AuditAction = "install" | "uninstall" | "switch";
+
+    // what started the action: a window, the tray, a shortcut, a `nvmd://` link, a
+    // client of the daemon socket or an environment lockfile being applied
+    type AuditInitiator = "ui" | "tray" | "shortcut" | "deep-link" | "daemon" | "lock";
+
+    interface AuditEntry {
+      at: string;
+      action: AuditAction;
+      // without the `v`, or the group name a project was switched to
+      version: string;
+      // path of the project switched, absent for the global version
+      project?: string;
+      // a group switched as a whole
+      group?: string;
+      initiator: AuditInitiator;
+    }
+
+    // Every field set must match, `since` and `until` are ISO dates
+    interface AuditFilter {
+      action?: AuditAction;
+      version?: string;
+      project?: string;
+      initiator?: AuditInitiator;
+      since?: string;
+      until?: string;
+      // 100 by default
+      limit?: number;
+    }
+
+    // A version change that can be undone
+    interface VersionSwitch {
+      scope: "global" | "project";
+      // switched to
+      version: string;
+      // global: the version before
+      previous?: string;
+      // projects: the version (or group) each switched project had before
+      projects?: { path: string; version?: string }[];
+      at: string;
+    }
+
+    interface ProjectScript {
+      name: string;
+      command: string;
+    }
+
+    type AuditSeverity = "info" | "low" | "moderate" | "high" | "critical";
+
+    interface AuditVulnerability {
+      // the vulnerable package
+      name: string;
+      severity: AuditSeverity;
+      // a dependency of the project itself
+      direct: boolean;
+      // vulnerable versions
+      range?: string;
+      fixAvailable: boolean;
+    }
+
+    interface ProjectAudit {
+      path: string;
+      // Node.js version npm ran with
+      version: string;
+      at: string;
+      counts: Record<AuditSeverity, number>;
+      total: number;
+      // the most severe first
+      vulnerabilities: AuditVulnerability[];
+    }
+
+    interface ScriptResult {
+      name: string;
+      // npm, yarn, pnpm or bun
+      packageManager: string;
+      // null when the script was killed by `signal`
+      code: number | null;
+      signal?: string;
+      aborted: boolean;
+      // milliseconds
+      duration: number;
+    }
+
+    // Mirror and proxy used instead of the settings for the installs of a project or
+    // group, and given to npm in the project
+    interface NetworkOverride {
+      mirror?: string;
+      proxy?: Proxy;
+    }
+
+    // npm registries of a group's projects, a project's own replace them field by field
+    interface NpmRegistries {
+      // default registry
+      registry?: string;
+      // `@scope` -> registry of its packages
+      scopes?: Record<string, string>;
+    }
+
+    // Yarn declared by the `packageManager` field of a project's `package.json`
+    interface YarnStatus {
+      // the field as written, e.g. `yarn@4.1.0+sha512.abc`
+      spec: string;
+      version: string;
+      // whether the project's Node.js version ships Corepack
+      corepack: boolean;
+      // downloaded to the Corepack cache
+      provisioned: boolean;
+      // `yarn` is linked in the bin folder of the project's Node.js version
+      shimmed: boolean;
+    }
+
+    // Step of the resolution a project's version comes from
+    type VersionSource = "group" | "project" | "nvmdrc" | "tool-versions" | "repository";
+
+    // Values found in the project folder, absent when the file or field isn't there
+    interface VersionSources {
+      nvmdrc?: string;
+      nvmrc?: string;
+      // `.node-version`
+      nodeVersion?: string;
+      // the nodejs entry of `.tool-versions`
+      toolVersions?: string;
+      // `engines.node` of `package.json`
+      enginesNode?: string;
+      packageManager?: string;
+      // none when no version applies
+      from?: VersionSource;
+      // the group whose version is used
+      group?: string;
+      // the git repository root whose `.nvmdrc` is inherited
+      repository?: string;
+    }
+
+    type VersionSpecKind = "exact" | "partial" | "lts" | "latest" | "unknown";
+
+    // A version value as nvm accepts them (`20`, `lts/*`, `v20.11.1`) and what it stands for
+    interface ResolvedVersionSpec {
+      spec: string;
+      // as written to `.nvmdrc`
+      normalized: string;
+      kind: VersionSpecKind;
+      version?: string;
+      // why it can't be resolved
+      error?: string;
+    }
+
+    interface ProjectDetails {
+      path: string;
+      // resolved Node.js version, empty when none applies
+      version: string;
+      // the value it was resolved from
+      spec?: ResolvedVersionSpec;
+      // whether the resolved version is installed
+      installed: boolean;
+      sources: VersionSources;
+      runtimes: Partial<Record<RuntimeName, string>>;
+      yarn?: YarnStatus;
+    }
+
+    // Runtimes and tools managed alongside Node.js
+    type RuntimeName = "deno" | "bun" | "pnpm";
+
+    interface RuntimeRelease {
+      version: string;
+      date: string;
+      prerelease: boolean;
+      // whether there's a build for this platform
+      available: boolean;
+    }
+
+    interface RuntimeInfo {
+      name: RuntimeName;
+      installed: string[];
+      // version run outside of pinned projects
+      default?: string;
+    }
+
+    // Versions embedded in an Electron release
+    interface ElectronMapping {
+      electron: string;
+      node: string;
+      chrome: string;
+      v8: string;
+      date: string;
+      // whether the matching Node.js version is installed
+      installed: boolean;
+    }
+
+    interface ProjectProcess {
+      pid: number;
+      command: string;
+      // unknown on Windows
+      cwd?: string;
+    }
+
+    // `scripts` are run by the app and can be restarted, `processes` are all the node
+    // processes started from the project
+    interface ProjectProcesses {
+      scripts: string[];
+      processes: ProjectProcess[];
+    }
+
+    type ProcessAction = "restart" | "stop";
+
+    // A project as exported to JSON/CSV, for sharing the list with a team
+    interface ProjectListEntry {
+      name: string;
+      path: string;
+      version: string;
+      group?: string;
+    }
+
+    type ProjectListFormat = "json" | "csv";
+
+    interface ProjectListImport {
+      projects: Project[];
+      groups: Group[];
+      added: number;
+      updated: number;
+      // listed paths that don't exist on this machine
+      skipped: string[];
+      // projects whose `.nvmdrc` couldn't be written
+      warnings: CommandWarning[];
+    }
+
+    interface LockedVersion {
+      version: string;
+      // platform string of the build, e.g. `win-x64`
+      platform?: string;
+      // sha256 of its node executable
+      checksum?: string;
+    }
+
+    // The Node.js setup of a machine, reproduced on another one by applying it
+    interface EnvironmentLock {
+      lockfileVersion: number;
+      createAt: string;
+      // global version
+      current?: string;
+      versions: LockedVersion[];
+      groups: { name: string; version: string; desc?: string }[];
+      projects: ProjectListEntry[];
+    }
+
+    interface EnvironmentLockApply {
+      // versions installed to match the lockfile
+      installed: string[];
+      // projects added and updated
+      added: number;
+      updated: number;
+      // locked projects that don't exist on this machine
+      skipped: string[];
+      // global version set, when it wasn't already
+      current?: string;
     }
 
     interface Group {
@@ -51,6 +1099,103 @@ declare global {
       desc?: string;
       version: string;
       projects: string[];
+      network?: NetworkOverride;
+      // environment variables inherited by its projects
+      env?: Record<string, string>;
+      npm?: NpmRegistries;
+    }
+
+    type CommandDanger = "safe" | "write" | "destructive";
+
+    interface CommandParam {
+      name: string;
+      // TypeScript type of the argument, as written in the `Nvmd` namespace
+      type: string;
+      optional?: boolean;
+    }
+
+    interface Command {
+      // IPC channel
+      name: string;
+      description: string;
+      danger: CommandDanger;
+      params: CommandParam[];
+    }
+
+    interface DeepLink {
+      // `use` switches the global version to `argument`, anything else focuses the app
+      action: string;
+      argument?: string;
+    }
+
+    // Projects tracked under several paths of one folder (symlinks, letter case)
+    interface ProjectDuplicates {
+      // the project kept by a merge
+      path: string;
+      duplicates: string[];
+    }
+
+    interface ProjectDrift {
+      name: string;
+      path: string;
+      // version stored in the config, its group's version for projects in a group
+      expected: string;
+      // content of the `.nvmdrc`, empty when the file is missing
+      actual: string;
+      group?: string;
+    }
+
+    // keep the `.nvmdrc` value, or write the stored version back to the file
+    type DriftAction = "adopt" | "rewrite";
+
+    interface RequiredInstallResult {
+      installed: string[];
+      // already installed
+      present: string[];
+      failed: { version: string; message: string }[];
+    }
+
+    interface BulkVersionResult {
+      version: string;
+      // whether the version had to be installed first
+      installed: boolean;
+      projects: { path: string; from?: string }[];
+      groups: { name: string; from: string }[];
+      // project folders that no longer exist
+      missing: string[];
+      // frozen projects left on their version
+      frozen: string[];
+    }
+
+    interface BatchSyncResult {
+      succeeded: string[];
+      // project folders that no longer exist
+      missing: string[];
+      failed: Array<{ path: string; reason: string }>;
+      rolledBack: boolean;
+      // frozen projects, not written
+      frozen: string[];
+    }
+
+    interface SearchResult {
+      type: "installed" | "remote" | "project" | "group";
+      id: string;
+      label: string;
+      description: string;
+      score: number;
+      // what the palette should do when the result is picked
+      action: {
+        name: "use-version" | "install" | "open-project" | "open-group";
+        payload: string;
+      };
+    }
+
+    interface ConfigBackup {
+      // config file name without extension: "setting", "projects" or "groups"
+      name: string;
+      id: string;
+      size: number;
+      createAt: string;
     }
 
     interface ConfigrationExport {
@@ -1,3 +1,4 @@
+import { execSync } from "child_process";
 import { resolve } from "path";
 import { defineConfig } from "electron-vite-tsup";
 import react from "@vitejs/plugin-react-swc";
@@ -5,6 +6,15 @@ import stylePlugin from "esbuild-style-plugin";
 import tailwindcss from "tailwindcss";
 import autoprefixer from "autoprefixer";
 
+// Commit the app is built from, shown in the about screen and the bug reports
+const getCommit = () => {
+  try {
+    return execSync("git rev-parse --short HEAD", { stdio: "pipe" }).toString().trim();
+  } catch {
+    return "unknown";
+  }
+};
+
 export default defineConfig(({ mode }) => {
   const isProd = mode === "production",
     isTest = process.env.TEST === "true";
@@ -16,7 +26,9 @@ export default defineConfig(({ mode }) => {
       format: "esm",
       // The requested module 'electron-updater' is a CommonJS module, which may not support all module.exports as named exports.
       noExternal: isProd ? [/(.*)/] : ["electron-updater"],
-      minify: isProd
+      minify: isProd,
+      // read by `getBuildInfo`
+      env: { NVMD_COMMIT: getCommit(), NVMD_BUILD_DATE: new Date().toISOString() }
     },
     preload: {
       clean: true,
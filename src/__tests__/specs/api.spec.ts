@@ -32,5 +32,27 @@ describe("Electron APIs", () => {
         expect(setting).toHaveProperty("theme");
       });
     });
+
+    describe("list-commands", () => {
+      it("should describe the IPC commands", async () => {
+        const commands = await browser.execute(() => window.Context.listCommands());
+        const names = commands.map(({ name }) => name);
+
+        expect(names).toContain("use-version");
+        expect(new Set(names).size).toEqual(names.length);
+        commands.forEach(({ danger }) =>
+          expect(["safe", "write", "destructive"]).toContain(danger)
+        );
+      });
+    });
+
+    describe("app-paths", () => {
+      it("should report the app paths", async () => {
+        const paths = await browser.execute(() => window.Context.getAppPaths());
+
+        expect(paths).toHaveProperty("home");
+        expect(paths).toHaveProperty("config");
+      });
+    });
   });
 });
@@ -0,0 +1,63 @@
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { mkdir, mkdtemp, readFile, remove, writeFile } from "fs-extra";
+import { expect } from "@wdio/globals";
+import { browser } from "wdio-electron-service";
+
+describe("Unicode paths", () => {
+  let projectPath: string;
+
+  before(async () => {
+    projectPath = await mkdtemp(join(tmpdir(), "nvmd-项目-プロジェクト-"));
+  });
+
+  after(async () => {
+    await remove(projectPath);
+  });
+
+  it("should write .nvmdrc into a non-ASCII folder", async () => {
+    const code = await browser.execute(
+      (path) => window.Context.syncProjectVersion(path, "v20.11.0"),
+      projectPath
+    );
+
+    expect(code).toEqual(200);
+    // stored as the exact version, without the `v`
+    expect((await readFile(join(projectPath, ".nvmdrc"))).toString()).toEqual("20.11.0");
+  });
+
+  it("should resolve the version of a non-ASCII folder", async () => {
+    // written by an editor, with a BOM and a trailing line break
+    await writeFile(join(projectPath, ".nvmdrc"), "\uFEFFv18.19.0\r\n");
+
+    const version = await browser.execute(
+      (path) => window.Context.getProjectVersion(path),
+      projectPath
+    );
+
+    expect(version).toEqual("v18.19.0");
+  });
+
+  it("should keep a project added from a non-ASCII folder", async () => {
+    const path = join(projectPath, "应用 café");
+    await mkdir(path);
+
+    const projects = await browser.execute(
+      async (path) => {
+        const projects = await window.Context.getProjects();
+        const now = new Date().toISOString();
+        const project = { name: "应用 café", path, active: true, createAt: now, updateAt: now };
+        await window.Context.updateProjects([project, ...projects]);
+        await window.Context.syncProjectVersion(path, "20.11.0");
+        const added = await window.Context.getProjects(true);
+        // leave the list as it was
+        await window.Context.updateProjects(projects);
+        return added;
+      },
+      path
+    );
+
+    expect(projects.map((project) => project.path)).toContain(path);
+    expect((await readFile(join(path, ".nvmdrc"))).toString()).toEqual("20.11.0");
+  });
+});
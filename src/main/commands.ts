@@ -0,0 +1,401 @@
+// Metadata of the IPC channels handled by the main process, for the command palette
+// and for the e2e specs asserting the IPC surface. Keep it in step with `ipcMain`.
+// `danger`: "safe" only reads, "write" changes state, "destructive" deletes data.
+
+const command = (
+  name: string,
+  description: string,
+  danger: Nvmd.CommandDanger = "safe",
+  params: Nvmd.CommandParam[] = []
+): Nvmd.Command => ({ name, description, danger, params });
+
+const param = (name: string, type: string, optional = false): Nvmd.CommandParam => ({
+  name,
+  type,
+  ...(optional && { optional })
+});
+
+export const COMMANDS: Nvmd.Command[] = [
+  command("list-commands", "List the available commands"),
+  command("window:close", "Close the main window", "write"),
+  command("window:minimize", "Minimize the main window", "write"),
+  command("window:open-main", "Show the main window", "write"),
+  command("setting-data-get", "Get the settings"),
+  command("setting-data-set", "Update the settings", "write", [
+    param("setting", "Partial<Setting>")
+  ]),
+  command("policy-get", "Get the machine policy and the settings it locks"),
+  command("mirror-presets", "List the mirror presets"),
+  command("mirror-test", "Check how fast a mirror preset or URL answers", "safe", [
+    param("mirror", "string")
+  ]),
+  command("mirror-stats", "Show how reliable and fast the mirrors have been", "safe", [
+    param("days", "number", true)
+  ]),
+  command("mirror-apply", "Download from a mirror preset or URL", "write", [
+    param("mirror", "string")
+  ]),
+  command("setting-validate", "Check the mirror, proxy, directory and disk space", "safe", [
+    param("setting", "Partial<Setting>", true)
+  ]),
+  command("locale-data", "Get the messages of the current locale"),
+  command("locales-get", "List the available locales"),
+  command("locale-set", "Change the locale", "write", [param("locale", "string")]),
+  command("logs-tail", "Get the last lines of the log", "safe", [
+    param("lines", "number", true)
+  ]),
+  command("logs-export", "Save the logs to a file", "write", [param("title", "string")]),
+  command("diagnostics-export", "Save logs, config and checks to a zip for bug reports", "write", [
+    param("title", "string")
+  ]),
+  command("path-repair", "Put the shims first on the Windows user PATH, drop repeats", "write", [
+    param("dryRun", "boolean", true)
+  ]),
+  command("crash-reports-get", "List the crash reports"),
+  command("crash-report-submit", "Submit a crash report", "write", [param("id", "string")]),
+  command("crash-reports-clear", "Delete the crash reports", "destructive"),
+  command("telemetry-get", "List the recorded telemetry events"),
+  command("telemetry-purge", "Delete the recorded telemetry events", "destructive"),
+  command("get-native-arch", "Get the architecture of the machine"),
+  command("get-app-version", "Get the version of the app"),
+  command("get-system-theme", "Get the theme of the system"),
+  command("app-paths", "Get the directories used by the app"),
+  command("app-info", "Get the version, build and environment of the app"),
+  command("check-for-updates", "Check for a new version of the app"),
+  command("confirm-update", "Download the available update, as a delta when possible", "write"),
+  command("search-everything", "Search versions, projects and groups", "safe", [
+    param("query", "string"),
+    param("limit", "number", true)
+  ]),
+  command("all-node-versions", "List the Node.js versions available from the mirror", "safe", [
+    param("options", "{ id?: string; fetch?: boolean; force?: boolean }", true)
+  ]),
+  command("node-versions-query", "Search, sort and page the available versions", "safe", [
+    param("options", "VersionQuery & { id?: string; fetch?: boolean; force?: boolean }", true)
+  ]),
+  command("version-sources", "List the version sources and the one in use"),
+  command("release-lines", "List the versions grouped by major and LTS codename"),
+  command("latest-of-line", "Resolve the newest version of a major or LTS codename", "safe", [
+    param("line", "string | number")
+  ]),
+  command("installed-node-versions", "List the installed Node.js versions", "safe", [
+    param("refresh", "boolean", true)
+  ]),
+  command(
+    "installed-node-versions-details",
+    "List the installed versions with their npm, V8 and OpenSSL versions"
+  ),
+  command("current-version", "Get the global Node.js version", "safe", [
+    param("fetch", "boolean", true)
+  ]),
+  command("activation-info", "Get how the global version is activated"),
+  command("use-version", "Change the global Node.js version", "write", [
+    param("version", "string")
+  ]),
+  command("get-node", "Download and install a Node.js version", "write", [
+    param("options", "{ id: string; arch: string; version: string; location?: string }")
+  ]),
+  command("controller:abort", "Cancel a running install", "write", [param("id", "string")]),
+  command("operation-cancel", "Cancel a running operation by its id", "write", [
+    param("id", "string")
+  ]),
+  command("operations-running", "List the ids of the running operations"),
+  command("active-installs", "List the running installs"),
+  command("install-state", "Get the progress or outcome of the install of a version", "safe", [
+    param("version", "string")
+  ]),
+  command("install-required", "Install the versions required by groups and projects", "write", [
+    param("options", "{ id: string; groups?: string[]; paths?: string[] }")
+  ]),
+  command("uninstall-node-version", "Uninstall a Node.js version", "destructive", [
+    param("version", "string"),
+    param("current", "boolean", true)
+  ]),
+  command("sandboxes", "List the versions installed as sandboxes"),
+  command("sandbox-create", "Install a version again with its own globals and cache", "write", [
+    param("options", "{ id: string; version: string; name: string }")
+  ]),
+  command("version-metadata", "Get the install metadata of a version", "safe", [
+    param("version", "string"),
+    param("arch", "string")
+  ]),
+  command("verify-installation", "Check the files of an installed version", "safe", [
+    param("version", "string")
+  ]),
+  command("repair-installation", "Reinstall a damaged version", "write", [
+    param("options", "{ id: string; arch: string; version: string }")
+  ]),
+  command("versions-refresh", "Reinstall the versions failing their checks", "write", [
+    param("id", "string")
+  ]),
+  command("setup-wizard-plan", "Find what the first-run setup would do"),
+  command("setup-wizard-run", "Run the steps of the first-run setup", "write", [
+    param("options", "{ id: string; mirror?: string; steps?: SetupStepId[] }")
+  ]),
+  command("cache-cleanup-status", "Get when the cache is next cleaned up"),
+  command("prefetch-status", "Get the newest LTS archive prefetched to the cache"),
+  command("network-state", "Tell whether the connection is online, metered or through a VPN"),
+  command("groups-freshness", "Report how far behind its release line each group is", "safe", [
+    param("refresh", "boolean", true)
+  ]),
+  command("cache-cleanup-run", "Remove leftover download folders from the cache", "destructive", [
+    param("id", "string", true)
+  ]),
+  command("installs-interrupted", "List the installs the app quit in the middle of"),
+  command("install-retry", "Start an interrupted install again from the start", "write", [
+    param("options", "{ id: string; version: string }")
+  ]),
+  command("install-dismiss", "Forget an interrupted install", "write", [
+    param("version", "string")
+  ]),
+  command("quarantine-list", "List the broken installs moved away at launch"),
+  command("quarantine-delete", "Delete a quarantined folder", "destructive", [
+    param("entry", "string")
+  ]),
+  command("quarantine-repair", "Reinstall the version of a quarantined folder", "write", [
+    param("options", "{ id: string; entry: string }")
+  ]),
+  command("disk-status", "Get the free space of the install volume after the running installs"),
+  command("npm-cache-info", "Get the folder and size of the npm cache"),
+  command("npm-cache-clear", "Clear the npm cache", "destructive"),
+  command("versions-disk-usage", "Get the disk usage of the installed versions"),
+  command("versions-orphaned", "List the installed versions nothing refers to"),
+  command("versions-migrate", "Move or link the installed versions to a directory", "write", [
+    param("directory", "string"),
+    param("mode", "MigrateMode"),
+    param("id", "string", true)
+  ]),
+  command("configs-reload", "Read the config files again after another program saved them"),
+  command("rollback-points", "List the snapshots taken before cleanups and migrations"),
+  command(
+    "rollback-last-operation",
+    "Restore the config files and versions as they were before the last cleanup or migration",
+    "write"
+  ),
+  command("version-usage", "List the projects and groups using each version"),
+  command("locked-versions", "List the locked versions"),
+  command("version-lock", "Lock or unlock a version", "write", [
+    param("version", "string"),
+    param("locked", "boolean")
+  ]),
+  command("download-headers-get", "Get the headers sent to the mirror, masked"),
+  command("download-headers-set", "Change the headers sent to the mirror", "write", [
+    param("headers", "Record<string, string>")
+  ]),
+  command("webhook-secret-get", "Whether a webhook secret is stored"),
+  command("webhook-secret-set", "Change the secret signing the webhook payloads", "write", [
+    param("secret", "string")
+  ]),
+  command("webhook-test", "Send a ping to the webhook URL", "write"),
+  command("shims-sync", "Recreate the shims of the global packages", "write"),
+  command("release-schedule", "Get the start, LTS and end of life dates of each line", "safe", [
+    param("fetch", "boolean", true)
+  ]),
+  command("runtime-releases", "List the releases of Deno, Bun or pnpm", "safe", [
+    param("name", "RuntimeName"),
+    param("fetch", "boolean", true)
+  ]),
+  command("runtime-info", "Get the installed and default versions of a runtime", "safe", [
+    param("name", "RuntimeName")
+  ]),
+  command("runtime-install", "Install a version of a runtime", "write", [
+    param("options", "{ id: string; name: RuntimeName; version: string }")
+  ]),
+  command("runtime-uninstall", "Uninstall a version of a runtime", "destructive", [
+    param("name", "RuntimeName"),
+    param("version", "string")
+  ]),
+  command("runtime-default-set", "Change the default version of a runtime", "write", [
+    param("name", "RuntimeName"),
+    param("version", "string")
+  ]),
+  command("project-runtime-set", "Pin a runtime version for a project", "write", [
+    param("path", "string"),
+    param("name", "RuntimeName"),
+    param("version", "string")
+  ]),
+  command("project-npm-pin", "Run an npm version of its own in a project", "write", [
+    param("path", "string"),
+    param("version", "string")
+  ]),
+  command("project-network-set", "Set or clear the mirror and proxy of a project", "write", [
+    param("path", "string"),
+    param("network", "NetworkOverride", true)
+  ]),
+  command("project-npm-set", "Set or clear the npm registries of a project", "write", [
+    param("path", "string"),
+    param("npm", "NpmRegistries", true)
+  ]),
+  command("project-env-set", "Set the environment variables of a project", "write", [
+    param("path", "string"),
+    param("env", "Record<string, string>", true)
+  ]),
+  command("project-ca-set", "Set or clear the extra CA certificates of a project", "write", [
+    param("path", "string"),
+    param("file", "string", true)
+  ]),
+  command("project-frozen-set", "Keep a project on its version during bulk changes", "write", [
+    param("path", "string"),
+    param("frozen", "boolean")
+  ]),
+  command("electron-node", "Get the Node.js, Chromium and V8 versions of Electron", "safe", [
+    param("options", "{ version?: string; path?: string }")
+  ]),
+  command("electron-node-install", "Install the Node.js version embedded by Electron", "write", [
+    param("options", "{ id: string; version?: string; path?: string }")
+  ]),
+  command("get-projects", "List the projects", "safe", [
+    param("load", "boolean", true),
+    param("sort", '"recent"', true)
+  ]),
+  command("open-folder-selecter", "Pick folders and add them as projects", "write", [
+    param("options", "{ title: string; multiple?: boolean; project?: boolean }")
+  ]),
+  command("update-projects", "Save the projects, removing the .nvmdrc of path", "write", [
+    param("projects", "Project[]"),
+    param("path", "string", true),
+    param("options", "{ trash?: boolean }", true)
+  ]),
+  command("relocate-project", "Change the path of a project", "write", [
+    param("oldPath", "string"),
+    param("newPath", "string")
+  ]),
+  command("project-duplicates", "Find the projects tracked under several paths", "safe"),
+  command("projects-merge", "Merge the projects tracked under several paths", "write"),
+  command("project-version", "Resolve the Node.js version of a project", "safe", [
+    param("path", "string")
+  ]),
+  command("sync-project-version", "Change the Node.js version of a project", "write", [
+    param("path", "string"),
+    param("version", "string")
+  ]),
+  command("project-details", "Explain the version of a project and get its Yarn status", "safe", [
+    param("path", "string")
+  ]),
+  command("project-yarn-provision", "Install the Yarn of packageManager with Corepack", "write", [
+    param("path", "string")
+  ]),
+  command("version-archs", "Get the architecture of each installed build"),
+  command("version-emulation-set", "Reinstall a version as the x64 or native build", "write", [
+    param("options", "{ id: string; version: string; enabled: boolean }")
+  ]),
+  command("project-emulation-set", "Pin a project to the x64 build of its version", "write", [
+    param("options", "{ id: string; path: string; enabled: boolean }")
+  ]),
+  command("switch-history", "List the recent version switches, newest first"),
+  command("switch-undo", "Undo the last version switch, .nvmdrc files included", "write"),
+  command("shell-completion", "Get the nvmd completion script of a shell", "safe", [
+    param("shell", "bash | zsh | fish | powershell")
+  ]),
+  command("prompt-info", "Get the version in effect in a folder, and if it's installed", "safe", [
+    param("cwd", "string")
+  ]),
+  command("prompt-segment", "Get the nvmd segment of a prompt tool's config", "safe", [
+    param("tool", "PromptTool")
+  ]),
+  command("audit-log-query", "List the installs, uninstalls and switches, newest first", "safe", [
+    param("filter", "AuditFilter", true)
+  ]),
+  command("project-scripts", "List the scripts of a project's package.json", "safe", [
+    param("path", "string")
+  ]),
+  command("project-script-run", "Run a package.json script with the project's version", "write", [
+    param("options", "{ id: string; path: string; name: string }")
+  ]),
+  command("version-spec-resolve", "Resolve a version like 20, lts/* or v20.11.1", "safe", [
+    param("spec", "string")
+  ]),
+  command("project-audit", "Audit a project's dependencies with the npm of its version", "safe", [
+    param("path", "string"),
+    param("id", "string", true)
+  ]),
+  command("project-drift", "List projects whose .nvmdrc differs from the stored version", "safe", [
+    param("id", "string", true)
+  ]),
+  command("project-drift-resolve", "Settle the drift of a project", "write", [
+    param("path", "string"),
+    param("action", "DriftAction")
+  ]),
+  command("projects-move-to-lts", "Move projects to the latest LTS version", "write", [
+    param("options", "{ id: string; paths?: string[] }")
+  ]),
+  command("projects-export", "Save the project list to a JSON or CSV file", "write", [
+    param("title", "string"),
+    param("format", "ProjectListFormat")
+  ]),
+  command(
+    "environment-lock-export",
+    "Save the global version, groups, installed versions and project versions to a lockfile",
+    "write",
+    [param("directory", "string")]
+  ),
+  command(
+    "environment-lock-apply",
+    "Install the versions of a lockfile and set its groups, projects and global version",
+    "write",
+    [param("file", "string"), param("id", "string")]
+  ),
+  command("projects-import", "Add or update projects from a JSON or CSV file", "write", [
+    param("title", "string")
+  ]),
+  command("project-processes", "List the node processes started from a project", "safe", [
+    param("path", "string")
+  ]),
+  command("project-processes-restart", "Stop or restart the scripts run in a project", "write", [
+    param("path", "string"),
+    param("action", "ProcessAction")
+  ]),
+  command("update-project-remove-group", "Take projects out of a group", "write", [
+    param("paths", "string[]"),
+    param("group", "string"),
+    param("version", "string")
+  ]),
+  command("project-reveal", "Show a project in the file manager", "safe", [
+    param("path", "string")
+  ]),
+  command("project-open-editor", "Open a project in the editor", "safe", [
+    param("path", "string")
+  ]),
+  command("versions-benchmark", "Compare the startup and script times of versions", "safe", [
+    param("options", "{ id?: string; versions: string[]; script?: string; runs?: number }")
+  ]),
+  command("launch-repl", "Open a terminal running the REPL of a version", "safe", [
+    param("version", "string")
+  ]),
+  command("project-open-terminal", "Open a terminal in a project", "safe", [
+    param("path", "string")
+  ]),
+  command("project-copy-bin-path", "Copy the bin path of a project's version", "safe", [
+    param("path", "string")
+  ]),
+  command("group-get", "List the groups", "safe", [param("load", "boolean", true)]),
+  command("group-create", "Create a group", "write", [param("group", "Group")]),
+  command("group-update", "Save the groups", "write", [param("groups", "Group[]")]),
+  command("group-update-version", "Change the Node.js version of a group", "write", [
+    param("group", "Group"),
+    param("version", "string")
+  ]),
+  command("group-env-set", "Set the environment variables of a group's projects", "write", [
+    param("name", "string"),
+    param("env", "Record<string, string>", true)
+  ]),
+  command("group-network-set", "Set or clear the mirror and proxy of a group", "write", [
+    param("name", "string"),
+    param("network", "NetworkOverride", true)
+  ]),
+  command("group-npm-set", "Set or clear the npm registries of a group's projects", "write", [
+    param("name", "string"),
+    param("npm", "NpmRegistries", true)
+  ]),
+  command("configration-export", "Export the projects, groups and settings", "safe", [
+    param("options", "ConfigrationExport")
+  ]),
+  command("configration-import", "Import projects, groups and settings", "write", [
+    param("options", "{ sync: boolean; title: string }")
+  ]),
+  command("config-backups-get", "List the backups of the configuration files"),
+  command("config-backup-restore", "Restore a configuration file from a backup", "write", [
+    param("name", "string"),
+    param("id", "string")
+  ])
+];
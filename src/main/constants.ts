@@ -3,12 +3,57 @@ import { app } from 'electron';
 
 export const HOME = app.getPath('home');
 
+// On Linux the XDG base directories are honored. The shim reads `setting.json`, `default`,
+// `projects.json`, `groups.json`, `bin` and `versions` from `~/.nvmd`, so those stay where
+// they are.
+export const XDG = process.platform === 'linux';
+
+const xdgDir = (name: string, fallback: string) =>
+  join(process.env[name] || join(HOME, fallback), 'nvmd');
+
 export const APPDIR = join(HOME, '.nvmd'),
+  CONFIG_DIR = XDG ? xdgDir('XDG_CONFIG_HOME', '.config') : APPDIR,
+  STATE_DIR = XDG ? xdgDir('XDG_STATE_HOME', join('.local', 'state')) : APPDIR,
+  CACHE_DIR = XDG ? xdgDir('XDG_CACHE_HOME', '.cache') : APPDIR,
+  DOWNLOADS_DIR = XDG ? join(CACHE_DIR, 'downloads') : undefined,
   BIN_DIR = join(APPDIR, 'bin'),
   INSTALL_DIR = join(APPDIR, 'versions'),
-  VERSIONS_FILENAME = join(APPDIR, 'versions.json'),
+  // Deno, Bun and pnpm installed next to Node.js, read by their shims
+  RUNTIMES_DIR = join(APPDIR, 'runtimes'),
+  // broken installs moved out of the install directory at launch
+  QUARANTINE_DIR = join(STATE_DIR, 'quarantine'),
+  VERSIONS_FILENAME = join(CACHE_DIR, 'versions.json'),
+  // dates of the release lines, from the Node.js release team
+  SCHEDULE_JSONFILE = join(CACHE_DIR, 'schedule.json'),
+  // installed versions of the last run, shown while they are scanned again
+  STARTUP_CACHE_JSONFILE = join(CACHE_DIR, 'startup.json'),
   SETTING_JSONFILE = join(APPDIR, 'setting.json'),
   PROJECTS_JSONFILE = join(APPDIR, 'projects.json'),
   GROUPS_JSONFILE = join(APPDIR, 'groups.json'),
   MIRRATION_FILE = join(APPDIR, 'migration'),
-  NVMDRC_NAME = '.nvmdrc';
+  CHECKSUMS_JSONFILE = join(STATE_DIR, 'checksums.json'),
+  LOGS_DIR = join(STATE_DIR, 'logs'),
+  CRASHES_DIR = join(STATE_DIR, 'crashes'),
+  TELEMETRY_JSONFILE = join(STATE_DIR, 'telemetry.json'),
+  INSTALL_JOURNAL_JSONFILE = join(STATE_DIR, 'installs.json'),
+  // versions installed again under a name, with their own globals and npm cache
+  SANDBOXES_JSONFILE = join(STATE_DIR, 'sandboxes.json'),
+  // outcome and latency of the requests to each mirror
+  MIRROR_HEALTH_JSONFILE = join(STATE_DIR, 'mirror-health.json'),
+  // installs, uninstalls and switches made through the app
+  AUDIT_LOGFILE = join(APPDIR, 'audit.jsonl'),
+  SCHEMA_JSONFILE = join(CONFIG_DIR, 'schema.json'),
+  BACKUPS_DIR = join(STATE_DIR, 'backups'),
+  // config files and installed versions before cleanups and migrations
+  ROLLBACK_DIR = join(STATE_DIR, 'rollback'),
+  CURRENT_LINK = join(APPDIR, 'current'),
+  // group and project names for the shell completion scripts
+  COMPLETION_FILE = join(APPDIR, 'completion.txt'),
+  CREDENTIALS_FILE = join(CONFIG_DIR, 'credentials'),
+  WEBHOOK_SECRET_FILE = join(CONFIG_DIR, 'webhook-secret'),
+  LOCKED_JSONFILE = join(CONFIG_DIR, 'locked.json'),
+  NVMDRC_NAME = '.nvmdrc',
+  NVMRC_NAME = '.nvmrc',
+  NODE_VERSION_NAME = '.node-version',
+  TOOL_VERSIONS_NAME = '.tool-versions',
+  PACKAGE_JSON_NAME = 'package.json';
@@ -1,12 +1,15 @@
 import { platform } from 'node:process';
 import { join } from 'node:path';
-import { pathExists, readJson, readdir, writeJSON } from 'fs-extra';
+import { pathExists, readJson, readdir, stat } from 'fs-extra';
+import { valid } from 'semver';
 import { VERSIONS_FILENAME, INSTALL_DIR } from '../../../constants';
+import { mapLimit } from '../../../utils/concurrency';
+import { writeJsonAtomic } from '../../../utils/file';
 
 export async function setCache(versions: Nvmd.Versions, fetch: boolean = true) {
   if ((await pathExists(VERSIONS_FILENAME)) && !fetch) return;
 
-  await writeJSON(VERSIONS_FILENAME, versions);
+  await writeJsonAtomic(VERSIONS_FILENAME, versions);
 }
 
 export async function getCache({
@@ -20,21 +23,54 @@ export async function getCache({
   return versions;
 }
 
+// Adding or removing a version folder changes the mtime of the install
+// directory, the last scan is reused while it stays the same. Scans made within
+// the mtime granularity of the file system (2s on FAT, 1s on some network
+// shares) of the last change could miss a folder created right after, they
+// aren't reused. A deleted `bin/node` or an unmounted link target leaves the mtime
+// as it is, `refresh` always scans again.
+const MTIME_GRANULARITY = 2000;
+
+type Scan = {
+  path: string;
+  mtimeMs: number;
+  scannedAt: number;
+  versions: string[];
+};
+
+let lastScan: Scan | null = null;
+
 export async function getInstalledVersions(
   path: string = INSTALL_DIR,
+  { refresh = false }: { refresh?: boolean } = {},
 ): Promise<string[]> {
   if (!(await pathExists(path))) return [];
 
+  const { mtimeMs } = await stat(path);
+  if (
+    !refresh &&
+    lastScan?.path === path &&
+    lastScan.mtimeMs === mtimeMs &&
+    lastScan.scannedAt - mtimeMs > MTIME_GRANULARITY
+  ) {
+    return [...lastScan.versions];
+  }
+
+  const scannedAt = Date.now();
   const contents = await readdir(path);
-  const exists = await Promise.all(
-    contents.map(async (version) => {
-      return await pathExists(
+  const exists = (
+    await mapLimit(contents, (version) =>
+      pathExists(
         join(path, version, platform === 'win32' ? 'node.exe' : 'bin/node'),
-      );
-    }),
-  );
+      ),
+    )
+  ).map((result) => result.status === 'fulfilled' && result.value);
 
-  const versions = contents.filter((_, index) => exists[index]);
+  // folders unpacked by hand are listed once adopted under their version
+  const versions = contents.filter(
+    (version, index) => exists[index] && valid(version) === version,
+  );
+  lastScan = { path, mtimeMs, scannedAt, versions };
 
-  return versions;
+  return [...versions];
 }
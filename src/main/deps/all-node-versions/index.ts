@@ -7,22 +7,78 @@ import { fetchIndex } from './fetch';
 
 import type { Options } from './options';
 
+// Refreshes requested within this delay of the last fetch reuse its result,
+// unless `force` is set.
+const MIN_REFRESH_INTERVAL = 30 * 1000;
+
 let processCachedVersions: Nvmd.Versions, installedVersions: string[];
+let pendingFetch: {
+    promise: Promise<Nvmd.Versions>;
+    controller: AbortController;
+    callers: number;
+  } | null = null,
+  lastFetchAt = 0;
+
+// Callers asking while a fetch is running share its result. The fetch runs
+// with a signal of its own, aborted once every caller waiting for it has
+// aborted theirs.
+const sharedFetch = (options: Options): Promise<Nvmd.Versions> => {
+  const { signal } = options;
+  if (signal?.aborted) return Promise.reject(signal.reason);
+
+  if (!pendingFetch) {
+    const controller = new AbortController();
+    const promise = fetchIndex({ ...options, signal: controller.signal })
+      .then(async (versionsInfo) => {
+        await setCache(versionsInfo, options.fetch);
+        processCachedVersions = versionsInfo;
+        lastFetchAt = Date.now();
+        return versionsInfo;
+      })
+      .finally(() => {
+        if (pendingFetch?.promise === promise) pendingFetch = null;
+      });
+    pendingFetch = { promise, controller, callers: 0 };
+  }
+
+  const shared = pendingFetch;
+  shared.callers++;
+  // a caller that can't cancel keeps the fetch going
+  if (!signal) return shared.promise;
+
+  return new Promise((resolve, reject) => {
+    const onAbort = () => {
+      reject(signal.reason);
+      if (--shared.callers > 0) return;
 
-export const allNodeVersions = async (options: Options = {}) => {
+      shared.controller.abort(signal.reason);
+      if (pendingFetch === shared) pendingFetch = null;
+    };
+    signal.addEventListener('abort', onAbort, { once: true });
+    shared.promise
+      .then(resolve, reject)
+      .finally(() => signal.removeEventListener('abort', onAbort));
+  });
+};
+
+export const allNodeVersions = async ({ force, ...options }: Options = {}) => {
   if (processCachedVersions !== void 0 && options.fetch !== true) {
     return processCachedVersions;
   }
 
+  if (
+    processCachedVersions !== void 0 &&
+    !force &&
+    Date.now() - lastFetchAt < MIN_REFRESH_INTERVAL
+  ) {
+    return processCachedVersions;
+  }
+
   // from cache
   const cachedVersions = await getCache({ fetch: options.fetch });
   if (cachedVersions) return cachedVersions;
 
-  const versionsInfo = await fetchIndex(options);
-  await setCache(versionsInfo, options.fetch);
-  processCachedVersions = versionsInfo;
-
-  return versionsInfo;
+  return sharedFetch(options);
 };
 
 export async function allInstalledNodeVersions({
@@ -35,7 +91,7 @@ export async function allInstalledNodeVersions({
   if (installedVersions !== void 0 && refresh !== true)
     return installedVersions;
 
-  const versions = await getInstalledVersions(path);
+  const versions = await getInstalledVersions(path, { refresh });
 
   installedVersions = versions;
 
@@ -13,6 +13,11 @@ export interface Options {
    */
   mirror?: FetchNodeWebsiteOptions["mirror"];
 
+  /**
+   * URL of the release index, when it isn't `${mirror}/index.json`.
+   */
+  indexUrl?: FetchNodeWebsiteOptions["indexUrl"];
+
   /**
    * Cancels the release download when the signal is aborted.
    */
@@ -28,6 +33,13 @@ export interface Options {
    */
   fetch?: boolean | undefined;
 
+  /**
+   * With `fetch`, refresh even if the index was fetched moments ago.
+   *
+   * @default `undefined`
+   */
+  force?: boolean;
+
   /**
    * Milliseconds to wait for the server to end the response before aborting the request with `got.TimeoutError` error (a.k.a. `request` property).
    */
@@ -38,6 +50,16 @@ export interface Options {
    */
   proxy?: Nvmd.Proxy;
 
+  /**
+   * Extra HTTP headers, e.g. `Authorization` for private mirrors.
+   */
+  headers?: FetchNodeWebsiteOptions["headers"];
+
+  /**
+   * Trusted CA certificates (PEM).
+   */
+  certificateAuthority?: FetchNodeWebsiteOptions["certificateAuthority"];
+
   /**
    * Progress event callback.
    *
@@ -2,15 +2,21 @@
  * https://github.com/ehmicky/fetch-node-website
  */
 
+import { env } from "node:process";
 import { got } from "got";
 import { HttpProxyAgent } from "http-proxy-agent";
 import { HttpsProxyAgent } from "https-proxy-agent";
+import { SocksProxyAgent } from "socks-proxy-agent";
 import { getDefaultMirror } from "./mirror";
+import { expandUrlTemplate, isUrlTemplate } from "./template";
+import { shouldBypassProxy } from "./no-proxy";
 
 import type { Request, Delays } from "got";
 
 const LEADING_SLASH_REGEXP = /^\//u;
 
+const INDEX_PATH = "index.json";
+
 export interface Options {
   /**
    * Base URL.
@@ -19,10 +25,19 @@ export interface Options {
    * The following environment variables can also be used: `NODE_MIRROR`,
    * `NVM_NODEJS_ORG_MIRROR`, `N_NODE_MIRROR` or `NODIST_NODE_MIRROR`.
    *
+   * It can also be a URL template with `{version}` and `{filename}` placeholders
+   * (for example `https://artifactory.example.com/node/{version}/{filename}`).
+   *
    * @default 'https://nodejs.org/dist'
    */
   mirror?: string;
 
+  /**
+   * URL of the `index.json` listing the releases, when it isn't at the root of
+   * `mirror` (required when `mirror` is a URL template).
+   */
+  indexUrl?: string;
+
   /**
    * Cancels the release download when the signal is aborted.
    */
@@ -37,6 +52,23 @@ export interface Options {
    * Proxy server configuration
    */
   proxy?: Nvmd.Proxy;
+
+  /**
+   * Extra HTTP headers, e.g. `Authorization` for private mirrors.
+   */
+  headers?: Record<string, string>;
+
+  /**
+   * Trusted CA certificates (PEM), replacing the bundled ones when set.
+   */
+  certificateAuthority?: string[];
+
+  /**
+   * `HEAD` only retrieves the headers, e.g. the size of a release file.
+   *
+   * @default 'GET'
+   */
+  method?: "GET" | "HEAD";
 }
 
 /**
@@ -58,21 +90,49 @@ export interface Options {
  * ```
  */
 export const fetchNodeWebsite = async (path: string, opts?: Options): Promise<Request> => {
-  const { mirror = getDefaultMirror(), signal, timeout = {}, proxy } = opts || {};
+  const {
+    mirror = getDefaultMirror(),
+    signal,
+    timeout = {},
+    proxy,
+    method = "GET",
+    indexUrl,
+    headers,
+    certificateAuthority
+  } = opts || {};
 
   const pathA = path.replace(LEADING_SLASH_REGEXP, "");
-  // Configure proxy if provided
+  const url =
+    indexUrl && pathA === INDEX_PATH
+      ? indexUrl
+      : isUrlTemplate(mirror)
+        ? expandUrlTemplate(mirror, pathA)
+        : undefined;
+
+  // Configure proxy if provided, hosts matching `noProxy` (or the `NO_PROXY`
+  // environment variable) are requested directly
   let agent;
-  if (proxy?.enabled) {
-    const proxyOptions = `http://${proxy.ip}:${proxy.port}`;
-    agent = {
-      http: new HttpProxyAgent(proxyOptions),
-      https: new HttpsProxyAgent(proxyOptions)
-    };
+  const noProxy = proxy?.noProxy ?? env.NO_PROXY ?? env.no_proxy;
+  if (proxy?.enabled && !shouldBypassProxy(new URL(url ?? mirror), noProxy)) {
+    const { protocol = "http" } = proxy;
+    const proxyOptions = `${protocol}://${proxy.ip}:${proxy.port}`;
+    if (protocol === "http") {
+      agent = {
+        http: new HttpProxyAgent(proxyOptions),
+        https: new HttpsProxyAgent(proxyOptions)
+      };
+    } else {
+      // `socks5h` resolves host names on the proxy side, e.g. through an SSH tunnel
+      const socksAgent = new SocksProxyAgent(proxyOptions);
+      agent = { http: socksAgent, https: socksAgent };
+    }
   }
 
-  const response = got.stream(pathA, {
-    prefixUrl: mirror,
+  const response = got.stream(url ?? pathA, {
+    prefixUrl: url ? "" : mirror,
+    method,
+    headers,
+    https: { certificateAuthority },
     signal,
     timeout,
     agent
@@ -0,0 +1,56 @@
+import { BlockList, isIP } from "node:net";
+
+// Whether requests to `url` go around the proxy, following the usual `no_proxy`
+// conventions. `patterns` is a comma or space separated list of:
+//   - `*`, every host
+//   - `example.com` or `.example.com`, the domain and its subdomains
+//   - `*.example.com`, its subdomains only
+//   - `10.0.0.0/8` or `fd00::/8`, IP literals in a CIDR range
+//   - any host or IP followed by `:port`, only for that port
+// Host names are not resolved, CIDR ranges only match IP addresses in the URL.
+export const shouldBypassProxy = (url: URL, patterns: string = "") => {
+  const host = url.hostname.replace(/^\[|\]$/gu, "").toLowerCase();
+  const port = url.port || (url.protocol === "https:" ? "443" : "80");
+
+  return patterns
+    .split(/[\s,]+/u)
+    .map((pattern) => pattern.trim().toLowerCase())
+    .filter(Boolean)
+    .some((pattern) => {
+      if (pattern === "*") return true;
+
+      const [name, patternPort] = splitPort(pattern);
+      if (!name || (patternPort && patternPort !== port)) return false;
+      const target = name.replace(/^\[|\]$/gu, "");
+
+      if (target.includes("/")) return matchesCidr(host, target);
+      if (target.startsWith("*.")) return host.endsWith(target.slice(1));
+
+      const domain = target.replace(/^\./u, "");
+      return host === domain || host.endsWith(`.${domain}`);
+    });
+};
+
+// IPv6 literals only carry a port when written with brackets: `[fd00::1]:8080`.
+const splitPort = (pattern: string): [string, string?] => {
+  const bracketed = /^(\[[^\]]+\])(?::(\d+))?$/u.exec(pattern);
+  if (bracketed) return [bracketed[1], bracketed[2]];
+  if (pattern.split(":").length > 2) return [pattern];
+
+  const [name, port] = pattern.split(":");
+  return [name, port];
+};
+
+const matchesCidr = (host: string, cidr: string) => {
+  const [address, prefix] = cidr.split("/");
+  const family = isIP(address);
+  if (!family || family !== isIP(host)) return false;
+
+  try {
+    const list = new BlockList();
+    list.addSubnet(address, Number(prefix), family === 4 ? "ipv4" : "ipv6");
+    return list.check(host, family === 4 ? "ipv4" : "ipv6");
+  } catch {
+    return false;
+  }
+};
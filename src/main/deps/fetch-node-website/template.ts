@@ -0,0 +1,19 @@
+const PLACEHOLDER_REGEXP = /\{(?:version|filename)\}/u;
+
+// A mirror containing `{version}` or `{filename}` is a URL template, for private
+// repositories (e.g. Artifactory) that don't follow the nodejs.org layout:
+//   https://artifactory.example.com/node/{version}/{filename}
+// `{version}` is the release folder (`v20.11.0`), `{filename}` the file in it.
+export const isUrlTemplate = (mirror: string) => PLACEHOLDER_REGEXP.test(mirror);
+
+// Full URL of `path` (`v20.11.0/SHASUMS256.txt`) for a `template` mirror.
+// Files outside of a release folder, such as `index.json`, have no place in a
+// template and need their own URL.
+export const expandUrlTemplate = (template: string, path: string) => {
+  const [version, ...rest] = path.split("/");
+  if (!rest.length) {
+    throw new Error(`The mirror URL template has no location for ${path}, set an index URL`);
+  }
+
+  return template.replaceAll("{version}", version).replaceAll("{filename}", rest.join("/"));
+};
@@ -2,9 +2,9 @@ import { platform } from 'node:process';
 import { pipeline } from 'node:stream/promises';
 import { createGunzip } from 'node:zlib';
 import { rename } from 'fs-extra';
-import { throttle } from 'lodash';
 
 import { fetchNodeUrl, promiseOrFetchError } from '../fetch';
+import { trackExtraction, trackProgress } from '../progress';
 
 import { untar } from './tar';
 import type { Options } from './types';
@@ -24,12 +24,10 @@ export const downloadGz = async ({
     fetchOpts,
   );
 
-  const promise = pipeline(response, createGunzip(), untar(tmpFile));
+  const onEntry = trackExtraction(onProgress, { response });
+  const promise = pipeline(response, createGunzip(), untar(tmpFile, onEntry));
 
-  if (onProgress) {
-    const throttled = throttle(onProgress, 300);
-    response.on('downloadProgress', throttled);
-  }
+  trackProgress(response, onProgress);
 
   await promiseOrFetchError(promise, response);
 
@@ -1,10 +1,11 @@
 import { platform } from 'node:process';
 
 import { downloadGz } from './gz';
-import { downloadRaw } from './raw';
-import { shouldUseZip, downloadZip } from './zip';
+import { downloadTarCompressed, getTarFormat, getTarName } from './tar-compressed';
+import { downloadRaw, getFilepath } from './raw';
+import { shouldUseZip, downloadZip, getZipFilepath } from './zip';
 
-import type { Options } from './types';
+import type { Arch, Options } from './types';
 
 // Retrieve the Node binary from the Node website and persist it.
 // The URL depends on the current OS and CPU architecture.
@@ -39,6 +40,23 @@ export const downloadRuntime = ({
 
 const SUPPORTED_UNIX = new Set(['linux', 'darwin', 'aix', 'sunos']);
 
+// Path of the file `downloadRuntime` fetches, relative to the version folder
+// of the mirror.
+export const getArchiveFilepath = async (
+  version: string,
+  arch: Arch,
+  fetchOpts: Options['fetchOpts'],
+) => {
+  if (platform === 'win32') {
+    return shouldUseZip(version)
+      ? `${getZipFilepath(version, arch)}.zip`
+      : getFilepath(version, arch);
+  }
+
+  const format = await getTarFormat(version, arch, fetchOpts);
+  return `${getTarName(version, arch)}.tar.${format}`;
+};
+
 // The Windows Node binary comes as a regular file or as a .zip file. We try
 // to use the fastest method.
 export const downloadWindowsNode = async ({
@@ -59,7 +77,7 @@ export const downloadWindowsNode = async ({
   return downloadRaw({ version, tmpFile, arch, fetchOpts, onProgress });
 };
 
-// The Unix Node binary comes in a .tar.gz or .tar.xz archive.
+// The Unix Node binary comes in a .tar.gz or .tar.xz archive (and maybe .tar.zst).
 export const downloadUnixNode = async ({
   version,
   tmpFile,
@@ -67,9 +85,13 @@ export const downloadUnixNode = async ({
   fetchOpts,
   onProgress,
 }: Options) => {
-  // if (await shouldUseXz(version)) {
-  //   return downloadXz({ version, tmpFile, arch, fetchOpts });
-  // }
+  const format = await getTarFormat(version, arch, fetchOpts);
+  if (format !== 'gz') {
+    return downloadTarCompressed(
+      { version, tmpFile, arch, fetchOpts, onProgress },
+      format,
+    );
+  }
 
   return downloadGz({ version, tmpFile, arch, fetchOpts, onProgress });
 };
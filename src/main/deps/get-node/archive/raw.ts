@@ -1,8 +1,8 @@
 import { pipeline } from 'node:stream/promises';
 import semver from 'semver';
 import { ensureFile } from 'fs-extra';
-import { throttle } from 'lodash';
 import { fetchNodeUrl, promiseOrFetchError, writeNodeBinary } from '../fetch';
+import { trackProgress } from '../progress';
 
 import type { Arch, Options } from './types';
 
@@ -22,10 +22,7 @@ export const downloadRaw = async ({
     fetchOpts,
   );
 
-  if (onProgress) {
-    const throttled = throttle(onProgress, 300);
-    response.on('downloadProgress', throttled);
-  }
+  trackProgress(response, onProgress);
 
   await ensureFile(`${tmpFile}/${version}/node.exe`);
 
@@ -42,7 +39,7 @@ export const downloadRaw = async ({
 };
 
 // Before Node.js 4.0.0, the URL to the node.exe was different
-const getFilepath = (version: string, arch: Arch) => {
+export const getFilepath = (version: string, arch: Arch) => {
   if (semver.gte(version, NEW_URL_VERSION)) {
     return `win-${arch}/node.exe`;
   }
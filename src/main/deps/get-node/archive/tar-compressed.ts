@@ -0,0 +1,97 @@
+import { spawn } from 'node:child_process';
+import { platform } from 'node:process';
+import { pipeline } from 'node:stream/promises';
+import { rename } from 'fs-extra';
+
+import { getChecksumLines } from '../checksum';
+import { fetchNodeUrl, promiseOrFetchError } from '../fetch';
+import { trackExtraction, trackProgress } from '../progress';
+
+import { untar } from './tar';
+import type { Options } from './types';
+
+export type TarFormat = 'zst' | 'xz' | 'gz';
+
+// Smaller archives first. Node.js has no xz or zstd decoder, the system tools
+// are used instead, they decompress on all cores with `-T0`.
+const DECODERS: Record<Exclude<TarFormat, 'gz'>, { command: string; args: string[] }> = {
+  zst: { command: 'zstd', args: ['-d', '-T0', '-c'] },
+  xz: { command: 'xz', args: ['-d', '-T0', '-c'] },
+};
+
+const availableDecoders = new Map<string, Promise<boolean>>();
+
+const hasDecoder = (format: keyof typeof DECODERS) => {
+  if (!availableDecoders.has(format)) {
+    availableDecoders.set(
+      format,
+      new Promise((resolve) => {
+        const child = spawn(DECODERS[format].command, ['--version'], {
+          stdio: 'ignore',
+        });
+        child.once('error', () => resolve(false));
+        child.once('exit', (code) => resolve(code === 0));
+      }),
+    );
+  }
+  return availableDecoders.get(format)!;
+};
+
+export const getTarName = (version: string, arch: string) =>
+  `node-v${version}-${platform}-${arch}`;
+
+// Pick the smallest archive the mirror lists in SHASUMS256.txt and that can be
+// decompressed on this machine, `.tar.gz` otherwise.
+export const getTarFormat = async (
+  version: string,
+  arch: string,
+  fetchOpts: Options['fetchOpts'],
+): Promise<TarFormat> => {
+  const name = getTarName(version, arch);
+  const lines = await getChecksumLines(version, fetchOpts).catch(() => '');
+
+  for (const format of Object.keys(DECODERS) as (keyof typeof DECODERS)[]) {
+    if (lines.includes(`${name}.tar.${format}`) && (await hasDecoder(format))) {
+      return format;
+    }
+  }
+  return 'gz';
+};
+
+// Download a .tar.xz or .tar.zst archive and extract it, piping it through the
+// decoder process.
+export const downloadTarCompressed = async (
+  { version, arch, tmpFile, fetchOpts, onProgress }: Options,
+  format: keyof typeof DECODERS,
+) => {
+  const name = getTarName(version, arch);
+  const { response, checksumError } = await fetchNodeUrl(
+    version,
+    `${name}.tar.${format}`,
+    fetchOpts,
+  );
+
+  const { command, args } = DECODERS[format];
+  const decoder = spawn(command, args, { stdio: ['pipe', 'pipe', 'ignore'] });
+  const exited = new Promise<void>((resolve, reject) => {
+    decoder.once('error', reject);
+    decoder.once('exit', (code) =>
+      code === 0 ? resolve() : reject(new Error(`${command} exited with code ${code}`)),
+    );
+  });
+
+  const onEntry = trackExtraction(onProgress, { response });
+  const promise = Promise.all([
+    pipeline(response, decoder.stdin),
+    pipeline(decoder.stdout, untar(tmpFile, onEntry)),
+    exited,
+  ]).then(() => {});
+
+  trackProgress(response, onProgress);
+
+  await promiseOrFetchError(promise, response);
+
+  await rename(`${tmpFile}/${name}`, `${tmpFile}/${version}`);
+
+  return checksumError;
+};
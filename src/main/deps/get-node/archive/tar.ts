@@ -2,8 +2,14 @@ import { rename, rm } from 'node:fs/promises';
 
 import { extract as tarExtract } from 'tar-fs';
 
-// Extract .tar.gz and .tar.xz archive
-export const untar = (tmpFile: string) => tarExtract(tmpFile);
+// Extract .tar.gz and .tar.xz archive, calling `onEntry` for each entry
+export const untar = (tmpFile: string, onEntry?: () => void) =>
+  tarExtract(tmpFile, {
+    map: (header) => {
+      onEntry?.();
+      return header;
+    },
+  });
 
 // As a performance optimization, we only unpack the node binary, not the other
 // files.
@@ -20,6 +20,7 @@ export interface Options {
   version: string;
   tmpFile: string;
   arch: Arch;
-  fetchOpts: FetchNodeOptions;
+  // `archives`: folder of release files downloaded beforehand, see `fetchNodeUrl`
+  fetchOpts: FetchNodeOptions & { archives?: string };
   onProgress?: (data: Nvmd.ProgressData) => void;
 }
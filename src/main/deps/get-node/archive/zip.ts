@@ -1,10 +1,14 @@
+import { createWriteStream } from 'node:fs';
+import { mkdir } from 'node:fs/promises';
+import { dirname, resolve, sep } from 'node:path';
+import { pipeline } from 'node:stream/promises';
 import { buffer } from 'node:stream/consumers';
 
 import compressing from 'compressing';
 import semver from 'semver';
 import { rename } from 'fs-extra';
-import { throttle } from 'lodash';
 import { fetchNodeUrl, promiseOrFetchError } from '../fetch';
+import { trackExtraction, trackProgress } from '../progress';
 
 import type { Options } from './types';
 
@@ -30,13 +34,13 @@ export const downloadZip = async ({
     fetchOpts,
   );
 
-  if (onProgress) {
-    const throttled = throttle(onProgress, 300);
-    response.on('downloadProgress', throttled);
-  }
+  trackProgress(response, onProgress);
 
   const zipContent = await buffer(response);
-  const promise = getZipStream(zipContent, tmpFile);
+  const onEntry = trackExtraction(onProgress, {
+    totalEntries: countZipEntries(zipContent),
+  });
+  const promise = getZipStream(zipContent, tmpFile, onEntry);
 
   await promiseOrFetchError(promise, response);
 
@@ -45,9 +49,60 @@ export const downloadZip = async ({
   return checksumError;
 };
 
-const getZipFilepath = (version: string, arch: string) =>
+export const getZipFilepath = (version: string, arch: string) =>
   `node-v${version}-win-${arch}`;
 
-const getZipStream = async (zipContent: Buffer, tmpFile: string) => {
-  return compressing.zip.uncompress(zipContent, `${tmpFile}`);
+// Number of entries from the "end of central directory" record at the end of
+// the archive, `0` if it can't be found.
+export const countZipEntries = (zipContent: Buffer) => {
+  const start = Math.max(0, zipContent.length - EOCD_MAX_SIZE);
+  for (let offset = zipContent.length - EOCD_SIZE; offset >= start; offset--) {
+    if (zipContent.readUInt32LE(offset) === EOCD_SIGNATURE) {
+      return zipContent.readUInt16LE(offset + 10);
+    }
+  }
+  return 0;
 };
+
+const EOCD_SIGNATURE = 0x06054b50;
+const EOCD_SIZE = 22;
+// the record ends with a comment of up to 64KB
+const EOCD_MAX_SIZE = EOCD_SIZE + 0xffff;
+
+// Extract entry by entry, so the extraction progress can be reported.
+export const getZipStream = (
+  zipContent: Buffer,
+  tmpFile: string,
+  onEntry: () => void,
+) =>
+  new Promise<void>((resolvePromise, reject) => {
+    const root = resolve(tmpFile);
+    const uncompressStream = new compressing.zip.UncompressStream({
+      source: zipContent,
+    });
+
+    uncompressStream.on('error', reject);
+    uncompressStream.on('finish', resolvePromise);
+    uncompressStream.on('entry', (header, stream, next) => {
+      const destination = resolve(root, header.name);
+      // entries must not escape the destination (zip slip)
+      if (!destination.startsWith(`${root}${sep}`)) {
+        stream.resume();
+        return reject(new Error(`Invalid entry in the archive: ${header.name}`));
+      }
+
+      // the next entry is only read once this one is written out
+      const write =
+        header.type === 'file'
+          ? mkdir(dirname(destination), { recursive: true }).then(() =>
+              pipeline(stream, createWriteStream(destination)),
+            )
+          : mkdir(destination, { recursive: true }).then(() => {
+              stream.resume();
+            });
+      write.then(() => {
+        onEntry();
+        next();
+      }, reject);
+    });
+  });
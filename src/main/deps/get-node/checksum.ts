@@ -3,19 +3,22 @@ import { env } from "node:process";
 import { text } from "node:stream/consumers";
 
 import fetchNodeWebsite from "../fetch-node-website";
-import type { Request } from "got";
+import type { NodeResponse } from "./fetch";
 import type { Options } from "./archive/types";
 
 interface CheckOptions {
   version: string;
   filepath: string;
-  response: Request;
+  response: NodeResponse;
   fetchOpts: Options["fetchOpts"];
 }
 
+export const CHECKSUM_MISMATCH = "ERR_CHECKSUM_MISMATCH";
+
 // Verify Node.js binary checksum.
 // Checksums are available for every Node.js release.
-// This never throws, which allows it not to be awaited right away.
+// This never throws, which allows it not to be awaited right away. The error it
+// resolves with has the code `CHECKSUM_MISMATCH` when the download is corrupt.
 export const checkChecksum = async ({ version, filepath, response, fetchOpts }: CheckOptions) => {
   try {
     const [expectedChecksum, actualChecksum] = await Promise.all([
@@ -26,13 +29,16 @@ export const checkChecksum = async ({ version, filepath, response, fetchOpts }:
     // This should only happen during a network error
     // eslint-disable-next-line max-depth
     if (actualChecksum !== expectedChecksum) {
-      return `Could not download Node.js ${version}: checksum did not match`;
+      return Object.assign(
+        new Error(`Could not download Node.js ${version}: checksum did not match`),
+        { code: CHECKSUM_MISMATCH }
+      );
     }
     // This should only happen during a network error, or when using an
     // unsupported platform or CPU architecture
     return;
   } catch (error) {
-    return `Could not download Node.js ${version} checksum: ${error.message}`;
+    return new Error(`Could not download Node.js ${version} checksum: ${error.message}`);
   }
 };
 
@@ -41,7 +47,7 @@ export const checkChecksum = async ({ version, filepath, response, fetchOpts }:
 //   3ca24...23380  node-v6.12.3-aix-ppc64.tar.gz
 //   4e731...4278f  node-v6.12.3-darwin-x64.tar.gz
 //   etc.
-const getExpectedChecksum = async (
+export const getExpectedChecksum = async (
   version: string,
   filepath: string,
   fetchOpts: CheckOptions["fetchOpts"]
@@ -54,16 +60,31 @@ const getExpectedChecksum = async (
   return expectedChecksum;
 };
 
-const getChecksumLines = async (version: string, fetchOpts: CheckOptions["fetchOpts"]) => {
+// The list is also used to pick the archive format, keep it for the install.
+const checksumLinesCache = new Map<string, Promise<string>>();
+
+export const getChecksumLines = (version: string, fetchOpts: CheckOptions["fetchOpts"]) => {
   // We set this environment variable during tests. Otherwise there are no ways
   // to test checksums since they are always supposed to match unlike there is
   // a network error
   if (env.TEST_CHECKSUMS !== undefined) {
-    return env.TEST_CHECKSUMS;
+    return Promise.resolve(env.TEST_CHECKSUMS);
   }
 
+  const key = `${fetchOpts.mirror}|${version}`;
+  if (!checksumLinesCache.has(key)) {
+    const lines = fetchChecksumLines(version, fetchOpts);
+    checksumLinesCache.set(key, lines);
+    lines.catch(() => checksumLinesCache.delete(key));
+  }
+  return checksumLinesCache.get(key)!;
+};
+
+const fetchChecksumLines = async (version: string, fetchOpts: CheckOptions["fetchOpts"]) => {
   const response = await fetchNodeWebsite(`v${version}/SHASUMS256.txt`, {
     mirror: fetchOpts.mirror,
+    headers: fetchOpts.headers,
+    certificateAuthority: fetchOpts.certificateAuthority,
     signal: new AbortController().signal
   });
   response.once("error", (_err) => {
@@ -79,7 +100,7 @@ const parseChecksumLine = (checksumLine: string) =>
 const CHECKSUM_LINE_DELIMITER = /\s+/u;
 
 // Calculate actual checksum for this Node.js binary
-const getActualChecksum = async (response: Request) => {
+const getActualChecksum = async (response: NodeResponse) => {
   const hashStream = response.pipe(createHash("sha256", { encoding: "hex" }));
   const actualChecksum = await text(hashStream);
   return actualChecksum;
@@ -2,11 +2,12 @@ import { rm } from 'node:fs/promises';
 import { join } from 'node:path';
 import { platform } from 'node:process';
 
-import { pathExists, move } from 'fs-extra';
+import { ensureDir, pathExists, move } from 'fs-extra';
 import { tmpName } from 'tmp-promise';
 
 import { getArch } from './arch';
 import { downloadRuntime } from './archive';
+import { CHECKSUM_MISMATCH } from './checksum';
 import type { Options } from './archive/types';
 
 // Download the Node.js binary for a specific `version`.
@@ -14,10 +15,16 @@ import type { Options } from './archive/types';
 export const download = async ({
   version,
   output,
+  cache,
   arch,
   fetchOpts,
   onProgress,
-}: Omit<Options, 'tmpFile'> & { output: string }) => {
+  onChecksumMismatch,
+}: Omit<Options, 'tmpFile'> & {
+  output: string;
+  cache?: string;
+  onChecksumMismatch?: (path: string) => Promise<unknown>;
+}) => {
   const archA = getArch(arch);
   const nodePath = join(output, version);
 
@@ -25,7 +32,15 @@ export const download = async ({
     return nodePath;
   }
 
-  await downloadFile({ version, nodePath, arch: archA, fetchOpts, onProgress });
+  await downloadFile({
+    version,
+    nodePath,
+    cache,
+    arch: archA,
+    fetchOpts,
+    onProgress,
+    onChecksumMismatch,
+  });
 
   return nodePath;
 };
@@ -51,15 +66,31 @@ const NODE_FILENAME = platform === 'win32' ? 'node.exe' : 'bin/node';
 const downloadFile = async ({
   version,
   nodePath,
+  cache,
   arch,
   fetchOpts,
   onProgress,
-}: Omit<Options, 'tmpFile'> & { nodePath: string }) => {
-  const tmpFile = await tmpName({ prefix: `get-node-${version}-${arch}` });
+  onChecksumMismatch,
+}: Omit<Options, 'tmpFile'> & {
+  nodePath: string;
+  cache?: string;
+  onChecksumMismatch?: (path: string) => Promise<unknown>;
+}) => {
+  cache && (await ensureDir(cache));
+  const tmpFile = await tmpName({
+    prefix: `get-node-${version}-${arch}`,
+    tmpdir: cache,
+  });
 
   try {
     await tmpDownload({ version, tmpFile, arch, fetchOpts, onProgress });
     await moveTmpFile(`${tmpFile}/${version}`, nodePath);
+  } catch (error) {
+    // the archive is extracted as it streams in, what it extracted to is kept instead
+    if (error.code === CHECKSUM_MISMATCH && onChecksumMismatch) {
+      await onChecksumMismatch(tmpFile);
+    }
+    throw error;
   } finally {
     await cleanTmpFile(tmpFile);
   }
@@ -84,7 +115,7 @@ const tmpDownload = async ({
   // due to wrong platform, connectivity or wrong `mirror` option are shown
   // instead of the checksum error.
   if (checksumError !== undefined) {
-    throw new Error(await checksumError);
+    throw checksumError;
   }
 };
 
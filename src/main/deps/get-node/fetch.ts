@@ -1,20 +1,36 @@
 import { once } from "node:events";
-import { createWriteStream } from "node:fs";
+import { createReadStream, createWriteStream } from "node:fs";
+import { stat } from "node:fs/promises";
+import { join } from "node:path";
+import { Transform } from "node:stream";
+import { pathToFileURL } from "node:url";
+
+import { pathExists } from "fs-extra";
 
 import fetchNodeWebsite from "../fetch-node-website";
 import { checkChecksum } from "./checksum";
 
-import type { Request } from "got";
-import type { Options as FetchNodeOptions } from "../fetch-node-website";
+import type { Readable } from "node:stream";
+import type { Options } from "./archive/types";
+
+// The download of an archive, or the prefetched one read from the disk. Both emit
+// got's `downloadProgress` events.
+export type NodeResponse = Readable & { requestUrl?: URL };
 
 // Make HTTP request to retrieve a Node.js binary.
 // Also make another HTTP request to calculate the checksum.
+// A file prefetched to `archives` is read instead, still checked against the checksum.
 export const fetchNodeUrl = async (
   version: string,
   filepath: string,
-  fetchOpts: FetchNodeOptions
+  fetchOpts: Options["fetchOpts"]
 ) => {
-  const response = await fetchNodeWebsite(`v${version}/${filepath}`, fetchOpts);
+  const path = `v${version}/${filepath}`;
+  const prefetched = fetchOpts.archives && join(fetchOpts.archives, path);
+  const response: NodeResponse =
+    prefetched && (await pathExists(prefetched))
+      ? await readCachedArchive(prefetched)
+      : await fetchNodeWebsite(path, fetchOpts);
   const checksumError = checkChecksum({
     version,
     filepath,
@@ -24,13 +40,31 @@ export const fetchNodeUrl = async (
   return { response, checksumError };
 };
 
+// Read a prefetched archive the way it would be downloaded, reporting its progress.
+const readCachedArchive = async (file: string): Promise<NodeResponse> => {
+  const { size: total } = await stat(file);
+  let transferred = 0;
+  const progress = new Transform({
+    transform(chunk: Buffer, _encoding, callback) {
+      transferred += chunk.length;
+      const percent = total ? transferred / total : 1;
+      this.emit("downloadProgress", { percent, transferred, total });
+      callback(null, chunk);
+    }
+  });
+  createReadStream(file)
+    .once("error", (error) => progress.destroy(error))
+    .pipe(progress);
+  return Object.assign(progress, { requestUrl: pathToFileURL(file) });
+};
+
 // `response` `error` events do not necessarily make piped streams error, so we
 // need to await either.
-export const promiseOrFetchError = async (promise: Promise<void>, response: Request) => {
+export const promiseOrFetchError = async (promise: Promise<void>, response: NodeResponse) => {
   await Promise.race([promise, throwOnFetchError(response)]);
 };
 
-const throwOnFetchError = async (response: Request) => {
+const throwOnFetchError = async (response: NodeResponse) => {
   const [error] = await once(response, "error");
   throw error;
 };
@@ -56,14 +56,16 @@ import type { Options } from "./options";
  * ```
  */
 const getNode = async (version: string, opts: Options = {}) => {
-  const { output, arch, fetchOpts, onProgress } = await getOpts(opts);
+  const { output, cache, arch, fetchOpts, onProgress, onChecksumMismatch } = await getOpts(opts);
   checkVersion(version);
   const nodePath = await download({
     version,
     output,
+    cache,
     arch,
     fetchOpts,
-    onProgress
+    onProgress,
+    onChecksumMismatch
   });
   return { version, path: nodePath };
 };
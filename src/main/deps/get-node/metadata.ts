@@ -0,0 +1,43 @@
+import fetchNodeWebsite from '../fetch-node-website';
+import { getArchiveFilepath } from './archive';
+import { getExpectedChecksum } from './checksum';
+
+import type { Arch } from './archive/types';
+import type { Options as FetchNodeOptions } from '../fetch-node-website';
+
+const cache = new Map<string, Nvmd.DownloadMetadata>();
+
+// Size of a release file, from the `Content-Length` of a `HEAD` request.
+const getSize = async (path: string, fetchOpts: FetchNodeOptions) => {
+  const request = await fetchNodeWebsite(path, { ...fetchOpts, method: 'HEAD' });
+  return new Promise<number | undefined>((resolve, reject) => {
+    request.once('response', ({ headers }) => {
+      const length = Number(headers['content-length']);
+      resolve(Number.isFinite(length) && length > 0 ? length : undefined);
+      request.destroy();
+    });
+    request.once('error', reject);
+    request.resume();
+  });
+};
+
+// Download size and expected sha256 of the file installing `version` fetches
+// on this platform. Both are best effort, mirrors don't always report a size.
+export const getDownloadMetadata = async (
+  version: string,
+  arch: Arch,
+  fetchOpts: FetchNodeOptions,
+): Promise<Nvmd.DownloadMetadata> => {
+  const file = await getArchiveFilepath(version, arch, fetchOpts);
+  const key = `${fetchOpts.mirror}|${version}|${file}`;
+  if (cache.has(key)) return cache.get(key)!;
+
+  const [size, checksum] = await Promise.all([
+    getSize(`v${version}/${file}`, fetchOpts).catch(() => undefined),
+    getExpectedChecksum(version, file, fetchOpts).catch(() => undefined),
+  ]);
+
+  const metadata = { version, arch, file, size, checksum };
+  (size || checksum) && cache.set(key, metadata);
+  return metadata;
+};
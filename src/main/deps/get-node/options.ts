@@ -29,6 +29,22 @@ export type Options = Partial<{
    */
   output: string;
 
+  /**
+   * Directory used for the temporary archive and extraction before the result
+   * is moved into `output`. Useful when `output` is on a disk with little free space.
+   *
+   * @default The OS temporary directory
+   */
+  cache?: string;
+
+  /**
+   * Directory of release files downloaded beforehand, laid out like the mirror
+   * (`v20.11.1/node-v20.11.1-linux-x64.tar.xz`). A file found there isn't downloaded.
+   *
+   * @default undefined
+   */
+  archives?: string;
+
   /**
    * Base URL to retrieve Node.js binaries.
    * Can be customized (for example `https://npmmirror.com/mirrors/node`).
@@ -61,6 +77,14 @@ export type Options = Partial<{
    */
   onProgress?: (progress: Nvmd.ProgressData) => void;
 
+  /**
+   * Called with the temporary folder of a download whose checksum didn't match,
+   * before it is removed, e.g. to move it elsewhere for inspection.
+   *
+   * @default undefined
+   */
+  onChecksumMismatch?: (path: string) => Promise<unknown>;
+
   /**
    * Node.js binary's CPU architecture. This is useful for example when you're
    * on x64 but would like to run Node.js x32.
@@ -76,6 +100,16 @@ export type Options = Partial<{
    * Proxy server configuration
    */
   proxy?: Nvmd.Proxy;
+
+  /**
+   * Extra HTTP headers sent with every request, e.g. `Authorization`.
+   */
+  headers?: Record<string, string>;
+
+  /**
+   * Trusted CA certificates (PEM), replacing the bundled ones when set.
+   */
+  certificateAuthority?: string[];
 }>;
 
 // Validate input parameters and assign default values.
@@ -90,19 +124,26 @@ export const getOpts = async (opts: Options = {}) => {
     arch = processArch as Arch,
     mirror = DEFAULT_MIRROR,
     proxy = undefined,
+    headers,
+    certificateAuthority,
+    cache,
+    archives,
     signal,
-    onProgress
+    onProgress,
+    onChecksumMismatch
   } = opts;
 
   validateOutput(output);
   validateArch(arch);
 
-  const fetchOpts = { mirror, signal, proxy };
+  const fetchOpts = { mirror, signal, proxy, headers, certificateAuthority, archives };
   return {
     output,
+    cache,
     arch,
     fetchOpts,
-    onProgress
+    onProgress,
+    onChecksumMismatch
   };
 };
 
@@ -0,0 +1,100 @@
+import { throttle } from 'lodash';
+import { logger } from '../../utils/logger';
+
+import type { Progress } from 'got';
+import type { NodeResponse } from './fetch';
+
+// Weight of the latest sample in the exponential moving average of the speed.
+const SMOOTHING = 0.3;
+
+const DEFAULT_INTERVAL = 300;
+
+let interval = DEFAULT_INTERVAL,
+  minBytes = 0,
+  verbose = false;
+
+// `interval` (ms) between two reports, `minBytes` downloaded since the last one.
+// `verbose` reports and logs every sample, to look into a slow mirror.
+export function setProgressOptions(options: Nvmd.ProgressOptions = {}) {
+  verbose = !!options.verbose;
+  interval = verbose ? 0 : Math.max(0, options.interval ?? DEFAULT_INTERVAL);
+  minBytes = verbose ? 0 : Math.max(0, options.minBytes ?? 0);
+}
+
+// Report the download progress of `response`, enriched with a smoothed speed
+// (bytes per second) and the estimated time remaining (seconds).
+export const trackProgress = (
+  response: NodeResponse,
+  onProgress?: (data: Nvmd.ProgressData) => void,
+) => {
+  if (!onProgress) return;
+
+  let speed = 0,
+    lastTime = Date.now(),
+    lastTransferred = 0;
+
+  const report = throttle((progress: Progress) => {
+    // the last report always goes through, to end on 100%
+    if (progress.percent < 1 && progress.transferred - lastTransferred < minBytes) return;
+
+    const now = Date.now();
+    const elapsed = (now - lastTime) / 1000;
+
+    if (elapsed > 0) {
+      const current = (progress.transferred - lastTransferred) / elapsed;
+      speed = speed ? SMOOTHING * current + (1 - SMOOTHING) * speed : current;
+    }
+    lastTime = now;
+    lastTransferred = progress.transferred;
+
+    const total = progress.total || 0;
+    verbose &&
+      logger.info(
+        `Downloaded ${progress.transferred}/${total} bytes at ${Math.round(speed)} B/s ` +
+          `from ${response.requestUrl}`,
+      );
+    onProgress({
+      percent: progress.percent,
+      transferred: progress.transferred,
+      total,
+      speed: Math.round(speed),
+      eta: speed && total ? Math.ceil((total - progress.transferred) / speed) : -1,
+      phase: progress.percent >= 1 ? 'extracting' : 'downloading',
+    });
+  }, interval);
+
+  response.on('downloadProgress', report);
+};
+
+// Report the extraction progress in archive entries, `totalEntries` when the
+// archive tells it up front (zip). Streamed archives are extracted while they
+// download, so reports start once `response` has been fully received.
+export const trackExtraction = (
+  onProgress?: (data: Nvmd.ProgressData) => void,
+  { response, totalEntries = 0 }: { response?: NodeResponse; totalEntries?: number } = {},
+) => {
+  if (!onProgress) return () => {};
+
+  let entries = 0,
+    downloaded = !response;
+  response?.once('end', () => {
+    downloaded = true;
+  });
+
+  const report = throttle(() => {
+    onProgress({
+      percent: totalEntries ? Math.min(entries / totalEntries, 1) : 1,
+      transferred: 0,
+      total: 0,
+      eta: -1,
+      phase: 'extracting',
+      entries,
+      totalEntries,
+    });
+  }, interval);
+
+  return () => {
+    entries += 1;
+    downloaded && report();
+  };
+};
@@ -0,0 +1,137 @@
+import { spawn } from 'node:child_process';
+import { availableParallelism } from 'node:os';
+import { join } from 'node:path';
+import { platform } from 'node:process';
+import { pipeline } from 'node:stream/promises';
+import { createGunzip } from 'node:zlib';
+import { copy, ensureDir, remove } from 'fs-extra';
+import { dir as tmpDir } from 'tmp-promise';
+
+import fetchNodeWebsite from '../fetch-node-website';
+import { untar } from './archive/tar';
+import { checkChecksum } from './checksum';
+import { trackProgress } from './progress';
+
+import type { Arch } from './archive/types';
+import type { Options as FetchNodeOptions } from '../fetch-node-website';
+
+export interface BuildOptions {
+  version: string;
+  arch: Arch;
+  // directory holding the installed versions
+  output: string;
+  // directory for the source tree, the system temp directory by default
+  cache?: string;
+  fetchOpts: FetchNodeOptions;
+  onProgress?: (data: Nvmd.ProgressData) => void;
+  // each line printed by the build tools
+  onOutput?: (line: string) => void;
+  // check the tarball against `SHASUMS256.txt`
+  checksum?: boolean;
+}
+
+// Run a build step, streaming its output line by line.
+const run = (
+  command: string,
+  args: string[],
+  cwd: string,
+  signal: AbortSignal | undefined,
+  onOutput?: (line: string) => void,
+) =>
+  new Promise<void>((resolve, reject) => {
+    const child = spawn(command, args, {
+      cwd,
+      signal,
+      shell: platform === 'win32',
+      windowsHide: true,
+    });
+
+    let rest = '';
+    const onData = (chunk: Buffer) => {
+      const lines = (rest + chunk.toString()).split(/\r?\n/u);
+      rest = lines.pop() || '';
+      lines.forEach((line) => onOutput?.(line));
+    };
+    child.stdout.on('data', onData);
+    child.stderr.on('data', onData);
+
+    child.once('error', reject);
+    child.once('close', (code) => {
+      rest && onOutput?.(rest);
+      code === 0
+        ? resolve()
+        : reject(new Error(`${command} ${args.join(' ')} exited with code ${code}`));
+    });
+  });
+
+const phase = (
+  onProgress: BuildOptions['onProgress'],
+  name: Nvmd.ProgressPhase,
+) => onProgress?.({ percent: 1, transferred: 0, total: 0, eta: -1, phase: name });
+
+// Download the source tarball of `version` and build it with the platform tools
+// (`configure` and `make`, or `vcbuild` on Windows), for platforms without a
+// prebuilt binary or policies requiring local builds.
+// The build toolchain (Python, a C++ compiler) has to be installed already.
+export const buildFromSource = async ({
+  version,
+  arch,
+  output,
+  cache,
+  fetchOpts,
+  onProgress,
+  onOutput,
+  checksum,
+}: BuildOptions) => {
+  const name = `node-v${version}`;
+  const { signal } = fetchOpts;
+  cache && (await ensureDir(cache));
+  const { path: tmpPath, cleanup } = await tmpDir({
+    prefix: `build-node-${version}`,
+    tmpdir: cache,
+    unsafeCleanup: true,
+  });
+
+  try {
+    const response = await fetchNodeWebsite(`v${version}/${name}.tar.gz`, fetchOpts);
+    trackProgress(response, onProgress);
+    // hashed while it is extracted
+    const checksumError = checksum
+      ? checkChecksum({ version, filepath: `${name}.tar.gz`, response, fetchOpts })
+      : undefined;
+    await pipeline(response, createGunzip(), untar(tmpPath));
+    const error = await checksumError;
+    if (error !== undefined) throw error;
+
+    const source = join(tmpPath, name);
+    const target = join(output, version);
+    await remove(target);
+
+    if (platform === 'win32') {
+      phase(onProgress, 'building');
+      await run('vcbuild.bat', ['release', arch, 'nosign'], source, signal, onOutput);
+
+      phase(onProgress, 'installing');
+      await copy(join(source, 'out', 'Release', 'node.exe'), join(target, 'node.exe'));
+      await copy(join(source, 'deps', 'npm'), join(target, 'node_modules', 'npm'));
+      for (const bin of ['npm.cmd', 'npx.cmd']) {
+        await copy(join(source, 'deps', 'npm', 'bin', bin), join(target, bin));
+      }
+      return { version, path: target };
+    }
+
+    phase(onProgress, 'configuring');
+    const configure = [`--prefix=${target}`, `--dest-cpu=${arch}`];
+    await run('./configure', configure, source, signal, onOutput);
+
+    phase(onProgress, 'building');
+    await run('make', [`-j${availableParallelism()}`], source, signal, onOutput);
+
+    phase(onProgress, 'installing');
+    await run('make', ['install'], source, signal, onOutput);
+
+    return { version, path: target };
+  } finally {
+    await cleanup().catch(() => {});
+  }
+};
@@ -1,8 +1,9 @@
 import { join } from "path";
-import { readFileSync } from "fs-extra";
+import { readFileSync, readdirSync } from "fs-extra";
 import { app } from "electron";
 import { merge } from "lodash";
 import { setup } from "./i18n";
+import { logger } from "./utils/logger";
 
 function normalizeLocaleName(locale: string) {
   if (/^en-/.test(locale)) {
@@ -12,16 +13,31 @@ function normalizeLocaleName(locale: string) {
   return locale;
 }
 
+const LOCALES_DIR = app.isPackaged
+  ? join(process.resourcesPath, "_locales")
+  : join(__dirname, "../..", "_locales");
+
 function getLocaleMessages(locale: string): I18n.Message {
   const onDiskLocale = locale.replace("-", "_");
 
-  const targetFile = app.isPackaged
-    ? join(process.resourcesPath, "_locales", onDiskLocale, "messages.json")
-    : join(__dirname, "../..", "_locales", onDiskLocale, "messages.json");
+  const targetFile = join(LOCALES_DIR, onDiskLocale, "messages.json");
 
   return JSON.parse(readFileSync(targetFile, "utf-8"));
 }
 
+// Every folder under `_locales` is an available catalog, named by its own `Language-Name`.
+export function getLocales(): Array<{ locale: string; name: string }> {
+  return readdirSync(LOCALES_DIR).map((onDiskLocale) => {
+    const locale = onDiskLocale.replace("_", "-");
+    try {
+      const messages = getLocaleMessages(locale);
+      return { locale, name: messages["Language-Name"]?.message || locale };
+    } catch {
+      return { locale, name: locale };
+    }
+  });
+}
+
 export default function loadLocale({ appLocale }: { appLocale?: string } = {}): I18n.Locale {
   if (!appLocale) {
     throw new TypeError("`appLocale` is required");
@@ -43,8 +59,8 @@ export default function loadLocale({ appLocale }: { appLocale?: string } = {}):
     // We start with english, then overwrite that with anything present in locale
     messages = merge(english, messages);
   } catch (err) {
-    console.log(`Problem loading messages for locale ${localeName} ${err.stack}`);
-    console.log("Falling back to en locale");
+    logger.warn(`Problem loading messages for locale ${localeName} ${err.stack}`);
+    logger.warn("Falling back to en locale");
 
     localeName = "en";
     messages = english;
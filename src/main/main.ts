@@ -8,33 +8,298 @@
  * When running `npm run build` or `npm run build:main`, this file is compiled to
  * `./src/main.js` using webpack. This gives us some performance wins.
  */
-import { join } from "node:path";
+import { dirname, join, resolve } from "node:path";
 import { platform } from "node:process";
 import { app, BrowserWindow, shell, ipcMain, nativeTheme, dialog, Tray, Menu } from "electron";
+import { isEqual, throttle } from "lodash";
 import MenuBuilder from "./menu";
+import { getQuickWindow, toggleQuickWindow } from "./quick-window";
+import { COMMANDS } from "./commands";
+import { HIDDEN_ARG, setLaunchAtLogin, shouldStartHidden } from "./utils/autostart";
+import { PROTOCOL, findDeepLink, parseDeepLink } from "./utils/deep-link";
+import { assertNotRunning, getProjectProcesses } from "./utils/processes";
+import { cleanupCache, getCacheCleanupStatus, setCacheCleanup } from "./utils/cleanup";
+import {
+  downloadArchive,
+  getPrefetchDir,
+  getPrefetchStatus,
+  removeArchive,
+  setLtsPrefetch
+} from "./utils/prefetch";
+import { getFreshnessReport, runFreshnessReport, setFreshnessReport } from "./utils/freshness";
+import {
+  checkNetwork,
+  getNetworkBlock,
+  getNetworkState,
+  onNetworkChanged,
+  setNetworkMonitor,
+  waitForNetwork
+} from "./utils/network";
+import { getReleaseSchedule } from "./utils/release-schedule";
+import { registerShortcuts, unregisterShortcuts, validateShortcuts } from "./utils/shortcuts";
 import { AppUpdater } from "./updater";
 import { resolveHtmlPath } from "./utils/resolvePath";
-import { allNodeVersions, allInstalledNodeVersions } from "./deps/all-node-versions";
+import { allInstalledNodeVersions } from "./deps/all-node-versions";
 import getNode from "./deps/get-node";
+import { CHECKSUM_MISMATCH } from "./deps/get-node/checksum";
+import { getDownloadMetadata } from "./deps/get-node/metadata";
+import { buildFromSource } from "./deps/get-node/source";
 import { updateSchema } from "./utils/migration";
+import { migrateConfigs } from "./utils/schema";
+import { getAppPaths, migrateToXdg } from "./utils/paths";
+import { getAppInfo } from "./utils/app-info";
+import { rebuildDerivedFiles } from "./utils/derived-files";
+import { syncShims } from "./utils/shims";
+import { getNodeEnv } from "./utils/env";
+import { normalizePath } from "./utils/winpath";
+import { getNativeArch, resolveInstallArch } from "./utils/arch";
+import { checkDiskSpace } from "./utils/disk";
+import { getDiskStatus, setDiskMonitor } from "./utils/disk-monitor";
+import { migrateVersions } from "./utils/relocate";
+import { adoptManualInstalls } from "./utils/adopt";
+import { createRollbackPoint, getRollbackPoints, rollbackLastOperation } from "./utils/rollback";
+import { assertUnlocked, getLockedVersions, setVersionLocked } from "./utils/locks";
+import { getOrphanedVersions, getRequiredVersions, getVersionUsage } from "./utils/usage";
+import {
+  getDiskUsage,
+  getVersionLocation,
+  linkVersion,
+  removeVersionLocation
+} from "./utils/locations";
+import { describeFetchError, getCertificates, loadCertificates } from "./utils/tls";
+import {
+  getDownloadHeaders,
+  getMaskedHeaders,
+  loadDownloadHeaders,
+  setDownloadHeaders
+} from "./utils/credentials";
+import {
+  beginInstall,
+  endInstall,
+  getActiveInstalls,
+  getInstallState,
+  onInstallEvent,
+  setInstallStage,
+  updateInstall
+} from "./utils/installs";
+import { watchConfigs } from "./utils/watcher";
+import { searchEverything } from "./utils/search";
+import {
+  getConfigBackups,
+  getRecoveredConfigs,
+  onConfigSaved,
+  restoreConfigBackup
+} from "./utils/backup";
 import { configrationExport, configrationImport } from "./utils/configration";
 import { getCurrentVersion, setCurrentVersion, uninstallVersion } from "./utils/version";
 import { setSetting, getSetting } from "./utils/setting";
+import { logger, setLogLevel, tailLogs, exportLogs } from "./utils/logger";
+import { exportDiagnostics } from "./utils/diagnostics";
+import { readStartupCache, writeStartupCache } from "./utils/startup-cache";
+import { validateSetting } from "./utils/setting-checks";
+import {
+  getRunningScripts,
+  listScripts,
+  runTrackedScript,
+  stopRunningScripts
+} from "./utils/scripts";
+import { resolveVersionSpec } from "./utils/version-spec";
+import { getNpmCli, installNpm, removeUnusedNpm } from "./utils/npm-pin";
+import { getNpmRegistries } from "./utils/project-env";
+import { createSandbox, forgetSandbox, getSandboxes, withoutSandboxes } from "./utils/sandbox";
+import { repairPath } from "./utils/path-repair";
+import { auditProject } from "./utils/npm-audit";
+import { getSwitchHistory, onSwitchHistoryChanged, undoLastSwitch } from "./utils/switch-history";
+import { audit, queryAuditLog } from "./utils/audit";
+import {
+  assertVersionAllowed,
+  getLockedFields,
+  getPolicy,
+  isChecksumRequired,
+  loadPolicy,
+  POLICY_FILE,
+  withoutLocked
+} from "./utils/policy";
+import {
+  getAlternateMirrors,
+  getMirrorPresets,
+  resolveMirror,
+  testMirror
+} from "./utils/mirrors";
+import { getMirrorStats, recordMirrorSample } from "./utils/mirror-health";
+import { exportProjectList, importProjectList } from "./utils/project-list";
+import {
+  applyLockedPins,
+  createEnvironmentLock,
+  exportEnvironmentLock,
+  getLockedArch,
+  matchesLockedChecksum,
+  readEnvironmentLock
+} from "./utils/environment-lock";
+import {
+  configureShell,
+  detectNodeManagers,
+  isShellConfigured,
+  rankMirrors,
+  runSetupSteps
+} from "./utils/setup";
+import {
+  hasWebhookSecret,
+  loadWebhookSecret,
+  notifyWebhook,
+  setWebhook,
+  setWebhookSecret,
+  testWebhook
+} from "./utils/webhook";
+import { getSources, getSourceLocation, listVersions, setSources } from "./utils/sources";
+import { getYarnStatus, provisionYarn } from "./utils/package-manager";
+import {
+  getRuntimeInfo,
+  getRuntimeReleases,
+  installRuntime,
+  setProjectRuntime,
+  setRuntimeDefault,
+  syncRuntimeShims,
+  uninstallRuntime
+} from "./utils/runtimes";
+import { getElectronMapping } from "./utils/electron";
+import { benchmarkVersions } from "./utils/benchmark";
+import { getInstalledDetails } from "./utils/toolchain";
+import { getLatestOfLine, getReleaseLines } from "./utils/release-lines";
+import { queryVersions } from "./utils/version-query";
+import {
+  dismissInterruptedInstall,
+  getInterruptedInstalls,
+  journalInstall,
+  journalProgress,
+  loadInstallJournal
+} from "./utils/install-journal";
+import { setProgressOptions } from "./deps/get-node/progress";
+import { CommandError, toCommandError, withCommandErrors } from "./utils/errors";
+import { DAEMON_ARG, isDaemon, registerHandler, startDaemonServer } from "./utils/daemon";
+import { getCompletionScript } from "./utils/completion";
+import { clearPromptCache, getPromptInfo, getPromptSegment } from "./utils/prompt";
+import {
+  EMULATED_BUILD,
+  assertEmulationAvailable,
+  getInstalledArchs,
+  isEmulatedVersion,
+  toEmulatedVersion,
+  toNativeVersion
+} from "./utils/emulation";
+import {
+  cancelOperation,
+  endOperation,
+  getOperations,
+  runOperation,
+  startOperation
+} from "./utils/operations";
+import {
+  deleteQuarantined,
+  getQuarantined,
+  quarantineBrokenInstalls,
+  quarantineDownload
+} from "./utils/quarantine";
+import { clearNpmCache, getNpmCacheInfo, setNpmCache, writeNpmCache } from "./utils/npm-cache";
+import {
+  setupCrashReporter,
+  getCrashReports,
+  getMinidumps,
+  submitCrashReport,
+  clearCrashReports
+} from "./utils/crash";
+import {
+  setTelemetryEnabled,
+  recordEvent,
+  recordLaunch,
+  getTelemetryEvents,
+  purgeTelemetry
+} from "./utils/telemetry";
+import { setTrashEnabled } from "./utils/trash";
+import { setProjectsBase } from "./utils/portable-paths";
+import { logFailures, mapLimit, setConcurrency, toWarnings } from "./utils/concurrency";
+import {
+  MACHINE_INSTALL_DIR,
+  getStagingDir,
+  isWritable,
+  moveElevated,
+  removeElevated
+} from "./utils/elevate";
+import { getActivationInfo, linkCurrent, setActivation, unlinkCurrent } from "./utils/activation";
+import {
+  copyToClipboard,
+  getNodeBinPath,
+  openInEditor,
+  openInTerminal,
+  openRepl,
+  revealInFileManager
+} from "./utils/launcher";
+import {
+  matchesReleaseChecksum,
+  recordInstallation,
+  repairInstallation,
+  verifyInstallation
+} from "./utils/verify";
 import {
   getProjects,
-  getVersion,
+  getInheritedVersion,
+  moveProjectsToVersion,
+  detectProjectDrift,
+  resolveProjectDrift,
+  setSyncEnginesEnabled,
+  setVersionResolver,
+  setToolVersionsEnabled,
+  relocateProject,
+  findTrackedPaths,
+  findDuplicateProjects,
+  mergeDuplicateProjects,
+  resolveProjectNetwork,
+  getProject,
+  getVersionSources,
+  setProjectEmulation,
+  setProjectFrozen,
+  resolveProjectVariables,
+  resolveProjectVersion,
+  setProjectCaFile,
+  setProjectEnv,
+  setProjectNpmVersion,
+  setProjectNpm,
+  setProjectNetwork,
+  validateProjects,
+  withKnownMissing,
+  markUsed,
+  touchProject,
+  sortByRecency,
   syncProjectVersion,
   updateProjectAndSyncVersion,
   updateProjects,
   updateProjectsAndSync
 } from "./utils/projects";
-import { createGroup, getGroups, updateGroupVersion, updateGroups } from "./utils/groups";
+import {
+  createGroup,
+  getGroups,
+  setGroupEnv,
+  setGroupNpm,
+  setGroupNetwork,
+  updateGroupVersion,
+  updateGroups
+} from "./utils/groups";
 import { gt } from "semver";
-import loadLocale from "./locale";
+import { ensureDir, pathExists, remove } from "fs-extra";
+import loadLocale, { getLocales } from "./locale";
 import { Closer, Themes } from "../types";
+import {
+  DOWNLOADS_DIR,
+  GROUPS_JSONFILE,
+  HOME,
+  INSTALL_DIR,
+  PROJECTS_JSONFILE,
+  SETTING_JSONFILE
+} from "./constants";
 
 import type { MenuItemConstructorOptions, OpenDialogOptions } from "electron";
 import type { Arch } from "./deps/get-node/archive/types";
+import type { SetupStep } from "./utils/setup";
 
 let mainWindow: BrowserWindow | null = null,
   updater: AppUpdater | null = null,
@@ -44,6 +309,18 @@ let mainWindow: BrowserWindow | null = null,
   setting: Nvmd.Setting,
   installedVersions: string[];
 
+// Settles once the startup scan and the journal load are done. Installs wait for it, the
+// scan would quarantine the folder of one running, and the journal would lose its entry.
+let startupScan: Promise<unknown> = Promise.resolve();
+
+setupCrashReporter();
+
+// Commands are served to the windows and to the daemon clients.
+const handle: typeof ipcMain.handle = (channel, listener) => {
+  registerHandler(channel, listener);
+  ipcMain.handle(channel, withCommandErrors(channel, listener));
+};
+
 if (process.env.NODE_ENV === "production") {
   import("source-map-support").then((mapper) => mapper.default.install());
 }
@@ -64,7 +341,7 @@ const installExtensions = async () => {
       extensions.map((name) => devtools[name]),
       forceDownload
     )
-    .catch(console.log);
+    .catch((err) => logger.warn("Could not install devtools extensions", err));
 };
 
 nativeTheme.on("updated", () => {
@@ -75,7 +352,35 @@ nativeTheme.on("updated", () => {
     );
 });
 
-const createWindow = async (code?: number) => {
+const getBackgroundColor = () =>
+  setting.theme === Themes.System
+    ? nativeTheme.shouldUseDarkColors
+      ? "#000000"
+      : "#ffffff"
+    : setting.theme === Themes.Dark
+      ? "#000000"
+      : "#ffffff";
+
+const showMainWindow = () => {
+  if (mainWindow === null) {
+    createWindow();
+    return;
+  }
+
+  mainWindow.isMinimized() && mainWindow.restore();
+  mainWindow.show();
+  mainWindow.focus();
+};
+
+const openQuickWindow = () =>
+  toggleQuickWindow({
+    backgroundColor: getBackgroundColor(),
+    // tray bounds are only reported on macOS and Windows
+    trayBounds: tray && platform !== "linux" ? tray.getBounds() : undefined
+  });
+
+// With `hidden` the window is only created, it's shown from the tray later on.
+const createWindow = async (code?: number, hidden: boolean = false) => {
   if (isDebug) {
     await installExtensions();
   }
@@ -107,14 +412,7 @@ const createWindow = async (code?: number) => {
       y: 12
     },
     opacity: 0,
-    backgroundColor:
-      setting.theme === Themes.System
-        ? nativeTheme.shouldUseDarkColors
-          ? "#000000"
-          : "#ffffff"
-        : setting.theme === Themes.Dark
-          ? "#000000"
-          : "#ffffff",
+    backgroundColor: getBackgroundColor(),
     webPreferences: {
       preload: app.isPackaged
         ? join(__dirname, "../preload/preload.js")
@@ -131,7 +429,13 @@ const createWindow = async (code?: number) => {
       throw new Error('"mainWindow" is not defined');
     }
 
-    if (process.env.START_MINIMIZED) {
+    if (hidden) {
+      platform === "darwin" && app.dock.hide();
+      mainWindow.once("show", () => {
+        platform === "darwin" && app.dock.show();
+        setTimeout(() => mainWindow?.setOpacity(1), 60);
+      });
+    } else if (process.env.START_MINIMIZED) {
       mainWindow.minimize();
     } else {
       mainWindow.show();
@@ -147,6 +451,8 @@ const createWindow = async (code?: number) => {
 
   mainWindow.on("closed", () => {
     mainWindow = null;
+    // a hidden quick window would keep the app from quitting
+    setting.closer === Closer.Close && getQuickWindow()?.destroy();
   });
 
   createTray();
@@ -163,7 +469,7 @@ const createWindow = async (code?: number) => {
     // windows only
     // Remove this if your app does not use auto updates
     // eslint-disable-next-line
-    updater = new AppUpdater(mainWindow);
+    updater = new AppUpdater(mainWindow, setting.updateChannel);
   }
 };
 
@@ -171,6 +477,8 @@ const createWindow = async (code?: number) => {
  * Add event listeners...
  */
 
+app.on("will-quit", () => unregisterShortcuts());
+
 app.on("window-all-closed", () => {
   if (setting.closer == Closer.Minimize) {
     platform === "darwin" && app.dock.hide();
@@ -185,49 +493,435 @@ app.on("window-all-closed", () => {
   }
 });
 
+// Act on a `nvmd://` link, the app is brought to the front in any case.
+const handleDeepLink = async (link: string) => {
+  const deepLink = parseDeepLink(link);
+  logger.info(`Opened with ${link}`);
+
+  showMainWindow();
+  if (deepLink?.action === "use" && deepLink.argument) {
+    // any page can open a link, ask before it changes the global version
+    const version = deepLink.argument.replace(/^v/, "");
+    const options: Electron.MessageBoxOptions = {
+      type: "question",
+      message: locale.i18n("Deep-Link-Use-Confirm", { version }) as string,
+      buttons: [locale.i18n("OK") as string, locale.i18n("Cancel") as string],
+      defaultId: 1,
+      cancelId: 1
+    };
+    const { response } = await (mainWindow
+      ? dialog.showMessageBox(mainWindow, options)
+      : dialog.showMessageBox(options));
+    if (response === 0) await switchVersion(version, "deep-link");
+  }
+};
+
+// Undo the last version switch and show the restored versions in the windows.
+const undoSwitch = async (initiator: Nvmd.AuditInitiator = "ui") => {
+  const change = await undoLastSwitch();
+  if (!change) return;
+
+  if (change.scope === "global") {
+    audit({ action: "switch", version: change.previous ?? "", initiator });
+    [mainWindow, getQuickWindow()].forEach((window) =>
+      window?.webContents.send("current-version-update", change.previous)
+    );
+  } else {
+    change.projects?.forEach(({ path, version }) =>
+      audit({ action: "switch", version: version ?? "", project: path, initiator })
+    );
+    const [projects, groups] = await Promise.all([getProjects(), getGroups()]);
+    mainWindow?.webContents.send("call-projects-update", { projects, groups });
+  }
+  buildTray();
+  return change;
+};
+
+// Commands come from a window, or from a client of the daemon socket without one.
+const initiatorOf = (event: Electron.IpcMainInvokeEvent): Nvmd.AuditInitiator =>
+  event.sender ? "ui" : "daemon";
+
+const notifyProjectVersions = (paths: string[], version: string) =>
+  paths.forEach((path) => notifyWebhook("project-version", { path, version }));
+
+// Global version changes from outside the windows: deep links and shortcuts.
+const switchVersion = async (version: string, initiator: Nvmd.AuditInitiator) => {
+  version = version.replace(/^v/, "");
+  if (!installedVersions?.includes(version)) {
+    logger.warn(`Cannot use Node.js v${version}, it is not installed`);
+    return;
+  }
+
+  await setCurrentVersion(version);
+  audit({ action: "switch", version, initiator });
+  notifyWebhook("use", { version });
+  [mainWindow, getQuickWindow()].forEach((window) =>
+    window?.webContents.send("current-version-update", version)
+  );
+  buildTray();
+};
+
+const applyShortcuts = (shortcuts?: Nvmd.Shortcuts) =>
+  registerShortcuts(shortcuts, {
+    toggleWindow: () =>
+      mainWindow?.isVisible() && mainWindow.isFocused() ? mainWindow.hide() : showMainWindow(),
+    quickSwitch: openQuickWindow,
+    useVersion: (version) => switchVersion(version, "shortcut")
+  });
+
+// Arguments of a launch, forwarded by the second instance to the running one.
+const handleLaunchArgs = (argv: string[]) => {
+  const link = findDeepLink(argv);
+  if (link) return handleDeepLink(link);
+
+  // launched at login or as a daemon while already running, nothing to show
+  if (argv.includes(HIDDEN_ARG) || argv.includes(DAEMON_ARG)) return;
+  showMainWindow();
+};
+
 /**
  * Prevent multiple instances from starting
  * https://www.electronjs.org/zh/docs/latest/api/app#apprequestsingleinstancelockadditionaldata
+ * The second instance exits right away, the running one gets its arguments.
  */
-const gotTheLock = app.requestSingleInstanceLock();
+const gotTheLock = app.requestSingleInstanceLock({ argv: process.argv });
 if (!gotTheLock) {
   app.quit();
 } else {
-  app.on("second-instance", () => {
-    /// Make the window take focus when trying to run a second instance
-    if (mainWindow) {
-      if (mainWindow.isMinimized()) mainWindow.restore();
-      mainWindow.focus();
-    }
+  app.on("second-instance", (_event, argv, _workingDirectory, additionalData) => {
+    handleLaunchArgs((additionalData as { argv?: string[] })?.argv ?? argv);
+  });
+
+  // macOS delivers links through this event, also for the first launch
+  app.on("open-url", (event, url) => {
+    event.preventDefault();
+    app.isReady() ? handleDeepLink(url) : app.once("ready", () => handleDeepLink(url));
   });
 
+  // in development the app runs through the electron binary, pass the entry script along
+  process.defaultApp
+    ? app.setAsDefaultProtocolClient(PROTOCOL, process.execPath, [resolve(process.argv[1])])
+    : app.setAsDefaultProtocolClient(PROTOCOL);
+
   app
     .whenReady()
     .then(async () => {
-      const [code, settingFromCache] = await Promise.all([updateSchema(), getSetting()]);
-      const iVersions = await allInstalledNodeVersions({
-        path: settingFromCache.directory
-      });
+      const [code, settingFromCache] = await Promise.all([
+        updateSchema(),
+        migrateToXdg()
+          .then(() => migrateConfigs())
+          .then(() => loadPolicy())
+          .then(() => getSetting())
+      ]);
+      const startupCache = await readStartupCache(settingFromCache.directory);
+      const scan = scanInstalls(settingFromCache.directory);
+      startupScan = scan.catch(() => {});
+      // the versions of the last run are listed until the scan is done, only a first
+      // launch waits for it
+      const scanned = startupCache.installed ? undefined : await scan;
 
       if (!setting) setting = settingFromCache;
-      if (!installedVersions)
-        installedVersions = iVersions.sort((version1, version2) =>
-          gt(version2, version1) ? 1 : -1
-        );
+      setLogLevel(setting.logLevel);
+      setTelemetryEnabled(setting.telemetry);
+      setTrashEnabled(setting.trash);
+      setToolVersionsEnabled(setting.toolVersions);
+      setSyncEnginesEnabled(setting.syncEngines);
+      setVersionResolver(resolveRelease);
+      setProjectsBase(setting.projectsBase);
+      // the executable may have moved since the entry was written, e.g. after an update
+      setting.launchAtLogin && setLaunchAtLogin(true);
+      setConcurrency(setting.concurrency);
+      setProgressOptions(setting.progress);
+      setDiskMonitor(setting.lowDiskThreshold, {
+        getContext: () => ({ directory: setting.directory, fetchOpts: getFetchOptions() }),
+        onLow: (status) => mainWindow?.webContents.send("disk-low", status)
+      });
+      setCacheCleanup(setting.cacheCleanup, () => setting.cacheDirectory || DOWNLOADS_DIR);
+      setNetworkMonitor(setting.pauseOnMetered);
+      onNetworkChanged((state, previous) => {
+        mainWindow?.webContents.send("network-changed", getNetworkState());
+        // the index couldn't be fetched while offline
+        !previous.online && state.online && refreshVersionIndex();
+      });
+      setLtsPrefetch(setting.prefetchLts, { getContext: getPrefetchContext });
+      setFreshnessReport(setting.freshness, {
+        groups: () => getGroups(),
+        versions: () => listVersions(getFetchOptions()),
+        schedule: () => getReleaseSchedule(getFetchOptions()),
+        i18n: () => locale.i18n
+      });
+      setWebhook(setting.webhook);
+      setSources(setting.sources, setting.source);
+      setActivation(setting.activation, setting.directory);
+      await Promise.all([
+        loadDownloadHeaders(),
+        loadWebhookSecret(),
+        loadCertificates(setting.tls)
+      ]);
+      recordLaunch();
+      if (!installedVersions) installedVersions = scanned?.versions ?? startupCache.installed!;
 
       if (!locale) {
         const appLocale = setting.locale;
         locale = loadLocale({ appLocale });
       }
 
-      mainWindow === null && createWindow(code);
+      watchConfigs(onConfigChanged);
+      onInstallEvent((event) => {
+        mainWindow?.webContents.send("install:lifecycle", event);
+        // count the new install in the projected usage of the volume
+        event.stage === "queued" &&
+          getDiskStatus().catch((err) => logger.warn(`Could not check the disk space: ${err}`));
+        // list the running installs and their stage in the tray tooltip
+        const lines = getActiveInstalls().map(({ version, stage }) => `v${version}: ${stage}`);
+        tray?.setToolTip(["NVM-Desktop", ...lines].join("\n"));
+      });
+      onSwitchHistoryChanged(() => buildTray());
+      onConfigSaved((file) => {
+        if (file !== PROJECTS_JSONFILE && file !== GROUPS_JSONFILE) return;
+        rebuildDerivedFiles();
+        clearPromptCache();
+      });
+      rebuildDerivedFiles();
+      refreshShims();
+      // Global packages are installed outside of the app, pick up their bins on focus
+      app.on("browser-window-focus", () => refreshShims());
+
+      if (isDaemon()) {
+        // no window to focus, look for new bins now and then
+        setInterval(() => refreshShims(), 5 * 60 * 1000);
+        platform === "darwin" && app.dock.hide();
+        const server = await startDaemonServer();
+        app.on("will-quit", () => server.close());
+        return;
+      }
+
+      mainWindow === null && createWindow(code, shouldStartHidden(setting.startHidden));
+      scan
+        .then(onInstallsScanned)
+        .catch((err) => logger.warn(`Could not scan the installed versions: ${err.message}`));
+      refreshVersionIndex();
+      applyShortcuts(setting.shortcuts);
+      // Windows and Linux pass the link of the first launch as an argument
+      const link = findDeepLink(process.argv);
+      link && handleDeepLink(link);
       app.on("activate", () => {
         // On macOS it's common to re-create a window in the app when the
         // dock icon is clicked and there are no other windows open.
         if (mainWindow === null) createWindow();
       });
     })
-    .catch(console.log);
+    .catch((err) => logger.error("App startup failed", err));
+}
+
+// Quarantine the broken installs and adopt the versions unpacked by hand, then list
+// the usable ones.
+const scanInstalls = async (directory: string) => {
+  const quarantined = await quarantineBrokenInstalls(directory).catch((err) => {
+    logger.warn(`Could not scan the installed versions: ${err.message}`);
+    return [] as Nvmd.QuarantinedEntry[];
+  });
+  const adopted = await adoptManualInstalls(directory).catch((err) => {
+    logger.warn(`Could not adopt the versions unpacked by hand: ${err.message}`);
+    return { adopted: [], failed: [] } as Nvmd.AdoptedInstalls;
+  });
+  const versions = (await allInstalledNodeVersions({ path: directory, refresh: true })).sort(
+    (version1, version2) => (gt(version2, version1) ? 1 : -1)
+  );
+  const interrupted = await loadInstallJournal().catch((err) => {
+    logger.warn(`Could not read the install journal: ${err.message}`);
+    return [] as Nvmd.InterruptedInstall[];
+  });
+  return { quarantined, adopted, versions, interrupted };
+};
+
+// Run `callback` once the main window has loaded, or right away if it has.
+const whenLoaded = (callback: () => void) => {
+  if (!mainWindow) return;
+  mainWindow.webContents.isLoading()
+    ? mainWindow.webContents.once("did-finish-load", callback)
+    : callback();
+};
+
+const onInstallsScanned = ({
+  quarantined,
+  adopted,
+  versions,
+  interrupted
+}: Awaited<ReturnType<typeof scanInstalls>>) => {
+  if (!isEqual(versions, installedVersions)) {
+    installedVersions = versions;
+    buildTray();
+    whenLoaded(() => mainWindow?.webContents.send("installed-versions-updated", versions));
+  }
+  writeStartupCache({ directory: setting.directory, installed: versions });
+
+  // prompt to reinstall or delete what the scan moved away, and to retry installs
+  whenLoaded(() => {
+    quarantined.length && mainWindow?.webContents.send("versions-quarantined", quarantined);
+    (adopted.adopted.length || adopted.failed.length) &&
+      mainWindow?.webContents.send("versions-adopted", adopted);
+    interrupted.length && mainWindow?.webContents.send("installs-interrupted", interrupted);
+  });
+};
+
+// The index is read from its cache on launch, fetched again once the window is up
+// (or the network is back).
+const refreshVersionIndex = async () =>
+  !getNetworkBlock() &&
+  listVersions({ ...getFetchOptions(), fetch: true })
+    .then(() => whenLoaded(() => mainWindow?.webContents.send("versions-updated")))
+    .catch((err) => logger.info(`Could not refresh the version index: ${err.message}`));
+
+const refreshShims = throttle(
+  () =>
+    syncShims(setting.directory).catch((err) =>
+      logger.warn(`Could not update the shims: ${err.message}`)
+    ),
+  30 * 1000
+);
+
+// The version the project at `path` runs, its `.nvmdrc` may hold a specifier like `20`,
+// `lts/*` or `v20.11.1`. Rejects with the reason when it can't be resolved.
+const resolveProjectRelease = async (path: string) => {
+  const spec = await resolveProjectVersion(path);
+  return spec ? resolveRelease(spec) : "";
+};
+
+// The version `spec` stands for, installed ones first.
+const resolveRelease = async (spec: string) => {
+  const versions = await listVersions(getFetchOptions()).catch(() => [] as Nvmd.Versions);
+  const { version, error } = resolveVersionSpec(spec, { installed: installedVersions, versions });
+  return version ?? Promise.reject(error);
+};
+
+// Environment pinned to the project's version, undefined to inherit ours. The
+// variables the shim adds in the project are set as well.
+const getProjectEnv = async (path: string) => {
+  const version = await resolveProjectRelease(path);
+  if (!version) return undefined;
+
+  return { ...getNodeEnv(setting.directory, version), ...(await resolveProjectVariables(path)) };
+};
+
+// The npm pinned by the project, the one of its version otherwise.
+const getProjectNpmCli = async (path: string) => {
+  const npmVersion = (await getProject(path))?.npmVersion;
+  return npmVersion ? getNpmCli(npmVersion) : undefined;
+};
+
+const applyLocale = (appLocale: string) => {
+  locale = loadLocale({ appLocale });
+  menuBuilder.buildMenu(locale.i18n);
+  buildTray();
+};
+
+// Apply the changes of `data` over the current setting, which is not saved.
+// Resolves to the warnings of the batch changes, e.g. versions whose npm cache
+// couldn't be moved.
+async function applySetting(data: Partial<Nvmd.Setting>) {
+  const warnings: Nvmd.CommandWarning[] = [];
+  data = withoutLocked(data, setting);
+  // reject conflicting shortcuts before anything is applied
+  data.shortcuts && validateShortcuts(data.shortcuts);
+  if (data.locale && data.locale !== setting.locale) applyLocale(data.locale);
+
+  if (data.installScope && data.installScope !== setting.installScope) {
+    const directory = data.installScope === "machine" ? MACHINE_INSTALL_DIR : INSTALL_DIR;
+    data = { ...data, directory };
+  }
+
+  if (data.directory !== setting.directory) {
+    const versions = await allInstalledNodeVersions({
+      path: data.directory,
+      refresh: true
+    });
+
+    installedVersions = versions.sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+    buildTray();
+  }
+
+  if (data.logLevel && data.logLevel !== setting.logLevel) setLogLevel(data.logLevel);
+  if (data.telemetry !== void 0) setTelemetryEnabled(data.telemetry);
+  if (data.trash !== void 0) setTrashEnabled(data.trash);
+  if (data.toolVersions !== void 0) setToolVersionsEnabled(data.toolVersions);
+  if (data.syncEngines !== void 0) setSyncEnginesEnabled(data.syncEngines);
+  if ("projectsBase" in data && data.projectsBase !== setting.projectsBase) {
+    setProjectsBase(data.projectsBase);
+    // stored again in the form of the new base
+    await Promise.all([updateProjects(await getProjects()), updateGroups(await getGroups())]);
+  }
+  if (data.launchAtLogin !== void 0 && data.launchAtLogin !== setting.launchAtLogin)
+    await setLaunchAtLogin(data.launchAtLogin);
+  if (data.concurrency !== void 0) setConcurrency(data.concurrency);
+  if (data.progress !== void 0) setProgressOptions(data.progress);
+  if (data.lowDiskThreshold !== void 0) setDiskMonitor(data.lowDiskThreshold);
+  if (data.cacheCleanup && !isEqual(data.cacheCleanup, setting.cacheCleanup))
+    setCacheCleanup(data.cacheCleanup);
+  if (data.prefetchLts !== void 0) setLtsPrefetch(data.prefetchLts);
+  if (data.pauseOnMetered !== void 0) setNetworkMonitor(data.pauseOnMetered);
+  if (data.freshness && !isEqual(data.freshness, setting.freshness))
+    setFreshnessReport(data.freshness);
+  if (data.webhook !== void 0) setWebhook(data.webhook);
+  if (data.npmCache !== void 0 && data.npmCache !== setting.npmCache)
+    warnings.push(
+      ...(await setNpmCache(
+        setting.directory,
+        // sandboxes keep their own
+        await withoutSandboxes(installedVersions),
+        data.npmCache,
+        setting.npmCache
+      ))
+    );
+  if (data.sources !== void 0 || data.source !== void 0)
+    setSources(data.sources ?? setting.sources, data.source ?? setting.source);
+  if (data.shortcuts && !isEqual(data.shortcuts, setting.shortcuts))
+    applyShortcuts(data.shortcuts);
+  if (
+    (data.activation && data.activation !== setting.activation) ||
+    (data.directory && data.directory !== setting.directory)
+  ) {
+    setActivation(data.activation ?? setting.activation, data.directory ?? setting.directory);
+    const current = await getCurrentVersion();
+    try {
+      if (data.activation === "shim") await unlinkCurrent();
+      else if (current) await linkCurrent(current);
+    } catch (err) {
+      logger.warn(`Could not link the current version: ${err}`);
+    }
+  }
+  if (data.updateChannel && data.updateChannel !== setting.updateChannel)
+    updater?.setChannel(data.updateChannel);
+
+  if (data.tls && !isEqual(data.tls, setting.tls)) await loadCertificates(data.tls);
+
+  setting = { ...setting, ...data };
+  return warnings;
+}
+
+// The setting was changed elsewhere than in the settings form
+const notifySettingChanged = () =>
+  mainWindow?.webContents.send("settings-changed", {
+    ...setting,
+    locked: getLockedFields(),
+    localeMessages: locale.messages
+  });
+
+// A config file was changed outside of the app, reload it instead of overwriting
+// those edits on the next save.
+async function onConfigChanged(name: "setting" | "projects" | "groups") {
+  if (name === "setting") {
+    // the proxy and mirror are read from `setting` on each request
+    await applySetting(await getSetting());
+    notifySettingChanged();
+    return;
+  }
+
+  const [projects, groups] = await Promise.all([getProjects(true), getGroups(true)]);
+  rebuildDerivedFiles();
+  clearPromptCache();
+  mainWindow?.webContents.send("call-projects-update", { projects, groups });
+  buildTray();
 }
 
 function createTray() {
@@ -285,16 +979,17 @@ async function buildTray() {
           checked: projectVersion === version,
           async click() {
             const code = await syncProjectVersion(path, version);
+            audit({ action: "switch", version, project: path, initiator: "tray" });
 
             const [newProjects, newGroups] = await Promise.all([
               (async () => {
                 const newProjects = [...projects];
-                newProjects[index] = {
+                newProjects[index] = markUsed({
                   ...project,
                   version: version,
                   active: code === 200 ? true : false,
                   updateAt: new Date().toISOString()
-                };
+                });
                 await updateProjects(newProjects);
                 return newProjects;
               })(),
@@ -334,13 +1029,14 @@ async function buildTray() {
           checked: projectVersion === name,
           async click() {
             const code = await syncProjectVersion(path, version);
+            audit({ action: "switch", version: name, project: path, initiator: "tray" });
             const newProjects = [...projects];
-            newProjects[index] = {
+            newProjects[index] = markUsed({
               ...project,
               version: name,
               active: code === 200 ? true : false,
               updateAt: new Date().toISOString()
-            };
+            });
 
             const newGroups = [...groups];
             newGroups.forEach((group) => {
@@ -394,17 +1090,38 @@ async function buildTray() {
         mainWindow.focus();
       }
     },
+    {
+      label: locale.i18n("Quick-Switch") as string,
+      click: openQuickWindow
+    },
+    {
+      label: locale.i18n("Undo-Switch") as string,
+      enabled: getSwitchHistory().length > 0,
+      click: () =>
+        undoSwitch("tray").catch((err) => logger.warn(`Could not undo the switch: ${err}`))
+    },
+    {
+      label: locale.i18n("Launch-At-Login") as string,
+      type: "checkbox",
+      checked: !!setting.launchAtLogin,
+      async click({ checked }) {
+        await setLaunchAtLogin(checked);
+        setting = { ...setting, launchAtLogin: checked };
+        await setSetting(setting);
+      }
+    },
     { type: "separator" },
     {
-      label: "Global (default)",
+      label: locale.i18n("Global-Default") as string,
       submenu: installedVersions.map((version) => ({
         label: `v${version}`,
         type: "radio",
         checked: curVersion === version,
         async click() {
-          if (mainWindow === null) return;
           await setCurrentVersion(version);
-          mainWindow.webContents.send("current-version-update", version);
+          audit({ action: "switch", version, initiator: "tray" });
+          mainWindow?.webContents.send("current-version-update", version);
+          getQuickWindow()?.webContents.send("current-version-update", version);
         }
       }))
     },
@@ -426,7 +1143,375 @@ async function buildTray() {
   tray.setContextMenu(contextMenu);
 }
 
-const controllers = new Map<string, AbortController>();
+// Connection options shared by every request to the mirror. The `network` overrides
+// of a project or group replace the mirror and proxy of the settings.
+const getFetchOptions = (network?: Nvmd.NetworkOverride) => ({
+  mirror: setting.mirror,
+  indexUrl: setting.indexUrl,
+  ...getSourceLocation(),
+  // a mirror pinned by the policy can't be overridden either
+  ...(network?.mirror && !getPolicy().mirror && { mirror: network.mirror, indexUrl: undefined }),
+  proxy: network?.proxy ?? setting.proxy,
+  headers: getDownloadHeaders(),
+  certificateAuthority: getCertificates()
+});
+
+// Options shared by every Node.js download, progress is reported under the operation `id`.
+const getNodeOptions = ({
+  id,
+  arch,
+  signal,
+  network
+}: {
+  id: string;
+  arch: Arch;
+  signal: AbortSignal;
+  network?: Nvmd.NetworkOverride;
+}) => ({
+  ...getFetchOptions(network),
+  id,
+  arch,
+  output: setting.directory,
+  cache: setting.cacheDirectory || DOWNLOADS_DIR,
+  archives: getPrefetchDir(setting.cacheDirectory || DOWNLOADS_DIR),
+  signal,
+  onProgress: (data: Nvmd.ProgressData) => {
+    updateInstall(id, data);
+    journalProgress(id, data);
+    mainWindow?.webContents.send("get-node:progress", id, data);
+  },
+  onOutput: (line: string) => {
+    mainWindow?.webContents.send("get-node:output", id, line);
+  }
+});
+
+// The newest LTS release from the cached index, for the idle prefetch.
+const getPrefetchContext = async () => {
+  const versions = await listVersions(getFetchOptions());
+  const version = versions.find(({ lts }) => lts)?.version.slice(1);
+  if (!version) return;
+
+  return {
+    version,
+    installed: !!installedVersions?.includes(version),
+    arch: resolveInstallArch(version, getNativeArch()).arch as Arch,
+    cache: setting.cacheDirectory || DOWNLOADS_DIR,
+    fetchOpts: getFetchOptions()
+  };
+};
+
+const DEFAULT_CHECKSUM_RETRIES = 2;
+
+// Download `version`, and again from other mirror presets when the download doesn't
+// match its checksum, each corrupt one being quarantined. Mirrors pinned by the policy
+// or given by a source have no alternative.
+const downloadNode = async (
+  version: string,
+  options: ReturnType<typeof getNodeOptions> & { output: string }
+) => {
+  const { id, arch } = options;
+  const retries = Math.max(0, setting.checksumRetries ?? DEFAULT_CHECKSUM_RETRIES);
+  const alternates =
+    getPolicy().mirror || setting.source ? [] : getAlternateMirrors(options.mirror);
+  const mirrors = [options.mirror, ...alternates.slice(0, retries)];
+
+  for (const [index, mirror] of mirrors.entries()) {
+    let quarantined: Nvmd.QuarantinedEntry | undefined;
+    try {
+      return await getNode(version, {
+        ...options,
+        mirror,
+        onChecksumMismatch: async (path) => {
+          quarantined = await quarantineDownload(path, version, arch).catch((err) => {
+            logger.warn(`Could not quarantine the download: ${err.message}`);
+            return undefined;
+          });
+        }
+      });
+    } catch (err) {
+      if (err.code !== CHECKSUM_MISMATCH) throw err;
+
+      const retryMirror = mirrors[index + 1];
+      const mismatch: Nvmd.ChecksumMismatch = {
+        id,
+        version,
+        arch,
+        mirror,
+        attempt: index + 1,
+        ...(quarantined && { quarantined: quarantined.entry }),
+        ...(retryMirror && { retryMirror })
+      };
+      logger.warn(
+        `Node.js v${version} from ${mirror} didn't match its checksum` +
+          (retryMirror ? `, downloading it from ${retryMirror}` : "")
+      );
+      mainWindow?.webContents.send("install:checksum-mismatch", mismatch);
+      if (!retryMirror) throw err;
+    }
+  }
+  throw new Error(`Could not download Node.js v${version}`);
+};
+
+// In per-machine mode the install directory usually isn't writable by the user,
+// the version is then staged in a temporary folder and moved into place elevated.
+// With `fromSource` the version is built from its source tarball instead.
+// Downloads check for free disk space first rather than failing while extracting.
+// A `location` installs the version in that folder, linked from the install directory.
+const installNode = async (
+  version: string,
+  options: ReturnType<typeof getNodeOptions>,
+  fromSource: boolean = !!setting.buildFromSource,
+  location?: string
+) => {
+  // every download goes through here: installs, repairs and reinstalls
+  assertVersionAllowed(version);
+  const fetchNode = async (output: string) => {
+    if (fromSource) {
+      return buildFromSource({
+        version,
+        arch: options.arch,
+        output,
+        cache: options.cache,
+        fetchOpts: options,
+        onProgress: options.onProgress,
+        onOutput: options.onOutput,
+        checksum: isChecksumRequired()
+      });
+    }
+
+    const { arch, cache } = options;
+    await checkDiskSpace({ version, arch, cache, output, fetchOpts: options });
+    return downloadNode(version, { ...options, output });
+  };
+
+  if (location) {
+    const result = await fetchNode(location);
+    await linkVersion(setting.directory, version, result.path);
+    return result;
+  }
+
+  if (setting.installScope !== "machine" || (await isWritable(setting.directory))) {
+    return fetchNode(options.output);
+  }
+
+  const staging = await getStagingDir();
+  try {
+    const result = await fetchNode(staging);
+    const path = join(setting.directory, version);
+    await moveElevated(result.path, path);
+    return { ...result, path };
+  } finally {
+    await remove(staging).catch((err) =>
+      logger.warn(`Could not remove the staging folder ${staging}: ${err.message}`)
+    );
+  }
+};
+
+// Install `version` again over the current install, which is restored on failure.
+const reinstallVersion = async ({
+  id,
+  arch,
+  version
+}: {
+  id: string;
+  arch: Arch;
+  version: string;
+}) => {
+  assertVersionAllowed(version);
+  await startupScan;
+  await beginInstall(id, version, arch);
+  const signal = startOperation(id);
+
+  try {
+    // a version installed elsewhere is reinstalled there
+    const location = await getVersionLocation(setting.directory, version);
+    const parent = location === join(setting.directory, version) ? undefined : dirname(location);
+    const result = await repairInstallation({ path: setting.directory, version, arch }, () =>
+      installNode(version, getNodeOptions({ id, arch, signal }), undefined, parent)
+    );
+    endInstall(id);
+    return result;
+  } catch (err) {
+    endInstall(id, err);
+    return Promise.reject(err.message);
+  } finally {
+    endOperation(id);
+  }
+};
+
+const MAX_NETWORK_RESTARTS = 5;
+
+// Hold the install `id` `paused` while offline or on a metered connection the settings
+// avoid, until the network allows downloads again.
+const waitUntilAllowed = async (id: string, signal: AbortSignal) => {
+  const reason = getNetworkBlock();
+  if (!reason) return;
+
+  logger.info(`Install ${id} paused: ${reason}`);
+  setInstallStage(id, "paused", reason);
+  await waitForNetwork(signal);
+  setInstallStage(id, "queued");
+};
+
+// Run the download of the install `id` once the network allows it. A download cut by
+// such a change, or by a VPN connecting or disconnecting, is paused and picks up where
+// it stopped when it's allowed again. Only the download: the extraction isn't cut.
+const downloadWhenAllowed = async <T>(
+  id: string,
+  signal: AbortSignal,
+  download: (signal: AbortSignal) => Promise<T>
+): Promise<T> => {
+  for (let restarts = 0; ; restarts++) {
+    await waitUntilAllowed(id, signal);
+
+    const interrupted = new AbortController();
+    const stop = onNetworkChanged(
+      (state, previous) =>
+        (getNetworkBlock() || state.vpn !== previous.vpn) && interrupted.abort()
+    );
+    try {
+      return await download(AbortSignal.any([signal, interrupted.signal]));
+    } catch (err) {
+      if (signal.aborted || !interrupted.signal.aborted || restarts >= MAX_NETWORK_RESTARTS)
+        throw err;
+      logger.info(`Download of ${id} cut by a network change, resuming it`);
+    } finally {
+      stop();
+    }
+  }
+};
+
+// Install `version` a second time as the `<version>-<name>` sandbox, in the `arch` build.
+const installSandbox = async ({
+  id,
+  version,
+  name,
+  arch,
+  network
+}: {
+  id: string;
+  version: string;
+  name: string;
+  arch: Arch;
+  network?: Nvmd.NetworkOverride;
+}) => {
+  assertVersionAllowed(version);
+  await startupScan;
+  const signal = startOperation(id);
+  const options = getNodeOptions({ id, arch, signal, network });
+
+  try {
+    const sandbox = await createSandbox({
+      directory: setting.directory,
+      version,
+      name,
+      fetchNode: (output) => downloadNode(version, { ...options, output })
+    });
+    await recordInstallation({ path: setting.directory, version: sandbox.version, arch });
+    installedVersions = (
+      await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+    ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+    buildTray();
+    await syncShims(setting.directory).catch(() => {});
+    return sandbox;
+  } catch (err) {
+    return Promise.reject(err.code ? describeFetchError(err) : err.message || err);
+  } finally {
+    endOperation(id);
+  }
+};
+
+// Install `version` under the operation `id`, reporting its lifecycle stages.
+const installVersion = async ({
+  id,
+  arch: requested,
+  version,
+  source,
+  location,
+  network,
+  initiator = "ui",
+  signal: parent
+}: {
+  id: string;
+  arch: Arch;
+  version: string;
+  source?: boolean;
+  location?: string;
+  // mirror and proxy of the project or group the install is for
+  network?: Nvmd.NetworkOverride;
+  initiator?: Nvmd.AuditInitiator;
+  // of the operation the install is part of, canceling it cancels the install
+  signal?: AbortSignal;
+}) => {
+  const { arch, warning } = resolveInstallArch(version, requested);
+  warning && logger.warn(warning);
+
+  await startupScan;
+  await beginInstall(id, version, arch);
+  journalInstall(id, { version, arch, source, location });
+  const signal = startOperation(id, parent);
+  const options = getNodeOptions({ id, arch: arch as Arch, signal, network });
+  logger.info(`Installing Node.js v${version} (${arch}) from ${options.mirror}`);
+
+  const fromSource = source ?? !!setting.buildFromSource;
+  const start = Date.now();
+  try {
+    // only the download of the archive is paused, a build from source waits to start
+    if (fromSource) {
+      await waitUntilAllowed(id, signal);
+    } else {
+      await downloadWhenAllowed(id, signal, (downloadSignal) =>
+        downloadArchive({
+          version,
+          arch: arch as Arch,
+          cache: options.cache,
+          fetchOpts: { ...options, signal: downloadSignal },
+          onProgress: options.onProgress
+        })
+      ).catch((err) => {
+        if (err.code !== CHECKSUM_MISMATCH) throw err;
+        // the install downloads it again, from the other mirrors when needed
+        logger.warn(`Downloaded archive of v${version} is corrupt: ${err.message}`);
+      });
+    }
+    const result = await installNode(
+      version,
+      options,
+      fromSource,
+      location && normalizePath(location)
+    );
+    fromSource || (await removeArchive(options.cache, version).catch(() => {}));
+    recordMirrorSample(options.mirror, { kind: "download", ok: true, time: Date.now() - start });
+    setInstallStage(id, "verifying");
+    await recordInstallation({ path: setting.directory, version, arch });
+    await writeNpmCache(setting.directory, version, setting.npmCache).catch((err) =>
+      logger.warn(`Could not set the npm cache of v${version}: ${err}`)
+    );
+    logger.info(`Installed Node.js v${version} to ${result.path}`);
+    recordEvent("install", { major: version.split(".")[0], arch });
+    audit({ action: "install", version, initiator });
+    notifyWebhook("install", { version, arch });
+
+    setInstallStage(id, "linking");
+    await syncShims(setting.directory).catch(() => {});
+    endInstall(id);
+    return { ...result, arch, warning };
+  } catch (err) {
+    logger.error(`Installing Node.js v${version} failed: ${err.message}`);
+    const { kind, code } = toCommandError(err, "install");
+    (kind === "network" || kind === "tls") &&
+      recordMirrorSample(options.mirror, { kind: "download", ok: false });
+    endInstall(id, err);
+    throw err instanceof CommandError
+      ? err
+      : new CommandError(kind, err.code ? describeFetchError(err) : err.message || `${err}`, {
+          code,
+          context: { version }
+        });
+  } finally {
+    endOperation(id);
+  }
+};
 
 // defer actions
 Promise.resolve().then(() => {
@@ -441,140 +1526,949 @@ Promise.resolve().then(() => {
   }
 
   ipcMain.on("setting-data-get", (event) => {
-    event.returnValue = { ...setting, localeMessages: locale.messages };
+    event.returnValue = { ...setting, locked: getLockedFields(), localeMessages: locale.messages };
   });
 
-  ipcMain.handle("setting-data-set", async (_event, data: Partial<Nvmd.Setting>) => {
-    if (data.locale !== setting.locale) {
-      locale = loadLocale({ appLocale: data.locale });
-      menuBuilder.buildMenu(locale.i18n);
-      buildTray();
-    }
+  handle("setting-data-set", async (_event, data: Partial<Nvmd.Setting>) => {
+    const warnings = await applySetting(data);
+    await setSetting(setting);
+    return warnings;
+  });
 
-    if (data.directory !== setting.directory) {
-      const versions = await allInstalledNodeVersions({
-        path: data.directory,
-        refresh: true
-      });
+  handle("policy-get", () => ({
+    path: POLICY_FILE,
+    policy: getPolicy(),
+    locked: getLockedFields()
+  }));
 
-      installedVersions = versions.sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
-      buildTray();
-    }
+  handle("mirror-presets", () => getMirrorPresets(setting.mirror));
+
+  handle("mirror-test", async (_event, mirror: string) =>
+    testMirror(await resolveMirror(mirror), getFetchOptions())
+  );
+
+  // success rate and latency of the mirrors tested or downloaded from in the last `days`
+  handle("mirror-stats", (_event, days?: number) => getMirrorStats(days));
 
-    setting = { ...setting, ...data };
+  handle("mirror-apply", async (_event, mirror: string) => {
+    if (getPolicy().mirror) return Promise.reject("The mirror is set by the policy");
+    await applySetting({ mirror: await resolveMirror(mirror) });
     await setSetting(setting);
-    return;
+    notifySettingChanged();
+    return getMirrorPresets(setting.mirror);
   });
 
-  ipcMain.on("locale-data", (event) => {
-    event.returnValue = locale.messages;
+  // * First-run setup
+  // What the setup would do: the other version managers found, the presets from the
+  // fastest (the first reachable one is proposed) and the LTS release it installs.
+  handle("setup-wizard-plan", async (): Promise<Nvmd.SetupPlan> => {
+    const [managers, mirrors, versions, shellConfigured, exists] = await Promise.all([
+      detectNodeManagers(),
+      getPolicy().mirror ? [] : rankMirrors(getFetchOptions()),
+      listVersions(getFetchOptions()).catch(() => [] as Nvmd.Versions),
+      isShellConfigured().catch(() => false),
+      pathExists(setting.directory)
+    ]);
+    const lts = versions.find(({ lts }) => !!lts)?.version.slice(1);
+    const mirror = mirrors.find(({ ok }) => ok)?.url;
+    return {
+      managers,
+      mirrors,
+      ...(mirror && { mirror }),
+      directory: { path: setting.directory, exists },
+      shellConfigured,
+      ...(lts && { lts: { version: lts, installed: installedVersions.includes(lts) } })
+    };
   });
 
-  ipcMain.on("get-app-version", (event) => {
-    event.returnValue = app.getVersion();
-  });
+  // Run the `steps` (all of them by default) under the operation `id`, each one reported
+  // through `setup-wizard:progress`. `mirror` is the one chosen from the plan, a preset
+  // id or a URL, the fastest preset is taken otherwise.
+  handle(
+    "setup-wizard-run",
+    (
+      _event,
+      { id, mirror, steps }: { id: string; mirror?: string; steps?: Nvmd.SetupStepId[] }
+    ) =>
+      runOperation(id, async (signal) => {
+        const all: SetupStep[] = [
+          {
+            id: "detect",
+            run: async () => {
+              const managers = await detectNodeManagers();
+              const names = managers.map(({ name }) => name).join(", ");
+              return { skipped: !managers.length, ...(names && { detail: names }) };
+            }
+          },
+          {
+            id: "mirror",
+            run: async () => {
+              if (getPolicy().mirror) return { skipped: true, detail: getPolicy().mirror };
+
+              const url = mirror
+                ? await resolveMirror(mirror)
+                : (await rankMirrors(getFetchOptions())).find(({ ok }) => ok)?.url;
+              if (!url) return Promise.reject("None of the mirrors could be reached");
+              if (url === setting.mirror?.replace(/\/+$/, "")) {
+                return { skipped: true, detail: url };
+              }
 
-  ipcMain.handle(
-    "all-node-versions",
-    async (_event, { id, fetch }: { id?: string; fetch?: boolean } = {}) => {
-      const abortController = new AbortController();
-      id && controllers.set(id, abortController);
-      let result;
-      try {
-        result = await allNodeVersions({
-          mirror: setting.mirror,
-          proxy: setting.proxy,
-          signal: abortController.signal,
-          fetch,
-          timeout: {
-            request: 1000 * 20,
-            response: 1000 * 60
+              await applySetting({ mirror: url });
+              await setSetting(setting);
+              notifySettingChanged();
+              return { detail: url };
+            }
+          },
+          {
+            id: "directory",
+            run: async () => {
+              const exists = await pathExists(setting.directory);
+              exists || (await ensureDir(setting.directory));
+              return { skipped: exists, detail: setting.directory };
+            }
+          },
+          {
+            id: "shims",
+            run: async () => {
+              await syncShims(setting.directory);
+              return {};
+            }
+          },
+          {
+            id: "shell",
+            run: async () => {
+              const changed = (await configureShell()).join(", ");
+              return { skipped: !changed, ...(changed && { detail: changed }) };
+            }
+          },
+          {
+            id: "install-lts",
+            run: async () => {
+              const versions = await listVersions(getFetchOptions());
+              const lts = versions.find(({ lts }) => !!lts)?.version.slice(1);
+              if (!lts) return Promise.reject("No LTS release found, check the mirror");
+
+              const install = !installedVersions.includes(lts);
+              if (install) {
+                await installVersion({
+                  id: `${id}:${lts}`,
+                  arch: getNativeArch() as Arch,
+                  version: lts
+                });
+                installedVersions = (
+                  await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+                ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+              }
+              // the version of a first install becomes the global one
+              !(await getCurrentVersion()) && (await setCurrentVersion(lts));
+              buildTray();
+              return { skipped: !install, detail: `v${lts}` };
+            }
           }
+        ];
+
+        return runSetupSteps(steps ? all.filter((step) => steps.includes(step.id)) : all, {
+          signal,
+          onProgress: (progress) =>
+            mainWindow?.webContents.send("setup-wizard:progress", id, progress)
         });
-      } catch (err) {
-        return Promise.reject(`${err.name}: ${err.message}`);
-      } finally {
-        id && controllers.delete(id);
+      })
+  );
+
+  // unsaved values of the settings form are checked in place of the saved ones
+  handle("setting-validate", (_event, data: Partial<Nvmd.Setting> = {}) =>
+    validateSetting(
+      { directory: data.directory ?? setting.directory, proxy: data.proxy ?? setting.proxy },
+      {
+        ...getFetchOptions(),
+        ...(data.mirror && { mirror: data.mirror, indexUrl: data.indexUrl }),
+        proxy: data.proxy ?? setting.proxy
       }
-      return result;
-    }
+    )
   );
 
-  ipcMain.handle("controller:abort", async (_event, id) => {
-    const controller = controllers.get(id);
-    if (!controller) return;
+  ipcMain.on("locale-data", (event) => {
+    event.returnValue = locale.messages;
+  });
 
-    controller.abort();
-    controllers.delete(id);
-    return "successfully";
+  handle("locales-get", () => getLocales());
+
+  handle("locale-set", async (_event, appLocale: string) => {
+    if (appLocale !== setting.locale) {
+      applyLocale(appLocale);
+      setting = { ...setting, locale: appLocale };
+      await setSetting(setting);
+    }
+    return locale.messages;
   });
 
-  ipcMain.handle("installed-node-versions", async (_event, refresh: boolean = false) => {
-    if (!refresh) return installedVersions;
+  handle("logs-tail", (_event, lines?: number) => tailLogs(lines));
 
-    const versions = await allInstalledNodeVersions({
-      path: setting.directory,
-      refresh
+  handle("logs-export", async (_event, { title }: { title: string }) => {
+    const { canceled, filePaths } = await dialog.showOpenDialog(mainWindow!, {
+      title,
+      properties: ["openDirectory", "createDirectory", "showHiddenFiles"]
     });
 
-    installedVersions = versions.sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+    if (canceled) return { canceled };
 
-    refresh && buildTray();
+    const path = await exportLogs(filePaths[0]);
+    return { canceled, path };
+  });
 
-    return versions;
+  handle("diagnostics-export", async (_event, { title }: { title: string }) => {
+    const { canceled, filePath } = await dialog.showSaveDialog(mainWindow!, {
+      title,
+      defaultPath: `nvmd_diagnostics_${Date.now()}.zip`,
+      filters: [{ name: "Zip", extensions: ["zip"] }]
+    });
+
+    if (canceled || !filePath) return { canceled: true };
+
+    const path = await exportDiagnostics(filePath, {
+      directory: setting.directory,
+      installed: installedVersions
+    });
+    return { canceled, path };
   });
 
-  ipcMain.handle(
-    "get-node",
-    async (_event, { id, arch, version }: { id: string; arch: Arch; version: string }) => {
-      const abortController = new AbortController();
-      controllers.set(id, abortController);
-
-      try {
-        const result = await getNode(version, {
-          arch,
-          output: setting.directory,
-          mirror: setting.mirror,
-          proxy: setting.proxy,
-          signal: abortController.signal,
-          onProgress: (data) => {
-            mainWindow?.webContents.send("get-node:progress", id, data);
-          }
-        });
-        return result;
-      } catch (err) {
-        return Promise.reject(err.message);
-      } finally {
-        controllers.delete(id);
-      }
-    }
-  );
+  // Windows only, `dryRun` reports the changes without writing them
+  handle("path-repair", (_event, dryRun: boolean = false) => repairPath({ dryRun }));
 
-  ipcMain.handle(
-    "uninstall-node-version",
-    async (_event, version: string, current: boolean = false) => {
-      return uninstallVersion({ path: setting.directory, version, current });
-    }
+  handle("crash-reports-get", async () => {
+    const [reports, minidumps] = await Promise.all([getCrashReports(), getMinidumps()]);
+    return { reports, minidumps };
+  });
+
+  handle("crash-report-submit", (_event, id: string) =>
+    submitCrashReport(id, setting.crashReport)
   );
 
-  ipcMain.handle("current-version", async (_event, fetch: boolean = false) => {
-    const version = await getCurrentVersion(fetch);
+  handle("crash-reports-clear", () => clearCrashReports());
 
-    return version;
-  });
+  handle("telemetry-get", () => getTelemetryEvents());
 
-  ipcMain.handle("use-version", async (_event, version: string) => {
-    await setCurrentVersion(version);
+  handle("telemetry-purge", () => purgeTelemetry());
 
-    buildTray();
-    return;
+  ipcMain.on("get-native-arch", (event) => {
+    event.returnValue = getNativeArch();
   });
 
-  ipcMain.on("get-system-theme", (event) => {
-    event.returnValue = nativeTheme.shouldUseDarkColors ? "dark" : "light";
+  ipcMain.on("get-app-version", (event) => {
+    event.returnValue = app.getVersion();
+  });
+
+  const fetchVersions = async ({
+    id,
+    fetch,
+    force
+  }: { id?: string; fetch?: boolean; force?: boolean } = {}) => {
+    const signal = startOperation(id);
+    try {
+      return await listVersions({
+        ...getFetchOptions(),
+        signal,
+        fetch,
+        force,
+        timeout: {
+          request: 1000 * 20,
+          response: 1000 * 60
+        }
+      });
+    } catch (err) {
+      const { kind, code } = toCommandError(err, "all-node-versions");
+      throw new CommandError(kind, describeFetchError(err), { code });
+    } finally {
+      endOperation(id);
+    }
+  };
+
+  handle(
+    "all-node-versions",
+    (_event, options?: { id?: string; fetch?: boolean; force?: boolean }) =>
+      fetchVersions(options)
+  );
+
+  // the index stays in the main process, only the requested page is sent
+  handle(
+    "node-versions-query",
+    async (
+      _event,
+      {
+        id,
+        fetch,
+        force,
+        ...query
+      }: Nvmd.VersionQuery & { id?: string; fetch?: boolean; force?: boolean } = {}
+    ) => queryVersions(await fetchVersions({ id, fetch, force }), query)
+  );
+
+  handle("version-sources", () => getSources());
+
+  handle("release-lines", async () =>
+    getReleaseLines(await listVersions(getFetchOptions()).catch(() => [] as Nvmd.Versions))
+  );
+
+  handle("latest-of-line", async (_event, line: string | number) => {
+    const versions = await listVersions(getFetchOptions()).catch((err) =>
+      Promise.reject(describeFetchError(err))
+    );
+    const latest = getLatestOfLine(versions, line);
+    return latest ?? Promise.reject(`No release line named ${line}`);
+  });
+
+  handle("search-everything", async (_event, query: string, limit?: number) => {
+    const [versions, projects, groups, current] = await Promise.all([
+      listVersions(getFetchOptions()).catch(() => []),
+      getProjects(),
+      getGroups(),
+      getCurrentVersion()
+    ]);
+
+    return searchEverything(
+      query,
+      { installed: installedVersions, versions, projects, groups, current },
+      limit
+    );
+  });
+
+  handle("controller:abort", async (_event, id) => {
+    if (!cancelOperation(id)) return;
+    return "successfully";
+  });
+
+  handle("operation-cancel", (_event, id: string) => cancelOperation(id));
+
+  handle("operations-running", () => getOperations());
+
+  handle("installed-node-versions", async (_event, refresh: boolean = false) => {
+    if (!refresh) return installedVersions;
+
+    const adopted = await adoptManualInstalls(setting.directory);
+    (adopted.adopted.length || adopted.failed.length) &&
+      mainWindow?.webContents.send("versions-adopted", adopted);
+
+    const versions = await allInstalledNodeVersions({
+      path: setting.directory,
+      refresh
+    });
+
+    installedVersions = versions.sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+
+    refresh && buildTray();
+
+    return versions;
+  });
+
+  handle("installed-node-versions-details", async () =>
+    getInstalledDetails(
+      setting.directory,
+      installedVersions,
+      await listVersions(getFetchOptions()).catch(() => [] as Nvmd.Versions)
+    )
+  );
+
+  handle(
+    "get-node",
+    async (
+      event,
+      {
+        id,
+        arch: requested,
+        version,
+        source,
+        location
+      }: { id: string; arch: Arch; version: string; source?: boolean; location?: string }
+    ) =>
+      installVersion({
+        id,
+        arch: requested,
+        version,
+        source,
+        location,
+        initiator: initiatorOf(event)
+      })
+  );
+
+  // Install the versions the `groups` and projects at `paths` need that are missing.
+  // Each install runs under `${id}:${version}`, reporting its own progress and stages.
+  handle(
+    "install-required",
+    async (
+      event,
+      { id, groups, paths }: { id: string; groups?: string[]; paths?: string[] }
+    ): Promise<Nvmd.RequiredInstallResult> => {
+      const requirements = await getRequiredVersions({ groups, paths });
+      const required = [...requirements.keys()];
+      const missing = required.filter((version) => !installedVersions.includes(version));
+
+      // each install runs under `<id>:<version>`, canceling `id` skips those not started
+      const results = await runOperation(id, (signal) =>
+        mapLimit(
+          missing,
+          (version) =>
+            isEmulatedVersion(version)
+              ? installSandbox({
+                  id: `${id}:${version}`,
+                  version: toNativeVersion(version),
+                  name: EMULATED_BUILD,
+                  arch: "x64",
+                  network: requirements.get(version)?.network
+                }).then(() => version)
+              : installVersion({
+                  id: `${id}:${version}`,
+                  arch: getNativeArch() as Arch,
+                  version,
+                  network: requirements.get(version)?.network,
+                  initiator: initiatorOf(event)
+                }),
+          { signal }
+        )
+      );
+      logFailures("Could not install Node.js", results, (version) => `v${version}`);
+
+      if (results.some(({ status }) => status === "fulfilled")) {
+        installedVersions = (
+          await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+        ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+        buildTray();
+      }
+
+      return {
+        installed: results.flatMap((result) =>
+          result.status === "fulfilled" ? [result.item] : []
+        ),
+        present: required.filter((version) => !missing.includes(version)),
+        failed: results.flatMap((result) =>
+          result.status === "rejected" ? [{ version: result.item, message: result.reason }] : []
+        )
+      };
+    }
+  );
+
+  // Resolve the newest installed LTS release, else install the newest one, and switch
+  // the projects at `paths` (every project by default) to it.
+  handle(
+    "projects-move-to-lts",
+    async (event, { id, paths }: { id: string; paths?: string[] }) => {
+      const versions = await listVersions(getFetchOptions()).catch(() => [] as Nvmd.Versions);
+      const releases = versions.filter(({ lts }) => !!lts).map(({ version }) => version.slice(1));
+      const lts = releases.find((version) => installedVersions.includes(version)) ?? releases[0];
+      if (!lts) return Promise.reject("No LTS release found, check the mirror");
+
+      const install = !installedVersions.includes(lts);
+      if (install) {
+        const initiator = initiatorOf(event);
+        await installVersion({ id, arch: getNativeArch() as Arch, version: lts, initiator });
+        installedVersions = (
+          await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+        ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+        buildTray();
+      }
+
+      const projects = paths ?? (await getProjects()).map(({ path }) => path);
+      const result = await moveProjectsToVersion(projects, lts);
+      const movedGroups = (await getGroups()).filter(({ name }) =>
+        result.groups.some((group) => group.name === name)
+      );
+      notifyProjectVersions(
+        [
+          ...result.projects.map(({ path }) => path),
+          ...movedGroups.flatMap(({ projects }) => projects)
+        ],
+        lts
+      );
+      buildTray();
+      return { ...result, installed: install };
+    }
+  );
+
+  handle("active-installs", () => getActiveInstalls());
+
+  handle("install-state", (_event, version: string) => getInstallState(version));
+
+  handle("cache-cleanup-status", () => getCacheCleanupStatus());
+
+  handle("prefetch-status", () => getPrefetchStatus());
+
+  handle("network-state", async () => {
+    await checkNetwork();
+    return getNetworkState();
+  });
+
+  // the last daily report, `refresh` computes it again
+  handle("groups-freshness", async (_event, refresh: boolean = false) =>
+    !refresh && getFreshnessReport() ? getFreshnessReport() : runFreshnessReport()
+  );
+
+  handle("cache-cleanup-run", (_event, id?: string) =>
+    runOperation(id, (signal) => cleanupCache(signal))
+  );
+
+  handle("installs-interrupted", () => getInterruptedInstalls());
+
+  handle(
+    "install-retry",
+    async (event, { id, version }: { id: string; version: string }) => {
+      const entry = getInterruptedInstalls().find((item) => item.version === version);
+      if (!entry) return Promise.reject(`No interrupted install of Node.js v${version}`);
+
+      const { arch, source, location } = entry;
+      const result = await installVersion({
+        id,
+        arch: arch as Arch,
+        version,
+        source,
+        location,
+        initiator: initiatorOf(event)
+      });
+      installedVersions = (
+        await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+      ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+      buildTray();
+      return result;
+    }
+  );
+
+  handle("install-dismiss", (_event, version: string) =>
+    dismissInterruptedInstall(version)
+  );
+
+  handle("quarantine-list", () => getQuarantined());
+
+  handle("quarantine-delete", (_event, entry: string) => deleteQuarantined(entry));
+
+  // reinstall the version of a quarantined folder, which is deleted once it's done
+  handle(
+    "quarantine-repair",
+    async (event, { id, entry }: { id: string; entry: string }) => {
+      const quarantined = (await getQuarantined()).find((item) => item.entry === entry);
+      if (!quarantined?.version) return Promise.reject(`${entry} is not a quarantined version`);
+
+      const { version } = quarantined;
+      const initiator = initiatorOf(event);
+      await installVersion({ id, arch: getNativeArch() as Arch, version, initiator });
+      installedVersions = (
+        await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+      ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+      buildTray();
+      return deleteQuarantined(entry);
+    }
+  );
+
+  handle("disk-status", () => getDiskStatus());
+
+  handle("npm-cache-info", () => getNpmCacheInfo(setting.npmCache));
+
+  handle("npm-cache-clear", () => clearNpmCache(setting.npmCache));
+
+  handle("versions-disk-usage", () => getDiskUsage(setting.directory));
+
+  handle("locked-versions", () => getLockedVersions());
+
+  handle("version-usage", () => getVersionUsage(installedVersions));
+
+  handle("versions-orphaned", () =>
+    getOrphanedVersions(setting.directory, installedVersions)
+  );
+
+  handle("version-lock", (_event, version: string, locked: boolean) =>
+    setVersionLocked(version, locked)
+  );
+
+  handle(
+    "versions-migrate",
+    (_event, directory: string, mode: Nvmd.MigrateMode, id?: string) => {
+      if (getActiveInstalls().length)
+        return Promise.reject("Wait for the running installs to finish");
+      return runOperation(id, async (signal) => {
+        const from = setting.directory;
+        await createRollbackPoint("relocation", from);
+        return migrateVersions({ from, to: normalizePath(directory), mode, signal });
+      });
+    }
+  );
+
+  handle("rollback-points", () => getRollbackPoints());
+
+  // after a `conflict`, the config files are read again before the window reloads
+  handle("configs-reload", async () => {
+    await onConfigChanged("setting");
+    await onConfigChanged("projects");
+  });
+
+  // config files first, the setting may point back to another install directory
+  handle("rollback-last-operation", async () => {
+    const result = await rollbackLastOperation(setting.directory);
+    await onConfigChanged("setting");
+    await onConfigChanged("projects");
+    await onConfigChanged("groups");
+    installedVersions = (
+      await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+    ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+    const current = await getCurrentVersion();
+    [mainWindow, getQuickWindow()].forEach((window) =>
+      window?.webContents.send("current-version-update", current)
+    );
+    buildTray();
+    return result;
+  });
+
+  handle("download-headers-get", () => getMaskedHeaders());
+
+  handle("download-headers-set", (_event, headers: Record<string, string>) =>
+    setDownloadHeaders(headers)
+  );
+
+  handle("webhook-secret-get", () => hasWebhookSecret());
+
+  handle("webhook-secret-set", (_event, secret: string) => setWebhookSecret(secret));
+
+  handle("webhook-test", () => testWebhook());
+
+  handle("version-metadata", (_event, version: string, arch: Arch) =>
+    getDownloadMetadata(version, arch, getFetchOptions())
+  );
+
+  handle("verify-installation", async (_event, version: string) => {
+    return verifyInstallation({ path: setting.directory, version });
+  });
+
+  handle(
+    "repair-installation",
+    (_event, { id, arch, version }: { id: string; arch: Arch; version: string }) =>
+      reinstallVersion({ id, arch, version })
+  );
+
+  // Check every installed version against the checksum recorded at its install and the
+  // one published with the release, those that differ are reinstalled in parallel. Each
+  // reinstall runs under `<id>:<version>`, canceling `id` skips those not started.
+  handle("versions-refresh", (_event, id: string) =>
+    runOperation(id, async (signal) => {
+      const versions = [...(installedVersions || [])];
+      const archs = await getInstalledArchs();
+      let completed = 0;
+      const report = (version: string, stage: Nvmd.RefreshProgress["stage"]) => {
+        const progress: Nvmd.RefreshProgress = {
+          version,
+          stage,
+          completed,
+          total: versions.length
+        };
+        mainWindow?.webContents.send("versions-refresh:progress", id, progress);
+      };
+
+      const check = async (version: string): Promise<Nvmd.RefreshedVersion> => {
+        report(version, "checking");
+        const arch = (archs[version] ?? getNativeArch()) as Arch;
+        const { missing, mismatched } = await verifyInstallation({
+          path: setting.directory,
+          version
+        });
+        const release =
+          missing.length || mismatched.length
+            ? undefined
+            : await matchesReleaseChecksum({
+                path: setting.directory,
+                version,
+                arch,
+                fetchOpts: getFetchOptions()
+              });
+        const reason = missing.length
+          ? "missing-files"
+          : mismatched.length
+            ? "modified"
+            : release === false
+              ? "release-checksum"
+              : undefined;
+        if (!reason) return { version, reinstalled: false };
+
+        logger.info(`Reinstalling Node.js v${version} (${reason})`);
+        report(version, "reinstalling");
+        await reinstallVersion({ id: `${id}:${version}`, arch, version });
+        return { version, reinstalled: true, reason };
+      };
+
+      const results = await mapLimit(
+        versions,
+        async (version) => {
+          try {
+            const result = await check(version);
+            completed++;
+            report(version, "done");
+            return result;
+          } catch (err) {
+            completed++;
+            report(version, "failed");
+            throw err;
+          }
+        },
+        { signal }
+      );
+      results.some((result) => result.status === "fulfilled" && result.value.reinstalled) &&
+        syncShims(setting.directory).catch(() => {});
+
+      return {
+        data: results.flatMap((result) => (result.status === "fulfilled" ? [result.value] : [])),
+        warnings: toWarnings(results, (version) => `v${version}`)
+      };
+    })
+  );
+
+  handle("version-archs", () => getInstalledArchs());
+
+  // The installed build is replaced by the x64 one, or by the native one again.
+  handle(
+    "version-emulation-set",
+    async (_event, { id, version, enabled }: { id: string; version: string; enabled: boolean }) => {
+      enabled && (await assertEmulationAvailable());
+      const arch = (enabled ? "x64" : getNativeArch()) as Arch;
+      const result = await reinstallVersion({ id, arch, version });
+      syncShims(setting.directory).catch(() => {});
+      return result;
+    }
+  );
+
+  // The x64 build of the project's version is installed beside the native one, as the
+  // `<version>-x64` sandbox, and only this project is pinned to it. Disabling pins it
+  // back to the native build, which the other projects on that version keep running.
+  handle(
+    "project-emulation-set",
+    async (_event, { id, path, enabled }: { id: string; path: string; enabled: boolean }) => {
+      enabled && (await assertEmulationAvailable());
+      const version = toNativeVersion(await resolveProjectRelease(path));
+      if (!version) return Promise.reject("The project has no Node.js version");
+
+      const emulated = toEmulatedVersion(version);
+      if (enabled && !installedVersions.includes(emulated)) {
+        await installSandbox({
+          id,
+          version,
+          name: EMULATED_BUILD,
+          arch: "x64",
+          network: await resolveProjectNetwork(path)
+        });
+      }
+      await syncProjectVersion(path, enabled ? emulated : version);
+      refreshShims();
+      return setProjectEmulation(path, enabled);
+    }
+  );
+
+  handle(
+    "uninstall-node-version",
+    async (event, version: string, current: boolean = false) => {
+      await assertUnlocked(version);
+      await assertNotRunning(version, await getVersionLocation(setting.directory, version));
+      if (setting.installScope === "machine" && !(await isWritable(setting.directory))) {
+        await removeElevated(join(setting.directory, version));
+      }
+      await removeVersionLocation(setting.directory, version);
+      await uninstallVersion({ path: setting.directory, version, current });
+      await forgetSandbox(version);
+      recordEvent("uninstall", { major: version.split(".")[0] });
+      audit({ action: "uninstall", version, initiator: initiatorOf(event) });
+      notifyWebhook("uninstall", { version });
+      syncShims(setting.directory).catch(() => {});
+    }
+  );
+
+  handle("sandboxes", () => getSandboxes());
+
+  // Install `version` again as `<version>-<name>`, removed with `uninstall-node-version`.
+  handle(
+    "sandbox-create",
+    (_event, { id, version, name }: { id: string; version: string; name: string }) =>
+      installSandbox({ id, version, name, arch: getNativeArch() as Arch })
+  );
+
+  handle("current-version", async (_event, fetch: boolean = false) => {
+    const version = await getCurrentVersion(fetch);
+
+    return version;
+  });
+
+  handle("use-version", async (event, version: string) => {
+    await setCurrentVersion(version);
+    recordEvent("switch", { scope: "global" });
+    audit({ action: "switch", version, initiator: initiatorOf(event) });
+    notifyWebhook("use", { version });
+
+    // keep the other window in sync, the one switching already knows
+    [mainWindow, getQuickWindow()].forEach((window) => {
+      if (!window || window.webContents === event.sender) return;
+      window.webContents.send("current-version-update", version);
+    });
+
+    buildTray();
+    return;
+  });
+
+  handle("activation-info", () => getActivationInfo());
+
+  handle("list-commands", () => COMMANDS);
+
+  ipcMain.on("window:open-main", () => {
+    getQuickWindow()?.hide();
+    showMainWindow();
+  });
+
+  handle("app-paths", () => getAppPaths());
+
+  handle("app-info", () => getAppInfo(setting.directory));
+
+  handle("shims-sync", async () => {
+    await syncShims(setting.directory);
+    await syncRuntimeShims();
+  });
+
+  // * Other runtimes and tools (Deno, Bun, pnpm)
+  // GitHub, not the Node.js mirror, only the connection settings are shared
+  const getRuntimeFetchOptions = () => ({
+    proxy: setting.proxy,
+    certificateAuthority: getCertificates()
   });
 
-  ipcMain.handle(
+  handle("release-schedule", (_event, fetch: boolean = false) =>
+    getReleaseSchedule(getRuntimeFetchOptions(), fetch).catch((err) =>
+      Promise.reject(describeFetchError(err))
+    )
+  );
+
+  handle("runtime-releases", (_event, name: Nvmd.RuntimeName, fetch?: boolean) =>
+    getRuntimeReleases(name, getRuntimeFetchOptions(), fetch).catch((err) =>
+      Promise.reject(describeFetchError(err))
+    )
+  );
+
+  handle("runtime-info", (_event, name: Nvmd.RuntimeName) => getRuntimeInfo(name));
+
+  handle(
+    "runtime-install",
+    async (
+      _event,
+      { id, name, version }: { id: string; name: Nvmd.RuntimeName; version: string }
+    ) => {
+      return runOperation(id, (signal) =>
+        installRuntime(name, version, {
+          fetchOpts: { ...getRuntimeFetchOptions(), signal },
+          requireChecksum: isChecksumRequired(),
+          onProgress: (data) => mainWindow?.webContents.send("get-node:progress", id, data)
+        })
+      );
+    }
+  );
+
+  handle("runtime-uninstall", (_event, name: Nvmd.RuntimeName, version: string) =>
+    uninstallRuntime(name, version)
+  );
+
+  handle("runtime-default-set", (_event, name: Nvmd.RuntimeName, version: string) =>
+    setRuntimeDefault(name, version)
+  );
+
+  handle(
+    "project-runtime-set",
+    async (_event, path: string, name: Nvmd.RuntimeName, version: string) => {
+      const projects = await setProjectRuntime(path, name, version);
+      mainWindow?.webContents.send("call-projects-update", { projects });
+      return projects;
+    }
+  );
+
+  // The npm is downloaded from the project's registry, the versions no project pins
+  // anymore are removed.
+  handle("project-npm-pin", async (_event, path: string, version: string) => {
+    const project = await getProject(path);
+    if (!project) return Promise.reject(`${path} is not a project`);
+
+    if (version) {
+      const registry = getNpmRegistries(project, await getGroups())?.registry;
+      await installNpm(version, { fetchOpts: getRuntimeFetchOptions(), registry }).catch((err) =>
+        Promise.reject(err.code ? describeFetchError(err) : err)
+      );
+    }
+    const projects = await setProjectNpmVersion(path, version);
+    await removeUnusedNpm(projects.flatMap(({ npmVersion }) => (npmVersion ? [npmVersion] : [])));
+    mainWindow?.webContents.send("call-projects-update", { projects });
+    return projects;
+  });
+
+  handle(
+    "project-network-set",
+    async (_event, path: string, network?: Nvmd.NetworkOverride) => {
+      const projects = await setProjectNetwork(path, network);
+      mainWindow?.webContents.send("call-projects-update", { projects });
+      return projects;
+    }
+  );
+
+  handle(
+    "project-env-set",
+    async (_event, path: string, env?: Record<string, string>) => {
+      const projects = await setProjectEnv(path, env);
+      mainWindow?.webContents.send("call-projects-update", { projects });
+      return projects;
+    }
+  );
+
+  handle("project-npm-set", async (_event, path: string, npm?: Nvmd.NpmRegistries) => {
+    const projects = await setProjectNpm(path, npm);
+    mainWindow?.webContents.send("call-projects-update", { projects });
+    return projects;
+  });
+
+  handle("project-ca-set", async (_event, path: string, file?: string) => {
+    const projects = await setProjectCaFile(path, file);
+    mainWindow?.webContents.send("call-projects-update", { projects });
+    return projects;
+  });
+
+  handle("project-frozen-set", async (_event, path: string, frozen: boolean) => {
+    const projects = await setProjectFrozen(path, frozen);
+    mainWindow?.webContents.send("call-projects-update", { projects });
+    return projects;
+  });
+
+  // * Node.js embedded by Electron, to rebuild native modules against it
+  const electronMapping = (options: { version?: string; path?: string }) =>
+    getElectronMapping(options, {
+      fetchOpts: getRuntimeFetchOptions(),
+      installed: installedVersions
+    }).catch((err) => Promise.reject(err.code ? describeFetchError(err) : err));
+
+  handle("electron-node", (_event, options: { version?: string; path?: string }) =>
+    electronMapping(options)
+  );
+
+  handle(
+    "electron-node-install",
+    async (
+      _event,
+      { id, version, path }: { id: string; version?: string; path?: string }
+    ): Promise<Nvmd.ElectronMapping> => {
+      const mapping = await electronMapping({ version, path });
+      if (mapping.installed) return mapping;
+
+      await installVersion({
+        id,
+        arch: getNativeArch() as Arch,
+        version: mapping.node,
+        network: path ? await resolveProjectNetwork(path) : undefined
+      });
+      installedVersions = (
+        await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+      ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+      buildTray();
+      return { ...mapping, installed: true };
+    }
+  );
+
+  ipcMain.on("get-system-theme", (event) => {
+    event.returnValue = nativeTheme.shouldUseDarkColors ? "dark" : "light";
+  });
+
+  handle(
     "open-folder-selecter",
     async (
       _event,
@@ -586,50 +2480,377 @@ Promise.resolve().then(() => {
         "showHiddenFiles"
       ];
       multiple && properties.push("multiSelections");
-      const { canceled, filePaths } = await dialog.showOpenDialog(mainWindow!, {
+      const { canceled, filePaths: selected } = await dialog.showOpenDialog(mainWindow!, {
         title,
         properties: properties
       });
+      // UNC and long-path selections are stored in their regular form
+      const filePaths = selected.map((path) => normalizePath(path));
 
       if (canceled) return { canceled, filePaths };
 
       if (!project) return { canceled, filePaths };
 
-      const versions = await Promise.all(filePaths.map((path) => getVersion(path)));
+      const [versions, tracked] = await Promise.all([
+        Promise.all(filePaths.map((path) => getInheritedVersion(path))),
+        // the same folder may be tracked under another path
+        findTrackedPaths(filePaths)
+      ]);
 
-      return { canceled, filePaths, versions };
+      return { canceled, filePaths, versions, tracked };
     }
   );
 
   // * Projects
-  ipcMain.handle("get-projects", async (_event, load: boolean = false) => {
-    return getProjects(load);
+  // Folders on slow or unmounted drives are checked after answering, the projects are
+  // sent again when one of them turned out missing or back.
+  handle("get-projects", async (_event, load: boolean = false, sort?: "recent") => {
+    const projects = withKnownMissing(await getProjects(load));
+    validateProjects(projects).then(async (validated) => {
+      if (validated.every(({ missing }, index) => missing === projects[index].missing)) return;
+      const groups = await getGroups();
+      mainWindow?.webContents.send("call-projects-update", { projects: validated, groups });
+    });
+    return sort === "recent" ? sortByRecency(projects) : projects;
   });
 
-  ipcMain.handle("update-projects", async (_event, projects: Nvmd.Project[], path?: string) => {
-    await updateProjects(projects, path);
-
+  handle("relocate-project", async (_event, oldPath: string, newPath: string) => {
+    const result = await relocateProject(oldPath, newPath);
+    const version = await resolveProjectRelease(newPath);
+    version && notifyProjectVersions([newPath], version);
     buildTray();
-    return;
+    return result;
+  });
+
+  handle("project-duplicates", () => findDuplicateProjects());
+
+  handle("projects-merge", async () => {
+    const result = await mergeDuplicateProjects();
+    result.merged.length && buildTray();
+    return result;
+  });
+
+  handle(
+    "update-projects",
+    async (_event, projects: Nvmd.Project[], path?: string, options?: { trash?: boolean }) => {
+      const result = await updateProjects(projects, path, options);
+
+      buildTray();
+      return result;
+    }
+  );
+
+  handle("project-reveal", (_event, path: string) => revealInFileManager(path));
+
+  handle("project-open-editor", async (_event, path: string) =>
+    openInEditor(path, setting.editor, await getProjectEnv(path))
+  );
+
+  handle("project-open-terminal", async (_event, path: string) =>
+    openInTerminal(path, await getProjectEnv(path))
+  );
+
+  handle(
+    "versions-benchmark",
+    (
+      _event,
+      {
+        id,
+        versions,
+        script,
+        runs
+      }: { id?: string; versions: string[]; script?: string; runs?: number }
+    ) => {
+      const missing = versions.filter((version) => !installedVersions.includes(version));
+      if (missing.length) {
+        return Promise.reject(`Not installed: ${missing.map((version) => `v${version}`)}`);
+      }
+      return runOperation(id, (signal) =>
+        benchmarkVersions(setting.directory, versions, { script, runs, signal })
+      );
+    }
+  );
+
+  // the version's own environment, the global version is left as it is
+  handle("launch-repl", (_event, version: string) => {
+    if (!installedVersions.includes(version)) {
+      return Promise.reject(`Node.js v${version} is not installed`);
+    }
+
+    const node = join(
+      getNodeBinPath(setting.directory, version),
+      platform === "win32" ? "node.exe" : "node"
+    );
+    return openRepl(node, HOME, getNodeEnv(setting.directory, version));
+  });
+
+  handle("project-copy-bin-path", async (_event, path: string) => {
+    const version = await resolveProjectRelease(path);
+    if (!version) return Promise.reject(`No Node.js version is set for ${path}`);
+
+    return copyToClipboard(getNodeBinPath(setting.directory, version));
+  });
+
+  handle("project-version", (_event, path: string) => resolveProjectVersion(path));
+
+  // What a `.nvmdrc` value like `20`, `lts/*` or `v20.11.1` resolves to, or why it can't
+  handle("version-spec-resolve", async (_event, spec: string) => {
+    const versions = await listVersions(getFetchOptions()).catch(() => [] as Nvmd.Versions);
+    return resolveVersionSpec(spec, { installed: installedVersions, versions });
+  });
+
+  handle("project-details", async (_event, path: string) => {
+    const value = await resolveProjectVersion(path);
+    const versions = await listVersions(getFetchOptions()).catch(() => [] as Nvmd.Versions);
+    const spec = value
+      ? resolveVersionSpec(value, { installed: installedVersions, versions })
+      : undefined;
+    const version = spec?.version ?? "";
+    const project = (await getProjects()).find((project) => project.path === path);
+    const details: Nvmd.ProjectDetails = {
+      path,
+      version,
+      ...(spec && { spec }),
+      installed: !!version && !!installedVersions?.includes(version),
+      sources: await getVersionSources(path),
+      runtimes: project?.runtimes ?? {},
+      yarn: await getYarnStatus(setting.directory, version, path)
+    };
+    return details;
+  });
+
+  handle("project-yarn-provision", async (_event, path: string) => {
+    const version = await resolveProjectRelease(path);
+    if (!version) return Promise.reject(`No Node.js version is set for ${path}`);
+
+    const status = await provisionYarn(setting.directory, version, path);
+    await syncShims(setting.directory);
+    return status;
+  });
+
+  handle("switch-history", () => getSwitchHistory());
+
+  handle("switch-undo", (event) => undoSwitch(initiatorOf(event)));
+
+  handle("audit-log-query", (_event, filter?: Nvmd.AuditFilter) => queryAuditLog(filter));
+
+  // answered from memory, for the prompt segments asking after every command
+  handle("prompt-info", async (_event, cwd: string) =>
+    getPromptInfo(cwd, { installed: installedVersions, current: await getCurrentVersion() })
+  );
+
+  handle("prompt-segment", (_event, tool: Nvmd.PromptTool) => getPromptSegment(tool));
+
+  handle("shell-completion", (_event, shell: Nvmd.CompletionShell) =>
+    getCompletionScript(shell)
+  );
+
+  handle("project-scripts", (_event, path: string) => listScripts(path));
+
+  // Output lines come through `project-script:output` under the operation `id`, which
+  // stops the script when canceled.
+  handle(
+    "project-script-run",
+    async (_event, { id, path, name }: { id: string; path: string; name: string }) => {
+      // checked again on each restart, the project may have switched meanwhile
+      const prepare = async () => {
+        const version = await resolveProjectRelease(path);
+        if (!version) return Promise.reject(`No Node.js version is set for ${path}`);
+        if (!installedVersions.includes(version))
+          return Promise.reject(`Node.js v${version} is not installed`);
+        return { env: await getProjectEnv(path), npmCli: await getProjectNpmCli(path) };
+      };
+
+      await prepare();
+      return runOperation(id, (signal) =>
+        runTrackedScript(id, path, name, {
+          prepare,
+          signal,
+          onOutput: (line, stream) =>
+            mainWindow?.webContents.send("project-script:output", id, line, stream)
+        })
+      );
+    }
+  );
+
+  // `npm audit` of the project with the npm of its version, canceled through `id`
+  handle("project-audit", async (_event, path: string, id?: string) => {
+    const version = await resolveProjectRelease(path);
+    if (!version) return Promise.reject(`No Node.js version is set for ${path}`);
+    if (!installedVersions.includes(version))
+      return Promise.reject(`Node.js v${version} is not installed`);
+
+    const [env, npmCli] = await Promise.all([getProjectEnv(path), getProjectNpmCli(path)]);
+    return runOperation(id, (signal) => auditProject(path, version, { env, npmCli, signal }));
   });
 
-  ipcMain.handle("sync-project-version", (_event, path: string, version: string) => {
-    return syncProjectVersion(path, version);
+  handle("project-drift", (_event, id?: string) =>
+    runOperation(id, (signal) => detectProjectDrift(signal))
+  );
+
+  handle(
+    "project-drift-resolve",
+    async (_event, path: string, action: Nvmd.DriftAction) => {
+      const result = await resolveProjectDrift(path, action);
+      buildTray();
+      return result;
+    }
+  );
+
+  handle("sync-project-version", async (event, path: string, version: string) => {
+    const code = await syncProjectVersion(path, version);
+    code === 200 && (await touchProject(path));
+    recordEvent("switch", { scope: "project" });
+    audit({ action: "switch", version, project: path, initiator: initiatorOf(event) });
+    code === 200 && notifyProjectVersions([path], version);
+    return code;
   });
 
-  ipcMain.handle(
+  handle(
+    "projects-export",
+    async (_event, { title, format }: { title: string; format: Nvmd.ProjectListFormat }) => {
+      const { canceled, filePath } = await dialog.showSaveDialog(mainWindow!, {
+        title,
+        defaultPath: `projects_${Date.now()}.${format}`,
+        filters: [{ name: format.toUpperCase(), extensions: [format] }]
+      });
+
+      if (canceled || !filePath) return { canceled: true };
+
+      const count = await exportProjectList(filePath, format);
+      return { canceled, count };
+    }
+  );
+
+  handle("environment-lock-export", async (_event, directory: string) =>
+    exportEnvironmentLock(
+      directory,
+      await createEnvironmentLock(installedVersions, await getCurrentVersion())
+    )
+  );
+
+  // install the locked versions missing here, then set the groups, the projects and
+  // the global version as locked
+  handle("environment-lock-apply", (_event, file: string, id: string) =>
+    runOperation(id, async (signal): Promise<Nvmd.CommandResult<Nvmd.EnvironmentLockApply>> => {
+      const lock = await readEnvironmentLock(file);
+      const warnings: Nvmd.CommandWarning[] = [];
+      const installed: string[] = [];
+      for (const locked of lock.versions) {
+        const { version } = locked;
+        if (!installedVersions.includes(version)) {
+          if (signal.aborted) {
+            warnings.push({ message: "Canceled before all the versions were installed" });
+            break;
+          }
+
+          try {
+            const arch = (getLockedArch(locked) || getNativeArch()) as Arch;
+            await installVersion({
+              id: `${id}:${version}`,
+              arch,
+              version,
+              initiator: "lock",
+              signal
+            });
+            installed.push(version);
+          } catch (err) {
+            warnings.push({ message: `${err.message || err}`, subject: version });
+            continue;
+          }
+        }
+
+        (await matchesLockedChecksum(locked)) === false &&
+          warnings.push({
+            message: "Its node executable differs from the locked one",
+            subject: version
+          });
+      }
+      installedVersions = (
+        await allInstalledNodeVersions({ path: setting.directory, refresh: true })
+      ).sort((version1, version2) => (gt(version2, version1) ? 1 : -1));
+      // the projects, groups and global version are left as they were
+      if (signal.aborted) {
+        buildTray();
+        return { data: { installed, added: 0, updated: 0, skipped: [] }, warnings };
+      }
+
+      const applied = await applyLockedPins(lock);
+      const { projects, groups, added, updated, skipped } = applied;
+      warnings.push(...applied.warnings);
+      skipped.forEach((path) =>
+        warnings.push({ message: "Not found on this machine, skipped", subject: path })
+      );
+      mainWindow?.webContents.send("call-projects-update", { projects, groups });
+
+      let current: string | undefined;
+      if (lock.current && lock.current !== (await getCurrentVersion())) {
+        if (installedVersions.includes(lock.current)) {
+          current = lock.current;
+          await switchVersion(current, "lock");
+        } else {
+          warnings.push({
+            message: "Not installed, the global version is unchanged",
+            subject: lock.current
+          });
+        }
+      }
+
+      buildTray();
+      const data = { installed, added, updated, skipped, ...(current && { current }) };
+      return { data, warnings };
+    })
+  );
+
+  handle("projects-import", async (_event, { title }: { title: string }) => {
+    const { canceled, filePaths } = await dialog.showOpenDialog(mainWindow!, {
+      title,
+      filters: [{ name: "", extensions: ["json", "csv"] }],
+      properties: ["openFile", "showHiddenFiles"]
+    });
+
+    if (canceled) return { canceled };
+
+    const result = await importProjectList(filePaths[0]);
+    const { projects, groups } = result;
+    mainWindow?.webContents.send("call-projects-update", { projects, groups });
+    buildTray();
+    return { canceled, ...result };
+  });
+
+  // Scripts the app runs can be restarted, other processes are only reported.
+  handle("project-processes", async (_event, path: string) => ({
+    scripts: getRunningScripts(path),
+    processes: await getProjectProcesses(path)
+  }));
+
+  handle(
+    "project-processes-restart",
+    (_event, path: string, action: Nvmd.ProcessAction) =>
+      stopRunningScripts(path, action, cancelOperation)
+  );
+
+  handle(
     "update-project-remove-group",
-    (_event, projectsPath: string[], groupName: string, version: string) => {
-      return updateProjectAndSyncVersion({
+    async (_event, projectsPath: string[], groupName: string, version: string) => {
+      const projects = await updateProjectAndSyncVersion({
         projects: projectsPath,
         groupName,
         version
       });
+      // frozen projects kept their previous version
+      notifyProjectVersions(
+        projects
+          .filter(({ path, version }) => projectsPath.includes(path) && version === groupName)
+          .map(({ path }) => path),
+        version
+      );
+      return projects;
     }
   );
 
   // * Configration
-  ipcMain.handle("configration-export", async (_event, args: Nvmd.ConfigrationExport) => {
+  handle("configration-export", async (_event, args: Nvmd.ConfigrationExport) => {
     const { color, setting: exportSetting, projects, path, mirrors } = args;
     let output: Nvmd.Configration = {};
 
@@ -646,7 +2867,7 @@ Promise.resolve().then(() => {
     return configrationExport(path, output);
   });
 
-  ipcMain.handle(
+  handle(
     "configration-import",
     async (_event, { sync, title }: { sync: boolean; title: string }) => {
       const { canceled, filePaths } = await dialog.showOpenDialog(mainWindow!, {
@@ -655,7 +2876,7 @@ Promise.resolve().then(() => {
         properties: ["openFile", "createDirectory", "showHiddenFiles"]
       });
 
-      if (canceled) return { canceled };
+      if (canceled) return { data: { canceled }, warnings: [] };
       const [path] = filePaths;
       const {
         color,
@@ -665,12 +2886,15 @@ Promise.resolve().then(() => {
         groups
       } = await configrationImport(path);
 
+      const warnings: Nvmd.CommandWarning[] = [];
       if (projects) {
-        await updateProjectsAndSync({
-          projects,
-          groups,
-          sync
-        });
+        warnings.push(
+          ...(await updateProjectsAndSync({
+            projects,
+            groups,
+            sync
+          }))
+        );
         projects && mainWindow?.webContents.send("call-projects-update", { projects, groups });
 
         setTimeout(() => {
@@ -678,22 +2902,64 @@ Promise.resolve().then(() => {
         });
       }
 
-      return { canceled, color, mirrors, setting: importSetting };
+      return { data: { canceled, color, mirrors, setting: importSetting }, warnings };
     }
   );
 
+  // * Config backups
+  handle("config-backups-get", async () => {
+    const backups = await getConfigBackups();
+    return { backups, recovered: getRecoveredConfigs() };
+  });
+
+  handle("config-backup-restore", async (_event, name: string, id: string) => {
+    const files: Record<string, string> = {
+      setting: SETTING_JSONFILE,
+      projects: PROJECTS_JSONFILE,
+      groups: GROUPS_JSONFILE
+    };
+    if (!files[name]) return Promise.reject(`Unknown config file: ${name}`);
+
+    await restoreConfigBackup(files[name], id);
+
+    if (name === "setting") {
+      setting = await getSetting();
+      return;
+    }
+
+    const [projects, groups] = await Promise.all([getProjects(true), getGroups(true)]);
+    mainWindow?.webContents.send("call-projects-update", { projects, groups });
+    buildTray();
+    return;
+  });
+
   // * Groups
-  ipcMain.handle("group-get", (_event, load: boolean = false) => getGroups(load));
+  handle("group-get", (_event, load: boolean = false) => getGroups(load));
 
-  ipcMain.handle("group-create", (_event, group: Nvmd.Group) => createGroup(group));
+  handle("group-create", (_event, group: Nvmd.Group) => createGroup(group));
 
-  ipcMain.handle("group-update", async (_event, groups: Nvmd.Group[]) => {
+  handle("group-update", async (_event, groups: Nvmd.Group[]) => {
     await updateGroups(groups);
     buildTray();
     return;
   });
 
-  ipcMain.handle("group-update-version", (_event, group: Nvmd.Group, version: string) =>
-    updateGroupVersion(group, version)
+  handle("group-update-version", async (event, group: Nvmd.Group, version: string) => {
+    const result = await updateGroupVersion(group, version);
+    audit({ action: "switch", version, group: group.name, initiator: initiatorOf(event) });
+    notifyProjectVersions(result.find(({ name }) => name === group.name)?.projects ?? [], version);
+    return result;
+  });
+
+  handle("group-network-set", (_event, name: string, network?: Nvmd.NetworkOverride) =>
+    setGroupNetwork(name, network)
+  );
+
+  handle("group-env-set", (_event, name: string, env?: Record<string, string>) =>
+    setGroupEnv(name, env)
+  );
+
+  handle("group-npm-set", (_event, name: string, npm?: Nvmd.NpmRegistries) =>
+    setGroupNpm(name, npm)
   );
 });
@@ -0,0 +1,83 @@
+import { join } from "node:path";
+import { platform } from "node:process";
+import { app, BrowserWindow, screen } from "electron";
+import { resolveHtmlPath } from "./utils/resolvePath";
+
+import type { Rectangle } from "electron";
+
+const WIDTH = 300,
+  HEIGHT = 380;
+
+let quickWindow: BrowserWindow | null = null;
+
+export const getQuickWindow = () => quickWindow;
+
+// Next to the tray icon when its position is known, the cursor's display otherwise.
+function getPosition(trayBounds?: Rectangle) {
+  const point = trayBounds ?? screen.getCursorScreenPoint();
+  const { workArea } = screen.getDisplayNearestPoint({ x: point.x, y: point.y });
+
+  const x = trayBounds
+    ? trayBounds.x + trayBounds.width / 2 - WIDTH / 2
+    : workArea.x + (workArea.width - WIDTH) / 2;
+  const y = trayBounds
+    ? trayBounds.y < workArea.y + workArea.height / 2
+      ? workArea.y + 4
+      : workArea.y + workArea.height - HEIGHT - 4
+    : workArea.y + (workArea.height - HEIGHT) / 2;
+
+  return {
+    x: Math.round(Math.min(Math.max(x, workArea.x), workArea.x + workArea.width - WIDTH)),
+    y: Math.round(y)
+  };
+}
+
+// A small always-on-top window to switch versions and a few settings from the
+// tray, without loading the main window. It's hidden as soon as it loses focus
+// and kept around, so showing it again is instant.
+export function toggleQuickWindow({
+  backgroundColor,
+  trayBounds
+}: {
+  backgroundColor: string;
+  trayBounds?: Rectangle;
+}) {
+  if (quickWindow && quickWindow.isVisible()) {
+    quickWindow.hide();
+    return;
+  }
+
+  if (!quickWindow) {
+    quickWindow = new BrowserWindow({
+      show: false,
+      frame: false,
+      width: WIDTH,
+      height: HEIGHT,
+      resizable: false,
+      minimizable: false,
+      maximizable: false,
+      fullscreenable: false,
+      skipTaskbar: true,
+      alwaysOnTop: true,
+      backgroundColor,
+      webPreferences: {
+        preload: app.isPackaged
+          ? join(__dirname, "../preload/preload.js")
+          : join(__dirname, "../../out/preload/preload.js")
+      }
+    });
+    platform === "darwin" && quickWindow.setVisibleOnAllWorkspaces(true);
+    quickWindow.loadURL(`${resolveHtmlPath("index.html")}#quick`);
+
+    quickWindow.on("blur", () => quickWindow?.hide());
+    quickWindow.on("closed", () => {
+      quickWindow = null;
+    });
+  }
+
+  const { x, y } = getPosition(trayBounds);
+  quickWindow.setPosition(x, y);
+  quickWindow.webContents.isLoading()
+    ? quickWindow.once("ready-to-show", () => quickWindow?.show())
+    : quickWindow.show();
+}
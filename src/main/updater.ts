@@ -1,14 +1,25 @@
+import { stat } from "node:fs/promises";
 import { ipcMain } from "electron";
 import { autoUpdater } from "electron-updater";
-import log from "electron-log";
+import { logger } from "./utils/logger";
 
 import type { BrowserWindow } from "electron";
 
 export class AppUpdater {
-  constructor(private readonly mainWindow: BrowserWindow) {
-    log.transports.file.level = "info";
-    autoUpdater.logger = log;
+  // bytes fetched by the last download, as told by its progress
+  private transferred = 0;
+
+  constructor(
+    private readonly mainWindow: BrowserWindow,
+    channel: Nvmd.UpdateChannel = "stable"
+  ) {
+    autoUpdater.logger = logger;
     autoUpdater.autoDownload = false;
+    // Only the blocks of the installer that changed are downloaded, against the
+    // `.blockmap` files published next to the NSIS installer, the AppImage and the
+    // macOS zip (`differentialPackage` in the build config). A delta that fails is
+    // downloaded again in full, see `comfirmUpdate`.
+    this.setChannel(channel);
 
     autoUpdater.on("update-available", (info) => {
       // had updates
@@ -21,7 +32,8 @@ export class AppUpdater {
     });
 
     autoUpdater.on("download-progress", (progress) => {
-      // download progress
+      // download progress, `total` counts only the changed blocks of a delta update
+      this.transferred = progress.total;
       mainWindow?.webContents.send("download-progress", progress);
     });
 
@@ -55,11 +67,42 @@ export class AppUpdater {
   }
 
   comfirmUpdate() {
-    ipcMain.handle("confirm-update", () => {
-      return autoUpdater.downloadUpdate();
+    ipcMain.handle("confirm-update", async (): Promise<Nvmd.UpdateDownload> => {
+      this.transferred = 0;
+      let file: string,
+        fallback = false;
+      try {
+        [file] = await autoUpdater.downloadUpdate();
+      } catch (err) {
+        // e.g. a blockmap missing from the release or not matching the installer
+        logger.warn(`Delta update failed, downloading the full installer: ${err.message || err}`);
+        fallback = true;
+        this.transferred = 0;
+        autoUpdater.disableDifferentialDownload = true;
+        try {
+          [file] = await autoUpdater.downloadUpdate();
+        } finally {
+          autoUpdater.disableDifferentialDownload = false;
+        }
+      }
+      const { size } = await stat(file);
+      // nothing is reported when the update was downloaded before
+      const differential = !fallback && this.transferred > 0 && this.transferred < size;
+      logger.info(
+        differential
+          ? `Downloaded a delta update: ${this.transferred} of ${size} bytes`
+          : `Downloaded the full update: ${size} bytes`
+      );
+      return { size, transferred: this.transferred, differential, fallback };
     });
   }
 
+  // GitHub releases marked as pre-release are the beta feed.
+  setChannel(channel: Nvmd.UpdateChannel = "stable") {
+    autoUpdater.allowPrerelease = channel === "beta";
+    autoUpdater.allowDowngrade = false;
+  }
+
   clearMainBindings() {
     autoUpdater.removeAllListeners();
   }
@@ -0,0 +1,83 @@
+import { platform } from "node:process";
+import { join } from "node:path";
+import { lstat, readlink, rename, rm, symlink } from "node:fs/promises";
+import { ensureDir, pathExists } from "fs-extra";
+import { APPDIR, CURRENT_LINK, INSTALL_DIR } from "../constants";
+import { logger } from "./logger";
+
+let mode: Nvmd.ActivationMode = "shim",
+  directory: string = INSTALL_DIR;
+
+export function setActivation(
+  activation: Nvmd.ActivationMode = "shim",
+  dir: string = INSTALL_DIR
+) {
+  mode = activation;
+  directory = dir;
+}
+
+// Junctions don't require the symlink privilege (admin or developer mode) on Windows.
+const LINK_TYPE = platform === "win32" ? "junction" : "dir";
+
+// Directory to put on PATH when the symlink activation mode is used.
+export const CURRENT_BIN = platform === "win32" ? CURRENT_LINK : join(CURRENT_LINK, "bin");
+
+// Repoint `~/.nvmd/current` to the installed `version`. A new link is created next to
+// the old one and renamed over it, so `current` never points nowhere on POSIX systems.
+// Windows can't rename over an existing junction, it's removed right before instead.
+export async function linkCurrent(version: string) {
+  if (mode !== "symlink") return;
+
+  const target = join(directory, version);
+  if (!(await pathExists(target))) return Promise.reject(`Node.js v${version} is not installed`);
+
+  const tmpLink = `${CURRENT_LINK}.${process.pid}.tmp`;
+  await ensureDir(APPDIR);
+  await rm(tmpLink, { force: true }).catch(() => {});
+  await symlink(target, tmpLink, LINK_TYPE);
+
+  try {
+    platform === "win32" && (await unlinkCurrent());
+    await rename(tmpLink, CURRENT_LINK);
+  } catch (err) {
+    await rm(tmpLink, { force: true }).catch(() => {});
+    throw err;
+  }
+  logger.info(`Linked ${CURRENT_LINK} to ${target}`);
+}
+
+export async function unlinkCurrent() {
+  try {
+    if ((await lstat(CURRENT_LINK)).isSymbolicLink()) await rm(CURRENT_LINK);
+  } catch {}
+}
+
+// Check that links can be created in the app directory, this fails on Windows when
+// the folder is on a file system without junction support or lacks write permission.
+async function canLink() {
+  const probe = join(APPDIR, `.link-probe-${process.pid}`);
+  try {
+    await ensureDir(APPDIR);
+    await symlink(APPDIR, probe, LINK_TYPE);
+    await rm(probe);
+    return true;
+  } catch (err) {
+    logger.warn(`Cannot create links in ${APPDIR}: ${err.message}`);
+    return false;
+  }
+}
+
+export async function getActivationInfo(): Promise<Nvmd.ActivationInfo> {
+  let target: string | undefined;
+  try {
+    target = await readlink(CURRENT_LINK);
+  } catch {}
+
+  return {
+    mode,
+    link: CURRENT_LINK,
+    binPath: CURRENT_BIN,
+    target,
+    linkable: await canLink()
+  };
+}
@@ -0,0 +1,73 @@
+import { execFile } from "node:child_process";
+import { join } from "node:path";
+import { platform } from "node:process";
+import { move, pathExists, readdir, stat } from "fs-extra";
+import { getActiveInstalls } from "./installs";
+import { recordInstallation, verifyInstallation } from "./verify";
+import { logger } from "./logger";
+import { assertVersionAllowed } from "./policy";
+
+const NODE_EXECUTABLE = platform === "win32" ? "node.exe" : join("bin", "node");
+
+// The folder of a release archive (`node-v20.11.1-linux-x64`, `node-v20.11.1-win-x64`),
+// or a version with its `v` (`v20.11.1`).
+const UNPACKED_NAME = /^(?:node-)?v(\d+\.\d+\.\d+)(?:-[a-z]+-[a-z0-9]+)?$/i;
+
+// The folders that couldn't be adopted, by name, with the mtime they had. They're
+// reported once, and tried again only when they change.
+const failures = new Map<string, number>();
+
+// `process.version` and `process.arch` of the node executable, the version must be
+// the one of the folder name.
+const probe = (node: string) =>
+  new Promise<{ version: string; arch: string }>((resolve, reject) => {
+    const args = ["-p", "process.version + ' ' + process.arch"];
+    execFile(node, args, { timeout: 10 * 1000, windowsHide: true }, (err, stdout, stderr) => {
+      if (err) return reject(stderr.trim() || err.message);
+      const [version, arch] = stdout.trim().split(" ");
+      resolve({ version: version.replace(/^v/, ""), arch });
+    });
+  });
+
+async function adopt(directory: string, name: string, version: string) {
+  const { valid: complete, missing } = await verifyInstallation({ path: directory, version: name });
+  if (!complete) throw new Error(`missing ${missing.join(", ")}`);
+
+  const { version: actual, arch } = await probe(join(directory, name, NODE_EXECUTABLE));
+  if (actual !== version) throw new Error(`its node is v${actual}`);
+
+  if (await pathExists(join(directory, version))) throw new Error(`v${version} is installed`);
+  assertVersionAllowed(version);
+  await move(join(directory, name), join(directory, version));
+  await recordInstallation({ path: directory, version, arch, source: "manual" });
+  return { version, arch };
+}
+
+// Versions unpacked by hand into `directory`, release archives extracted as they
+// come. Their layout is checked and their node run once, then they are renamed after
+// their version and recorded with their architecture like the versions the app
+// installed. Those that fail are left as they are, out of the list, and reported once.
+export async function adoptManualInstalls(directory: string): Promise<Nvmd.AdoptedInstalls> {
+  const result: Nvmd.AdoptedInstalls = { adopted: [], failed: [] };
+  if (getActiveInstalls().length || !(await pathExists(directory))) return result;
+
+  for (const name of await readdir(directory)) {
+    const version = UNPACKED_NAME.exec(name)?.[1];
+    if (!version || !(await pathExists(join(directory, name, NODE_EXECUTABLE)))) continue;
+
+    const { mtimeMs } = await stat(join(directory, name));
+    if (failures.get(name) === mtimeMs) continue;
+
+    try {
+      const adopted = await adopt(directory, name, version);
+      logger.info(`Adopted ${name} as Node.js v${version} (${adopted.arch})`);
+      result.adopted.push({ name, ...adopted });
+    } catch (err) {
+      const message = `${err.message || err}`;
+      logger.warn(`Could not adopt ${name}: ${message}`);
+      failures.set(name, mtimeMs);
+      result.failed.push({ name, version, message });
+    }
+  }
+  return result;
+}
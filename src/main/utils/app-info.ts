@@ -0,0 +1,50 @@
+import { execFile } from "node:child_process";
+import { release } from "node:os";
+import { join } from "node:path";
+import { arch, platform, versions } from "node:process";
+import { app } from "electron";
+import { pathExists } from "fs-extra";
+import { BIN_DIR } from "../constants";
+import { getNativeArch } from "./arch";
+import { getAppPaths } from "./paths";
+
+const SHIM = join(BIN_DIR, platform === "win32" ? "nvmd.exe" : "nvmd");
+const SHIM_TIMEOUT = 5 * 1000;
+
+// Set when the main process is bundled (see electron.vite.config.ts), unknown when
+// it's built outside of a git checkout. The bundler only replaces the literal
+// `process.env.NVMD_*`, they can't be read through `env`.
+export const getBuildInfo = () => ({
+  commit: process.env.NVMD_COMMIT || "unknown",
+  ...(process.env.NVMD_BUILD_DATE && { date: process.env.NVMD_BUILD_DATE })
+});
+
+// `nvmd --version` prints `nvmd 3.2.0`, the shim may be older than the app.
+async function getShimVersion() {
+  if (!(await pathExists(SHIM))) return;
+
+  return new Promise<string | undefined>((resolve) => {
+    execFile(SHIM, ["--version"], { timeout: SHIM_TIMEOUT, windowsHide: true }, (err, stdout) =>
+      resolve(err ? undefined : stdout.trim().split(/\s+/).pop())
+    );
+  });
+}
+
+// The app and its environment, the same values for the about screen and bug reports.
+export async function getAppInfo(directory: string): Promise<Nvmd.AppInfo> {
+  const shim = await getShimVersion();
+  return {
+    version: app.getVersion(),
+    build: getBuildInfo(),
+    packaged: app.isPackaged,
+    os: platform,
+    osRelease: release(),
+    arch,
+    nativeArch: getNativeArch(),
+    electron: versions.electron,
+    chrome: versions.chrome,
+    node: versions.node,
+    paths: { ...getAppPaths(), install: directory },
+    ...(shim && { shim })
+  };
+}
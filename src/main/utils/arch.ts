@@ -0,0 +1,23 @@
+import { arch, platform } from "node:process";
+import { app } from "electron";
+import { gte } from "semver";
+
+// Official win-arm64 builds are published since Node.js 19.9.0.
+const WINDOWS_ARM64_VERSION = "19.9.0";
+
+// CPU architecture of the machine. An x64 build of the app runs emulated on
+// Windows on ARM (and under Rosetta on macOS), where `process.arch` says x64.
+export const getNativeArch = () => (app.runningUnderARM64Translation ? "arm64" : arch);
+
+// Architecture to install `version` for: versions without a win-arm64 build fall
+// back to x64, which Windows on ARM runs emulated.
+export function resolveInstallArch(version: string, arch: string) {
+  if (platform !== "win32" || arch !== "arm64" || gte(version, WINDOWS_ARM64_VERSION)) {
+    return { arch };
+  }
+
+  return {
+    arch: "x64",
+    warning: `Node.js v${version} has no win-arm64 build, the x64 build is installed instead`
+  };
+}
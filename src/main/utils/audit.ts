@@ -0,0 +1,50 @@
+import { appendFile, ensureFile } from "fs-extra";
+import { AUDIT_LOGFILE } from "../constants";
+import { readJsonLines, trimJsonLines } from "./jsonl";
+import { logger } from "./logger";
+
+const DEFAULT_LIMIT = 100;
+// Entries older than that are dropped, and at most that many kept.
+const MAX_AGE_DAYS = 365;
+const MAX_ENTRIES = 5000;
+// the file is trimmed on the first write and then every that many writes
+const TRIM_EVERY = 100;
+
+// Writes go one after the other, a trim must not drop an entry appended meanwhile.
+let queue: Promise<void> = Promise.resolve(),
+  writes = 0;
+
+async function write(entry: Omit<Nvmd.AuditEntry, "at">) {
+  try {
+    await ensureFile(AUDIT_LOGFILE);
+    writes++ % TRIM_EVERY === 0 &&
+      (await trimJsonLines(AUDIT_LOGFILE, { maxAgeDays: MAX_AGE_DAYS, maxEntries: MAX_ENTRIES }));
+    const line = JSON.stringify({ at: new Date().toISOString(), ...entry });
+    await appendFile(AUDIT_LOGFILE, `${line}\n`);
+  } catch (err) {
+    logger.warn(`Could not write to the audit log: ${err.message}`);
+  }
+}
+
+// One JSON entry per line, appended to by the app.
+export function audit(entry: Omit<Nvmd.AuditEntry, "at">) {
+  queue = queue.then(() => write(entry));
+  return queue;
+}
+
+const matches = (entry: Nvmd.AuditEntry, filter: Nvmd.AuditFilter) =>
+  (!filter.action || entry.action === filter.action) &&
+  (!filter.version || entry.version === filter.version.replace(/^v/, "")) &&
+  (!filter.project || entry.project === filter.project) &&
+  (!filter.initiator || entry.initiator === filter.initiator) &&
+  (!filter.since || entry.at >= filter.since) &&
+  (!filter.until || entry.at <= filter.until);
+
+// Entries matching every field of `filter`, newest first.
+export async function queryAuditLog(filter: Nvmd.AuditFilter = {}): Promise<Nvmd.AuditEntry[]> {
+  const entries = await readJsonLines<Nvmd.AuditEntry>(AUDIT_LOGFILE);
+  return entries
+    .filter((entry) => matches(entry, filter))
+    .reverse()
+    .slice(0, filter.limit ?? DEFAULT_LIMIT);
+}
@@ -0,0 +1,52 @@
+import { platform } from "node:process";
+import { homedir } from "node:os";
+import { join } from "node:path";
+import { app } from "electron";
+import { outputFile, pathExists, remove } from "fs-extra";
+import { logger } from "./logger";
+
+// Passed when launched at login, the window then stays in the tray.
+export const HIDDEN_ARG = "--hidden";
+
+const AUTOSTART_FILE = join(
+  process.env.XDG_CONFIG_HOME || join(homedir(), ".config"),
+  "autostart",
+  "nvm-desktop.desktop"
+);
+
+// Login items on macOS and Windows, an XDG autostart entry on Linux.
+export async function setLaunchAtLogin(enabled: boolean = false) {
+  try {
+    if (platform !== "linux") {
+      app.setLoginItemSettings({ openAtLogin: enabled, args: [HIDDEN_ARG] });
+      return;
+    }
+
+    if (!enabled) {
+      (await pathExists(AUTOSTART_FILE)) && (await remove(AUTOSTART_FILE));
+      return;
+    }
+
+    // AppImages run from a temporary mount, the image itself has to be started
+    const exec = process.env.APPIMAGE || process.execPath;
+    await outputFile(
+      AUTOSTART_FILE,
+      [
+        "[Desktop Entry]",
+        "Type=Application",
+        "Name=NVM-Desktop",
+        `Exec="${exec}" ${HIDDEN_ARG}`,
+        "X-GNOME-Autostart-enabled=true",
+        ""
+      ].join("\n")
+    );
+  } catch (err) {
+    logger.warn(`Could not update the launch at login entry: ${err.message}`);
+  }
+}
+
+// Whether this launch shouldn't show the main window.
+export const shouldStartHidden = (startHidden: boolean = false) =>
+  startHidden ||
+  process.argv.includes(HIDDEN_ARG) ||
+  (platform === "darwin" && app.getLoginItemSettings().wasOpenedAtLogin);
@@ -0,0 +1,133 @@
+import { basename, join } from "node:path";
+import { copy, ensureDir, pathExists, readFile, readJson, readdir, remove, stat } from "fs-extra";
+import { BACKUPS_DIR } from "../constants";
+import { writeFileAtomic } from "./file";
+import { setConfigRevision, withConfigLock } from "./config-lock";
+import { logger } from "./logger";
+import { fromPortableConfig, toPortableConfig } from "./portable-paths";
+
+// Number of good copies kept for each config file.
+const MAX_BACKUPS = 10;
+
+const recovered: Nvmd.ConfigBackup[] = [];
+
+const savedListeners: ((file: string) => void)[] = [];
+
+// Get notified after a config file has been written by the app.
+export function onConfigSaved(listener: (file: string) => void) {
+  savedListeners.push(listener);
+}
+
+const backupDir = (file: string) => join(BACKUPS_DIR, basename(file, ".json"));
+
+const isValidJson = async (file: string) => {
+  try {
+    await readJson(file);
+    return true;
+  } catch {
+    return false;
+  }
+};
+
+export async function getConfigBackups(file?: string): Promise<Nvmd.ConfigBackup[]> {
+  if (!(await pathExists(BACKUPS_DIR))) return [];
+
+  const names = file ? [basename(file, ".json")] : await readdir(BACKUPS_DIR);
+  const backups = await Promise.all(
+    names.map(async (name) => {
+      const dir = join(BACKUPS_DIR, name);
+      if (!(await pathExists(dir))) return [];
+
+      return Promise.all(
+        (await readdir(dir)).map(async (id) => {
+          const { size, mtime } = await stat(join(dir, id));
+          return { name, id, size, createAt: mtime.toISOString() };
+        })
+      );
+    })
+  );
+
+  // newest first, ids are timestamps
+  return backups.flat().sort((a, b) => b.id.localeCompare(a.id));
+}
+
+// Keep a copy of the current (valid) content of `file` before it gets overwritten.
+async function backupConfig(file: string) {
+  if (!(await pathExists(file)) || !(await isValidJson(file))) return;
+
+  const dir = backupDir(file);
+  await ensureDir(dir);
+
+  const [latest, ...rest] = await getConfigBackups(file);
+  const content = (await readFile(file)).toString();
+  if (latest && (await readFile(join(dir, latest.id))).toString() === content) return;
+
+  await copy(file, join(dir, `${Date.now()}.json`));
+  await Promise.all(
+    (latest ? rest : []).slice(MAX_BACKUPS - 2).map(({ id }) => remove(join(dir, id)))
+  );
+}
+
+// Rejects with a `conflict` when another program (the daemon) is writing the file, or
+// saved it since the app last read it.
+export async function saveConfig(file: string, data: unknown) {
+  await withConfigLock(file, async () => {
+    try {
+      await backupConfig(file);
+    } catch (err) {
+      logger.warn(`Could not back up ${file}: ${err.message}`);
+    }
+
+    const content = `${JSON.stringify(toPortableConfig(file, data))}\n`;
+    await writeFileAtomic(file, content);
+    setConfigRevision(file, content);
+  });
+  savedListeners.forEach((listener) => listener(file));
+}
+
+const readConfigFile = async (file: string) => {
+  const content = (await readFile(file)).toString();
+  const data = JSON.parse(content.replace(/^\uFEFF/, ""));
+  setConfigRevision(file, content);
+  return data;
+};
+
+// Read a config file. If it can't be parsed, the newest valid backup is restored
+// instead of silently starting over with `fallback`.
+export async function readConfig<T>(file: string, fallback: T): Promise<T> {
+  if (!(await pathExists(file))) return fallback;
+
+  try {
+    return fromPortableConfig(file, (await readConfigFile(file)) ?? fallback) as T;
+  } catch (err) {
+    logger.error(`Could not parse ${file}: ${err.message}`);
+  }
+
+  for (const backup of await getConfigBackups(file)) {
+    const backupFile = join(backupDir(file), backup.id);
+    if (!(await isValidJson(backupFile))) continue;
+
+    await copy(file, `${file}.corrupted`, { overwrite: true });
+    await copy(backupFile, file, { overwrite: true });
+    recovered.push(backup);
+    logger.warn(`Restored ${file} from backup ${backup.id}`);
+    return fromPortableConfig(file, await readConfigFile(file)) as T;
+  }
+
+  return fallback;
+}
+
+// Backups restored automatically since launch, so the UI can tell the user.
+export function getRecoveredConfigs() {
+  return [...recovered];
+}
+
+export async function restoreConfigBackup(file: string, id: string) {
+  const backupFile = join(backupDir(file), basename(id));
+  if (!(await isValidJson(backupFile))) {
+    return Promise.reject(`Backup ${id} of ${basename(file)} is missing or invalid`);
+  }
+
+  await saveConfig(file, fromPortableConfig(file, await readJson(backupFile)));
+  return;
+}
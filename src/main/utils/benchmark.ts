@@ -0,0 +1,87 @@
+import { execFile } from "node:child_process";
+import { dirname, join } from "node:path";
+import { platform } from "node:process";
+import { pathExists } from "fs-extra";
+import { getNodeEnv } from "./env";
+import { getNodeBinPath } from "./launcher";
+import { logger } from "./logger";
+
+const DEFAULT_RUNS = 5;
+const MAX_RUNS = 50;
+// a single run, the script included
+const RUN_TIMEOUT = 60 * 1000;
+
+const run = (
+  node: string,
+  args: string[],
+  { cwd, env, signal }: { cwd: string; env: NodeJS.ProcessEnv; signal?: AbortSignal }
+) =>
+  new Promise<number>((resolve, reject) => {
+    const start = process.hrtime.bigint();
+    const options = { cwd, env, signal, timeout: RUN_TIMEOUT, windowsHide: true };
+    execFile(node, args, options, (err, _stdout, stderr) => {
+      if (err) return reject(stderr.trim().split("\n").pop() || err.message);
+      resolve(Number(process.hrtime.bigint() - start) / 1e6);
+    });
+  });
+
+const summarize = (times: number[]): Nvmd.BenchmarkTimes => {
+  const sorted = [...times].sort((time1, time2) => time1 - time2);
+  const middle = Math.floor(sorted.length / 2);
+  return {
+    mean: times.reduce((sum, time) => sum + time, 0) / times.length,
+    median: sorted.length % 2 ? sorted[middle] : (sorted[middle - 1] + sorted[middle]) / 2,
+    min: sorted[0],
+    max: sorted[sorted.length - 1]
+  };
+};
+
+// Time `runs` startups of an empty program, then `runs` executions of `script`, with
+// each version in its own processes. Versions run one after the other so they don't
+// compete for the CPU, and one warm-up run of each is discarded to leave the cold
+// file cache out of the numbers.
+export async function benchmarkVersions(
+  directory: string,
+  versions: string[],
+  {
+    script,
+    runs = DEFAULT_RUNS,
+    signal
+  }: { script?: string; runs?: number; signal?: AbortSignal } = {}
+): Promise<Nvmd.BenchmarkResult[]> {
+  if (script && !(await pathExists(script))) return Promise.reject(`${script} does not exist`);
+  runs = Math.min(Math.max(1, Math.floor(runs) || DEFAULT_RUNS), MAX_RUNS);
+
+  const results: Nvmd.BenchmarkResult[] = [];
+  for (const version of versions) {
+    signal?.throwIfAborted();
+    const node = join(
+      getNodeBinPath(directory, version),
+      platform === "win32" ? "node.exe" : "node"
+    );
+    const env = getNodeEnv(directory, version);
+    const cwd = script ? dirname(script) : directory;
+
+    const measure = async (args: string[]) => {
+      await run(node, args, { cwd, env, signal });
+      const times: number[] = [];
+      for (let index = 0; index < runs; index++) {
+        times.push(await run(node, args, { cwd, env, signal }));
+      }
+      return summarize(times);
+    };
+
+    try {
+      const startup = await measure(["-e", ""]);
+      const result = script ? await measure([script]) : undefined;
+      results.push({ version, runs, startup, ...(result && { script: result }) });
+    } catch (err) {
+      signal?.throwIfAborted();
+      logger.warn(`Benchmark of Node.js v${version} failed: ${err}`);
+      results.push({ version, runs, error: `${err.message || err}` });
+    }
+  }
+
+  logger.info(`Benchmarked Node.js ${versions.map((version) => `v${version}`).join(", ")}`);
+  return results;
+}
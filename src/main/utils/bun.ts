@@ -0,0 +1,32 @@
+import { arch, platform } from "node:process";
+
+import type { RuntimeDefinition } from "./runtimes";
+
+// Windows on ARM runs the x64 build.
+const TARGETS: Record<string, string> = {
+  "linux-x64": "linux-x64",
+  "linux-arm64": "linux-aarch64",
+  "darwin-x64": "darwin-x64",
+  "darwin-arm64": "darwin-aarch64",
+  "win32-x64": "windows-x64",
+  "win32-arm64": "windows-x64"
+};
+
+const target = TARGETS[`${platform}-${arch}`];
+
+export const bun: RuntimeDefinition = {
+  name: "bun",
+  title: "Bun",
+  repository: "oven-sh/bun",
+  getTag: (version) => `bun-v${version}`,
+  parseTag: (tag) => /^bun-v(\d+\.\d+\.\d+.*)$/.exec(tag)?.[1],
+  getAsset: () => target && `bun-${target}.zip`,
+  getChecksumAsset: () => "SHASUMS256.txt",
+  // the executable is in a folder named like the archive
+  getArchivePath: (asset) =>
+    `${asset.replace(/\.zip$/, "")}/${platform === "win32" ? "bun.exe" : "bun"}`,
+  // `bunx` is `bun x`, the installer only links it to `bun`
+  shims: { bun: [], bunx: ["x"] },
+  // the file read by setup-bun
+  versionFile: ".bun-version"
+};
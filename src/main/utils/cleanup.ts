@@ -0,0 +1,129 @@
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { readdir, rm, stat } from "node:fs/promises";
+import { STAGING_DIR } from "./elevate";
+import { getActiveInstalls } from "./installs";
+import { getSize } from "./locations";
+import { createRollbackPoint } from "./rollback";
+import { logger } from "./logger";
+
+const DAY = 24 * 60 * 60 * 1000;
+// first run a while after launch, not to compete with startup
+const INITIAL_DELAY = 10 * 60 * 1000;
+const INTERVAL = DAY;
+const DEFAULT_MAX_AGE_DAYS = 7;
+
+// Temporary folders left by downloads, source builds and elevated installs that were
+// interrupted. Other files of a custom cache directory are never touched.
+const PREFIXES = ["get-node-", "build-node-"];
+
+let policy: Nvmd.CacheCleanup = {};
+let getCacheDirectory: () => string | undefined = () => undefined;
+let timer: NodeJS.Timeout | null = null;
+let nextRunAt: number | undefined;
+let lastRun: Nvmd.CacheCleanupRun | undefined;
+
+type Entry = { path: string; size: number; mtime: number };
+
+async function getEntries(directory: string, filter: (name: string) => boolean) {
+  const names = await readdir(directory).catch(() => [] as string[]);
+  const entries = await Promise.all(
+    names.filter(filter).map(async (name): Promise<Entry | undefined> => {
+      const path = join(directory, name);
+      try {
+        const { mtimeMs } = await stat(path);
+        return { path, size: await getSize(path), mtime: mtimeMs };
+      } catch {
+        return;
+      }
+    })
+  );
+  return entries.filter((entry): entry is Entry => !!entry);
+}
+
+// Entries older than `maxAgeDays`, then the oldest ones until the rest fits in
+// `maxSizeMB`. Folders of running installs are kept whatever their age.
+export async function cleanupCache(
+  signal?: AbortSignal
+): Promise<Nvmd.CommandResult<Nvmd.CacheCleanupRun>> {
+  const { maxAgeDays = DEFAULT_MAX_AGE_DAYS, maxSizeMB } = policy;
+  const active = getActiveInstalls().map(({ version }) => version.replace(/^v/, ""));
+  const isLeftover = (name: string) =>
+    PREFIXES.some((prefix) => name.startsWith(prefix)) &&
+    !active.some((version) => name.includes(`-${version}`) || name.includes(`-v${version}`));
+
+  const entries = [
+    ...(await getEntries(getCacheDirectory() || tmpdir(), isLeftover)),
+    ...(active.length ? [] : await getEntries(STAGING_DIR, () => true))
+  ].sort((entry1, entry2) => entry1.mtime - entry2.mtime);
+
+  const now = Date.now();
+  let total = entries.reduce((sum, { size }) => sum + size, 0);
+  const expired = entries.filter(({ mtime, size }) => {
+    if (now - mtime <= maxAgeDays * DAY) {
+      if (maxSizeMB === void 0 || total <= maxSizeMB * 1024 * 1024) return false;
+    }
+    // sorted by age, the remaining ones are younger
+    total -= size;
+    return true;
+  });
+
+  expired.length && (await createRollbackPoint("cleanup"));
+
+  const removed: string[] = [];
+  const warnings: Nvmd.CommandWarning[] = [];
+  let freed = 0;
+  for (const { path, size } of expired) {
+    if (signal?.aborted) {
+      warnings.push({ message: "Canceled before the cleanup was over" });
+      break;
+    }
+
+    try {
+      await rm(path, { recursive: true, force: true });
+      removed.push(path);
+      freed += size;
+    } catch (err) {
+      logger.warn(`Could not remove ${path} from the cache: ${err}`);
+      warnings.push({ message: `Could not remove it: ${err.message}`, subject: path });
+    }
+  }
+
+  removed.length
+    ? logger.info(`Cache cleanup removed ${removed.length} entries (${freed} bytes): ${removed}`)
+    : logger.info("Cache cleanup found nothing to remove");
+  lastRun = { at: new Date(now).toISOString(), removed, freed };
+  return { data: lastRun, warnings };
+}
+
+const schedule = (delay: number) => {
+  timer && clearTimeout(timer);
+  timer = null;
+  nextRunAt = void 0;
+  if (!policy.enabled) return;
+
+  nextRunAt = Date.now() + delay;
+  timer = setTimeout(async () => {
+    await cleanupCache().catch((err) => logger.error(`Cache cleanup failed: ${err}`));
+    schedule(INTERVAL);
+  }, delay);
+  // don't keep the app alive for the cleanup
+  timer.unref();
+};
+
+export function setCacheCleanup(
+  cleanup: Nvmd.CacheCleanup = {},
+  cacheDirectory?: () => string | undefined
+) {
+  policy = cleanup;
+  if (cacheDirectory) getCacheDirectory = cacheDirectory;
+  schedule(nextRunAt ? Math.max(nextRunAt - Date.now(), 0) : INITIAL_DELAY);
+}
+
+export function getCacheCleanupStatus(): Nvmd.CacheCleanupStatus {
+  return {
+    enabled: !!policy.enabled,
+    ...(nextRunAt && { nextRunAt: new Date(nextRunAt).toISOString() }),
+    ...(lastRun && { lastRun })
+  };
+}
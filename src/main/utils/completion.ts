@@ -0,0 +1,126 @@
+import { COMPLETION_FILE } from "../constants";
+
+const COMMANDS = ["current", "list", "ls", "use", "which", "help"];
+
+// Group and project names, `group <name>` and `project <name>` lines read by the
+// completion scripts on each <TAB>. Versions come from `nvmd ls` itself.
+export const encodeCompletionNames = (projects: Nvmd.Project[], groups: Nvmd.Group[]) =>
+  [
+    ...groups.map(({ name }) => `group ${name}`),
+    ...projects.map(({ name }) => `project ${name}`)
+  ]
+    .filter((line) => !line.includes("\n"))
+    .join("\n") + "\n";
+
+const quote = (value: string, escaped: string) => `'${value.replace(/'/g, escaped)}'`;
+
+const bash = (file: string) => `# nvmd completion for bash, source it from ~/.bashrc
+_nvmd_names() {
+  [ -f ${quote(file, "'\\''")} ] && sed -n "s/^$1 //p" ${quote(file, "'\\''")}
+}
+
+_nvmd() {
+  local IFS=$'\\n' cur="\${COMP_WORDS[COMP_CWORD]}" prev="\${COMP_WORDS[COMP_CWORD-1]}" words
+  if [ "$COMP_CWORD" -eq 1 ]; then
+    words=$'${COMMANDS.join("\\n")}'
+  elif [ "$prev" = "--project" ]; then
+    words="$(_nvmd_names project)"
+  else
+    local versions="$(nvmd ls 2>/dev/null | sed 's/ .*//')"
+    case "\${COMP_WORDS[1]}" in
+      use) words="$versions"$'\\n'"$(_nvmd_names group)"$'\\n--project' ;;
+      which) words="$versions" ;;
+    esac
+  fi
+  COMPREPLY=($(compgen -W "$words" -- "$cur"))
+}
+
+complete -F _nvmd nvmd
+`;
+
+const zsh = (file: string) => `#compdef nvmd
+# nvmd completion for zsh, source it from ~/.zshrc after compinit
+_nvmd_names() {
+  [[ -f ${quote(file, "'\\''")} ]] && sed -n "s/^$1 //p" ${quote(file, "'\\''")}
+}
+
+_nvmd() {
+  local -a versions
+  if (( CURRENT == 2 )); then
+    compadd -- ${COMMANDS.join(" ")}
+    return
+  fi
+  if [[ $words[CURRENT-1] == --project ]]; then
+    compadd -- \${(f)"$(_nvmd_names project)"}
+    return
+  fi
+  versions=(\${(f)"$(nvmd ls 2>/dev/null | sed 's/ .*//')"})
+  case $words[2] in
+    use) compadd -- $versions \${(f)"$(_nvmd_names group)"} --project ;;
+    which) compadd -- $versions ;;
+  esac
+}
+
+compdef _nvmd nvmd
+`;
+
+const fish = (file: string) => {
+  const names = (kind: string) =>
+    `"(string replace -rf '^${kind} ' '' < ${quote(file, "\\'")} 2>/dev/null)"`;
+  return `# nvmd completion for fish, save it as ~/.config/fish/completions/nvmd.fish
+complete -c nvmd -f
+complete -c nvmd -n __fish_use_subcommand -a '${COMMANDS.join(" ")}'
+complete -c nvmd -n '__fish_seen_subcommand_from use which' \\
+  -a "(nvmd ls 2>/dev/null | string replace -r ' .*' '')"
+complete -c nvmd -n '__fish_seen_subcommand_from use' -a ${names("group")}
+complete -c nvmd -n '__fish_seen_subcommand_from use' -l project -x -a ${names("project")}
+`;
+};
+
+const powershell = (file: string) => `# nvmd completion for PowerShell, dot-source it from $PROFILE
+Register-ArgumentCompleter -Native -CommandName nvmd -ScriptBlock {
+  param($wordToComplete, $commandAst, $cursorPosition)
+  $file = ${quote(file, "''")}
+  $names = {
+    param($kind)
+    if (Test-Path $file) {
+      Get-Content $file | Where-Object { $_.StartsWith("$kind ") } |
+        ForEach-Object { $_.Substring($kind.Length + 1) }
+    }
+  }
+  $versions = { nvmd ls 2>$null | ForEach-Object { ($_ -split ' ')[0] } }
+
+  # the word being completed is part of the elements when it isn't empty
+  $words = @($commandAst.CommandElements | ForEach-Object { $_.ToString() })
+  if ($wordToComplete) { $words = $words[0..($words.Count - 2)] }
+
+  $candidates = if ($words.Count -eq 1) {
+    ${COMMANDS.map((command) => `'${command}'`).join(", ")}
+  } elseif ($words[-1] -eq '--project') {
+    & $names 'project'
+  } elseif ($words[1] -eq 'use') {
+    @(& $versions) + @(& $names 'group') + '--project'
+  } elseif ($words[1] -eq 'which') {
+    & $versions
+  }
+
+  $candidates | Where-Object { $_ -like "$wordToComplete*" } | ForEach-Object {
+    $text = if ($_ -match '\\s') { "'$_'" } else { $_ }
+    [System.Management.Automation.CompletionResult]::new($text, $_, 'ParameterValue', $_)
+  }
+}
+`;
+
+const SCRIPTS: Record<Nvmd.CompletionShell, (file: string) => string> = {
+  bash,
+  zsh,
+  fish,
+  powershell
+};
+
+// Completion script of `shell` for the nvmd CLI, with subcommands, installed
+// versions, group names (for `use`) and project names (after `--project`).
+export function getCompletionScript(shell: Nvmd.CompletionShell) {
+  if (!SCRIPTS[shell]) throw `Unsupported shell: ${shell}`;
+  return SCRIPTS[shell](COMPLETION_FILE);
+}
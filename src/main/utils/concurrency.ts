@@ -0,0 +1,65 @@
+import { logger } from "./logger";
+
+export const DEFAULT_CONCURRENCY = 3;
+
+let limit: number = DEFAULT_CONCURRENCY;
+
+export function setConcurrency(value: number = DEFAULT_CONCURRENCY) {
+  limit = Math.max(1, Math.floor(value) || DEFAULT_CONCURRENCY);
+}
+
+export type BatchResult<T, R> =
+  | { item: T; status: "fulfilled"; value: R }
+  | { item: T; status: "rejected"; reason: string };
+
+// Run `fn` over `items` with at most `concurrency` calls in flight.
+// Failures don't stop the batch, every item gets its own result in input order.
+// Once `signal` is aborted the items not started yet are rejected as canceled.
+export async function mapLimit<T, R>(
+  items: T[],
+  fn: (item: T, index: number) => Promise<R>,
+  { concurrency = limit, signal }: { concurrency?: number; signal?: AbortSignal } = {}
+): Promise<BatchResult<T, R>[]> {
+  const results: BatchResult<T, R>[] = new Array(items.length);
+  let next = 0;
+
+  const worker = async () => {
+    while (next < items.length) {
+      const index = next++;
+      const item = items[index];
+      if (signal?.aborted) {
+        results[index] = { item, status: "rejected", reason: "Canceled" };
+        continue;
+      }
+      try {
+        results[index] = { item, status: "fulfilled", value: await fn(item, index) };
+      } catch (err) {
+        results[index] = { item, status: "rejected", reason: err?.message ?? String(err) };
+      }
+    }
+  };
+
+  await Promise.all(Array.from({ length: Math.min(concurrency, items.length) }, worker));
+  return results;
+}
+
+// Failures of a batch, as the warnings of its command result.
+export const toWarnings = <T, R>(
+  results: BatchResult<T, R>[],
+  describe: (item: T) => string = String
+): Nvmd.CommandWarning[] =>
+  results.flatMap((result) =>
+    result.status === "rejected" ? [{ message: result.reason, subject: describe(result.item) }] : []
+  );
+
+export function logFailures<T, R>(
+  label: string,
+  results: BatchResult<T, R>[],
+  describe: (item: T) => string = String
+) {
+  results.forEach((result) => {
+    result.status === "rejected" &&
+      logger.warn(`${label} ${describe(result.item)}: ${result.reason}`);
+  });
+  return results;
+}
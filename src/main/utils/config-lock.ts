@@ -0,0 +1,110 @@
+import { createHash } from "node:crypto";
+import { open, readFile, rm, stat } from "node:fs/promises";
+import { basename } from "node:path";
+import { CommandError } from "./errors";
+import { logger } from "./logger";
+
+// How long a writer waits for the lock, and the age after which a lock left behind
+// by a writer that died is taken over.
+const LOCK_TIMEOUT = 5 * 1000;
+const LOCK_RETRY = 50;
+const LOCK_STALE = 30 * 1000;
+
+// Revision of each config file as last read or written by the app.
+const revisions = new Map<string, string>();
+
+// Writes of the app to the same file wait for each other instead of the lock.
+const queues = new Map<string, Promise<unknown>>();
+
+const getRevision = (content: string) => createHash("sha1").update(content).digest("hex");
+
+export const setConfigRevision = (file: string, content: string) =>
+  revisions.set(file, getRevision(content));
+
+const isRunning = (pid: number) => {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch {
+    return false;
+  }
+};
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+async function isStale(lock: string) {
+  try {
+    const [{ mtimeMs }, pid] = await Promise.all([
+      stat(lock),
+      readFile(lock, "utf-8").then((content) => Number(content.trim()))
+    ]);
+    return Date.now() - mtimeMs > LOCK_STALE || (pid > 0 && !isRunning(pid));
+  } catch {
+    // removed in between, or being written
+    return false;
+  }
+}
+
+// `<file>.lock` is created exclusively by whoever writes a config file, the app or
+// another instance of it like the daemon, and holds its pid.
+async function acquire(file: string) {
+  const lock = `${file}.lock`;
+  const deadline = Date.now() + LOCK_TIMEOUT;
+  for (;;) {
+    try {
+      const handle = await open(lock, "wx");
+      await handle.writeFile(`${process.pid}`);
+      await handle.close();
+      return lock;
+    } catch (err) {
+      if (err.code !== "EEXIST") throw err;
+    }
+
+    if (await isStale(lock)) {
+      logger.warn(`Removing the stale lock ${lock}`);
+      await rm(lock, { force: true });
+      continue;
+    }
+    if (Date.now() > deadline) {
+      throw new CommandError("conflict", `${basename(file)} is being written by another program`, {
+        context: { path: file }
+      });
+    }
+    await sleep(LOCK_RETRY);
+  }
+}
+
+// Reject when `file` changed since the app last read or wrote it: another program
+// saved it in between, and writing over it would lose its changes. Files the app
+// hasn't read yet are written over.
+async function checkRevision(file: string) {
+  const known = revisions.get(file);
+  if (!known) return;
+
+  const content = await readFile(file, "utf-8").catch(() => void 0);
+  if (content === void 0 || getRevision(content) === known) return;
+
+  throw new CommandError(
+    "conflict",
+    `${basename(file)} was changed by another program, reload it and try again`,
+    { context: { path: file } }
+  );
+}
+
+// Run `write` holding the lock of `file`, once it's known to be the revision the
+// app last saw.
+export function withConfigLock<T>(file: string, write: () => Promise<T>): Promise<T> {
+  const run = async () => {
+    const lock = await acquire(file);
+    try {
+      await checkRevision(file);
+      return await write();
+    } finally {
+      await rm(lock, { force: true });
+    }
+  };
+
+  const result = (queues.get(file) || Promise.resolve()).then(run, run);
+  queues.set(file, result.catch(() => {}));
+  return result;
+}
@@ -1,16 +1,33 @@
 import { join } from "node:path";
 import { readJson, writeJson } from "fs-extra";
+import {
+  fromPortablePath,
+  mapGroupPaths,
+  mapProjectPaths,
+  toPortablePath
+} from "./portable-paths";
 
+// Project paths are exported relative to the projects base folder, when set.
 export async function configrationExport(path: string, output: Nvmd.Configration) {
   const filename = `configration_${Date.now()}.json`;
-  await writeJson(join(path, `configration_${Date.now()}.json`), output);
+  const { projects, groups } = output;
+  await writeJson(join(path, `configration_${Date.now()}.json`), {
+    ...output,
+    ...(projects && { projects: mapProjectPaths(projects, toPortablePath) }),
+    ...(groups && { groups: mapGroupPaths(groups, toPortablePath) })
+  });
   return filename;
 }
 
 export async function configrationImport(path: string) {
   try {
     const input = (await readJson(path)) as Nvmd.Configration;
-    return input;
+    const { projects, groups } = input;
+    return {
+      ...input,
+      ...(Array.isArray(projects) && { projects: mapProjectPaths(projects, fromPortablePath) }),
+      ...(Array.isArray(groups) && { groups: mapGroupPaths(groups, fromPortablePath) })
+    };
   } catch {
     return {};
   }
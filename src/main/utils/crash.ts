@@ -0,0 +1,119 @@
+import { arch, platform, versions } from "node:process";
+import { release } from "node:os";
+import { join } from "node:path";
+import { app, crashReporter, shell } from "electron";
+import {
+  ensureDir,
+  ensureDirSync,
+  pathExists,
+  readdir,
+  readJson,
+  remove,
+  writeJsonSync
+} from "fs-extra";
+import { CRASHES_DIR } from "../constants";
+import { logger } from "./logger";
+
+const ISSUES_URL = "https://github.com/1111mp/nvm-desktop/issues/new";
+// An app crashing sooner after launch isn't started again, it would only crash again.
+const RELAUNCH_MIN_UPTIME = 10;
+
+// Crashes are only ever written to disk, nothing leaves the machine unless the
+// user explicitly submits a report (see `submitCrashReport`).
+export function setupCrashReporter() {
+  crashReporter.start({ uploadToServer: false, compress: true });
+
+  const write = (type: string) => (err: unknown) => {
+    const error = err instanceof Error ? err : new Error(String(err));
+    const report: Nvmd.CrashReport = {
+      id: `${Date.now()}`,
+      type,
+      message: error.message,
+      stack: error.stack || "",
+      appVersion: app.getVersion(),
+      electron: versions.electron,
+      os: `${platform} ${release()} ${arch}`,
+      createAt: new Date().toISOString()
+    };
+
+    logger.error(`Uncaught ${type}`, error);
+    try {
+      ensureDirSync(CRASHES_DIR);
+      writeJsonSync(join(CRASHES_DIR, `${report.id}.json`), report, { spaces: 2 });
+    } catch (err) {
+      logger.error(`Could not write the crash report: ${err.message}`);
+    }
+
+    // the state of the process can't be trusted after an uncaught exception
+    if (type === "exception") {
+      process.uptime() > RELAUNCH_MIN_UPTIME && app.relaunch();
+      app.exit(1);
+    }
+  };
+
+  process.on("uncaughtException", write("exception"));
+  process.on("unhandledRejection", write("rejection"));
+}
+
+export async function getCrashReports(): Promise<Nvmd.CrashReport[]> {
+  if (!(await pathExists(CRASHES_DIR))) return [];
+
+  const files = (await readdir(CRASHES_DIR)).filter((name) => name.endsWith(".json"));
+  const reports = await Promise.all(
+    files.map((name) => readJson(join(CRASHES_DIR, name), { throws: false }))
+  );
+
+  return (reports.filter(Boolean) as Nvmd.CrashReport[]).sort((a, b) =>
+    b.createAt.localeCompare(a.createAt)
+  );
+}
+
+// Native crashes of the main/renderer processes are captured by Electron as minidumps.
+export async function getMinidumps(): Promise<string[]> {
+  const dir = app.getPath("crashDumps");
+  if (!(await pathExists(dir))) return [];
+
+  const walk = async (path: string): Promise<string[]> => {
+    const entries = await readdir(path, { withFileTypes: true });
+    const files = await Promise.all(
+      entries.map((entry) =>
+        entry.isDirectory() ? walk(join(path, entry.name)) : [join(path, entry.name)]
+      )
+    );
+    return files.flat().filter((file) => file.endsWith(".dmp"));
+  };
+
+  return walk(dir);
+}
+
+// Submitting opens a pre-filled issue in the browser, so the user sees exactly
+// what is shared and can still decide not to post it.
+export async function submitCrashReport(id: string, consent: boolean = false) {
+  if (!consent) return Promise.reject("Crash report submission requires consent");
+
+  const report = (await getCrashReports()).find((report) => report.id === id);
+  if (!report) return Promise.reject(`Crash report ${id} not found`);
+
+  const body = [
+    `**App version:** ${report.appVersion}`,
+    `**Electron:** ${report.electron}`,
+    `**OS:** ${report.os}`,
+    "",
+    "```",
+    report.stack || report.message,
+    "```"
+  ].join("\n");
+  const title = `[Crash] ${report.message}`;
+
+  await shell.openExternal(
+    `${ISSUES_URL}?title=${encodeURIComponent(title)}&body=${encodeURIComponent(body)}`
+  );
+  return;
+}
+
+export async function clearCrashReports() {
+  await remove(CRASHES_DIR);
+  await remove(app.getPath("crashDumps"));
+  await ensureDir(CRASHES_DIR);
+  return;
+}
@@ -0,0 +1,49 @@
+import { pathExists, readFile, remove } from "fs-extra";
+import { safeStorage } from "electron";
+import { CREDENTIALS_FILE } from "../constants";
+import { writeFileAtomic } from "./file";
+import { logger } from "./logger";
+
+// Extra HTTP headers sent with index and archive requests, e.g. a bearer token
+// for a private mirror. They are encrypted with the OS keychain (Keychain,
+// DPAPI, libsecret) and never written to `setting.json`.
+let headers: Record<string, string> = {};
+
+export const getDownloadHeaders = () => headers;
+
+// Header names with masked values, for display.
+export const getMaskedHeaders = () =>
+  Object.fromEntries(
+    Object.entries(headers).map(([name, value]) => [name, value.replace(/(?<=.{4})./gu, "*")])
+  );
+
+export async function loadDownloadHeaders() {
+  if (!(await pathExists(CREDENTIALS_FILE))) return;
+
+  try {
+    const content = await readFile(CREDENTIALS_FILE);
+    headers = JSON.parse(safeStorage.decryptString(content));
+  } catch (err) {
+    logger.error(`Could not read the download headers: ${err.message}`);
+  }
+}
+
+export async function setDownloadHeaders(value: Record<string, string>) {
+  const entries = Object.entries(value)
+    .map(([name, header]) => [name.trim(), header.trim()])
+    .filter(([name, header]) => name && header);
+
+  if (!entries.length) {
+    headers = {};
+    await remove(CREDENTIALS_FILE);
+    return;
+  }
+
+  if (!safeStorage.isEncryptionAvailable()) {
+    return Promise.reject("No OS keychain is available to store the headers securely");
+  }
+
+  const next = Object.fromEntries(entries);
+  await writeFileAtomic(CREDENTIALS_FILE, safeStorage.encryptString(JSON.stringify(next)));
+  headers = next;
+}
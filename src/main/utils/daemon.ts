@@ -0,0 +1,98 @@
+import { randomBytes, timingSafeEqual } from "node:crypto";
+import { chmod, writeFile } from "node:fs/promises";
+import { createServer, type Server, type Socket } from "node:net";
+import { userInfo } from "node:os";
+import { join } from "node:path";
+import { platform } from "node:process";
+import { remove } from "fs-extra";
+import type { IpcMainInvokeEvent } from "electron";
+import { APPDIR } from "../constants";
+import { toCommandError } from "./errors";
+import { logger } from "./logger";
+
+// Run without any window, tray or shortcut, the commands are served on `DAEMON_SOCKET`.
+export const DAEMON_ARG = "--daemon";
+
+export const isDaemon = () => process.argv.includes(DAEMON_ARG);
+
+// A pipe per user on Windows. Node.js can't set who may open a pipe, so every request
+// must carry the token written to `DAEMON_TOKEN`, which only the user running the
+// daemon can read (`0600`, or the ACL of the profile on Windows). The Unix socket is
+// `0600` as well.
+export const DAEMON_SOCKET =
+  platform === "win32"
+    ? `\\\\.\\pipe\\nvmd-daemon-${userInfo().username.replace(/[^\w.-]/g, "_")}`
+    : join(APPDIR, "daemon.sock");
+export const DAEMON_TOKEN = join(APPDIR, "daemon.token");
+
+// written again by each daemon, clients read it to authenticate
+let token: Buffer;
+
+const isAuthorized = (value: unknown) =>
+  typeof value === "string" &&
+  Buffer.byteLength(value) === token.length &&
+  timingSafeEqual(Buffer.from(value), token);
+
+type Handler = (event: IpcMainInvokeEvent, ...args: unknown[]) => unknown;
+
+const handlers = new Map<string, Handler>();
+
+// Keep the handler of a command, to call it for the socket clients.
+export function registerHandler(channel: string, handler: Handler) {
+  handlers.set(channel, handler);
+}
+
+type Request = { id?: string | number; token?: string; channel: string; args?: unknown[] };
+
+async function respond(socket: Socket, line: string) {
+  let request: Request;
+  try {
+    request = JSON.parse(line);
+  } catch {
+    socket.write(`${JSON.stringify({ error: { kind: "failed", message: "Invalid JSON" } })}\n`);
+    return;
+  }
+
+  const { id, channel, args = [] } = request;
+  const handler = handlers.get(channel);
+  try {
+    if (!isAuthorized(request.token)) throw `Missing or wrong token, read it from ${DAEMON_TOKEN}`;
+    if (!handler) throw `Unknown command: ${channel}`;
+    // no window sent it
+    const result = await handler({ sender: null } as unknown as IpcMainInvokeEvent, ...args);
+    socket.write(`${JSON.stringify({ id, result })}\n`);
+  } catch (err) {
+    socket.write(`${JSON.stringify({ id, error: toCommandError(err, channel) })}\n`);
+  }
+}
+
+// One JSON request per line, `{ id, token, channel, args }`, answered by `{ id, result }`
+// or `{ id, error }` with the `CommandError` the renderer would get.
+export async function startDaemonServer(): Promise<Server> {
+  // left behind by a daemon that didn't exit cleanly
+  platform !== "win32" && (await remove(DAEMON_SOCKET));
+
+  const server = createServer((socket) => {
+    let rest = "";
+    socket.on("data", (chunk) => {
+      const lines = (rest + chunk.toString()).split("\n");
+      rest = lines.pop() || "";
+      lines.filter((line) => line.trim()).forEach((line) => respond(socket, line));
+    });
+    socket.on("error", (err) => logger.warn(`Daemon client error: ${err.message}`));
+  });
+
+  const secret = randomBytes(32).toString("hex");
+  await writeFile(DAEMON_TOKEN, secret, { mode: 0o600 });
+  await chmod(DAEMON_TOKEN, 0o600);
+  token = Buffer.from(secret);
+
+  await new Promise<void>((resolve, reject) => {
+    server.once("error", reject);
+    server.listen(DAEMON_SOCKET, resolve);
+  });
+  // reachable by others until then, their requests are refused without the token
+  platform !== "win32" && (await chmod(DAEMON_SOCKET, 0o600));
+  logger.info(`Daemon listening on ${DAEMON_SOCKET}`);
+  return server;
+}
@@ -0,0 +1,19 @@
+export const PROTOCOL = "nvmd";
+
+// `nvmd://<action>/<argument>` links found in a command line, e.g. `nvmd://use/20.11.1`.
+// Windows and Linux pass them as arguments, macOS through the `open-url` event.
+export function findDeepLink(argv: string[]) {
+  return argv.find((arg) => arg.startsWith(`${PROTOCOL}://`));
+}
+
+export function parseDeepLink(link: string): Nvmd.DeepLink | undefined {
+  try {
+    const url = new URL(link);
+    if (url.protocol !== `${PROTOCOL}:`) return;
+
+    const [argument] = url.pathname.split("/").filter(Boolean).map(decodeURIComponent);
+    return { action: url.hostname, argument };
+  } catch {
+    return;
+  }
+}
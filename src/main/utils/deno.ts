@@ -0,0 +1,30 @@
+import { arch, platform } from "node:process";
+
+import type { RuntimeDefinition } from "./runtimes";
+
+// Rust target triples of the published builds. Windows on ARM runs the x64 build.
+const TARGETS: Record<string, string> = {
+  "linux-x64": "x86_64-unknown-linux-gnu",
+  "linux-arm64": "aarch64-unknown-linux-gnu",
+  "darwin-x64": "x86_64-apple-darwin",
+  "darwin-arm64": "aarch64-apple-darwin",
+  "win32-x64": "x86_64-pc-windows-msvc",
+  "win32-arm64": "x86_64-pc-windows-msvc"
+};
+
+const target = TARGETS[`${platform}-${arch}`];
+
+export const deno: RuntimeDefinition = {
+  name: "deno",
+  title: "Deno",
+  repository: "denoland/deno",
+  getTag: (version) => `v${version}`,
+  parseTag: (tag) => /^v(\d+\.\d+\.\d+.*)$/.exec(tag)?.[1],
+  getAsset: () => target && `deno-${target}.zip`,
+  // only published since 1.43, older releases are installed unverified
+  getChecksumAsset: (asset) => `${asset}.sha256sum`,
+  getArchivePath: () => (platform === "win32" ? "deno.exe" : "deno"),
+  shims: { deno: [] },
+  // the file dvm reads, so projects pinned here keep working with it
+  versionFile: ".dvmrc"
+};
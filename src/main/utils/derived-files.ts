@@ -0,0 +1,30 @@
+import { debounce } from "lodash";
+import { COMPLETION_FILE, GROUPS_JSONFILE, PROJECTS_JSONFILE } from "../constants";
+import { readConfig } from "./backup";
+import { encodeCompletionNames } from "./completion";
+import { writeFileAtomic } from "./file";
+import { logger } from "./logger";
+
+let lastNames: string | null = null;
+
+// Files derived from the projects and groups for tools outside of the app, written
+// again only when their content changes.
+export async function buildDerivedFiles() {
+  const [projects, groups] = await Promise.all([
+    readConfig<Nvmd.Project[]>(PROJECTS_JSONFILE, []),
+    readConfig<Nvmd.Group[]>(GROUPS_JSONFILE, [])
+  ]);
+
+  const names = encodeCompletionNames(projects, groups);
+  if (names === lastNames) return;
+
+  await writeFileAtomic(COMPLETION_FILE, names);
+  lastNames = names;
+}
+
+// Config saves come in bursts (projects and groups are often written together).
+export const rebuildDerivedFiles = debounce(() => {
+  buildDerivedFiles().catch((err) =>
+    logger.warn(`Could not write the files derived from the config: ${err.message}`)
+  );
+}, 200);
@@ -0,0 +1,76 @@
+import { estimateInstallSize, formatSize, getFreeSpace } from "./disk";
+import { getActiveInstalls } from "./installs";
+import { logger } from "./logger";
+
+import type { Arch } from "../deps/get-node/archive/types";
+import type { Options as FetchNodeOptions } from "../deps/fetch-node-website";
+
+const MB = 1024 * 1024;
+const CHECK_INTERVAL = 10 * 60 * 1000;
+// in MB, room for a few installs
+const DEFAULT_THRESHOLD = 1024;
+
+// the version folder of those installs isn't written yet
+const PENDING_STAGES: Nvmd.InstallStage[] = ["queued", "paused", "downloading", "extracting"];
+
+type Context = { directory: string; fetchOpts: FetchNodeOptions };
+
+let threshold = DEFAULT_THRESHOLD,
+  low = false,
+  timer: NodeJS.Timeout | undefined,
+  getContext: (() => Context) | undefined,
+  onLow: ((status: Nvmd.DiskStatus) => void) | undefined;
+
+// Free space of the install volume once the running installs are done. `onLow` is
+// called when it drops below the threshold, then again only after it recovered.
+export async function getDiskStatus(): Promise<Nvmd.DiskStatus> {
+  if (!getContext) return Promise.reject("The disk monitor is not set up");
+
+  const { directory, fetchOpts } = getContext();
+  const pending = getActiveInstalls().filter(({ stage }) => PENDING_STAGES.includes(stage));
+  const [free, sizes] = await Promise.all([
+    getFreeSpace(directory),
+    Promise.all(
+      pending.map(({ version, arch }) => estimateInstallSize(version, arch as Arch, fetchOpts))
+    )
+  ]);
+
+  const projected = sizes.reduce((sum, { unpacked }) => sum + unpacked, 0);
+  const status: Nvmd.DiskStatus = {
+    path: directory,
+    free,
+    projected,
+    threshold: threshold * MB,
+    low: free - projected < threshold * MB
+  };
+
+  if (status.low && !low) {
+    logger.warn(
+      `Low disk space in ${directory}: ${formatSize(free)} free, ` +
+        `${formatSize(projected)} needed by the running installs`
+    );
+    onLow?.(status);
+  }
+  low = status.low;
+  return status;
+}
+
+// `lowDiskThreshold` in MB. The volume is checked periodically and the app checks it
+// when an install is queued.
+export function setDiskMonitor(
+  lowDiskThreshold: number = DEFAULT_THRESHOLD,
+  options?: { getContext: () => Context; onLow: (status: Nvmd.DiskStatus) => void }
+) {
+  threshold = Math.max(0, lowDiskThreshold);
+  if (options) {
+    getContext = options.getContext;
+    onLow = options.onLow;
+  }
+
+  if (timer) return;
+  timer = setInterval(() => {
+    getDiskStatus().catch((err) => logger.warn(`Could not check the disk space: ${err}`));
+  }, CHECK_INTERVAL);
+  // don't keep the app alive for the checks
+  timer.unref();
+}
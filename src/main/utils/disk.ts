@@ -0,0 +1,99 @@
+import { statfs, stat } from "node:fs/promises";
+import { dirname, extname } from "node:path";
+import { tmpdir } from "node:os";
+import { pathExists } from "fs-extra";
+import { getDownloadMetadata } from "../deps/get-node/metadata";
+
+import type { Arch } from "../deps/get-node/archive/types";
+import type { Options as FetchNodeOptions } from "../deps/fetch-node-website";
+
+const MB = 1024 * 1024;
+
+// Rough unpacked / archive size ratios of the Node.js releases.
+const UNPACK_RATIO: Record<string, number> = {
+  ".gz": 3.2,
+  ".xz": 4.8,
+  ".zst": 4.2,
+  ".zip": 2.6,
+  ".exe": 1
+};
+
+// When the mirror doesn't report a size, recent releases unpack to ~200MB.
+const FALLBACK_SIZE = 200 * MB;
+
+// `statfs` needs a path that exists, the install folder may not be created yet.
+async function existingAncestor(path: string) {
+  while (!(await pathExists(path)) && dirname(path) !== path) path = dirname(path);
+  return path;
+}
+
+async function getVolume(path: string) {
+  const existing = await existingAncestor(path);
+  const [{ bavail, bsize }, { dev }] = await Promise.all([statfs(existing), stat(existing)]);
+  return { path, dev, free: bavail * bsize };
+}
+
+// Free bytes of the volume `path` is (or will be created) on.
+export const getFreeSpace = async (path: string) => (await getVolume(path)).free;
+
+export const formatSize = (bytes: number) => `${Math.ceil(bytes / MB)}MB`;
+
+// Space an install of `version` takes: its download, and the folder it unpacks to.
+export async function estimateInstallSize(
+  version: string,
+  arch: Arch,
+  fetchOpts: FetchNodeOptions
+) {
+  const { file, size } = await getDownloadMetadata(version, arch, fetchOpts).catch(
+    () => ({ file: "", size: undefined })
+  );
+  return {
+    archive: size ?? FALLBACK_SIZE / 3,
+    unpacked: size ? size * (UNPACK_RATIO[extname(file)] ?? 3) : FALLBACK_SIZE
+  };
+}
+
+// Fail before downloading when the cache (where the archive is downloaded and
+// extracted) or the install directory don't have room for the version. When
+// both are on the same volume the space needed adds up.
+export async function checkDiskSpace({
+  version,
+  arch,
+  cache,
+  output,
+  fetchOpts
+}: {
+  version: string;
+  arch: Arch;
+  cache?: string;
+  output: string;
+  fetchOpts: FetchNodeOptions;
+}) {
+  const { archive, unpacked } = await estimateInstallSize(version, arch, fetchOpts);
+
+  const [cacheVolume, outputVolume] = await Promise.all([
+    getVolume(cache || tmpdir()),
+    getVolume(output)
+  ]).catch(() => []);
+  // unknown free space, let the install try
+  if (!cacheVolume || !outputVolume) return;
+
+  const required = [
+    { volume: cacheVolume, bytes: archive + unpacked },
+    { volume: outputVolume, bytes: unpacked }
+  ];
+  if (cacheVolume.dev === outputVolume.dev) {
+    required.splice(0, 2, { volume: outputVolume, bytes: archive + unpacked * 2 });
+  }
+
+  for (const { volume, bytes } of required) {
+    if (volume.free >= bytes) continue;
+
+    const error: Error & { code?: string } = new Error(
+      `Not enough disk space to install Node.js v${version}: ` +
+        `${formatSize(bytes)} needed in ${volume.path}, ${formatSize(volume.free)} available`
+    );
+    error.code = "ENOSPC";
+    throw error;
+  }
+}
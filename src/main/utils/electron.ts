@@ -0,0 +1,73 @@
+import { join } from "node:path";
+import { json } from "node:stream/consumers";
+import { pathExists, readJson } from "fs-extra";
+import { clean, maxSatisfying, valid, validRange } from "semver";
+import { PACKAGE_JSON_NAME } from "../constants";
+import { fetchNodeWebsite } from "../deps/fetch-node-website";
+
+import type { Options as FetchOptions } from "../deps/fetch-node-website";
+
+// Versions embedded in each Electron release, published by the Electron team
+const RELEASES_URL = "https://releases.electronjs.org";
+
+interface ElectronRelease {
+  version: string;
+  node: string;
+  chrome: string;
+  v8: string;
+  date: string;
+}
+
+let releases: ElectronRelease[] | undefined;
+
+export async function getElectronReleases(fetchOpts: FetchOptions, fetch: boolean = false) {
+  if (releases && !fetch) return releases;
+
+  const response = await fetchNodeWebsite("releases.json", {
+    ...fetchOpts,
+    mirror: RELEASES_URL
+  });
+  const list = (await json(response as NodeJS.ReadableStream)) as ElectronRelease[];
+  // nightlies don't match a published Node.js release
+  releases = list.filter(({ version, node }) => !!valid(version) && !!valid(node));
+  return releases;
+}
+
+// The installed Electron of the project wins over the range of its `package.json`,
+// which is resolved to the newest release satisfying it.
+export async function detectElectronVersion(path: string, list: ElectronRelease[]) {
+  const installed = join(path, "node_modules", "electron", PACKAGE_JSON_NAME);
+  if (await pathExists(installed)) {
+    const { version } = (await readJson(installed, { throws: false })) || {};
+    if (valid(version)) return version as string;
+  }
+
+  const file = join(path, PACKAGE_JSON_NAME);
+  if (!(await pathExists(file))) return;
+
+  const { dependencies, devDependencies } = (await readJson(file, { throws: false })) || {};
+  const range = devDependencies?.electron ?? dependencies?.electron;
+  if (typeof range !== "string" || !validRange(range)) return;
+
+  return maxSatisfying(list.map(({ version }) => version), range) ?? undefined;
+}
+
+export async function getElectronMapping(
+  { version, path }: { version?: string; path?: string },
+  { fetchOpts, installed }: { fetchOpts: FetchOptions; installed: string[] }
+): Promise<Nvmd.ElectronMapping> {
+  const list = await getElectronReleases(fetchOpts);
+
+  const electron = version ? clean(version) : path && (await detectElectronVersion(path, list));
+  if (!electron) {
+    return Promise.reject(
+      version ? `${version} is not an Electron version` : "No Electron dependency in the project"
+    );
+  }
+
+  const release = list.find((item) => item.version === electron);
+  if (!release) return Promise.reject(`Electron ${electron} is not a known release`);
+
+  const { node, chrome, v8, date } = release;
+  return { electron, node, chrome, v8, date, installed: installed.includes(node) };
+}
@@ -0,0 +1,104 @@
+import { spawn } from "node:child_process";
+import { access, constants, mkdtemp } from "node:fs/promises";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { env, platform } from "node:process";
+import { ensureDir, pathExists } from "fs-extra";
+import { logger } from "./logger";
+import { toLongPath } from "./winpath";
+
+// System-wide location used by the per-machine install mode.
+export const MACHINE_INSTALL_DIR =
+  platform === "win32"
+    ? join(env.ProgramFiles || "C:\\Program Files", "nvmd", "versions")
+    : "/usr/local/nvmd/versions";
+
+// Per-machine installs are downloaded and extracted here without privileges,
+// only moving the result into place needs elevation.
+export const STAGING_DIR = join(tmpdir(), "nvmd-staging");
+
+export async function isWritable(path: string) {
+  try {
+    await access((await pathExists(path)) ? path : join(path, ".."), constants.W_OK);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+const quote = (value: string) =>
+  platform === "win32" ? `"${value.replace(/"/g, '""')}"` : `'${value.replace(/'/g, "'\\''")}'`;
+
+// Run a single shell command with administrator rights, prompting the user
+// through the native mechanism of each OS (UAC, macOS authorization, polkit).
+export function runElevated(command: string) {
+  let file: string, args: string[];
+
+  if (platform === "win32") {
+    const inner = command.replace(/'/g, "''");
+    file = "powershell.exe";
+    args = [
+      "-NoProfile",
+      "-Command",
+      [
+        `$p = Start-Process -FilePath cmd.exe -ArgumentList '/c ${inner}'`,
+        "-Verb RunAs -Wait -PassThru -WindowStyle Hidden; exit $p.ExitCode"
+      ].join(" ")
+    ];
+  } else if (platform === "darwin") {
+    const escaped = command.replace(/\\/g, "\\\\").replace(/"/g, '\\"');
+    file = "osascript";
+    args = ["-e", `do shell script "${escaped}" with administrator privileges`];
+  } else {
+    file = "pkexec";
+    args = ["sh", "-c", command];
+  }
+
+  return new Promise<number>((resolve, reject) => {
+    const child = spawn(file, args, { stdio: "ignore", windowsHide: true });
+    child.once("error", reject);
+    child.once("exit", (code) => resolve(code ?? 1));
+  });
+}
+
+export async function moveElevated(source: string, target: string) {
+  logger.info(`Moving ${source} to ${target} with elevated privileges`);
+  const command =
+    platform === "win32"
+      ? // robocopy exit codes below 8 are successes
+        `robocopy ${quote(source)} ${quote(target)} /E /MOVE /NFL /NDL /NJH /NJS`
+      : [
+          `mkdir -p ${quote(join(target, ".."))}`,
+          `rm -rf ${quote(target)}`,
+          `mv ${quote(source)} ${quote(target)}`
+        ].join(" && ");
+
+  const code = await runElevated(command);
+  // a denied prompt exits with 1 on Windows too, the target then doesn't exist
+  const moved = platform === "win32" ? code < 8 : code === 0;
+  if (!moved || !(await pathExists(target))) {
+    return Promise.reject(
+      `Could not move ${source} to ${target}, elevation was denied or failed (exit code ${code})`
+    );
+  }
+}
+
+export async function removeElevated(path: string) {
+  logger.info(`Removing ${path} with elevated privileges`);
+  const command =
+    platform === "win32" ? `rmdir /S /Q ${quote(toLongPath(path))}` : `rm -rf ${quote(path)}`;
+
+  const code = await runElevated(command);
+  if (code !== 0 || (await pathExists(path))) {
+    return Promise.reject(
+      `Could not remove ${path}, elevation was denied or failed (exit code ${code})`
+    );
+  }
+}
+
+// A folder of its own under `STAGING_DIR` for each install, removed by the caller
+// once the install is moved into place or has failed.
+export async function getStagingDir() {
+  await ensureDir(STAGING_DIR);
+  return mkdtemp(join(STAGING_DIR, "install-"));
+}
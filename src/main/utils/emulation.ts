@@ -0,0 +1,43 @@
+import { platform } from "node:process";
+import { pathExists } from "fs-extra";
+import { getNativeArch } from "./arch";
+import { getChecksums } from "./verify";
+
+// present once Rosetta 2 is installed
+const ROSETTA_RUNTIME = "/Library/Apple/usr/libexec/oah/libRosettaRuntime";
+
+// x64 builds run under Rosetta 2 on Apple Silicon and emulated on Windows on ARM,
+// for native dependencies without arm64 prebuilds. The shims need nothing more,
+// the OS translates the x64 node they execute.
+export const canEmulateX64 = () =>
+  getNativeArch() === "arm64" && (platform === "darwin" || platform === "win32");
+
+export async function assertEmulationAvailable() {
+  if (!canEmulateX64()) {
+    return Promise.reject("x64 emulation is only available on Apple Silicon and Windows on ARM");
+  }
+  if (platform === "darwin" && !(await pathExists(ROSETTA_RUNTIME))) {
+    return Promise.reject("Rosetta is not installed, run `softwareupdate --install-rosetta`");
+  }
+}
+
+// Architecture of the installed build of each version, as recorded at install.
+export async function getInstalledArchs(): Promise<Record<string, string>> {
+  const checksums = await getChecksums();
+  return Object.fromEntries(
+    Object.entries(checksums).flatMap(([version, { platform }]) =>
+      platform ? [[version, platform.split("-").pop()!]] : []
+    )
+  );
+}
+
+// A project running emulated is pinned to the x64 build of its version, installed
+// beside the native one as the `<version>-x64` sandbox.
+export const EMULATED_BUILD = "x64";
+
+export const isEmulatedVersion = (version: string) => version.endsWith(`-${EMULATED_BUILD}`);
+
+export const toEmulatedVersion = (version: string) => `${version}-${EMULATED_BUILD}`;
+
+export const toNativeVersion = (version: string) =>
+  isEmulatedVersion(version) ? version.slice(0, -EMULATED_BUILD.length - 1) : version;
@@ -0,0 +1,42 @@
+import { delimiter, join, resolve } from "node:path";
+import { platform } from "node:process";
+import { BIN_DIR } from "../constants";
+import { getNodeBinPath } from "./launcher";
+import { isInside, samePath } from "./winpath";
+
+// Environment for processes the app starts for a version, so that tools they spawn
+// (npm scripts, node-gyp, nested `npx` calls) keep resolving the same version:
+//  - the version's bin folder comes first in PATH, ahead of the shims, so `node` is
+//    found there without the shim, and the bin folders of other installed versions
+//    are dropped
+//  - `npm_config_prefix` points global installs at that version
+export function getNodeEnv(
+  directory: string,
+  version: string,
+  env: NodeJS.ProcessEnv = process.env
+): NodeJS.ProcessEnv {
+  const binPath = getNodeBinPath(directory, version);
+  const pathKey = Object.keys(env).find((key) => key.toUpperCase() === "PATH") || "PATH";
+  const normalize = (path: string) => {
+    const normalized = resolve(path);
+    return platform === "win32" ? normalized.toLowerCase() : normalized;
+  };
+
+  const seen = new Set<string>();
+  const paths = [binPath, BIN_DIR, ...(env[pathKey] || "").split(delimiter)].filter((path) => {
+    if (!path) return false;
+
+    const normalized = normalize(path);
+    if (seen.has(normalized)) return false;
+    seen.add(normalized);
+
+    // another installed version
+    return samePath(path, binPath) || !isInside(path, directory);
+  });
+
+  return {
+    ...env,
+    [pathKey]: paths.join(delimiter),
+    npm_config_prefix: join(directory, version)
+  };
+}
@@ -0,0 +1,107 @@
+import { basename, join } from "node:path";
+import { platform } from "node:process";
+import { readJson, writeJson } from "fs-extra";
+import { createGroup, getGroups, updateGroupVersion } from "./groups";
+import { getProjectList, importProjectEntries } from "./project-list";
+import { fromPortablePath, toPortablePath } from "./portable-paths";
+import { withoutSandboxes } from "./sandbox";
+import { HASHED_FILE, getChecksums } from "./verify";
+
+const LOCKFILE_VERSION = 1;
+export const LOCKFILE_NAME = "nvmd-lock.json";
+
+// Prefix of the platform strings of the builds recorded on this machine.
+const OS = platform === "win32" ? "win" : platform;
+
+// The global version, the groups (named versions), the installed versions with the
+// checksum of their node executable and the version of each project. Project paths
+// are relative to the projects base folder when one is set, like in config exports.
+// Sandboxes are left out, they can't be installed from a mirror.
+export async function createEnvironmentLock(
+  installed: string[],
+  current: string
+): Promise<Nvmd.EnvironmentLock> {
+  const [checksums, groups, projects] = await Promise.all([
+    getChecksums(),
+    getGroups(),
+    getProjectList()
+  ]);
+
+  const versions = (await withoutSandboxes(installed)).map((version) => {
+    const recorded = checksums[version] || {};
+    return {
+      version,
+      ...(recorded.platform && { platform: recorded.platform }),
+      ...(recorded[HASHED_FILE] && { checksum: recorded[HASHED_FILE] })
+    };
+  });
+
+  return {
+    lockfileVersion: LOCKFILE_VERSION,
+    createAt: new Date().toISOString(),
+    ...(current && { current }),
+    versions,
+    groups: groups.map(({ name, version, desc }) => ({ name, version, ...(desc && { desc }) })),
+    projects: projects.map((entry) => ({ ...entry, path: toPortablePath(entry.path) }))
+  };
+}
+
+export async function exportEnvironmentLock(directory: string, lock: Nvmd.EnvironmentLock) {
+  const file = join(directory, LOCKFILE_NAME);
+  await writeJson(file, lock, { spaces: 2 });
+  return file;
+}
+
+export async function readEnvironmentLock(file: string): Promise<Nvmd.EnvironmentLock> {
+  const lock = await readJson(file, { throws: false });
+  if (!lock || !Array.isArray(lock.versions)) {
+    return Promise.reject(`${basename(file)} is not an environment lockfile`);
+  }
+  if (lock.lockfileVersion > LOCKFILE_VERSION) {
+    return Promise.reject(`${basename(file)} was written by a newer version of the app`);
+  }
+
+  return {
+    ...lock,
+    groups: lock.groups || [],
+    projects: ((lock.projects || []) as Nvmd.ProjectListEntry[]).map((entry) => ({
+      ...entry,
+      path: fromPortablePath(entry.path)
+    }))
+  };
+}
+
+// Architecture of the locked build when it was locked on the same OS, the native
+// one is installed otherwise.
+export const getLockedArch = ({ platform: locked }: Nvmd.LockedVersion) =>
+  locked?.startsWith(`${OS}-`) ? locked.slice(OS.length + 1) : void 0;
+
+// Whether the node executable installed for a locked version is the locked one.
+// Undefined when it can't be told: no checksum locked, or another build.
+export async function matchesLockedChecksum({
+  version,
+  platform: locked,
+  checksum
+}: Nvmd.LockedVersion) {
+  if (!checksum) return;
+
+  const recorded = (await getChecksums())[version];
+  if (!recorded?.[HASHED_FILE] || recorded.platform !== locked) return;
+  return recorded[HASHED_FILE] === checksum;
+}
+
+// Create the locked groups missing here and move the others to their locked version,
+// then add or update the locked projects found on this machine.
+export async function applyLockedPins(lock: Nvmd.EnvironmentLock) {
+  const groups = await getGroups();
+  for (const { name, version, desc } of lock.groups) {
+    const group = groups.find((item) => item.name === name);
+    if (!group) {
+      await createGroup({ name, version, projects: [], ...(desc && { desc }) });
+    } else if (group.version !== version) {
+      await updateGroupVersion(group, version);
+    }
+  }
+
+  return importProjectEntries(lock.projects);
+}
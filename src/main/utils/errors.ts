@@ -0,0 +1,84 @@
+import type { IpcMainInvokeEvent } from "electron";
+
+// Only the message of a rejection makes it through IPC, so commands resolve with the
+// structured error under this key instead. The preload script rejects with it.
+export const COMMAND_ERROR_KEY = "nvmdCommandError";
+
+const NETWORK_CODES = new Set([
+  "ENOTFOUND",
+  "EAI_AGAIN",
+  "ECONNREFUSED",
+  "ECONNRESET",
+  "ETIMEDOUT",
+  "EHOSTUNREACH",
+  "ENETUNREACH",
+  "ERR_GOT_REQUEST_ERROR"
+]);
+
+// Thrown to pick the kind and code of an error, anything else thrown by a command
+// is classified from its `code`.
+export class CommandError extends Error {
+  kind: Nvmd.ErrorKind;
+  code?: string;
+  context?: Record<string, string>;
+
+  constructor(
+    kind: Nvmd.ErrorKind,
+    message: string,
+    { code, context }: { code?: string; context?: Record<string, string> } = {}
+  ) {
+    super(message);
+    this.name = "CommandError";
+    this.kind = kind;
+    this.code = code;
+    this.context = context;
+  }
+}
+
+type ThrownError = Error & {
+  code?: string;
+  path?: string;
+  response?: { statusCode?: number };
+  options?: { url?: URL };
+};
+
+function getKind({ name, code, response }: ThrownError): Nvmd.ErrorKind {
+  if (name === "AbortError" || code === "ERR_CANCELED") return "aborted";
+  if (code === "ENOSPC") return "disk-space";
+  if (code === "EACCES" || code === "EPERM") return "permission";
+  if (code === "ENOENT" || response?.statusCode === 404) return "not-found";
+  if (code && /CERT|SSL|TLS/.test(code)) return "tls";
+  if (code && NETWORK_CODES.has(code)) return "network";
+  if (response?.statusCode) return "network";
+  return "failed";
+}
+
+export function toCommandError(err: unknown, channel: string): Nvmd.CommandError {
+  if (err instanceof CommandError) {
+    const { kind, code, message, context } = err;
+    return { kind, message, ...(code && { code }), context: { channel, ...context } };
+  }
+
+  // most commands reject with a message
+  if (typeof err === "string") return { kind: "failed", message: err, context: { channel } };
+
+  const error = (err || {}) as ThrownError;
+  const host = error.options?.url?.host;
+  return {
+    kind: getKind(error),
+    message: error.message || String(err),
+    ...(error.code && { code: error.code }),
+    context: { channel, ...(error.path && { path: error.path }), ...(host && { host }) }
+  };
+}
+
+// The handler of `channel` resolving with its rejection as a `CommandError`.
+export const withCommandErrors =
+  (channel: string, listener: (event: IpcMainInvokeEvent, ...args: any[]) => unknown) =>
+  async (event: IpcMainInvokeEvent, ...args: any[]) => {
+    try {
+      return await listener(event, ...args);
+    } catch (err) {
+      return { [COMMAND_ERROR_KEY]: toCommandError(err, channel) };
+    }
+  };
@@ -0,0 +1,36 @@
+import { open, rename, rm } from "node:fs/promises";
+import { dirname } from "node:path";
+import { ensureDir } from "fs-extra";
+
+// Content of the last write to each file, so changes made outside of the app
+// can be told apart from our own writes.
+const lastWrites = new Map<string, string>();
+
+export const isOwnWrite = (file: string, content: string) => lastWrites.get(file) === content;
+
+// Write `content` next to `file` first and rename it over the target once it's
+// flushed to disk. A crash or power loss mid-write leaves either the previous
+// or the new file, never a truncated one.
+export async function writeFileAtomic(file: string, content: string | Uint8Array) {
+  const tmpFile = `${file}.${process.pid}.${Date.now()}.tmp`;
+  await ensureDir(dirname(file));
+
+  try {
+    const handle = await open(tmpFile, "w");
+    try {
+      await handle.writeFile(content, "utf-8");
+      await handle.sync();
+    } finally {
+      await handle.close();
+    }
+    typeof content === "string" && lastWrites.set(file, content);
+    await rename(tmpFile, file);
+  } catch (err) {
+    await rm(tmpFile, { force: true });
+    throw err;
+  }
+}
+
+export function writeJsonAtomic(file: string, data: unknown) {
+  return writeFileAtomic(file, `${JSON.stringify(data)}\n`);
+}
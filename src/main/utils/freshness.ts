@@ -0,0 +1,128 @@
+import { Notification } from "electron";
+import { valid } from "semver";
+import { getReleaseLines } from "./release-lines";
+import { getLineSchedule } from "./release-schedule";
+import { getNetworkBlock } from "./network";
+import { logger } from "./logger";
+
+const HOUR = 60 * 60 * 1000;
+const DAY = 24 * HOUR;
+// first report a while after launch, the index is refreshed at startup
+const INITIAL_DELAY = 5 * 60 * 1000;
+
+type Context = {
+  groups: () => Promise<Nvmd.Group[]>;
+  versions: () => Promise<Nvmd.Versions>;
+  schedule: () => Promise<Nvmd.ReleaseSchedule | undefined>;
+  // the notification is in the language of the app
+  i18n: () => I18n.I18nFn;
+};
+
+let options: Nvmd.FreshnessOptions = {},
+  getContext: Context | undefined,
+  timer: NodeJS.Timeout | null = null,
+  last: Nvmd.FreshnessReport | undefined;
+
+const plural = (count: number, word: string) => `${count} ${word}${count === 1 ? "" : "s"}`;
+
+// How far behind the newest release of its line (major) each group's version is, and
+// when the line reached its end of life. Groups on a version that isn't in the index
+// (custom builds, nightlies) are left out.
+export function getGroupsFreshness(
+  groups: Nvmd.Group[],
+  versions: Nvmd.Versions,
+  schedule?: Nvmd.ReleaseSchedule
+): Nvmd.GroupFreshness[] {
+  const lines = getReleaseLines(versions);
+
+  return groups.flatMap(({ name, version }) => {
+    const pinned = valid(version);
+    const line = pinned && lines.find(({ major }) => `${major}` === pinned.split(".")[0]);
+    if (!pinned || !line?.versions.includes(pinned)) return [];
+
+    // the line is sorted newest first
+    const behind = line.versions.indexOf(pinned);
+    const [, minor] = pinned.split(".");
+    const kind = line.latest.split(".")[1] === minor ? "patch" : "minor";
+    const dates = schedule && getLineSchedule(schedule, pinned);
+    const eol = dates?.status === "end-of-life" ? dates.end : undefined;
+    const message = behind
+      ? `Group '${name}' is ${plural(behind, `${kind} release`)} behind v${line.latest}`
+      : `Group '${name}' is on the newest v${line.major} release`;
+    return [
+      {
+        group: name,
+        version: pinned,
+        latest: line.latest,
+        behind,
+        ...(eol && { eol }),
+        message: eol ? `${message}, v${line.major} reached its end of life on ${eol}` : message
+      }
+    ];
+  });
+}
+
+const describe = (i18n: I18n.I18nFn, { group, latest, behind, eol }: Nvmd.GroupFreshness) => {
+  const major = latest.split(".")[0];
+  const message = behind
+    ? i18n("Freshness-Behind", { group, behind, latest })
+    : i18n("Freshness-Newest", { group, major });
+  return eol ? `${message}${i18n("Freshness-Eol", { major, eol })}` : `${message}`;
+};
+
+// Compute the report again, notifying the groups that fell behind since the last one.
+export async function runFreshnessReport(): Promise<Nvmd.FreshnessReport> {
+  if (!getContext) return Promise.reject("The freshness report is not set up");
+
+  const [groups, versions, schedule] = await Promise.all([
+    getContext.groups(),
+    getContext.versions(),
+    // the report goes on without the dates
+    getContext.schedule().catch(() => undefined)
+  ]);
+  const report = {
+    at: new Date().toISOString(),
+    groups: getGroupsFreshness(groups, versions, schedule)
+  };
+
+  const stale = report.groups.filter(({ behind, eol }) => behind || eol);
+  const isNew = ({ group, latest }: Nvmd.GroupFreshness) =>
+    !last?.groups.some((previous) => previous.group === group && previous.latest === latest);
+  if (options.notify && stale.some(isNew) && Notification.isSupported()) {
+    const i18n = getContext.i18n();
+    new Notification({
+      title: i18n("Freshness-Title") as string,
+      body: stale.map((freshness) => describe(i18n, freshness)).join("\n")
+    }).show();
+  }
+  stale.length && logger.info(stale.map(({ message }) => message).join(", "));
+
+  last = report;
+  return report;
+}
+
+export const getFreshnessReport = () => last;
+
+const schedule = (delay: number) => {
+  timer && clearTimeout(timer);
+  timer = null;
+  if (options.enabled === false) return;
+
+  timer = setTimeout(async () => {
+    // tried again in an hour, the release index can't be fetched
+    if (getNetworkBlock()) return schedule(HOUR);
+    await runFreshnessReport().catch((err) =>
+      logger.warn(`Could not compute the freshness report: ${err.message || err}`)
+    );
+    schedule(DAY);
+  }, delay);
+  // don't keep the app alive for the report
+  timer.unref();
+};
+
+// Daily, unless `enabled` is false. `context` gives the groups and the release index.
+export function setFreshnessReport(value: Nvmd.FreshnessOptions = {}, context?: Context) {
+  options = value;
+  if (context) getContext = context;
+  schedule(last ? DAY : INITIAL_DELAY);
+}
@@ -1,7 +1,15 @@
 import { join } from "node:path";
-import { pathExists, readFile, readJson, writeJson, writeFile, remove } from "fs-extra";
+import { pathExists, readFile, writeFile } from "fs-extra";
 import { GROUPS_JSONFILE, NVMDRC_NAME } from "../constants";
-import { syncProjectVersion, updateProjectAndSyncVersion } from "./projects";
+import {
+  detachFrozenMembers,
+  rollbackMessage,
+  syncProjectsVersion,
+  updateProjectAndSyncVersion
+} from "./projects";
+import { removePath } from "./trash";
+import { readConfig, saveConfig } from "./backup";
+import { validateEnv, validateNpmRegistries } from "./project-env";
 
 let cacheGroups: Nvmd.Group[];
 
@@ -15,7 +23,7 @@ export async function getGroups(load: boolean = false): Promise<Nvmd.Group[]> {
     return cacheGroups;
   }
 
-  const groups = (await readJson(GROUPS_JSONFILE, { throws: false })) || [];
+  const groups = await readConfig<Nvmd.Group[]>(GROUPS_JSONFILE, []);
 
   cacheGroups = groups;
 
@@ -45,33 +53,70 @@ export async function createGroup(group: Nvmd.Group) {
 
   const newGroups = [group, ...cacheGroups];
 
-  await writeJson(GROUPS_JSONFILE, newGroups);
+  await saveConfig(GROUPS_JSONFILE, newGroups);
   cacheGroups = newGroups;
   return;
 }
 
 export async function updateGroups(groups: Nvmd.Group[], path?: string) {
   if (path && (await pathExists(join(path, NVMDRC_NAME)))) {
-    await remove(join(path, NVMDRC_NAME));
+    await removePath(join(path, NVMDRC_NAME));
   }
 
-  await writeJson(GROUPS_JSONFILE, groups);
+  await saveConfig(GROUPS_JSONFILE, groups);
   cacheGroups = groups;
   return;
 }
 
 export async function updateGroupVersion(group: Nvmd.Group, version: string) {
   const { projects, name } = group;
-  await Promise.all(projects.map((projectPath) => syncProjectVersion(projectPath, version)));
+  const result = await syncProjectsVersion(projects, version);
+  if (result.rolledBack) {
+    return Promise.reject(rollbackMessage(result));
+  }
 
-  const newGroups = [...cacheGroups];
-  newGroups.forEach((group) => {
-    if (group.name === name) {
-      group.version = version;
-    }
-  });
+  // frozen projects stay on the previous version, out of the group
+  const [detached] = await detachFrozenMembers([group], result.frozen);
+  const newGroups = cacheGroups.map((group) =>
+    group.name === name ? { ...group, projects: detached.projects, version } : group
+  );
 
+  await saveConfig(GROUPS_JSONFILE, newGroups);
   cacheGroups = newGroups;
-  await writeJson(GROUPS_JSONFILE, newGroups);
   return cacheGroups;
 }
+
+// Replace the group `name` by the result of `update`.
+async function updateGroup(name: string, update: (group: Nvmd.Group) => Nvmd.Group) {
+  const groups = await getGroups();
+  if (!groups.some((group) => group.name === name)) {
+    return Promise.reject(`${name} is not a group`);
+  }
+
+  const newGroups = groups.map((group) => (group.name === name ? update(group) : group));
+  await saveConfig(GROUPS_JSONFILE, newGroups);
+  cacheGroups = newGroups;
+  return newGroups;
+}
+
+// Set or clear (`network` undefined) the mirror and proxy of a group's projects.
+export const setGroupNetwork = (name: string, network?: Nvmd.NetworkOverride) =>
+  updateGroup(name, ({ network: _previous, ...group }) =>
+    network ? { ...group, network } : group
+  );
+
+// Variables of every project in the group, an empty map clears them.
+export function setGroupEnv(name: string, env: Record<string, string> = {}) {
+  validateEnv(env);
+  return updateGroup(name, ({ env: _previous, ...group }) =>
+    Object.keys(env).length ? { ...group, env } : group
+  );
+}
+
+// Registries of the group's projects, those without their own. Cleared without `npm`.
+export function setGroupNpm(name: string, npm?: Nvmd.NpmRegistries) {
+  npm && validateNpmRegistries(npm);
+  return updateGroup(name, ({ npm: _previous, ...group }) =>
+    npm && (npm.registry || Object.keys(npm.scopes || {}).length) ? { ...group, npm } : group
+  );
+}
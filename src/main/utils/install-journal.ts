@@ -0,0 +1,68 @@
+import { pathExists, readJson } from "fs-extra";
+import { throttle } from "lodash";
+import { INSTALL_JOURNAL_JSONFILE } from "../constants";
+import { writeJsonAtomic } from "./file";
+import { onInstallEvent } from "./installs";
+import { logger } from "./logger";
+
+// Installs are journaled while they run, so the ones cut short by the app quitting
+// or crashing can be retried on the next launch, from the start. Whatever is left in the
+// journal at launch was interrupted.
+const running = new Map<string, Nvmd.InterruptedInstall>();
+let interrupted: Nvmd.InterruptedInstall[] = [];
+
+const save = () =>
+  writeJsonAtomic(INSTALL_JOURNAL_JSONFILE, [...interrupted, ...running.values()]).catch((err) =>
+    logger.warn(`Could not save the install journal: ${err.message}`)
+  );
+
+// progress comes several times a second, the byte count only needs to be close
+const saveProgress = throttle(save, 2000);
+
+export async function loadInstallJournal() {
+  if (await pathExists(INSTALL_JOURNAL_JSONFILE)) {
+    const entries = await readJson(INSTALL_JOURNAL_JSONFILE, { throws: false });
+    interrupted = Array.isArray(entries) ? entries : [];
+  }
+
+  onInstallEvent(({ id, stage }) => {
+    const entry = running.get(id);
+    if (!entry) return;
+
+    if (stage === "done" || stage === "failed" || stage === "aborted") {
+      running.delete(id);
+    } else {
+      entry.stage = stage;
+    }
+    save();
+  });
+  return interrupted;
+}
+
+// Journal the install `id` with what's needed to start it again.
+export function journalInstall(
+  id: string,
+  options: { version: string; arch: string; source?: boolean; location?: string }
+) {
+  // starting it again settles the interrupted one
+  interrupted = interrupted.filter(({ version }) => version !== options.version);
+  running.set(id, { ...options, stage: "queued", startAt: new Date().toISOString() });
+  save();
+}
+
+export function journalProgress(id: string, { transferred, total }: Nvmd.ProgressData) {
+  const entry = running.get(id);
+  if (!entry) return;
+
+  entry.transferred = transferred;
+  entry.total = total;
+  saveProgress();
+}
+
+export const getInterruptedInstalls = () => interrupted;
+
+export function dismissInterruptedInstall(version: string) {
+  interrupted = interrupted.filter((entry) => entry.version !== version);
+  save();
+  return interrupted;
+}
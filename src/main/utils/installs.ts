@@ -0,0 +1,95 @@
+// Installs currently running, keyed by the operation id the renderer passes.
+// Each install reports its own progress under that id, so several can run at once.
+const installs = new Map<string, Nvmd.ActiveInstall>();
+// How the last install of each version ended, for a renderer reloaded in the meantime.
+const finished = new Map<string, Nvmd.InstallState>();
+
+const listeners: ((event: Nvmd.InstallEvent) => void)[] = [];
+
+// Get notified of every lifecycle stage an install goes through.
+export function onInstallEvent(listener: (event: Nvmd.InstallEvent) => void) {
+  listeners.push(listener);
+}
+
+const emit = (install: Nvmd.ActiveInstall, error?: Nvmd.InstallError) => {
+  const event: Nvmd.InstallEvent = {
+    id: install.id,
+    version: install.version,
+    stage: install.stage,
+    at: new Date().toISOString(),
+    ...(install.pausedFor && { pausedFor: install.pausedFor }),
+    ...(error && { error })
+  };
+  listeners.forEach((listener) => listener(event));
+};
+
+// Claim `version` for the operation `id`, installing the same version twice at
+// once would have both writing to the same folder.
+export function beginInstall(id: string, version: string, arch: string) {
+  const running = [...installs.values()].find((install) => install.version === version);
+  if (running) return Promise.reject(`Node.js v${version} is already being installed`);
+
+  const install: Nvmd.ActiveInstall = {
+    id,
+    version,
+    arch,
+    stage: "queued",
+    startAt: new Date().toISOString()
+  };
+  installs.set(id, install);
+  finished.delete(version);
+  emit(install);
+  return Promise.resolve();
+}
+
+// `pausedFor` tells why an install is `paused`.
+export function setInstallStage(
+  id: string,
+  stage: Nvmd.InstallStage,
+  pausedFor?: Nvmd.NetworkPauseReason
+) {
+  const install = installs.get(id);
+  if (!install || (install.stage === stage && install.pausedFor === pausedFor)) return;
+
+  install.stage = stage;
+  pausedFor ? (install.pausedFor = pausedFor) : delete install.pausedFor;
+  emit(install);
+}
+
+export function updateInstall(id: string, progress: Nvmd.ProgressData) {
+  const install = installs.get(id);
+  if (!install) return;
+
+  install.progress = progress;
+  // the download phases map to stages, building from source only reports progress
+  (progress.phase === "downloading" || progress.phase === "extracting") &&
+    setInstallStage(id, progress.phase);
+}
+
+// Finish the install `id`, as failed when `err` is given.
+export function endInstall(id: string, err?: Error & { code?: string }) {
+  const install = installs.get(id);
+  if (!install) return;
+
+  installs.delete(id);
+  const endAt = new Date().toISOString();
+  if (!err) {
+    install.stage = "done";
+    finished.set(install.version, { ...install, endAt });
+    emit(install);
+    return;
+  }
+
+  const error = { stage: install.stage, code: err.code, message: err.message };
+  install.stage = err.name === "AbortError" ? "aborted" : "failed";
+  finished.set(install.version, { ...install, endAt, error });
+  emit(install, error);
+}
+
+export const getActiveInstalls = () => [...installs.values()];
+
+// The running install of `version` with its last progress, or how the last one ended.
+export function getInstallState(version: string): Nvmd.InstallState | undefined {
+  const running = [...installs.values()].find((install) => install.version === version);
+  return running ?? finished.get(version);
+}
@@ -0,0 +1,35 @@
+import { pathExists, readFile } from "fs-extra";
+import { writeFileAtomic } from "./file";
+
+const DAY = 24 * 60 * 60 * 1000;
+
+// Entries of a JSON Lines file, oldest first. Lines that can't be parsed, e.g. cut by
+// a crash, are skipped.
+export async function readJsonLines<T>(file: string): Promise<T[]> {
+  if (!(await pathExists(file))) return [];
+
+  const entries: T[] = [];
+  for (const line of (await readFile(file, "utf-8")).split("\n")) {
+    if (!line.trim()) continue;
+    try {
+      entries.push(JSON.parse(line));
+    } catch {
+      // skipped
+    }
+  }
+  return entries;
+}
+
+// Drop the entries older than `maxAgeDays` and keep at most `maxEntries` of the rest.
+// The file is only rewritten when something was dropped.
+export async function trimJsonLines<T extends { at: string }>(
+  file: string,
+  { maxAgeDays, maxEntries }: { maxAgeDays: number; maxEntries: number }
+) {
+  const entries = await readJsonLines<T>(file);
+  const since = Date.now() - maxAgeDays * DAY;
+  const kept = entries.filter(({ at }) => Date.parse(at) >= since).slice(-maxEntries);
+  if (kept.length === entries.length) return;
+
+  await writeFileAtomic(file, kept.map((entry) => `${JSON.stringify(entry)}\n`).join(""));
+}
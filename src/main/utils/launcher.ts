@@ -0,0 +1,258 @@
+import { spawn } from "node:child_process";
+import { env as processEnv, platform } from "node:process";
+import { extname, isAbsolute, join } from "node:path";
+import { pathExists } from "fs-extra";
+import { clipboard, shell } from "electron";
+import { logger } from "./logger";
+
+const LINUX_TERMINALS = [
+  "x-terminal-emulator",
+  "gnome-terminal",
+  "konsole",
+  "xfce4-terminal",
+  "xterm"
+];
+
+// Flag preceding the command a terminal runs instead of the user's shell.
+const EXEC_FLAGS: Record<string, string> = {
+  "gnome-terminal": "--",
+  "xfce4-terminal": "-x"
+};
+
+// Characters cmd.exe treats specially, escaped with `^`.
+const CMD_META = /([()\][%!^"`<>&|;, *?])/g;
+
+// Split a command line into its arguments without a shell: double or single quotes
+// group, and a backslash escapes the next character (in double quotes only `"` and
+// `\`). Backslashes are kept as they are on Windows, where they separate the path.
+export function splitCommandLine(line: string) {
+  const args: string[] = [];
+  let current = "",
+    quote = "",
+    started = false;
+
+  for (let index = 0; index < line.length; index++) {
+    const char = line[index];
+    const next = line[index + 1];
+    const escapes =
+      char === "\\" &&
+      platform !== "win32" &&
+      next !== void 0 &&
+      (quote === "" || (quote === '"' && (next === '"' || next === "\\")));
+
+    if (escapes) {
+      current += line[++index];
+      started = true;
+    } else if (quote) {
+      char === quote ? (quote = "") : (current += char);
+    } else if (char === '"' || char === "'") {
+      quote = char;
+      started = true;
+    } else if (/\s/.test(char)) {
+      started && args.push(current);
+      current = "";
+      started = false;
+    } else {
+      current += char;
+      started = true;
+    }
+  }
+
+  if (quote) throw `Unterminated quote in ${line}`;
+  started && args.push(current);
+  return args;
+}
+
+const getEnv = (env: NodeJS.ProcessEnv, name: string) =>
+  env[Object.keys(env).find((key) => key.toUpperCase() === name) ?? name];
+
+// Full path of `command` found in PATH with one of the PATHEXT extensions, the way
+// cmd.exe would find it.
+async function findWindowsExecutable(command: string, env: NodeJS.ProcessEnv) {
+  const extensions = (getEnv(env, "PATHEXT") || ".COM;.EXE;.BAT;.CMD").split(";");
+  const names = extname(command) ? [command] : extensions.map((ext) => `${command}${ext}`);
+  const dirs =
+    isAbsolute(command) || /[\\/]/.test(command)
+      ? [""]
+      : (getEnv(env, "PATH") || "").split(";").filter(Boolean);
+
+  for (const dir of dirs) {
+    for (const name of names) {
+      const file = dir ? join(dir, name) : name;
+      if (await pathExists(file)) return file;
+    }
+  }
+  return command;
+}
+
+// What to spawn for `command` and `args`. Batch files (`code.cmd`) can only be run
+// by cmd.exe: its command line is built here with every argument quoted and escaped,
+// instead of letting a shell join them.
+async function toSpawnArgs(
+  command: string,
+  args: string[],
+  env: NodeJS.ProcessEnv
+): Promise<[string, string[], boolean]> {
+  if (platform !== "win32") return [command, args, false];
+
+  const file = await findWindowsExecutable(command, env);
+  if (!/\.(cmd|bat)$/i.test(file)) return [file, args, false];
+
+  const quote = (arg: string) =>
+    `"${arg.replace(/(\\*)"/g, '$1$1\\"').replace(/(\\*)$/, "$1$1")}"`.replace(CMD_META, "^$1");
+  const line = [file.replace(CMD_META, "^$1"), ...args.map(quote)].join(" ");
+  return [getEnv(env, "COMSPEC") || "cmd.exe", ["/d", "/s", "/c", `"${line}"`], true];
+}
+
+// Start a detached process that outlives the app, resolving on spawn and
+// rejecting when the executable can't be started. No shell is involved.
+export async function launch(
+  command: string,
+  args: string[],
+  cwd?: string,
+  env?: NodeJS.ProcessEnv
+) {
+  const [file, argv, verbatim] = await toSpawnArgs(command, args, env || processEnv);
+  return new Promise<void>((resolve, reject) => {
+    const child = spawn(file, argv, {
+      cwd,
+      env,
+      detached: true,
+      stdio: "ignore",
+      windowsVerbatimArguments: verbatim
+    });
+    child.once("error", reject);
+    child.once("spawn", () => {
+      child.unref();
+      resolve();
+    });
+  });
+}
+
+// Run a PowerShell `script` that receives its values in `NVMD_LAUNCH_*` variables,
+// passed encoded so that no quoting is involved. `Start-Process` opens the new
+// console window cmd's `start` would.
+const launchPowerShell = (
+  script: string,
+  values: Record<string, string>,
+  env?: NodeJS.ProcessEnv
+) =>
+  launch(
+    "powershell.exe",
+    [
+      "-NoProfile",
+      "-NonInteractive",
+      "-EncodedCommand",
+      Buffer.from(script, "utf16le").toString("base64")
+    ],
+    void 0,
+    {
+      ...(env || processEnv),
+      ...Object.fromEntries(
+        Object.entries(values).map(([name, value]) => [`NVMD_LAUNCH_${name}`, value])
+      )
+    }
+  );
+
+const ensureFolder = async (path: string) => {
+  if (!(await pathExists(path))) return Promise.reject(`${path} does not exist`);
+};
+
+export async function revealInFileManager(path: string) {
+  await ensureFolder(path);
+
+  const error = await shell.openPath(path);
+  if (error) return Promise.reject(error);
+}
+
+// `editor` is either an executable (`code`, `webstorm`, a full path with spaces) that
+// receives the folder as its only argument, or a custom command line where `{path}` is
+// replaced by the folder. Paths with spaces in a command line are quoted.
+export async function openInEditor(
+  path: string,
+  editor: string = "code",
+  env?: NodeJS.ProcessEnv
+) {
+  await ensureFolder(path);
+
+  if (editor.includes("{path}")) {
+    const [command, ...args] = splitCommandLine(editor);
+    if (!command) return Promise.reject("The editor command is empty");
+    return launch(command, args.map((arg) => arg.replaceAll("{path}", path)), path, env);
+  }
+
+  return launch(editor.trim(), [path], path, env);
+}
+
+// `env` is only honored where the terminal is started directly (not on macOS,
+// where `open` hands the folder to an already running Terminal).
+export async function openInTerminal(path: string, env?: NodeJS.ProcessEnv) {
+  await ensureFolder(path);
+
+  if (platform === "darwin") return launch("open", ["-a", "Terminal", path]);
+
+  if (platform === "win32") {
+    // `cd` can't enter UNC folders, `pushd` maps them to a drive letter.
+    // The UTF-8 code page keeps non-ASCII folder names intact.
+    return launchPowerShell(
+      "Start-Process cmd.exe -ArgumentList '/K', " +
+        "('chcp 65001 >nul & pushd \"' + $env:NVMD_LAUNCH_FOLDER + '\"')",
+      { FOLDER: path },
+      env
+    );
+  }
+
+  for (const terminal of LINUX_TERMINALS) {
+    try {
+      return await launch(terminal, [], path, env);
+    } catch (err) {
+      logger.debug(`${terminal} is not available: ${err.message}`);
+    }
+  }
+  return Promise.reject("No terminal emulator found");
+}
+
+// Run the `node` executable at `node` in a new terminal. It's started by its full path,
+// so neither the global version nor PATH of the terminal are involved.
+export async function openRepl(node: string, cwd: string, env?: NodeJS.ProcessEnv) {
+  if (!(await pathExists(node))) return Promise.reject(`${node} does not exist`);
+
+  if (platform === "darwin") {
+    // the paths are arguments of the script, `quoted form of` escapes them for the shell
+    // of the new Terminal window
+    const script = [
+      "on run argv",
+      'tell application "Terminal" to do script "cd " & quoted form of item 2 of argv & ' +
+        '" && " & quoted form of item 1 of argv',
+      'tell application "Terminal" to activate',
+      "end run"
+    ];
+    return launch("osascript", [...script.flatMap((line) => ["-e", line]), node, cwd]);
+  }
+
+  if (platform === "win32") {
+    return launchPowerShell(
+      "Start-Process -FilePath $env:NVMD_LAUNCH_NODE -WorkingDirectory $env:NVMD_LAUNCH_FOLDER",
+      { NODE: node, FOLDER: cwd },
+      env
+    );
+  }
+
+  for (const terminal of LINUX_TERMINALS) {
+    try {
+      return await launch(terminal, [EXEC_FLAGS[terminal] ?? "-e", node], cwd, env);
+    } catch (err) {
+      logger.debug(`${terminal} is not available: ${err.message}`);
+    }
+  }
+  return Promise.reject("No terminal emulator found");
+}
+
+// Directory holding the `node` executable of an installed version.
+export const getNodeBinPath = (directory: string, version: string) =>
+  platform === "win32" ? join(directory, version) : join(directory, version, "bin");
+
+export function copyToClipboard(text: string) {
+  clipboard.writeText(text);
+  return text;
+}
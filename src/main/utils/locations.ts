@@ -0,0 +1,65 @@
+import { platform } from "node:process";
+import { dirname, join } from "node:path";
+import { lstat, readdir, realpath, rm, symlink } from "node:fs/promises";
+import { ensureDir, pathExists } from "fs-extra";
+import { getChecksums, forgetInstallation, recordInstallation } from "./verify";
+import { getVersionDirs } from "./relocate";
+import { removePath } from "./trash";
+import { logger } from "./logger";
+
+// Junctions don't require the symlink privilege on Windows.
+const LINK_TYPE = platform === "win32" ? "junction" : "dir";
+
+// A version can be installed outside of the install directory, e.g. on a secondary
+// drive. It's linked from the install directory so the shim and everything else
+// joining the directory with the version still find it, its real location is
+// kept in the installation metadata.
+export async function getVersionLocation(directory: string, version: string) {
+  const location = (await getChecksums())[version]?.location;
+  if (location) return location;
+
+  // linked before its location was recorded
+  const path = join(directory, version);
+  const stats = await lstat(path).catch(() => void 0);
+  return stats?.isSymbolicLink() ? realpath(path).catch(() => path) : path;
+}
+
+// Link the version installed at `target` into the install `directory`.
+export async function linkVersion(directory: string, version: string, target: string) {
+  const link = join(directory, version);
+  await ensureDir(dirname(link));
+  await rm(link, { force: true }).catch(() => {});
+  await symlink(target, link, LINK_TYPE);
+  await recordInstallation({ path: directory, version });
+  logger.info(`Linked ${link} to ${target}`);
+}
+
+// Remove the files of a version installed elsewhere, the link in the install
+// directory is removed along with the version.
+export async function removeVersionLocation(directory: string, version: string) {
+  const link = join(directory, version);
+  const location = await getVersionLocation(directory, version);
+  if (location !== link && (await pathExists(location))) await removePath(location);
+  await forgetInstallation(version);
+}
+
+export async function getSize(path: string): Promise<number> {
+  const stats = await lstat(path);
+  if (!stats.isDirectory()) return stats.size;
+
+  const sizes = await Promise.all(
+    (await readdir(path)).map((name) => getSize(join(path, name)).catch(() => 0))
+  );
+  return sizes.reduce((total, size) => total + size, stats.size);
+}
+
+// Disk usage of every installed version, measured where its files really are.
+export async function getDiskUsage(directory: string): Promise<Nvmd.DiskUsage[]> {
+  return Promise.all(
+    (await getVersionDirs(directory)).map(async (version) => {
+      const path = await getVersionLocation(directory, version);
+      const size = await getSize(path).catch(() => 0);
+      return { version, path, size, linked: path !== join(directory, version) };
+    })
+  );
+}
@@ -0,0 +1,28 @@
+import { pathExists, readJson } from "fs-extra";
+import { LOCKED_JSONFILE } from "../constants";
+import { writeJsonAtomic } from "./file";
+
+let locked: string[];
+
+// Versions locked against uninstall, e.g. the ones CI scripts depend on.
+export async function getLockedVersions() {
+  if (locked !== void 0) return locked;
+
+  locked = (await pathExists(LOCKED_JSONFILE))
+    ? (await readJson(LOCKED_JSONFILE, { throws: false })) || []
+    : [];
+  return locked;
+}
+
+export async function setVersionLocked(version: string, value: boolean) {
+  const versions = (await getLockedVersions()).filter((locked) => locked !== version);
+  value && versions.push(version);
+  locked = versions;
+  await writeJsonAtomic(LOCKED_JSONFILE, locked);
+}
+
+// Reject removing a locked version, it has to be unlocked explicitly first.
+export async function assertUnlocked(version: string) {
+  if ((await getLockedVersions()).includes(version))
+    return Promise.reject(`Node.js v${version} is locked, unlock it before uninstalling`);
+}
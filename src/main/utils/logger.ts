@@ -0,0 +1,76 @@
+import { existsSync, renameSync, rmSync } from "node:fs";
+import { basename, join } from "node:path";
+import { copy, pathExists, readFile, readdir } from "fs-extra";
+import log from "electron-log";
+import { LOGS_DIR } from "../constants";
+
+import type { LogFile, LogLevel } from "electron-log";
+
+const LOG_FILENAME = "main.log",
+  // Rotate once the active file reaches 1 MB, keeping up to 5 archived files.
+  MAX_SIZE = 1024 * 1024,
+  MAX_ARCHIVES = 5;
+
+const archiveName = (index: number) => join(LOGS_DIR, `main.${index}.log`);
+
+log.transports.file.resolvePathFn = () => join(LOGS_DIR, LOG_FILENAME);
+log.transports.file.maxSize = MAX_SIZE;
+log.transports.file.level = "info";
+log.transports.console.level = process.env.NODE_ENV === "development" ? "debug" : false;
+// electron-log only keeps a single `.old.log` by default, shift the archives
+// so the last `MAX_ARCHIVES` files survive a rotation.
+log.transports.file.archiveLogFn = (file: LogFile) => {
+  try {
+    rmSync(archiveName(MAX_ARCHIVES), { force: true });
+    for (let index = MAX_ARCHIVES - 1; index >= 1; index--) {
+      existsSync(archiveName(index)) && renameSync(archiveName(index), archiveName(index + 1));
+    }
+    renameSync(file.path, archiveName(1));
+  } catch (err) {
+    console.warn("Could not rotate log file", err);
+  }
+};
+
+export const logger = log.scope("main");
+
+export function setLogLevel(level: Nvmd.LogLevel = "info") {
+  log.transports.file.level = level as LogLevel;
+}
+
+// Log files ordered from the newest to the oldest.
+export async function getLogFiles() {
+  if (!(await pathExists(LOGS_DIR))) return [];
+
+  const files = await readdir(LOGS_DIR);
+  const archives = files
+    .filter((name) => /^main\.\d+\.log$/.test(name))
+    .sort((a, b) => Number(a.split(".")[1]) - Number(b.split(".")[1]));
+
+  return [LOG_FILENAME, ...archives]
+    .filter((name) => files.includes(name))
+    .map((name) => join(LOGS_DIR, name));
+}
+
+export async function tailLogs(lines: number = 200): Promise<string[]> {
+  const result: string[] = [];
+
+  for (const file of await getLogFiles()) {
+    const content = (await readFile(file)).toString().split(/\r?\n/).filter(Boolean);
+    result.unshift(...content.slice(-(lines - result.length)));
+    if (result.length >= lines) break;
+  }
+
+  return result;
+}
+
+export async function exportLogs(path: string) {
+  const target = join(path, `nvmd_logs_${Date.now()}`);
+
+  for (const file of await getLogFiles()) {
+    await copy(file, join(target, basename(file)));
+  }
+
+  return target;
+}
+
+export default logger;
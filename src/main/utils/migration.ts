@@ -1,10 +1,12 @@
 import { exec } from "node:child_process";
 import { platform, arch } from "node:process";
 import { join } from "node:path";
-import { pathExists, copy, readFile, readdir, writeFile, symlink, remove } from "fs-extra";
+import { pathExists, copy, readFile, readdir, symlink, remove } from "fs-extra";
 import { app } from "electron";
 
 import { APPDIR, BIN_DIR, MIRRATION_FILE } from "../constants";
+import { logger } from "./logger";
+import { writeFileAtomic } from "./file";
 
 const CURRENT_MIGRATION_VERSION: number = 13;
 
@@ -15,6 +17,7 @@ export async function updateSchema() {
     await updateToSchemaVersionDefault(schemaVersion);
     await updateToSchemaVersionLast(schemaVersion);
   } catch (err) {
+    logger.error("Schema migration failed", err);
     return 408;
   }
   return;
@@ -131,8 +134,8 @@ async function getSchemaVersion() {
 
 async function setSchemaVersion(version: number) {
   try {
-    await writeFile(MIRRATION_FILE, `${version}`);
+    await writeFileAtomic(MIRRATION_FILE, `${version}`);
   } catch (err) {
-    console.log(err);
+    logger.error("Could not write schema version", err);
   }
 }
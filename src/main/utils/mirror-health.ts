@@ -0,0 +1,78 @@
+import { pathExists, readJson } from "fs-extra";
+import { MIRROR_HEALTH_JSONFILE } from "../constants";
+import { writeJsonAtomic } from "./file";
+import { logger } from "./logger";
+
+const DAY = 24 * 60 * 60 * 1000;
+// Samples older than that are dropped, and at most that many kept per mirror.
+const MAX_AGE_DAYS = 30;
+const MAX_SAMPLES = 200;
+// Enough samples to judge, and the share of them that must have succeeded.
+const MIN_SAMPLES = 3;
+const MIN_SUCCESS_RATE = 0.8;
+
+type Samples = Record<string, Nvmd.MirrorSample[]>;
+
+// Loaded once, the mirrors are tested concurrently. Their samples are saved one after
+// the other, two writes of the file at once would step on each other.
+let samples: Promise<Samples>,
+  queue: Promise<void> = Promise.resolve();
+
+async function readSamples(): Promise<Samples> {
+  if (!(await pathExists(MIRROR_HEALTH_JSONFILE))) return {};
+  return (await readJson(MIRROR_HEALTH_JSONFILE, { throws: false })) || {};
+}
+
+const getSamples = () => (samples ??= readSamples());
+
+const normalize = (url: string) => url.replace(/\/+$/, "");
+
+// Record the outcome of a request to `url`: a test, or a download that failed for
+// a network reason. Canceled requests and local failures say nothing of the mirror.
+export function recordMirrorSample(url: string, sample: Omit<Nvmd.MirrorSample, "at">) {
+  const at = new Date().toISOString();
+  queue = queue.then(async () => {
+    try {
+      const all = await getSamples();
+      const since = Date.now() - MAX_AGE_DAYS * DAY;
+      const kept = (all[normalize(url)] || []).filter(({ at }) => Date.parse(at) >= since);
+      kept.push({ at, ...sample });
+      all[normalize(url)] = kept.slice(-MAX_SAMPLES);
+      await writeJsonAtomic(MIRROR_HEALTH_JSONFILE, all);
+    } catch (err) {
+      logger.warn(`Could not save the health of ${url}: ${err.message}`);
+    }
+  });
+  return queue;
+}
+
+const median = (values: number[]) => {
+  if (!values.length) return;
+  const sorted = [...values].sort((value1, value2) => value1 - value2);
+  const middle = Math.floor(sorted.length / 2);
+  return sorted.length % 2 ? sorted[middle] : (sorted[middle - 1] + sorted[middle]) / 2;
+};
+
+// Success rate and latency of each mirror over the last `days`. A mirror is flaky
+// when enough requests were made to it and too many of them failed.
+export async function getMirrorStats(days: number = 7): Promise<Nvmd.MirrorStats[]> {
+  const since = Date.now() - days * DAY;
+  return Object.entries(await getSamples())
+    .map(([url, all]) => {
+      const recent = all.filter(({ at }) => Date.parse(at) >= since);
+      const failures = recent.filter(({ ok }) => !ok);
+      const successRate = recent.length ? 1 - failures.length / recent.length : 1;
+      const time = median(recent.flatMap(({ ok, time }) => (ok && time ? [time] : [])));
+      const lastFailureAt = failures[failures.length - 1]?.at;
+      return {
+        url,
+        samples: recent.length,
+        failures: failures.length,
+        successRate,
+        ...(time !== void 0 && { medianTime: Math.round(time) }),
+        ...(lastFailureAt && { lastFailureAt }),
+        flaky: recent.length >= MIN_SAMPLES && successRate < MIN_SUCCESS_RATE
+      };
+    })
+    .filter(({ samples }) => samples);
+}
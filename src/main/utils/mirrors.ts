@@ -0,0 +1,54 @@
+import { pingMirror } from "./setting-checks";
+import { recordMirrorSample } from "./mirror-health";
+
+import type { Options as FetchOptions } from "../deps/fetch-node-website";
+
+// Mirrors of https://nodejs.org/dist known to carry every release with the same
+// layout and `index.json`, the fast ones from mainland China come after the official one.
+export const MIRROR_PRESETS: (Nvmd.MirrorPreset & { url: string })[] = [
+  { id: "official", name: "Node.js", url: "https://nodejs.org/dist" },
+  { id: "npmmirror", name: "npmmirror", url: "https://npmmirror.com/mirrors/node" },
+  {
+    id: "tsinghua",
+    name: "Tsinghua University",
+    url: "https://mirrors.tuna.tsinghua.edu.cn/nodejs-release"
+  },
+  { id: "ustc", name: "USTC", url: "https://mirrors.ustc.edu.cn/node" }
+];
+
+// The presets, followed by the `custom` entry holding the mirror in use when it
+// isn't one of them.
+export function getMirrorPresets(current?: string): Nvmd.MirrorPreset[] {
+  const url = current?.replace(/\/+$/, "");
+  const custom = url && !MIRROR_PRESETS.some((preset) => preset.url === url) ? url : undefined;
+  return [
+    ...MIRROR_PRESETS.map((preset) => ({ ...preset, active: preset.url === url })),
+    { id: "custom", name: "Custom", ...(custom && { url: custom }), active: !!custom }
+  ];
+}
+
+// The presets other than `current`, to download from when it serves a corrupt file.
+export const getAlternateMirrors = (current: string) =>
+  MIRROR_PRESETS.map(({ url }) => url).filter((url) => url !== current.replace(/\/+$/, ""));
+
+// Id of a preset or URL, to the URL of the mirror.
+export async function resolveMirror(mirror: string) {
+  const preset = MIRROR_PRESETS.find(({ id }) => id === mirror);
+  if (preset) return preset.url;
+
+  if (/^https?:\/\/\S+$/.test(mirror)) return mirror;
+  return Promise.reject(`${mirror} is neither a mirror preset nor a URL`);
+}
+
+// Reach the mirror with the connection settings in use (proxy, certificates, headers).
+// The outcome goes to the mirror's health history.
+export async function testMirror(url: string, fetchOpts: FetchOptions): Promise<Nvmd.MirrorTest> {
+  try {
+    const time = await pingMirror({ ...fetchOpts, mirror: url, indexUrl: undefined });
+    recordMirrorSample(url, { kind: "test", ok: true, time });
+    return { url, ok: true, time };
+  } catch (err) {
+    recordMirrorSample(url, { kind: "test", ok: false });
+    return { url, ok: false, error: `${err.message || err}` };
+  }
+}
@@ -0,0 +1,141 @@
+import { execFile } from "node:child_process";
+import { networkInterfaces } from "node:os";
+import { platform } from "node:process";
+import { net, powerMonitor } from "electron";
+import { logger } from "./logger";
+
+const CHECK_INTERVAL = 15 * 1000;
+// WireGuard, OpenVPN and IPsec tunnels, and the adapters of Windows VPN clients
+const VPN_INTERFACE = /^(tun|tap|utun|wg|ppp|ipsec)\d*$|vpn|wireguard|tap-windows/i;
+
+const COST_TYPE =
+  "[void][Windows.Networking.Connectivity.NetworkInformation, Windows.Networking.Connectivity," +
+  " ContentType=WindowsRuntime];" +
+  " $connection = [Windows.Networking.Connectivity.NetworkInformation]::" +
+  "GetInternetConnectionProfile();" +
+  " if ($connection) { $connection.GetConnectionCost().NetworkCostType }";
+
+let state: Nvmd.NetworkState = {
+    online: true,
+    metered: false,
+    vpn: false,
+    at: new Date().toISOString()
+  },
+  pauseOnMetered = false,
+  interfaces = "",
+  timer: NodeJS.Timeout | undefined;
+
+const listeners: ((state: Nvmd.NetworkState, previous: Nvmd.NetworkState) => void)[] = [];
+
+// Get notified when the connection goes offline or online, becomes metered or not,
+// or a VPN connects or disconnects. Returns a function to stop being notified.
+export function onNetworkChanged(
+  listener: (state: Nvmd.NetworkState, previous: Nvmd.NetworkState) => void
+) {
+  listeners.push(listener);
+  return () => {
+    listeners.includes(listener) && listeners.splice(listeners.indexOf(listener), 1);
+  };
+}
+
+const run = (command: string, args: string[]) =>
+  new Promise<string>((resolve, reject) =>
+    execFile(command, args, { timeout: 10 * 1000, windowsHide: true }, (err, stdout) =>
+      err ? reject(err) : resolve(stdout.trim())
+    )
+  );
+
+// The cost the OS gives the connection: a `Fixed` or `Variable` cost on Windows,
+// `yes` or `guess-yes` (`1`, `3`) from NetworkManager. macOS doesn't tell.
+async function isMetered() {
+  try {
+    if (platform === "win32") {
+      const args = ["-NoProfile", "-NonInteractive", "-Command", COST_TYPE];
+      return ["Fixed", "Variable"].includes(await run("powershell.exe", args));
+    }
+    if (platform === "linux") {
+      const metered = await run("busctl", [
+        "get-property",
+        "org.freedesktop.NetworkManager",
+        "/org/freedesktop/NetworkManager",
+        "org.freedesktop.NetworkManager",
+        "Metered"
+      ]);
+      return /^u [13]$/.test(metered);
+    }
+  } catch {}
+  return false;
+}
+
+// A tunnel interface with an IPv4 address, macOS keeps a few `utun` ones for its
+// own services with only a link-local IPv6 address.
+const isVpnConnected = () =>
+  Object.entries(networkInterfaces()).some(
+    ([name, addresses]) =>
+      VPN_INTERFACE.test(name) &&
+      !!addresses?.some(({ family, internal }) => family === "IPv4" && !internal)
+  );
+
+// Check the connection again. Whether it's metered is only asked to the OS when the
+// interfaces changed, that takes a process.
+export async function checkNetwork() {
+  const current = JSON.stringify(networkInterfaces());
+  const online = net.isOnline();
+  const metered =
+    online && (current !== interfaces || !state.online) ? await isMetered() : state.metered;
+  interfaces = current;
+
+  const next = { online, metered: online && metered, vpn: online && isVpnConnected() };
+  if (next.online === state.online && next.metered === state.metered && next.vpn === state.vpn) {
+    return state;
+  }
+
+  const previous = state;
+  state = { ...next, at: new Date().toISOString() };
+  logger.info(
+    `Network changed: ${state.online ? "online" : "offline"}` +
+      `${state.metered ? ", metered" : ""}${state.vpn ? ", VPN" : ""}`
+  );
+  listeners.forEach((listener) => listener(state, previous));
+  return state;
+}
+
+export const getNetworkState = () => ({ ...state, pauseOnMetered });
+
+// Why downloads and background fetches wait, undefined when they may go on.
+export const getNetworkBlock = (): Nvmd.NetworkPauseReason | undefined =>
+  !state.online ? "offline" : pauseOnMetered && state.metered ? "metered" : undefined;
+
+// Resolves once downloads may go on, rejects when `signal` aborts first.
+export function waitForNetwork(signal?: AbortSignal) {
+  return new Promise<void>((resolve, reject) => {
+    if (!getNetworkBlock()) return resolve();
+
+    const check = () => {
+      if (signal?.aborted) {
+        clearInterval(interval);
+        return reject(signal.reason);
+      }
+      if (getNetworkBlock()) return;
+      clearInterval(interval);
+      resolve();
+    };
+    // the monitor updates the state, the waits only read it
+    const interval = setInterval(check, 1000);
+    signal?.addEventListener("abort", check, { once: true });
+  });
+}
+
+// Watch the connection, `pauseOnMetered` keeps downloads from starting on a
+// metered connection (and pauses the running ones).
+export function setNetworkMonitor(value: boolean = false) {
+  pauseOnMetered = value;
+  if (timer) return;
+
+  checkNetwork().catch(() => {});
+  timer = setInterval(() => checkNetwork().catch(() => {}), CHECK_INTERVAL);
+  // don't keep the app alive for the checks
+  timer.unref();
+  // the machine may have moved to another network while asleep
+  powerMonitor.on("resume", () => checkNetwork().catch(() => {}));
+}
@@ -0,0 +1,105 @@
+import { execFile } from "node:child_process";
+import { join } from "node:path";
+import { platform } from "node:process";
+import { pathExists } from "fs-extra";
+import { logger } from "./logger";
+
+const SEVERITIES: Nvmd.AuditSeverity[] = ["info", "low", "moderate", "high", "critical"];
+// the registry answers slowly for large trees
+const AUDIT_TIMEOUT = 2 * 60 * 1000;
+const LOCK_FILES = ["package-lock.json", "npm-shrinkwrap.json"];
+
+type Report = {
+  error?: { code?: string; summary?: string };
+  metadata?: { vulnerabilities?: Partial<Record<Nvmd.AuditSeverity | "total", number>> };
+  // npm 7 and later
+  vulnerabilities?: Record<
+    string,
+    { severity: Nvmd.AuditSeverity; isDirect?: boolean; range?: string; fixAvailable?: unknown }
+  >;
+  // npm 6
+  advisories?: Record<
+    string,
+    { module_name: string; severity: Nvmd.AuditSeverity; vulnerable_versions?: string }
+  >;
+};
+
+// npm exits with 1 when vulnerabilities are found, the report is on stdout either way.
+const npmAudit = (
+  path: string,
+  { env, npmCli, signal }: { env?: NodeJS.ProcessEnv; npmCli?: string; signal?: AbortSignal }
+) =>
+  new Promise<Report>((resolve, reject) => {
+    const options = {
+      cwd: path,
+      env,
+      signal,
+      timeout: AUDIT_TIMEOUT,
+      maxBuffer: 64 * 1024 * 1024,
+      // npm is a `.cmd` script on Windows, a pinned npm runs on node itself
+      shell: !npmCli && platform === "win32",
+      windowsHide: true
+    };
+    const args = [...(npmCli ? [npmCli] : []), "audit", "--json"];
+    execFile(npmCli ? "node" : "npm", args, options, (err, stdout, stderr) => {
+      try {
+        resolve(JSON.parse(stdout));
+      } catch {
+        reject(stderr.trim().split("\n").pop() || err?.message || "npm audit printed no report");
+      }
+    });
+  });
+
+function getVulnerabilities({ vulnerabilities, advisories }: Report): Nvmd.AuditVulnerability[] {
+  if (vulnerabilities) {
+    return Object.entries(vulnerabilities).map(([name, vulnerability]) => ({
+      name,
+      severity: vulnerability.severity,
+      direct: !!vulnerability.isDirect,
+      ...(vulnerability.range && { range: vulnerability.range }),
+      fixAvailable: !!vulnerability.fixAvailable
+    }));
+  }
+
+  return Object.values(advisories || {}).map(({ module_name, severity, vulnerable_versions }) => ({
+    name: module_name,
+    severity,
+    direct: false,
+    ...(vulnerable_versions && { range: vulnerable_versions }),
+    fixAvailable: false
+  }));
+}
+
+// Vulnerabilities of the project's dependencies, from `npm audit` run with `env` (the
+// project's version first in PATH, so its own npm) or the npm `npmCli` pinned by the
+// project. Projects need an npm lock file,
+// those of other package managers are rejected.
+export async function auditProject(
+  path: string,
+  version: string,
+  options: { env?: NodeJS.ProcessEnv; npmCli?: string; signal?: AbortSignal } = {}
+): Promise<Nvmd.ProjectAudit> {
+  const locks = await Promise.all(LOCK_FILES.map((file) => pathExists(join(path, file))));
+  if (!locks.some(Boolean)) return Promise.reject(`${path} has no package-lock.json to audit`);
+
+  const report = await npmAudit(path, options);
+  if (report.error) {
+    return Promise.reject(report.error.summary || `npm audit failed (${report.error.code})`);
+  }
+
+  const vulnerabilities = getVulnerabilities(report).sort(
+    (vulnerability1, vulnerability2) =>
+      SEVERITIES.indexOf(vulnerability2.severity) - SEVERITIES.indexOf(vulnerability1.severity)
+  );
+  const counts = Object.fromEntries(
+    SEVERITIES.map((severity) => [
+      severity,
+      report.metadata?.vulnerabilities?.[severity] ??
+        vulnerabilities.filter((vulnerability) => vulnerability.severity === severity).length
+    ])
+  ) as Record<Nvmd.AuditSeverity, number>;
+  const total = SEVERITIES.reduce((sum, severity) => sum + counts[severity], 0);
+
+  logger.info(`Audited ${path} with Node.js v${version}: ${total} vulnerabilities`);
+  return { path, version, at: new Date().toISOString(), counts, total, vulnerabilities };
+}
@@ -0,0 +1,73 @@
+import { homedir } from "node:os";
+import { join } from "node:path";
+import { env, platform } from "node:process";
+import { pathExists, readFile, remove } from "fs-extra";
+import { logFailures, mapLimit, toWarnings } from "./concurrency";
+import { writeFileAtomic } from "./file";
+import { getSize } from "./locations";
+import { logger } from "./logger";
+
+// npm reads `<prefix>/etc/npmrc` as its global config, and the prefix of a version run
+// through nvmd is its install folder. The builtin `npmrc` of the npm package would be
+// lost when npm upgrades itself.
+const getNpmrc = (directory: string, version: string) => join(directory, version, "etc", "npmrc");
+
+// npm's own default, used when no shared cache is set
+const getDefaultCache = () =>
+  platform === "win32"
+    ? join(env.LOCALAPPDATA || join(homedir(), "AppData", "Local"), "npm-cache")
+    : join(homedir(), ".npm");
+
+const CACHE_LINE = /^\s*cache\s*=(.*)$/;
+
+// Replace the `cache` entry written for `previous` by one for `cache`, or drop it
+// when `cache` is empty. Entries the user wrote for another folder are left alone.
+async function writeNpmrc(file: string, cache?: string, previous?: string) {
+  const content = (await pathExists(file)) ? (await readFile(file)).toString() : "";
+  const lines = content.split(/\r?\n/).filter((line) => {
+    const value = CACHE_LINE.exec(line)?.[1].trim();
+    return value === undefined || (value !== previous && value !== cache);
+  });
+  while (lines.length && !lines[lines.length - 1]) lines.pop();
+
+  cache && lines.push(`cache=${cache}`);
+  if (!content && !lines.length) return;
+  await writeFileAtomic(file, lines.length ? `${lines.join("\n")}\n` : "");
+}
+
+// Point a newly installed version at the shared cache.
+export async function writeNpmCache(directory: string, version: string, cache?: string) {
+  if (!cache) return;
+  await writeNpmrc(getNpmrc(directory, version), cache);
+}
+
+// Move every installed version to the shared `cache`, or back to npm's default.
+// Resolves to the versions that couldn't be moved, as warnings.
+export async function setNpmCache(
+  directory: string,
+  versions: string[],
+  cache?: string,
+  previous?: string
+) {
+  const results = await mapLimit(versions, (version) =>
+    writeNpmrc(getNpmrc(directory, version), cache, previous)
+  );
+  logFailures("Could not set the npm cache of", results, (version) => `v${version}`);
+  logger.info(cache ? `npm cache shared in ${cache}` : "npm cache back to the npm default");
+  return toWarnings(results, (version) => `v${version}`);
+}
+
+// The content-addressed store is what grows, the logs and `_npx` next to it are
+// left to npm.
+export async function getNpmCacheInfo(cache?: string): Promise<Nvmd.NpmCacheInfo> {
+  const path = cache || getDefaultCache();
+  const size = await getSize(join(path, "_cacache")).catch(() => 0);
+  return { path, shared: !!cache, size };
+}
+
+export async function clearNpmCache(cache?: string): Promise<Nvmd.NpmCacheInfo> {
+  const path = cache || getDefaultCache();
+  await remove(join(path, "_cacache"));
+  logger.info(`Cleared the npm cache in ${path}`);
+  return { path, shared: !!cache, size: 0 };
+}
@@ -0,0 +1,78 @@
+import { createHash } from "node:crypto";
+import { join } from "node:path";
+import { Readable } from "node:stream";
+import { buffer, json } from "node:stream/consumers";
+import { pipeline } from "node:stream/promises";
+import { createGunzip } from "node:zlib";
+import { move, pathExists, readdir, remove } from "fs-extra";
+import { valid } from "semver";
+import { RUNTIMES_DIR } from "../constants";
+import { fetchNodeWebsite } from "../deps/fetch-node-website";
+import { untar } from "../deps/get-node/archive/tar";
+import { logger } from "./logger";
+
+import type { Options as FetchOptions } from "../deps/fetch-node-website";
+
+// npm versions pinned by projects, unpacked from the registry tarball (npm bundles
+// its dependencies) to `RUNTIMES_DIR/npm/<version>`. The app runs them on the project's
+// node for the scripts and audits it starts, the npm shim keeps the bundled one.
+const NPM_DIR = join(RUNTIMES_DIR, "npm");
+const DEFAULT_REGISTRY = "https://registry.npmjs.org";
+
+export const getNpmCli = (version: string) => join(NPM_DIR, version, "bin", "npm-cli.js");
+
+export async function getInstalledNpmVersions() {
+  if (!(await pathExists(NPM_DIR))) return [];
+
+  const versions = await Promise.all(
+    (await readdir(NPM_DIR)).map(async (version) =>
+      valid(version) && (await pathExists(getNpmCli(version))) ? version : ""
+    )
+  );
+  return versions.filter(Boolean);
+}
+
+// Download npm `version` from `registry`, checked against the integrity the registry
+// publishes for it. Installed versions are left as they are.
+export async function installNpm(
+  version: string,
+  { fetchOpts, registry = DEFAULT_REGISTRY }: { fetchOpts: FetchOptions; registry?: string }
+) {
+  if (!valid(version)) return Promise.reject(`${version} is not an npm version`);
+  if (await pathExists(getNpmCli(version))) return join(NPM_DIR, version);
+
+  const mirror = registry.replace(/\/+$/, "");
+  const manifest = (await json(
+    (await fetchNodeWebsite(`npm/${version}`, { ...fetchOpts, mirror })) as NodeJS.ReadableStream
+  )) as { dist?: { tarball?: string; integrity?: string } };
+  const [algorithm, digest] = manifest.dist?.integrity?.split("-") ?? [];
+  if (!manifest.dist?.tarball || !digest) {
+    return Promise.reject(`${registry} has no tarball of npm ${version}`);
+  }
+
+  const response = await fetchNodeWebsite(`npm/-/npm-${version}.tgz`, { ...fetchOpts, mirror });
+  const content = await buffer(response as NodeJS.ReadableStream);
+  if (createHash(algorithm).update(content).digest("base64") !== digest) {
+    return Promise.reject(`Could not download npm ${version}: integrity did not match`);
+  }
+
+  const tmpDir = join(NPM_DIR, `.${version}.${Date.now()}.tmp`);
+  try {
+    await pipeline(Readable.from(content), createGunzip(), untar(tmpDir));
+    // the tarball holds a `package` folder
+    await move(join(tmpDir, "package"), join(NPM_DIR, version), { overwrite: true });
+  } finally {
+    await remove(tmpDir).catch(() => {});
+  }
+  logger.info(`Installed npm ${version} to ${join(NPM_DIR, version)}`);
+  return join(NPM_DIR, version);
+}
+
+// Remove the npm versions no project pins anymore.
+export async function removeUnusedNpm(pinned: string[]) {
+  for (const version of await getInstalledNpmVersions()) {
+    if (pinned.includes(version)) continue;
+    await remove(join(NPM_DIR, version));
+    logger.info(`Removed npm ${version}, no project pins it anymore`);
+  }
+}
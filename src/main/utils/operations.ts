@@ -0,0 +1,42 @@
+// Long running operations (downloads, installs, migrations, cleanups, scans,
+// benchmarks) keyed by the id the renderer passes, so that any of them can be
+// canceled by `operation-cancel`. Each one checks its signal between two steps.
+const operations = new Map<string, AbortController>();
+
+// Canceled along with `parent` when given, e.g. an install of a batch.
+export function startOperation(id?: string, parent?: AbortSignal) {
+  const controller = new AbortController();
+  id && operations.set(id, controller);
+  parent?.aborted
+    ? controller.abort()
+    : parent?.addEventListener("abort", () => controller.abort(), { once: true });
+  return controller.signal;
+}
+
+export function endOperation(id?: string) {
+  id && operations.delete(id);
+}
+
+export function cancelOperation(id: string) {
+  const controller = operations.get(id);
+  if (!controller) return false;
+
+  controller.abort();
+  operations.delete(id);
+  return true;
+}
+
+export const getOperations = () => [...operations.keys()];
+
+// Run `operation` under `id`, it's forgotten once settled.
+export async function runOperation<T>(
+  id: string | undefined,
+  operation: (signal: AbortSignal) => Promise<T>
+) {
+  const signal = startOperation(id);
+  try {
+    return await operation(signal);
+  } finally {
+    endOperation(id);
+  }
+}
@@ -0,0 +1,104 @@
+import { execFile } from "node:child_process";
+import { homedir } from "node:os";
+import { join } from "node:path";
+import { env, platform } from "node:process";
+import { pathExists, readJson } from "fs-extra";
+import { PACKAGE_JSON_NAME } from "../constants";
+import { getNodeEnv } from "./env";
+import { getNodeBinPath } from "./launcher";
+import { logger } from "./logger";
+
+// `packageManager` of the project's `package.json`: `yarn@4.1.0+sha512.abc...`.
+export async function readPackageManager(path: string) {
+  const file = join(path, PACKAGE_JSON_NAME);
+  if (!(await pathExists(file))) return;
+
+  const { packageManager } = (await readJson(file, { throws: false })) || {};
+  if (typeof packageManager !== "string") return;
+
+  const match = /^(@?[^@]+)@([^+]+)(?:\+(.+))?$/.exec(packageManager);
+  if (!match) return;
+
+  const [, name, version, hash] = match;
+  return { spec: packageManager, name, version, hash };
+}
+
+// Where Corepack keeps the package manager releases it downloaded.
+function getCorepackHome() {
+  if (env.COREPACK_HOME) return env.COREPACK_HOME;
+  if (platform === "win32") {
+    return join(env.LOCALAPPDATA || join(homedir(), "AppData", "Local"), "node", "corepack");
+  }
+  return join(env.XDG_CACHE_HOME || join(homedir(), ".cache"), "node", "corepack");
+}
+
+// Corepack 0.26 moved its cache to a `v1` folder.
+const isProvisioned = async (name: string, version: string) => {
+  const home = getCorepackHome();
+  const found = await Promise.all(
+    [join(home, "v1", name, version), join(home, name, version)].map((dir) => pathExists(dir))
+  );
+  return found.some(Boolean);
+};
+
+const getCorepackScript = (directory: string, version: string) =>
+  platform === "win32"
+    ? join(directory, version, "node_modules", "corepack", "dist", "corepack.js")
+    : join(directory, version, "lib", "node_modules", "corepack", "dist", "corepack.js");
+
+const yarnShim = (directory: string, version: string) =>
+  join(getNodeBinPath(directory, version), platform === "win32" ? "yarn.cmd" : "yarn");
+
+// Yarn required by the project and whether it's ready to run: downloaded by Corepack
+// and linked into the bin folder of the project's Node.js version, where the shim finds it.
+export async function getYarnStatus(
+  directory: string,
+  nodeVersion: string,
+  path: string
+): Promise<Nvmd.YarnStatus | undefined> {
+  const packageManager = await readPackageManager(path);
+  if (packageManager?.name !== "yarn") return;
+
+  const [corepack, provisioned, shimmed] = await Promise.all([
+    nodeVersion ? pathExists(getCorepackScript(directory, nodeVersion)) : false,
+    isProvisioned("yarn", packageManager.version),
+    nodeVersion ? pathExists(yarnShim(directory, nodeVersion)) : false
+  ]);
+  const { spec, version } = packageManager;
+  return { spec, version, corepack, provisioned, shimmed };
+}
+
+const runCorepack = (directory: string, nodeVersion: string, path: string, args: string[]) =>
+  new Promise<string>((resolve, reject) => {
+    const node = join(
+      getNodeBinPath(directory, nodeVersion),
+      platform === "win32" ? "node.exe" : "node"
+    );
+    execFile(
+      node,
+      [getCorepackScript(directory, nodeVersion), ...args],
+      { cwd: path, env: getNodeEnv(directory, nodeVersion), windowsHide: true },
+      (err, stdout, stderr) => (err ? reject(stderr.trim() || err.message) : resolve(stdout))
+    );
+  });
+
+// Download the Yarn release of the project's `packageManager` with Corepack and link
+// `yarn` into the bin folder of its Node.js version. Versions without Corepack
+// (before 16.9) can't provision it.
+export async function provisionYarn(directory: string, nodeVersion: string, path: string) {
+  const status = await getYarnStatus(directory, nodeVersion, path);
+  if (!status) return Promise.reject("The project does not declare Yarn in packageManager");
+  if (!status.corepack) {
+    return Promise.reject(`Node.js v${nodeVersion} does not ship Corepack, use 16.9 or later`);
+  }
+
+  status.shimmed || (await runCorepack(directory, nodeVersion, path, ["enable", "yarn"]));
+  // `install` reads `packageManager` since Corepack 0.22, older ones only know `prepare`
+  status.provisioned ||
+    (await runCorepack(directory, nodeVersion, path, ["install"]).catch(() =>
+      runCorepack(directory, nodeVersion, path, ["prepare", status.spec])
+    ));
+  logger.info(`Provisioned ${status.spec} for ${path}`);
+
+  return getYarnStatus(directory, nodeVersion, path);
+}
@@ -0,0 +1,108 @@
+import { join } from "node:path";
+import { env, platform } from "node:process";
+import { execFile } from "node:child_process";
+import { pathExists } from "fs-extra";
+import { BIN_DIR } from "../constants";
+import { CURRENT_BIN } from "./activation";
+import { logger } from "./logger";
+import { samePath } from "./winpath";
+
+const KEYS: Record<Nvmd.PathScope, string> = {
+  user: "HKCU:\\Environment",
+  machine: "HKLM:\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Environment"
+};
+
+const powershell = (command: string) =>
+  new Promise<string>((resolve, reject) => {
+    const args = ["-NoProfile", "-NonInteractive", "-Command", command];
+    execFile("powershell.exe", args, { windowsHide: true }, (err, stdout, stderr) =>
+      err ? reject(stderr.trim() || err.message) : resolve(stdout.trim())
+    );
+  });
+
+const quote = (value: string) => `'${value.replace(/'/g, "''")}'`;
+
+// The PATH as stored, `%SystemRoot%` and the like left unexpanded so they survive the
+// rewrite.
+export async function readPath(scope: Nvmd.PathScope) {
+  const value = await powershell(
+    `(Get-Item ${quote(KEYS[scope])}).GetValue('Path', '', 'DoNotExpandEnvironmentNames')`
+  );
+  return value.split(";");
+}
+
+// The user PATH, stored as an expandable string. A variable is then set and cleared
+// through .NET, which tells running programs (Explorer, new terminals) to reload the
+// environment. The machine PATH is shared by every user, it's never written.
+export async function writeUserPath(paths: string[]) {
+  await powershell(
+    [
+      `Set-ItemProperty -Path ${quote(KEYS.user)} -Name Path -Type ExpandString ` +
+        `-Value ${quote(paths.join(";"))}`,
+      "[Environment]::SetEnvironmentVariable('NVMD_PATH_REFRESH', '1', 'User')",
+      "[Environment]::SetEnvironmentVariable('NVMD_PATH_REFRESH', $null, 'User')"
+    ].join("\n")
+  );
+}
+
+const expand = (path: string) =>
+  path.replace(/%([^%]+)%/g, (match, name) => {
+    const key = Object.keys(env).find((key) => key.toUpperCase() === name.toUpperCase());
+    return key ? env[key]! : match;
+  });
+
+const isNvmd = (path: string) =>
+  samePath(expand(path), BIN_DIR) || samePath(expand(path), CURRENT_BIN);
+
+// Directories providing a `node.exe` of their own, they win over the shims when first.
+const hasNode = (path: string) => !isNvmd(path) && pathExists(join(expand(path), "node.exe"));
+
+// Entries left once empty ones and repeated ones are dropped, the first spelling kept.
+// `seen` carries the entries of the machine PATH, which comes first.
+function dedupe(paths: string[], seen: string[]) {
+  const kept: string[] = [];
+  const removed: string[] = [];
+  for (const path of paths) {
+    const repeated = [...seen, ...kept].some((other) => samePath(expand(other), expand(path)));
+    (!path.trim() || repeated ? removed : kept).push(path);
+  }
+  return { kept, removed };
+}
+
+// Put the shims first in the user PATH, ahead of every other `node.exe` in it, and drop
+// its empty entries and those repeating the machine PATH. The machine PATH is searched
+// first but left alone, the entries of it holding a node are reported as `shadowing`:
+// they win over the shims. With `dryRun` nothing is written, the change is only reported.
+export async function repairPath({
+  dryRun = false
+}: { dryRun?: boolean } = {}): Promise<Nvmd.PathRepair> {
+  if (platform !== "win32") return Promise.reject("PATH repair is only available on Windows");
+
+  const [machine, user] = await Promise.all([readPath("machine"), readPath("user")]);
+  const nodes = await Promise.all(machine.map(hasNode));
+  const shadowing = machine.filter((_, index) => nodes[index]);
+  const shims = user.find(isNvmd) ?? BIN_DIR;
+
+  const { kept, removed } = dedupe(user, dedupe(machine, []).kept);
+  const after = [shims, ...kept.filter((path) => !isNvmd(path))];
+  const changes: Nvmd.PathChange[] =
+    after.join(";") === user.join(";")
+      ? []
+      : [
+          {
+            scope: "user",
+            before: user,
+            after,
+            removed,
+            added: after.filter((path) => !user.includes(path))
+          }
+        ];
+
+  if (!dryRun && changes.length) {
+    await writeUserPath(after);
+    logger.info(`Repaired the user PATH: ${after.join(";")}`);
+  }
+  shadowing.length &&
+    logger.warn(`The machine PATH has a node ahead of the shims: ${shadowing.join(";")}`);
+  return { changes, shadowing };
+}
@@ -0,0 +1,79 @@
+import { basename, join } from "node:path";
+import { move, pathExists } from "fs-extra";
+import {
+  APPDIR,
+  BACKUPS_DIR,
+  BIN_DIR,
+  CACHE_DIR,
+  CHECKSUMS_JSONFILE,
+  CONFIG_DIR,
+  CRASHES_DIR,
+  DOWNLOADS_DIR,
+  GROUPS_JSONFILE,
+  LOGS_DIR,
+  PROJECTS_JSONFILE,
+  SCHEMA_JSONFILE,
+  SETTING_JSONFILE,
+  STATE_DIR,
+  TELEMETRY_JSONFILE,
+  VERSIONS_FILENAME,
+  XDG
+} from "../constants";
+import { logger } from "./logger";
+
+export function getAppPaths(): Nvmd.AppPaths {
+  return {
+    xdg: XDG,
+    home: APPDIR,
+    bin: BIN_DIR,
+    config: CONFIG_DIR,
+    state: STATE_DIR,
+    cache: CACHE_DIR,
+    logs: LOGS_DIR,
+    downloads: DOWNLOADS_DIR,
+    setting: SETTING_JSONFILE,
+    projects: PROJECTS_JSONFILE,
+    groups: GROUPS_JSONFILE
+  };
+}
+
+// Move files written by older releases from `~/.nvmd` to the XDG base directories.
+// Existing files at the new location win, the old copy is then left untouched.
+export async function migrateToXdg() {
+  if (!XDG) return;
+
+  const targets = [
+    SCHEMA_JSONFILE,
+    VERSIONS_FILENAME,
+    CHECKSUMS_JSONFILE,
+    TELEMETRY_JSONFILE,
+    LOGS_DIR,
+    CRASHES_DIR,
+    BACKUPS_DIR
+  ];
+
+  for (const target of targets) {
+    const source = join(APPDIR, basename(target));
+    try {
+      if (!(await pathExists(source)) || (await pathExists(target))) continue;
+
+      await move(source, target);
+      logger.info(`Moved ${source} to ${target}`);
+    } catch (err) {
+      logger.warn(`Could not move ${source} to ${target}: ${err.message}`);
+    }
+  }
+
+  // the shim reads them from `~/.nvmd`, where releases that had moved them put them back
+  for (const target of [PROJECTS_JSONFILE, GROUPS_JSONFILE]) {
+    const source = join(CONFIG_DIR, basename(target));
+    try {
+      if (!(await pathExists(source)) || (await pathExists(target))) continue;
+
+      await move(source, target);
+      logger.info(`Moved ${source} back to ${target}`);
+    } catch (err) {
+      logger.warn(`Could not move ${source} to ${target}: ${err.message}`);
+    }
+  }
+}
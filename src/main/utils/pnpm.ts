@@ -0,0 +1,28 @@
+import { arch, platform } from "node:process";
+
+import type { RuntimeDefinition } from "./runtimes";
+
+// Standalone executables bundling their own Node.js. Windows on ARM runs the x64 build.
+const TARGETS: Record<string, string> = {
+  "linux-x64": "linux-x64",
+  "linux-arm64": "linux-arm64",
+  "darwin-x64": "macos-x64",
+  "darwin-arm64": "macos-arm64",
+  "win32-x64": "win-x64.exe",
+  "win32-arm64": "win-x64.exe"
+};
+
+const target = TARGETS[`${platform}-${arch}`];
+
+// Only the pnpm of the project's `.pnpm-version` (or the default one) runs through the
+// shim, `packageManager` is left to Corepack.
+export const pnpm: RuntimeDefinition = {
+  name: "pnpm",
+  title: "pnpm",
+  repository: "pnpm/pnpm",
+  getTag: (version) => `v${version}`,
+  parseTag: (tag) => /^v(\d+\.\d+\.\d+.*)$/.exec(tag)?.[1],
+  getAsset: () => target && `pnpm-${target}`,
+  shims: { pnpm: [], pnpx: ["dlx"] },
+  versionFile: ".pnpm-version"
+};
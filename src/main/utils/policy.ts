@@ -0,0 +1,70 @@
+import { join } from "node:path";
+import { env, platform } from "node:process";
+import { pathExists, readJson } from "fs-extra";
+import { isEqual } from "lodash";
+import { CommandError } from "./errors";
+import { logger } from "./logger";
+
+// Written by the administrators, in a system-wide location users can't write to.
+export const POLICY_FILE =
+  platform === "win32"
+    ? join(env.ProgramData || "C:\\ProgramData", "nvmd", "policy.json")
+    : platform === "darwin"
+      ? "/Library/Application Support/nvmd/policy.json"
+      : "/etc/nvmd/policy.json";
+
+let policy: Nvmd.Policy = {};
+
+export async function loadPolicy() {
+  try {
+    policy = (await pathExists(POLICY_FILE)) ? (await readJson(POLICY_FILE)) || {} : {};
+  } catch (err) {
+    // the restrictions read last stay in effect
+    logger.error(`Could not read the policy ${POLICY_FILE}: ${err.message}`);
+  }
+  Object.keys(policy).length && logger.info(`Applying the policy of ${POLICY_FILE}`);
+  return policy;
+}
+
+export const getPolicy = () => policy;
+
+// Setting fields the policy decides, shown read-only.
+export function getLockedFields(): (keyof Nvmd.Setting)[] {
+  const locked: (keyof Nvmd.Setting)[] = [];
+  policy.mirror && locked.push("mirror", "indexUrl", "source");
+  policy.disableTelemetry && locked.push("telemetry");
+  return locked;
+}
+
+// `setting` with the values imposed by the policy.
+export function enforcePolicy(setting: Nvmd.Setting): Nvmd.Setting {
+  return {
+    ...setting,
+    ...(policy.mirror && { mirror: policy.mirror, indexUrl: undefined, source: undefined }),
+    ...(policy.disableTelemetry && { telemetry: false })
+  };
+}
+
+// Leave out the changes of locked fields.
+export function withoutLocked(data: Partial<Nvmd.Setting>, setting: Nvmd.Setting) {
+  const locked = getLockedFields();
+  const entries = Object.entries(data).filter(([key, value]) => {
+    if (!locked.includes(key as keyof Nvmd.Setting)) return true;
+    isEqual(value, setting[key]) ||
+      logger.warn(`${key} is set by the policy and cannot be changed`);
+    return false;
+  });
+  return Object.fromEntries(entries) as Partial<Nvmd.Setting>;
+}
+
+export function assertVersionAllowed(version: string) {
+  const major = Number(version.replace(/^v/, "").split(".")[0]);
+  if (!policy.forbiddenMajors?.includes(major)) return;
+
+  throw new CommandError("failed", `Node.js ${major} is forbidden by the policy`, {
+    code: "POLICY",
+    context: { version }
+  });
+}
+
+export const isChecksumRequired = () => !!policy.enforceChecksums;
@@ -0,0 +1,43 @@
+import { homedir } from "node:os";
+import { isAbsolute, relative, resolve, sep } from "node:path";
+import { GROUPS_JSONFILE, PROJECTS_JSONFILE } from "../constants";
+import { isInside } from "./winpath";
+
+// Project paths inside this folder are stored relative to it, so a synced or exported
+// `projects.json` works on machines where the projects live under another home.
+let baseDirectory: string | undefined;
+
+export function setProjectsBase(directory?: string) {
+  baseDirectory = directory ? resolve(directory.replace(/^~(?=$|[\\/])/, homedir())) : undefined;
+}
+
+// `app/web` for `<base>/app/web`, with forward slashes whatever the OS.
+export const toPortablePath = (path: string) =>
+  baseDirectory && isInside(path, baseDirectory)
+    ? relative(baseDirectory, path).split(sep).join("/") || "."
+    : path;
+
+// Relative paths are resolved against the base of this machine, the home folder
+// when none is set.
+export const fromPortablePath = (path: string) =>
+  isAbsolute(path) ? path : resolve(baseDirectory ?? homedir(), path);
+
+export const mapProjectPaths = (projects: Nvmd.Project[], map: (path: string) => string) =>
+  projects.map((project) => ({ ...project, path: map(project.path) }));
+
+export const mapGroupPaths = (groups: Nvmd.Group[], map: (path: string) => string) =>
+  groups.map((group) => ({ ...group, projects: (group.projects || []).map(map) }));
+
+const convert = (file: string, data: unknown, map: (path: string) => string) => {
+  if (!Array.isArray(data)) return data;
+  if (file === PROJECTS_JSONFILE) return mapProjectPaths(data, map);
+  if (file === GROUPS_JSONFILE) return mapGroupPaths(data, map);
+  return data;
+};
+
+// The config files hold the portable form, the rest of the app only sees absolute paths.
+export const toPortableConfig = (file: string, data: unknown) =>
+  convert(file, data, toPortablePath);
+
+export const fromPortableConfig = (file: string, data: unknown) =>
+  convert(file, data, fromPortablePath);
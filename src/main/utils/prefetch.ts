@@ -0,0 +1,171 @@
+import { once } from "node:events";
+import { createReadStream, createWriteStream } from "node:fs";
+import { stat } from "node:fs/promises";
+import { dirname, join } from "node:path";
+import { pipeline } from "node:stream/promises";
+import { powerMonitor } from "electron";
+import { ensureDir, move, pathExists, readdir, remove } from "fs-extra";
+import fetchNodeWebsite from "../deps/fetch-node-website";
+import { getArchiveFilepath } from "../deps/get-node/archive";
+import { CHECKSUM_MISMATCH, checkChecksum } from "../deps/get-node/checksum";
+import { trackProgress } from "../deps/get-node/progress";
+import { getActiveInstalls } from "./installs";
+import { getNetworkBlock } from "./network";
+import { logger } from "./logger";
+
+import type { Arch } from "../deps/get-node/archive/types";
+import type { Options as FetchNodeOptions } from "../deps/fetch-node-website";
+
+const CHECK_INTERVAL = 30 * 60 * 1000;
+// seconds without keyboard or mouse input before a prefetch may start
+const IDLE_THRESHOLD = 5 * 60;
+
+type Context = {
+  // newest LTS release
+  version: string;
+  installed: boolean;
+  arch: Arch;
+  cache: string;
+  fetchOpts: FetchNodeOptions;
+};
+
+let enabled = false,
+  running = false,
+  timer: NodeJS.Timeout | undefined,
+  getContext: (() => Promise<Context | undefined>) | undefined,
+  last: Nvmd.PrefetchedArchive | undefined;
+
+// Archives downloaded ahead of an install, as `v<version>/<file>` like on the mirror.
+// Installs read the archive from there instead of downloading it.
+export const getPrefetchDir = (cache: string) => join(cache, "prefetch");
+
+// Download the archive of `version` to the prefetch folder, where installs read it from.
+// A download cut before its end leaves its part behind, the next one asks the mirror for
+// the rest with a range request. The whole file is then checked against its checksum.
+export async function downloadArchive({
+  version,
+  arch,
+  cache,
+  fetchOpts,
+  onProgress
+}: Omit<Context, "installed"> & { onProgress?: (data: Nvmd.ProgressData) => void }) {
+  const file = await getArchiveFilepath(version, arch, fetchOpts);
+  const path = join(getPrefetchDir(cache), `v${version}`, file);
+  if (await pathExists(path)) return path;
+
+  await ensureDir(dirname(path));
+  const partial = `${path}.download`;
+  const offset = (await stat(partial).catch(() => undefined))?.size ?? 0;
+  const response = await fetchNodeWebsite(`v${version}/${file}`, {
+    ...fetchOpts,
+    headers: { ...fetchOpts.headers, ...(offset && { Range: `bytes=${offset}-` }) }
+  });
+
+  let statusCode: number;
+  try {
+    [{ statusCode }] = await once(response, "response");
+  } catch (err) {
+    // the part is already the whole file, or the release changed since
+    if (err.response?.statusCode !== 416) throw err;
+    await remove(partial);
+    return downloadArchive({ version, arch, cache, fetchOpts, onProgress });
+  }
+
+  // mirrors ignoring the range send the whole file again
+  const start = statusCode === 206 ? offset : 0;
+  trackProgress(
+    response,
+    onProgress &&
+      ((data) =>
+        onProgress({
+          ...data,
+          percent: data.total ? (start + data.transferred) / (start + data.total) : data.percent,
+          transferred: start + data.transferred,
+          total: data.total && start + data.total,
+          phase: "downloading"
+        }))
+  );
+  await pipeline(response, createWriteStream(partial, { flags: start ? "a" : "w" }));
+
+  const error = await checkChecksum({
+    version,
+    filepath: file,
+    response: createReadStream(partial),
+    fetchOpts
+  });
+  if (error) {
+    (error as Error & { code?: string }).code === CHECKSUM_MISMATCH && (await remove(partial));
+    throw error;
+  }
+  await move(partial, path, { overwrite: true });
+  return path;
+}
+
+// The archive of `version` once installed, with the part of an unfinished download.
+export const removeArchive = (cache: string, version: string) =>
+  remove(join(getPrefetchDir(cache), `v${version}`));
+
+// Download the archive of `version` without installing it. Only one version is kept,
+// the archives of the others are removed.
+export async function prefetchVersion(context: Context) {
+  const directory = getPrefetchDir(context.cache);
+  const path = await downloadArchive(context);
+
+  const stale = (await readdir(directory)).filter((name) => name !== `v${context.version}`);
+  await Promise.all(stale.map((name) => remove(join(directory, name))));
+  return path;
+}
+
+// Nothing starts while the user is at the machine or an install is running, not to
+// compete with them for the bandwidth, nor offline or on an avoided metered connection.
+// Once the version is installed its archive goes.
+const run = async () => {
+  if (!enabled || running || !getContext) return;
+  if (getActiveInstalls().length || powerMonitor.getSystemIdleTime() < IDLE_THRESHOLD) return;
+  if (getNetworkBlock()) return;
+
+  running = true;
+  try {
+    const context = await getContext();
+    if (!context) return;
+    if (context.installed) {
+      await remove(getPrefetchDir(context.cache));
+      return;
+    }
+
+    const path = await prefetchVersion(context);
+    if (last?.path !== path) {
+      last = { version: context.version, path, at: new Date().toISOString() };
+      logger.info(`Prefetched Node.js v${context.version} to ${path}`);
+    }
+  } catch (err) {
+    logger.warn(`Could not prefetch the newest LTS release: ${err.message || err}`);
+  } finally {
+    running = false;
+  }
+};
+
+// Keep the archive of the newest LTS release in the cache, `getContext` gives it.
+export function setLtsPrefetch(
+  value: boolean = false,
+  options?: { getContext: () => Promise<Context | undefined> }
+) {
+  enabled = value;
+  if (options) getContext = options.getContext;
+
+  if (!enabled) {
+    timer && clearInterval(timer);
+    timer = undefined;
+    return;
+  }
+  if (timer) return;
+  timer = setInterval(run, CHECK_INTERVAL);
+  // don't keep the app alive for the checks
+  timer.unref();
+}
+
+export const getPrefetchStatus = (): Nvmd.PrefetchStatus => ({
+  enabled,
+  running,
+  ...(last && { last })
+});
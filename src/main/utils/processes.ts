@@ -0,0 +1,159 @@
+import { execFile } from "node:child_process";
+import { readFile, readdir, readlink, realpath } from "node:fs/promises";
+import { basename, join, sep } from "node:path";
+import { platform } from "node:process";
+import { logger } from "./logger";
+
+type RunningProcess = { pid: number; path: string };
+
+const run = (file: string, args: string[]) =>
+  new Promise<string>((resolve, reject) => {
+    execFile(file, args, { maxBuffer: 16 * 1024 * 1024, windowsHide: true }, (err, stdout) =>
+      err ? reject(err) : resolve(stdout)
+    );
+  });
+
+// Executable of every process the user can inspect.
+async function listProcesses(): Promise<RunningProcess[]> {
+  if (platform === "win32") {
+    const stdout = await run("powershell.exe", [
+      "-NoProfile",
+      "-NonInteractive",
+      "-Command",
+      "Get-CimInstance Win32_Process | Where-Object ExecutablePath | " +
+        "Select-Object ProcessId, ExecutablePath | ConvertTo-Json -Compress"
+    ]);
+    const list = JSON.parse(stdout || "[]");
+    return (Array.isArray(list) ? list : [list]).map(({ ProcessId, ExecutablePath }) => ({
+      pid: ProcessId,
+      path: ExecutablePath
+    }));
+  }
+
+  if (platform === "linux") {
+    const pids = (await readdir("/proc")).filter((name) => /^\d+$/.test(name));
+    const processes = await Promise.all(
+      pids.map(async (pid) => {
+        try {
+          return { pid: Number(pid), path: await readlink(join("/proc", pid, "exe")) };
+        } catch {
+          // processes of other users, or exited meanwhile
+          return;
+        }
+      })
+    );
+    return processes.filter((process): process is RunningProcess => !!process);
+  }
+
+  // `comm` is the full path of the executable on macOS
+  const stdout = await run("ps", ["-axww", "-o", "pid=,comm="]);
+  return stdout
+    .split("\n")
+    .map((line) => line.trim().match(/^(\d+)\s+(.+)$/))
+    .filter((match): match is RegExpMatchArray => !!match)
+    .map(([, pid, path]) => ({ pid: Number(pid), path }));
+}
+
+// Processes running an executable of the version at `location`, e.g. `node` or a
+// global package started through it. Inspection failures are logged, not thrown:
+// they should not prevent uninstalling.
+export async function getProcessesUsing(location: string): Promise<RunningProcess[]> {
+  const normalize = (path: string) => (platform === "win32" ? path.toLowerCase() : path);
+
+  try {
+    const prefix = normalize((await realpath(location)) + sep);
+    return (await listProcesses()).filter(({ path }) => normalize(path).startsWith(prefix));
+  } catch (err) {
+    logger.warn(`Could not list the processes using ${location}: ${err}`);
+    return [];
+  }
+}
+
+const isNode = (path: string) => /^node(\.exe)?$/i.test(basename(path));
+
+// `node` processes with their arguments and working directory. Windows doesn't expose
+// the working directory of other processes, the command line is used instead.
+async function listNodeProcesses(): Promise<Nvmd.ProjectProcess[]> {
+  if (platform === "win32") {
+    const stdout = await run("powershell.exe", [
+      "-NoProfile",
+      "-NonInteractive",
+      "-Command",
+      "Get-CimInstance Win32_Process -Filter \"Name='node.exe'\" | " +
+        "Select-Object ProcessId, CommandLine | ConvertTo-Json -Compress"
+    ]);
+    const list = JSON.parse(stdout || "[]");
+    return (Array.isArray(list) ? list : [list]).map(({ ProcessId, CommandLine }) => ({
+      pid: ProcessId,
+      command: CommandLine || ""
+    }));
+  }
+
+  if (platform === "linux") {
+    const processes = await Promise.all(
+      (await listProcesses())
+        .filter(({ path }) => isNode(path))
+        .map(async ({ pid }) => {
+          try {
+            const [cmdline, cwd] = await Promise.all([
+              readFile(join("/proc", String(pid), "cmdline"), "utf-8"),
+              readlink(join("/proc", String(pid), "cwd"))
+            ]);
+            return { pid, command: cmdline.split("\0").filter(Boolean).join(" "), cwd };
+          } catch {
+            return;
+          }
+        })
+    );
+    return processes.filter((process): process is Nvmd.ProjectProcess => !!process);
+  }
+
+  const stdout = await run("ps", ["-axww", "-o", "pid=,comm=,args="]);
+  const processes = stdout
+    .split("\n")
+    .map((line) => line.trim().match(/^(\d+)\s+(\S+)\s+(.+)$/))
+    .filter((match): match is RegExpMatchArray => !!match && isNode(match[2]))
+    .map(([, pid, , command]) => ({ pid: Number(pid), command }));
+  if (!processes.length) return [];
+
+  // `lsof` lists the working directory as the `n` field of the `cwd` descriptor
+  const cwds = new Map<number, string>();
+  const pids = processes.map(({ pid }) => pid).join(",");
+  const lsof = await run("lsof", ["-a", "-d", "cwd", "-Fpn", "-p", pids]).catch(() => "");
+  let pid = 0;
+  lsof.split("\n").forEach((line) => {
+    if (line.startsWith("p")) pid = Number(line.slice(1));
+    if (line.startsWith("n")) cwds.set(pid, line.slice(1));
+  });
+  return processes.map((process) => ({ ...process, cwd: cwds.get(process.pid) }));
+}
+
+// `node` processes started from the project, e.g. dev servers still running the
+// version the project used before a switch.
+export async function getProjectProcesses(path: string): Promise<Nvmd.ProjectProcess[]> {
+  const normalize = (path: string) => (platform === "win32" ? path.toLowerCase() : path);
+
+  try {
+    const root = normalize(await realpath(path));
+    const inProject = (dir: string) => dir === root || dir.startsWith(root + sep);
+    return (await listNodeProcesses()).filter(
+      ({ pid, cwd, command }) =>
+        pid !== process.pid &&
+        (cwd ? inProject(normalize(cwd)) : normalize(command).includes(root))
+    );
+  } catch (err) {
+    logger.warn(`Could not list the processes of ${path}: ${err}`);
+    return [];
+  }
+}
+
+// Deleting a running runtime fails halfway on Windows, files in use can't be removed.
+export async function assertNotRunning(version: string, location: string) {
+  const processes = await getProcessesUsing(location);
+  if (!processes.length) return;
+
+  const pids = processes.map(({ pid }) => pid).join(", ");
+  return Promise.reject(
+    `Node.js v${version} is used by running processes (${pids}), stop them before uninstalling`
+  );
+}
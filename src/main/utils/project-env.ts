@@ -0,0 +1,94 @@
+// Variables added to the environment of the processes the app starts in a project:
+// terminals, editors, scripts and audits. Commands run from other shells go through
+// the shim and don't get them.
+
+// A project's own overrides win over the ones of its group.
+export function getNetworkOverride(
+  project: Nvmd.Project,
+  groups: Nvmd.Group[]
+): Nvmd.NetworkOverride | undefined {
+  const group = groups.find(({ name }) => name === project.version);
+  const mirror = project.network?.mirror || group?.network?.mirror;
+  const proxy = project.network?.proxy ?? group?.network?.proxy;
+  return mirror || proxy ? { ...(mirror && { mirror }), ...(proxy && { proxy }) } : undefined;
+}
+
+// `npm_config_*` variables win over every npmrc. `disturl` is where node-gyp gets the
+// headers of the version from, and npm only talks to http proxies.
+export function getNetworkVariables({ mirror, proxy }: Nvmd.NetworkOverride) {
+  const variables: Record<string, string> = {};
+  if (mirror) variables.npm_config_disturl = mirror;
+  if (proxy?.enabled && (proxy.protocol ?? "http") === "http" && proxy.ip && proxy.port) {
+    const url = `http://${proxy.ip}:${proxy.port}`;
+    variables.npm_config_proxy = url;
+    variables.npm_config_https_proxy = url;
+    if (proxy.noProxy) variables.npm_config_noproxy = proxy.noProxy;
+  }
+  return variables;
+}
+
+// The group's registries with the project's on top, scopes merged one by one.
+export function getNpmRegistries(
+  project: Nvmd.Project,
+  groups: Nvmd.Group[]
+): Nvmd.NpmRegistries | undefined {
+  const group = groups.find(({ name }) => name === project.version);
+  const registry = project.npm?.registry || group?.npm?.registry;
+  const scopes = { ...group?.npm?.scopes, ...project.npm?.scopes };
+  const scoped = Object.keys(scopes).length > 0;
+  return registry || scoped
+    ? { ...(registry && { registry }), ...(scoped && { scopes }) }
+    : undefined;
+}
+
+// npm reads scoped registries from variables too, `npm_config_@acme:registry`.
+export function getNpmVariables({ registry, scopes = {} }: Nvmd.NpmRegistries) {
+  const variables: Record<string, string> = {};
+  if (registry) variables.npm_config_registry = registry;
+  for (const [scope, url] of Object.entries(scopes)) {
+    variables[`npm_config_${scope}:registry`] = url;
+  }
+  return variables;
+}
+
+const SCOPE_REGEXP = /^@[a-z0-9-~][a-z0-9-._~]*$/;
+
+// Throws on registries that aren't http(s) URLs and on invalid scope names.
+export function validateNpmRegistries({ registry, scopes = {} }: Nvmd.NpmRegistries) {
+  for (const [name, url] of Object.entries({ registry, ...scopes })) {
+    if (name !== "registry" && !SCOPE_REGEXP.test(name)) {
+      throw new Error(`${name} is not a valid npm scope`);
+    }
+    if (url === undefined) continue;
+    if (!/^https?:\/\/[^\s]+$/.test(url)) throw new Error(`${url} is not a registry URL`);
+  }
+}
+
+const NAME_REGEXP = /^[A-Za-z_][A-Za-z0-9_]*$/;
+// set by the app to run the right version
+const RESERVED_REGEXP = /^(PATH|NVMD_.*|npm_config_prefix)$/i;
+
+// Throws on names that aren't portable or that the app sets itself.
+export function validateEnv(env: Record<string, string>) {
+  for (const [name, value] of Object.entries(env)) {
+    if (!NAME_REGEXP.test(name)) throw new Error(`${name} is not a valid variable name`);
+    if (RESERVED_REGEXP.test(name)) throw new Error(`${name} is set by nvmd`);
+    if (typeof value !== "string") throw new Error(`The value of ${name} is not a string`);
+  }
+}
+
+// From the least to the most specific: the variables of the group, those derived from
+// the mirror, proxy, registries and CA bundle, then the project's own variables. The
+// CA bundle is the project's own, its group doesn't pass it on.
+export function getProjectVariables(project: Nvmd.Project, groups: Nvmd.Group[]) {
+  const group = groups.find(({ name }) => name === project.version);
+  const network = getNetworkOverride(project, groups);
+  const registries = getNpmRegistries(project, groups);
+  return {
+    ...group?.env,
+    ...(network && getNetworkVariables(network)),
+    ...(registries && getNpmVariables(registries)),
+    ...(project.caFile && { NODE_EXTRA_CA_CERTS: project.caFile }),
+    ...project.env
+  };
+}
@@ -0,0 +1,177 @@
+import { basename, extname } from "node:path";
+import { pathExists, readFile, writeFile } from "fs-extra";
+import { getGroups, updateGroups } from "./groups";
+import { getProjects, syncProjectVersion, updateProjects } from "./projects";
+import { logFailures, mapLimit, toWarnings } from "./concurrency";
+import { samePath } from "./winpath";
+
+const CSV_COLUMNS: (keyof Nvmd.ProjectListEntry)[] = ["name", "path", "version", "group"];
+
+// Quote the fields that would break the row: separators, quotes and line breaks.
+const csvField = (value: string = "") =>
+  /[",\r\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+
+function toCsv(entries: Nvmd.ProjectListEntry[]) {
+  const rows = entries.map((entry) => CSV_COLUMNS.map((column) => csvField(entry[column])));
+  return [CSV_COLUMNS, ...rows].map((row) => row.join(",")).join("\n") + "\n";
+}
+
+// RFC 4180: fields may be quoted, a quote inside a quoted field is doubled.
+function parseCsv(content: string): string[][] {
+  const rows: string[][] = [];
+  let row: string[] = [],
+    field = "",
+    quoted = false;
+
+  const endField = () => {
+    row.push(field);
+    field = "";
+  };
+  const endRow = () => {
+    endField();
+    rows.push(row);
+    row = [];
+  };
+
+  for (let index = 0; index < content.length; index++) {
+    const char = content[index];
+    if (quoted) {
+      if (char !== '"') {
+        field += char;
+      } else if (content[index + 1] === '"') {
+        field += '"';
+        index++;
+      } else {
+        quoted = false;
+      }
+    } else if (char === '"') {
+      quoted = true;
+    } else if (char === ",") {
+      endField();
+    } else if (char === "\n" || char === "\r") {
+      if (char === "\r" && content[index + 1] === "\n") index++;
+      endRow();
+    } else {
+      field += char;
+    }
+  }
+  if (field || row.length) endRow();
+
+  return rows.filter((row) => row.some(Boolean));
+}
+
+function fromCsv(content: string): Nvmd.ProjectListEntry[] {
+  const [header = [], ...rows] = parseCsv(content.replace(/^\uFEFF/, ""));
+  const columns = header.map((column) => column.trim().toLowerCase());
+  if (!columns.includes("path")) throw new Error("The CSV file has no path column");
+
+  return rows.map((row) =>
+    Object.fromEntries(
+      columns.map((column, index) => [column, row[index]?.trim() || void 0])
+    ) as unknown as Nvmd.ProjectListEntry
+  );
+}
+
+// The tracked projects with their resolved version, `group` when they belong to one.
+export async function getProjectList(): Promise<Nvmd.ProjectListEntry[]> {
+  const [projects, groups] = await Promise.all([getProjects(), getGroups()]);
+
+  return projects.map(({ name, path, version }) => {
+    const group = groups.find(({ name }) => name === version);
+    return {
+      name,
+      path,
+      version: group ? group.version : version || "",
+      ...(group && { group: group.name })
+    };
+  });
+}
+
+export async function exportProjectList(file: string, format: Nvmd.ProjectListFormat) {
+  const entries = await getProjectList();
+  await writeFile(file, format === "csv" ? toCsv(entries) : JSON.stringify(entries, null, 2));
+  return entries.length;
+}
+
+// Add the projects listed in `file` (exported by `exportProjectList`) and update the
+// ones already tracked.
+export async function importProjectList(file: string): Promise<Nvmd.ProjectListImport> {
+  const content = (await readFile(file)).toString();
+  const listed =
+    extname(file).toLowerCase() === ".csv" ? fromCsv(content) : JSON.parse(content);
+  if (!Array.isArray(listed)) throw new Error("The file does not contain a project list");
+
+  return importProjectEntries(listed);
+}
+
+// Paths that don't exist on this machine are skipped, a path listed twice keeps its
+// last entry. Unknown groups fall back to the listed version.
+export async function importProjectEntries(
+  listed: Nvmd.ProjectListEntry[]
+): Promise<Nvmd.ProjectListImport> {
+  const entries = new Map<string, Nvmd.ProjectListEntry>();
+  for (const entry of listed) {
+    if (!entry?.path) continue;
+    const duplicate = [...entries.keys()].find((path) => samePath(path, entry.path));
+    duplicate && entries.delete(duplicate);
+    entries.set(entry.path, entry);
+  }
+
+  const exists = await mapLimit([...entries.keys()], (path) => pathExists(path));
+  const skipped = [...entries.keys()].filter(
+    (_, index) => exists[index].status !== "fulfilled" || !exists[index].value
+  );
+  skipped.forEach((path) => entries.delete(path));
+
+  const [projects, groups] = await Promise.all([getProjects(), getGroups()]);
+  const newProjects = [...projects],
+    newGroups = groups.map((group) => ({ ...group, projects: [...group.projects] }));
+  const now = new Date().toISOString();
+  let added = 0,
+    updated = 0;
+
+  for (const { name, path, version, group: groupName } of entries.values()) {
+    const group = newGroups.find(({ name }) => name === groupName);
+    const index = newProjects.findIndex((project) => samePath(project.path, path));
+    const projectPath = index === -1 ? path : newProjects[index].path;
+
+    newGroups.forEach((item) => {
+      item.projects = item.projects.filter((source) => !samePath(source, projectPath));
+    });
+    group && group.projects.unshift(projectPath);
+
+    const changes = { version: group ? group.name : version || "", active: true, updateAt: now };
+    if (index === -1) {
+      newProjects.unshift({ name: name || basename(path), path, createAt: now, ...changes });
+      added++;
+    } else {
+      newProjects[index] = { ...newProjects[index], ...changes };
+      updated++;
+    }
+  }
+
+  await updateProjects(newProjects);
+  await updateGroups(newGroups);
+
+  // the `.nvmdrc` of each project, with its group's version when it joined one
+  const synced = [...entries.values()]
+    .map(({ path, version, group: groupName }) => {
+      const group = newGroups.find(({ name }) => name === groupName);
+      return { path, version: group ? group.version : version };
+    })
+    .filter(({ version }) => version);
+  const results = logFailures(
+    "Could not sync the version of",
+    await mapLimit(synced, ({ path, version }) => syncProjectVersion(path, version!)),
+    ({ path }) => path
+  );
+
+  return {
+    projects: newProjects,
+    groups: newGroups,
+    added,
+    updated,
+    skipped,
+    warnings: toWarnings(results, ({ path }) => path)
+  };
+}
@@ -1,9 +1,58 @@
-import { join } from "node:path";
-import { pathExists, readFile, readJson, writeJson, writeFile, remove } from "fs-extra";
-import { PROJECTS_JSONFILE, NVMDRC_NAME } from "../constants";
-import { updateGroups } from "./groups";
+import { realpath } from "node:fs/promises";
+import { dirname, join, resolve } from "node:path";
+import { platform } from "node:process";
+import { pathExists, readFile, readJson, writeFile } from "fs-extra";
+import { valid as semverValid } from "semver";
+import {
+  PROJECTS_JSONFILE,
+  NVMDRC_NAME,
+  NVMRC_NAME,
+  NODE_VERSION_NAME,
+  PACKAGE_JSON_NAME,
+  TOOL_VERSIONS_NAME
+} from "../constants";
+import { getGroups, updateGroups } from "./groups";
+import { removeIfExists } from "./trash";
+import { logger } from "./logger";
+import { readConfig, saveConfig } from "./backup";
+import { logFailures, mapLimit, toWarnings } from "./concurrency";
+import { normalizeVersionSpec } from "./version-spec";
+import {
+  getNetworkOverride,
+  getProjectVariables,
+  validateEnv,
+  validateNpmRegistries
+} from "./project-env";
+import { readPemBundle } from "./tls";
+import { readFileStates, recordSwitch, restoreFileStates } from "./switch-history";
+import { normalizePath, samePath } from "./winpath";
 
-let cacheProjects: Nvmd.Project[];
+import type { FileState } from "./switch-history";
+
+let cacheProjects: Nvmd.Project[],
+  toolVersions: boolean = true,
+  syncEngines: boolean = false,
+  resolveSpec: (spec: string) => Promise<string> = (spec) =>
+    Promise.reject(`${spec} can't be resolved yet`);
+
+export function setToolVersionsEnabled(value: boolean = true) {
+  toolVersions = value;
+}
+
+export function setSyncEnginesEnabled(value: boolean = false) {
+  syncEngines = value;
+}
+
+// `resolve` gives the exact version a specifier like `lts/iron` stands for.
+export function setVersionResolver(resolve: (spec: string) => Promise<string>) {
+  resolveSpec = resolve;
+}
+
+// The shim only reads exact versions in `.nvmdrc`, specifiers stay in the config.
+const toExactVersion = async (spec: string) => {
+  const version = normalizeVersionSpec(spec);
+  return semverValid(version) ? version : resolveSpec(version);
+};
 
 export async function getProjects(load: boolean = false): Promise<Nvmd.Project[]> {
   if (cacheProjects !== void 0 && !load) {
@@ -12,21 +61,198 @@ export async function getProjects(load: boolean = false): Promise<Nvmd.Project[]
 
   if (!(await pathExists(PROJECTS_JSONFILE))) return [];
 
-  const projects = (await readJson(PROJECTS_JSONFILE, { throws: false })) || [];
+  const projects = await readConfig<Nvmd.Project[]>(PROJECTS_JSONFILE, []);
 
   cacheProjects = projects;
 
   return projects;
 }
 
-export async function updateProjects(projects: Nvmd.Project[], path?: string) {
-  if (path && (await pathExists(join(path, NVMDRC_NAME)))) {
-    await remove(join(path, NVMDRC_NAME));
+// Last flagged by `validateProjects`, given right away while the folders are checked again.
+let missingPaths = new Set<string>();
+
+// Flag projects whose folder no longer exists (deleted, moved or on an unmounted drive).
+// The flag is computed on every call and never persisted.
+export async function validateProjects(projects: Nvmd.Project[]): Promise<Nvmd.Project[]> {
+  const results = await mapLimit(projects, ({ path }) => pathExists(path));
+  const validated = projects.map((project, index) => {
+    const result = results[index];
+    return { ...project, missing: result.status === "fulfilled" && !result.value };
+  });
+  missingPaths = new Set(validated.filter(({ missing }) => missing).map(({ path }) => path));
+  return validated;
+}
+
+export const withKnownMissing = (projects: Nvmd.Project[]) =>
+  projects.map((project) => ({ ...project, missing: missingPaths.has(project.path) }));
+
+// `path` is a project being removed, its `.nvmdrc` goes too (to the trash when `trash`,
+// or the setting, says so). Not being able to remove it doesn't fail the update.
+export async function updateProjects(
+  projects: Nvmd.Project[],
+  path?: string,
+  { trash }: { trash?: boolean } = {}
+): Promise<Nvmd.CommandResult<{ removed: string[] }>> {
+  const removed: string[] = [];
+  const warnings: Nvmd.CommandWarning[] = [];
+  if (path) {
+    const file = join(path, NVMDRC_NAME);
+    try {
+      (await removeIfExists(file, trash)) && removed.push(file);
+    } catch (err) {
+      logger.warn(`Could not remove ${file}: ${err.message}`);
+      warnings.push({ message: `Could not remove ${NVMDRC_NAME}: ${err.message}`, subject: path });
+    }
   }
 
-  await writeJson(PROJECTS_JSONFILE, projects);
-  cacheProjects = projects;
-  return;
+  // The renderer doesn't track usage, keep the values recorded here.
+  const usages = new Map((cacheProjects || []).map((project) => [project.path, project]));
+  const newProjects = projects.map(({ missing: _missing, ...project }) => {
+    const cached = usages.get(project.path);
+    if (!cached || (project.usageCount ?? 0) >= (cached.usageCount ?? 0)) return project;
+
+    return { ...project, lastUsedAt: cached.lastUsedAt, usageCount: cached.usageCount };
+  });
+  await saveConfig(PROJECTS_JSONFILE, newProjects);
+  cacheProjects = newProjects;
+  return { data: { removed }, warnings };
+}
+
+export const markUsed = (project: Nvmd.Project): Nvmd.Project => ({
+  ...project,
+  lastUsedAt: new Date().toISOString(),
+  usageCount: (project.usageCount ?? 0) + 1
+});
+
+// Record that the version of the project at `path` was just switched.
+export async function touchProject(path: string) {
+  const projects = await getProjects();
+  if (!projects.some((project) => project.path === path)) return;
+
+  const newProjects = projects.map((project) =>
+    project.path === path ? markUsed(project) : project
+  );
+  await saveConfig(PROJECTS_JSONFILE, newProjects);
+  cacheProjects = newProjects;
+}
+
+// Most recently used first, projects that were never used keep their order at the end.
+export const sortByRecency = (projects: Nvmd.Project[]) =>
+  [...projects].sort((a, b) => (b.lastUsedAt ?? "").localeCompare(a.lastUsedAt ?? ""));
+
+// Key of the folder `path` points to: symlinks resolved and, on the case-insensitive
+// file systems of Windows and macOS, lowercased. Missing folders keep their own path.
+async function getCanonicalPath(path: string) {
+  const canonical = normalizePath(await realpath(path).catch(() => path));
+  return platform === "win32" || platform === "darwin" ? canonical.toLowerCase() : canonical;
+}
+
+// For each of `paths`, the path of the tracked project pointing to the same folder.
+export async function findTrackedPaths(paths: string[]): Promise<(string | undefined)[]> {
+  const projects = await getProjects();
+  const keys = await Promise.all(projects.map(({ path }) => getCanonicalPath(path)));
+  return Promise.all(
+    paths.map(async (path) => projects[keys.indexOf(await getCanonicalPath(path))]?.path)
+  );
+}
+
+// Tracked projects pointing to the same folder, the first listed of each is the one kept.
+export async function findDuplicateProjects(): Promise<Nvmd.ProjectDuplicates[]> {
+  const projects = await getProjects();
+  const keys = await Promise.all(projects.map(({ path }) => getCanonicalPath(path)));
+
+  const folders = new Map<string, string[]>();
+  projects.forEach(({ path }, index) =>
+    folders.set(keys[index], [...(folders.get(keys[index]) ?? []), path])
+  );
+  return [...folders.values()]
+    .filter((paths) => paths.length > 1)
+    .map(([path, ...duplicates]) => ({ path, duplicates }));
+}
+
+// Fold the duplicates into the project kept: the settings it lacks are taken from
+// them, their usage adds up and a group membership wins over a plain version. Groups
+// list the kept path in place of the duplicates.
+export async function mergeDuplicateProjects() {
+  const [duplicates, projects, groups] = await Promise.all([
+    findDuplicateProjects(),
+    getProjects(),
+    getGroups()
+  ]);
+  if (!duplicates.length) return { merged: duplicates, projects, groups };
+
+  const byPath = new Map(projects.map((project) => [project.path, project]));
+  const keptPaths = new Map<string, string>();
+  const merged = new Map<string, Nvmd.Project>();
+  duplicates.forEach(({ path, duplicates: paths }) => {
+    const [kept, ...others] = [path, ...paths].map((item) => byPath.get(item)!);
+    const all = [kept, ...others];
+    const member = all.find(({ version }) => groups.some(({ name }) => name === version));
+    const used = all.filter(({ lastUsedAt }) => lastUsedAt).map(({ lastUsedAt }) => lastUsedAt!);
+
+    merged.set(path, {
+      ...Object.assign({}, ...others.reverse()),
+      ...kept,
+      version: (member ?? all.find(({ version }) => version))?.version,
+      usageCount: all.reduce((count, { usageCount = 0 }) => count + usageCount, 0),
+      ...(used.length && { lastUsedAt: used.sort().pop() }),
+      createAt: all.map(({ createAt }) => createAt).sort()[0],
+      updateAt: new Date().toISOString()
+    });
+    paths.forEach((duplicate) => keptPaths.set(duplicate, path));
+  });
+
+  const newProjects = projects
+    .filter(({ path }) => !keptPaths.has(path))
+    .map((project) => merged.get(project.path) ?? project);
+  await saveConfig(PROJECTS_JSONFILE, newProjects);
+  cacheProjects = newProjects;
+
+  const newGroups = groups.map((group) => {
+    const paths = [...new Set(group.projects.map((path) => keptPaths.get(path) ?? path))];
+    const members = paths.filter(
+      (path) => !merged.has(path) || merged.get(path)!.version === group.name
+    );
+    return { ...group, projects: members };
+  });
+  await updateGroups(newGroups);
+
+  return { merged: duplicates, projects: newProjects, groups: await getGroups() };
+}
+
+// Point a tracked project to its new folder, keeping its version and group membership.
+// The `.nvmdrc` is written to the new folder so the association follows the project.
+export async function relocateProject(oldPath: string, newPath: string) {
+  const projects = await getProjects();
+  const project = projects.find(({ path }) => path === oldPath);
+  if (!project) return Promise.reject(`Project ${oldPath} is not tracked`);
+  if (!(await pathExists(newPath))) return Promise.reject(`${newPath} does not exist`);
+  if (projects.some(({ path }) => path === newPath)) {
+    return Promise.reject(`${newPath} is already tracked`);
+  }
+
+  const groups = await getGroups();
+  const group = groups.find(({ name }) => name === project.version);
+  project.version && (await syncProjectVersion(newPath, group ? group.version : project.version));
+
+  const newProjects = projects.map((item) =>
+    item === project
+      ? { ...item, path: newPath, active: true, updateAt: new Date().toISOString() }
+      : item
+  );
+  await saveConfig(PROJECTS_JSONFILE, newProjects);
+  cacheProjects = newProjects;
+
+  if (groups.some(({ projects }) => projects.includes(oldPath))) {
+    await updateGroups(
+      groups.map((group) => ({
+        ...group,
+        projects: group.projects.map((path) => (path === oldPath ? newPath : path))
+      }))
+    );
+  }
+
+  return { projects: cacheProjects, groups: await getGroups() };
 }
 
 // For configration import
@@ -52,28 +278,324 @@ export async function updateProjectsAndSync({
     return;
   };
 
-  await Promise.all(projects.map((project, index) => syncProject(project, index)));
+  const results = logFailures(
+    "Could not sync project",
+    await mapLimit(projects, (project, index) => syncProject(project, index)),
+    ({ path }) => path
+  );
   cacheProjects = projects;
-  await writeJson(PROJECTS_JSONFILE, projects);
+  await saveConfig(PROJECTS_JSONFILE, projects);
   groups.length && (await updateGroups(groups));
+  return toWarnings(results, ({ path }) => path);
 }
 
-export async function getVersion(path: string): Promise<string> {
-  const target = join(path, NVMDRC_NAME);
+async function readVersionFile(path: string, name: string): Promise<string> {
+  const target = join(path, name);
   if (!(await pathExists(target))) return "";
 
-  const version = (await readFile(target)).toString();
+  // Editors on Windows may save it with a BOM and a trailing line break
+  const version = (await readFile(target, "utf-8")).replace(/^\uFEFF/, "").trim();
 
   return version || "";
 }
 
-export async function syncProjectVersion(path: string, version: string) {
+export const getVersion = (path: string) => readVersionFile(path, NVMDRC_NAME);
+
+// Closest folder above `path` (itself included) holding a `.git` directory, or a
+// `.git` file for worktrees and submodules.
+export async function findRepositoryRoot(path: string): Promise<string | undefined> {
+  let dir = resolve(path);
+  while (true) {
+    if (await pathExists(join(dir, ".git"))) return dir;
+    if (dirname(dir) === dir) return;
+    dir = dirname(dir);
+  }
+}
+
+// Version a tracked project resolves to, in order of precedence:
+//   1. the version of its group
+//   2. its own version
+//   3. the `.nvmdrc` in the folder
+//   4. the `.nvmdrc` at the root of the git repository the folder is in, so the
+//      subfolders of a monorepo share a single version file
+export async function resolveProjectVersion(path: string): Promise<string> {
+  const project = (await getProjects()).find((project) => samePath(project.path, path));
+  if (project?.version) {
+    const group = (await getGroups()).find(({ name }) => name === project.version);
+    return group ? group.version : project.version;
+  }
+
+  return getInheritedVersion(path);
+}
+
+// Mirror and proxy to install the version of the project at `path` with.
+export async function resolveProjectNetwork(path: string) {
+  const project = (await getProjects()).find((project) => samePath(project.path, path));
+  return project && getNetworkOverride(project, await getGroups());
+}
+
+// The tracked project at `path`, if any.
+export async function getProject(path: string) {
+  return (await getProjects()).find((project) => samePath(project.path, path));
+}
+
+// Variables the app gives the processes it starts in the project at `path`.
+export async function resolveProjectVariables(path: string) {
+  const project = (await getProjects()).find((project) => samePath(project.path, path));
+  return project ? getProjectVariables(project, await getGroups()) : {};
+}
+
+// Replace the project at `path` by the result of `update`.
+async function updateProject(path: string, update: (project: Nvmd.Project) => Nvmd.Project) {
+  const projects = await getProjects();
+  if (!projects.some((project) => samePath(project.path, path))) {
+    return Promise.reject(`${path} is not a project`);
+  }
+
+  const newProjects = projects.map((project) =>
+    samePath(project.path, path) ? update(project) : project
+  );
+  await saveConfig(PROJECTS_JSONFILE, newProjects);
+  cacheProjects = newProjects;
+  return newProjects;
+}
+
+// Set or clear (`network` undefined) the mirror and proxy of a project.
+export const setProjectNetwork = (path: string, network?: Nvmd.NetworkOverride) =>
+  updateProject(path, ({ network: _previous, ...project }) =>
+    network ? { ...project, network } : project
+  );
+
+// Variables of the project, over those of its group. An empty map clears them.
+export function setProjectEnv(path: string, env: Record<string, string> = {}) {
+  validateEnv(env);
+  return updateProject(path, ({ env: _previous, ...project }) =>
+    Object.keys(env).length ? { ...project, env } : project
+  );
+}
+
+// Set or clear (`npm` undefined) the npm registries of a project, over its group's.
+export function setProjectNpm(path: string, npm?: Nvmd.NpmRegistries) {
+  npm && validateNpmRegistries(npm);
+  return updateProject(path, ({ npm: _previous, ...project }) =>
+    npm && (npm.registry || Object.keys(npm.scopes || {}).length) ? { ...project, npm } : project
+  );
+}
+
+// Run npm `version` in the project rather than the npm of its Node.js version, an
+// empty version goes back to the latter. The version must be installed already.
+export const setProjectNpmVersion = (path: string, version: string) =>
+  updateProject(path, ({ npmVersion: _previous, ...project }) =>
+    version ? { ...project, npmVersion: version } : project
+  );
+
+// Set or clear (`file` undefined) the CA bundle trusted by node in a project.
+export async function setProjectCaFile(path: string, file?: string) {
+  const caFile = file && resolve(file);
+  if (caFile) await readPemBundle(caFile);
+
+  return updateProject(path, ({ caFile: _previous, ...project }) =>
+    caFile ? { ...project, caFile } : project
+  );
+}
+
+// Run the x64 build of the project's version under emulation, or the native one.
+export const setProjectEmulation = (path: string, enabled: boolean) =>
+  updateProject(path, ({ emulateX64: _previous, ...project }) =>
+    enabled ? { ...project, emulateX64: true } : project
+  );
+
+// Keep the project on its exact version, whatever happens to the projects around it.
+export const setProjectFrozen = (path: string, frozen: boolean) =>
+  updateProject(path, ({ frozen: _previous, ...project }) =>
+    frozen ? { ...project, frozen: true } : project
+  );
+
+// The nodejs entry of an asdf/mise `.tool-versions`, e.g. `nodejs 20.11.1`. Several
+// versions may be listed by preference, the first exact one is used.
+export async function getToolVersion(path: string): Promise<string> {
+  const target = join(path, TOOL_VERSIONS_NAME);
+  if (!(await pathExists(target))) return "";
+
+  for (const line of (await readFile(target, "utf-8")).split(/\r?\n/)) {
+    const [tool, ...versions] = line.replace(/#.*/, "").trim().split(/\s+/);
+    if (tool !== "nodejs" && tool !== "node") continue;
+
+    const version = versions.find((version) => /^v?\d+\.\d+\.\d+$/.test(version));
+    if (version) return version.replace(/^v/, "");
+  }
+  return "";
+}
+
+// `.nvmdrc` first, `.tool-versions` when enabled in the settings.
+const getFolderVersion = async (path: string) =>
+  (await getVersion(path)) || (toolVersions ? await getToolVersion(path) : "");
+
+// Steps 3 and 4 above, for folders that aren't tracked (yet). Both steps also read
+// `.tool-versions` when the folder has no `.nvmdrc`.
+export async function getInheritedVersion(path: string): Promise<string> {
+  const version = await getFolderVersion(path);
+  if (version) return version;
+
+  const root = await findRepositoryRoot(path);
+  return root && !samePath(root, path) ? getFolderVersion(root) : "";
+}
+
+// Every place the project at `path` declares a version in, and which step of
+// `resolveProjectVersion` its version comes from. `.nvmrc`, `.node-version` and
+// `engines.node` are reported as found, they never decide the version.
+export async function getVersionSources(path: string): Promise<Nvmd.VersionSources> {
+  const [project, groups, nvmdrc, nvmrc, nodeVersion, toolVersion, manifest] = await Promise.all([
+    getProject(path),
+    getGroups(),
+    getVersion(path),
+    readVersionFile(path, NVMRC_NAME),
+    readVersionFile(path, NODE_VERSION_NAME),
+    getToolVersion(path),
+    readJson(join(path, PACKAGE_JSON_NAME), { throws: false }).catch(() => null)
+  ]);
+  const enginesNode = manifest?.engines?.node;
+  const packageManager = manifest?.packageManager;
+  const sources: Nvmd.VersionSources = {
+    ...(nvmdrc && { nvmdrc }),
+    ...(nvmrc && { nvmrc }),
+    ...(nodeVersion && { nodeVersion }),
+    ...(toolVersion && { toolVersions: toolVersion }),
+    ...(typeof enginesNode === "string" && { enginesNode }),
+    ...(typeof packageManager === "string" && { packageManager })
+  };
+
+  if (project?.version) {
+    const group = groups.find(({ name }) => name === project.version);
+    return { ...sources, from: group ? "group" : "project", ...(group && { group: group.name }) };
+  }
+  if (nvmdrc) return { ...sources, from: "nvmdrc" };
+  if (toolVersions && toolVersion) return { ...sources, from: "tool-versions" };
+
+  const root = await findRepositoryRoot(path);
+  return root && !samePath(root, path) && (await getFolderVersion(root))
+    ? { ...sources, from: "repository", repository: root }
+    : sources;
+}
+
+// `package.json` is part of the state of a switch, `engines.node` may be synced.
+const getSwitchedFiles = (path: string) => [join(path, NVMDRC_NAME), join(path, PACKAGE_JSON_NAME)];
+
+const getStoredVersion = (path: string) =>
+  (cacheProjects || []).find((project) => samePath(project.path, path))?.version;
+
+// `record` keeps the previous state in the switch history, to undo the switch.
+export async function syncProjectVersion(path: string, version: string, record = true) {
   if (!(await pathExists(path))) return 404;
 
-  await writeFile(join(path, NVMDRC_NAME), version);
+  version = await toExactVersion(version);
+  const previous = record ? await readFileStates(getSwitchedFiles(path)) : [];
+  await writeFile(join(path, NVMDRC_NAME), version, "utf-8");
+  await syncEnginesNode(path, version);
+  record &&
+    recordSwitch(
+      { scope: "project", version, projects: [{ path, version: getStoredVersion(path) }] },
+      previous
+    );
   return 200;
 }
 
+// A project's own `syncEngines` option wins over the setting.
+const shouldSyncEngines = (path: string) =>
+  (cacheProjects || []).find((project) => samePath(project.path, path))?.syncEngines ?? syncEngines;
+
+// Keep `engines.node` of the project's `package.json` in line with its `.nvmdrc`.
+// Only the value is replaced in the text, so the formatting of the file stays as it
+// is, and its range operator (`>=`, `^`, `~`) is kept. The field is added when missing.
+// Sandboxes like `20.11.1-x64` leave it as is, they aren't a version npm knows.
+export async function syncEnginesNode(path: string, version: string) {
+  const file = join(path, PACKAGE_JSON_NAME);
+  if (!semverValid(version) || version.includes("-")) return;
+  if (!shouldSyncEngines(path) || !(await pathExists(file))) return;
+
+  const content = await readFile(file, "utf-8");
+  let manifest: { engines?: Record<string, string> };
+  try {
+    manifest = JSON.parse(content);
+  } catch {
+    return;
+  }
+
+  const current = manifest.engines?.node;
+  if (typeof current === "string") {
+    const prefix = current.match(/^\s*(>=|\^|~)?/)![1] ?? "";
+    const next = `${prefix}${version}`;
+    if (current === next) return;
+
+    const field = /("engines"\s*:\s*\{[^}]*?"node"\s*:\s*)"(?:[^"\\]|\\.)*"/;
+    if (field.test(content)) {
+      await writeFile(file, content.replace(field, `$1${JSON.stringify(next)}`), "utf-8");
+      return;
+    }
+  }
+
+  // the field is missing (or laid out unexpectedly): rewrite with the file's indent
+  const indent = content.match(/^[ \t]+(?=")/m)?.[0] ?? "  ";
+  const eol = content.includes("\r\n") ? "\r\n" : "\n";
+  manifest.engines = { ...manifest.engines, node: version };
+  const json = JSON.stringify(manifest, null, indent).replace(/\n/g, eol);
+  await writeFile(file, content.endsWith("\n") ? json + eol : json, "utf-8");
+}
+
+// Write `version` to the `.nvmdrc` of every project as a single unit: when one write fails,
+// the files already written are restored to their previous content (or removed if
+// they didn't exist), so projects never end up half switched.
+// Missing project folders are skipped and reported, like `syncProjectVersion` does.
+// Frozen projects are left alone and reported as well.
+export async function syncProjectsVersion(
+  paths: string[],
+  version: string
+): Promise<Nvmd.BatchSyncResult> {
+  const frozen = (await getProjects()).flatMap((project) =>
+    project.frozen && paths.includes(project.path) ? [project.path] : []
+  );
+  paths = paths.filter((path) => !frozen.includes(path));
+
+  const previous = new Map<string, FileState[]>();
+  const results = await mapLimit(paths, async (path) => {
+    if (!(await pathExists(path))) return 404;
+
+    previous.set(path, await readFileStates(getSwitchedFiles(path)));
+    return syncProjectVersion(path, version, false);
+  });
+  logFailures("Could not sync project", results);
+
+  const failed = results.flatMap((result) =>
+    result.status === "rejected" ? [{ path: result.item, reason: result.reason }] : []
+  );
+  const succeeded = results.flatMap((result) =>
+    result.status === "fulfilled" && result.value === 200 ? [result.item] : []
+  );
+  const missing = results.flatMap((result) =>
+    result.status === "fulfilled" && result.value === 404 ? [result.item] : []
+  );
+
+  if (!failed.length) {
+    succeeded.length &&
+      recordSwitch(
+        {
+          scope: "project",
+          version,
+          projects: succeeded.map((path) => ({ path, version: getStoredVersion(path) }))
+        },
+        succeeded.flatMap((path) => previous.get(path) || [])
+      );
+    return { succeeded, missing, failed, rolledBack: false, frozen };
+  }
+
+  await mapLimit(succeeded, (path) => restoreFileStates(previous.get(path) || []));
+
+  return { succeeded: [], missing, failed, rolledBack: true, frozen };
+}
+
+export const rollbackMessage = ({ failed }: Nvmd.BatchSyncResult) =>
+  `Could not update ${failed.map(({ path }) => path).join(", ")}, changes were rolled back`;
+
 type UpdateByGroup = {
   projects: string[];
   groupName: string;
@@ -81,24 +603,182 @@ type UpdateByGroup = {
 };
 
 export async function updateProjectAndSyncVersion({ projects, groupName, version }: UpdateByGroup) {
-  const asyncVerions: Promise<any>[] = [],
-    newProjects = [...cacheProjects];
-  projects.forEach((projectPath) => {
-    // update projectpath/.nvmdrc
-    asyncVerions.push(syncProjectVersion(projectPath, version));
-
-    // update $HOMEPATH/.nvmd/projects.json
-    // await updateProjectFromGroup({ groupName });
-    newProjects.forEach((project) => {
-      if (project.path === projectPath) {
-        project.version = groupName;
-      }
-    });
-  });
+  // update projectpath/.nvmdrc
+  const result = await syncProjectsVersion(projects, version);
+  if (result.rolledBack) {
+    return Promise.reject(rollbackMessage(result));
+  }
+
+  // update $HOMEPATH/.nvmd/projects.json
+  const newProjects = cacheProjects.map((project) =>
+    projects.includes(project.path) && !result.frozen.includes(project.path)
+      ? { ...project, version: groupName }
+      : project
+  );
 
+  await saveConfig(PROJECTS_JSONFILE, newProjects);
   cacheProjects = newProjects;
-  asyncVerions.push(writeJson(PROJECTS_JSONFILE, newProjects));
 
-  await Promise.all(asyncVerions);
   return cacheProjects;
 }
+
+// Switch the projects at `paths` to `version` as a single unit. Projects using a group
+// move the whole group, so its other projects follow. The `.nvmdrc` files are written
+// first and rolled back together on failure, the configs are only saved afterwards:
+// when that fails, the files and the configs are all put back as they were.
+export async function moveProjectsToVersion(
+  paths: string[],
+  version: string
+): Promise<Nvmd.BulkVersionResult> {
+  const [projects, groups] = await Promise.all([getProjects(), getGroups()]);
+  const selected = projects.filter(({ path }) => paths.includes(path));
+
+  const movedGroups = groups.filter(
+    (group) => group.version !== version && selected.some(({ version }) => version === group.name)
+  );
+  const movedProjects = selected.filter(
+    (project) => project.version !== version && !groups.some(({ name }) => name === project.version)
+  );
+
+  const targets = [
+    ...new Set([
+      ...movedProjects.map(({ path }) => path),
+      ...movedGroups.flatMap((group) => group.projects)
+    ])
+  ];
+  const previous = await readFileStates(targets.flatMap(getSwitchedFiles));
+  const result = await syncProjectsVersion(targets, version);
+  if (result.rolledBack) return Promise.reject(rollbackMessage(result));
+
+  try {
+    const now = new Date().toISOString();
+    const newProjects = projects.map((project) =>
+      movedProjects.includes(project) && !project.frozen
+        ? {
+            ...project,
+            version,
+            active: !result.missing.includes(project.path),
+            updateAt: now
+          }
+        : project
+    );
+    await saveConfig(PROJECTS_JSONFILE, newProjects);
+    cacheProjects = newProjects;
+
+    const detached = await detachFrozenMembers(movedGroups, result.frozen);
+    movedGroups.length &&
+      (await updateGroups(
+        groups.map((group) => {
+          const moved = detached.find(({ name }) => name === group.name);
+          return moved ? { ...moved, version } : group;
+        })
+      ));
+  } catch (err) {
+    logger.error(`Moving projects to v${version} failed, rolling back: ${err.message || err}`);
+    await restoreFileStates(previous);
+    await saveConfig(PROJECTS_JSONFILE, projects);
+    cacheProjects = projects;
+    await updateGroups(groups);
+    return Promise.reject(
+      `Could not save the projects, changes were rolled back: ${err.message || err}`
+    );
+  }
+
+  return {
+    version,
+    installed: false,
+    projects: movedProjects
+      .filter(({ frozen }) => !frozen)
+      .map(({ path, version: from }) => ({ path, from })),
+    groups: movedGroups.map(({ name, version: from }) => ({ name, from })),
+    missing: result.missing,
+    frozen: result.frozen
+  };
+}
+
+// Frozen projects of the `groups` about to change version leave them, pinned to the
+// version the group had, so their `.nvmdrc` and the config keep agreeing. Resolves to
+// the groups without them.
+export async function detachFrozenMembers(groups: Nvmd.Group[], frozen: string[]) {
+  const members = (await getProjects()).filter(
+    ({ path, version }) => frozen.includes(path) && groups.some(({ name }) => name === version)
+  );
+  if (!members.length) return groups;
+
+  const now = new Date().toISOString();
+  const newProjects = cacheProjects.map((project) => {
+    const group =
+      members.some(({ path }) => samePath(path, project.path)) &&
+      groups.find(({ name }) => name === project.version);
+    return group ? { ...project, version: group.version, updateAt: now } : project;
+  });
+  await saveConfig(PROJECTS_JSONFILE, newProjects);
+  cacheProjects = newProjects;
+
+  return groups.map((group) => ({
+    ...group,
+    projects: group.projects.filter((path) => !frozen.includes(path))
+  }));
+}
+
+// Projects whose `.nvmdrc` no longer matches the version stored in the config, e.g.
+// after editing it by hand or pulling a change. Missing folders aren't reported.
+export async function detectProjectDrift(signal?: AbortSignal): Promise<Nvmd.ProjectDrift[]> {
+  const [projects, groups] = await Promise.all([getProjects(), getGroups()]);
+
+  const drifts = await mapLimit(
+    projects,
+    async ({ name, path, version }) => {
+      if (!version || !(await pathExists(path))) return;
+
+      const group = groups.find((group) => group.name === version);
+      const expected = group ? group.version : version;
+      const actual = await getVersion(path);
+      // `v20.11.1` in the file is the same as `20.11.1` in the config, and a specifier
+      // in the config is written as the version it stands for
+      const exact = await toExactVersion(expected).catch(() => normalizeVersionSpec(expected));
+      if (normalizeVersionSpec(actual) === exact) return;
+
+      return { name, path, expected, actual, ...(group && { group: group.name }) };
+    },
+    { signal }
+  );
+  // a partial scan would report the projects left out as in sync
+  signal?.throwIfAborted();
+  logFailures("Could not check project", drifts, ({ path }) => path);
+
+  return drifts.flatMap((result) =>
+    result.status === "fulfilled" && result.value ? [result.value] : []
+  );
+}
+
+// Settle a drift either way: `adopt` stores the `.nvmdrc` value in the config (the
+// project then leaves its group), `rewrite` writes the stored version to the file.
+export async function resolveProjectDrift(path: string, action: Nvmd.DriftAction) {
+  const drift = (await detectProjectDrift()).find((drift) => drift.path === path);
+  if (!drift) return { projects: await getProjects(), groups: await getGroups() };
+
+  if (action === "rewrite" || !drift.actual) {
+    await syncProjectVersion(path, drift.expected);
+    return { projects: await getProjects(), groups: await getGroups() };
+  }
+
+  const newProjects = (await getProjects()).map((project) =>
+    project.path === path
+      ? { ...project, version: drift.actual, updateAt: new Date().toISOString() }
+      : project
+  );
+  await saveConfig(PROJECTS_JSONFILE, newProjects);
+  cacheProjects = newProjects;
+
+  if (drift.group) {
+    await updateGroups(
+      (await getGroups()).map((group) =>
+        group.name === drift.group
+          ? { ...group, projects: group.projects.filter((project) => project !== path) }
+          : group
+      )
+    );
+  }
+  return { projects: cacheProjects, groups: await getGroups() };
+}
@@ -0,0 +1,142 @@
+import { join } from "node:path";
+import { platform } from "node:process";
+import { writeFile } from "fs-extra";
+import { APPDIR } from "../constants";
+import { getCache } from "../deps/all-node-versions/cache";
+import { DAEMON_SOCKET, DAEMON_TOKEN } from "./daemon";
+import { getInheritedVersion, getProjects, resolveProjectVersion } from "./projects";
+import { resolveVersionSpec } from "./version-spec";
+import { isInside } from "./winpath";
+
+// A prompt is drawn after every command, the answer for a folder is reused that long.
+// `.nvmdrc` edits show up after it, config and install changes right away.
+const CACHE_TTL = 2 * 1000;
+const MAX_ENTRIES = 100;
+
+let cache = new Map<string, { info: Nvmd.PromptInfo; expires: number }>();
+let cacheKey = "";
+
+// The version in effect in `cwd` and whether it's installed: that of the tracked
+// project containing it (the innermost one), else the one its folder or repository
+// declares, else the global version. Only cached data is read, the release index
+// (for `lts/*` and the like) is never fetched.
+export async function getPromptInfo(
+  cwd: string,
+  { installed, current }: { installed: string[]; current: string }
+): Promise<Nvmd.PromptInfo> {
+  const key = `${current}|${installed.join(",")}`;
+  if (key !== cacheKey) {
+    cache = new Map();
+    cacheKey = key;
+  }
+  const cached = cache.get(cwd);
+  if (cached && cached.expires > Date.now()) return cached.info;
+
+  const info = await resolvePromptInfo(cwd, installed, current);
+  cache.size >= MAX_ENTRIES && cache.delete(cache.keys().next().value!);
+  cache.set(cwd, { info, expires: Date.now() + CACHE_TTL });
+  return info;
+}
+
+export const clearPromptCache = () => cache.clear();
+
+async function resolvePromptInfo(
+  cwd: string,
+  installed: string[],
+  current: string
+): Promise<Nvmd.PromptInfo> {
+  const project = (await getProjects())
+    .filter(({ path }) => isInside(cwd, path))
+    .sort((project1, project2) => project2.path.length - project1.path.length)[0];
+
+  let spec: string, source: Nvmd.PromptSource;
+  if (project && (spec = await resolveProjectVersion(project.path))) {
+    source = "project";
+  } else if ((spec = await getInheritedVersion(cwd))) {
+    source = "folder";
+  } else if ((spec = current)) {
+    source = "global";
+  } else {
+    return { cwd, source: "none", installed: false, text: "" };
+  }
+
+  const versions = (await getCache({}).catch(() => void 0)) || [];
+  const { version } = resolveVersionSpec(spec, { installed, versions });
+  const isInstalled = !!version && installed.includes(version);
+  return {
+    cwd,
+    source,
+    ...(version ? { version } : { spec }),
+    installed: isInstalled,
+    ...(source === "project" && { project: project!.name }),
+    // `v20.11.1`, followed by `!` when it isn't installed
+    text: `${version ? `v${version}` : spec}${isInstalled ? "" : "!"}`
+  };
+}
+
+// Run by the segments with the `node` on PATH. It asks the daemon for the `prompt-info`
+// of the current folder, and prints nothing when the daemon isn't running.
+const PROMPT_CLIENT = join(APPDIR, "prompt.js");
+const TIMEOUT = 500;
+
+const getClientScript = () => `// written by nvm-desktop for the prompt segments
+const { readFileSync } = require("node:fs");
+const { connect } = require("node:net");
+
+try {
+  const token = readFileSync(${JSON.stringify(DAEMON_TOKEN)}, "utf-8");
+  const socket = connect(${JSON.stringify(DAEMON_SOCKET)}, () => {
+    const request = { token, channel: "prompt-info", args: [process.cwd()] };
+    socket.write(JSON.stringify(request) + "\\n");
+  });
+  let data = "";
+  socket.setTimeout(${TIMEOUT}, () => socket.destroy());
+  socket.on("error", () => {});
+  socket.on("data", (chunk) => {
+    data += chunk;
+    if (!data.includes("\\n")) return;
+    socket.end();
+    try {
+      const { result } = JSON.parse(data);
+      result && process.stdout.write(result.text);
+    } catch {}
+  });
+} catch {}
+`;
+
+const COMMAND = `node ${JSON.stringify(PROMPT_CLIENT)}`;
+
+const starship = () => `# nvmd segment for starship, add it to ~/.config/starship.toml
+# it shows nothing unless the app runs as a daemon, started with \`--daemon\`
+[custom.nvmd]
+command = ${JSON.stringify(COMMAND)}
+when = true
+symbol = "⬢ "
+style = "bold green"
+format = "[$symbol($output )]($style)"
+`;
+
+const ohMyPosh = () => `{
+  "type": "command",
+  "style": "plain",
+  "foreground": "green",
+  "template": "{{ if .Output }}⬢ {{ .Output }} {{ end }}",
+  "properties": {
+    "shell": "${platform === "win32" ? "pwsh" : "bash"}",
+    "command": ${JSON.stringify(COMMAND)}
+  }
+}
+`;
+
+const SEGMENTS: Record<Nvmd.PromptTool, () => string> = {
+  starship,
+  "oh-my-posh": ohMyPosh
+};
+
+// Prompt segment of `tool` showing the version in effect, as the daemon answers it. The
+// client script it runs is written along.
+export async function getPromptSegment(tool: Nvmd.PromptTool) {
+  if (!SEGMENTS[tool]) throw `Unsupported prompt: ${tool}`;
+  await writeFile(PROMPT_CLIENT, getClientScript(), "utf-8");
+  return SEGMENTS[tool]();
+}
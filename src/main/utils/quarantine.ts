@@ -0,0 +1,91 @@
+import { join } from "node:path";
+import { platform } from "node:process";
+import { lstat, readdir } from "node:fs/promises";
+import { move, pathExists, remove } from "fs-extra";
+import { QUARANTINE_DIR } from "../constants";
+import { getActiveInstalls } from "./installs";
+import { logger } from "./logger";
+
+const NODE_EXECUTABLE = platform === "win32" ? "node.exe" : join("bin", "node");
+
+const VERSION_NAME = /^v?\d+\.\d+\.\d+/;
+// temporary folders of downloads and the backups kept during a repair
+const LEFTOVER_NAME = /^(get|build)-node-|\.tmp$|\.nvmd-repair$/;
+// downloads that didn't match their checksum, `v<version>-<arch>.checksum`
+const CHECKSUM_NAME = /\.checksum$/;
+
+// Entries are moved to `<time>-<name>`, so that a version quarantined twice
+// doesn't collide with the first one.
+const parseEntry = (entry: string): Nvmd.QuarantinedEntry | undefined => {
+  const match = /^(\d+)-(.+)$/.exec(entry);
+  if (!match) return;
+
+  const [, time, name] = match;
+  const version = VERSION_NAME.exec(name)?.[0].replace(/^v/, "");
+  return {
+    entry,
+    name,
+    ...(version && { version }),
+    at: new Date(Number(time)).toISOString(),
+    reason: CHECKSUM_NAME.test(name) ? "checksum" : version ? "incomplete" : "leftover"
+  };
+};
+
+// A version folder without its node executable (interrupted extraction, link to a
+// removed drive), or a temporary folder left by an interrupted install.
+async function isBroken(directory: string, name: string) {
+  if (LEFTOVER_NAME.test(name)) return true;
+  if (!VERSION_NAME.test(name)) return false;
+
+  const stats = await lstat(join(directory, name));
+  if (!stats.isDirectory() && !stats.isSymbolicLink()) return false;
+  return !(await pathExists(join(directory, name, NODE_EXECUTABLE)));
+}
+
+// Only existence checks, quick enough to run on every launch before the installed
+// versions are listed. Nothing is touched while an install is running, its folders
+// look just like broken ones.
+export async function quarantineBrokenInstalls(directory: string) {
+  if (getActiveInstalls().length || !(await pathExists(directory))) return [];
+
+  const quarantined: Nvmd.QuarantinedEntry[] = [];
+  for (const name of await readdir(directory)) {
+    try {
+      if (!(await isBroken(directory, name))) continue;
+
+      const entry = `${Date.now()}-${name}`;
+      await move(join(directory, name), join(QUARANTINE_DIR, entry));
+      logger.warn(`Quarantined ${name}, it is not a usable installation`);
+      quarantined.push(parseEntry(entry)!);
+    } catch (err) {
+      logger.warn(`Could not check ${name}: ${err.message}`);
+    }
+  }
+  return quarantined;
+}
+
+// Keep a download that didn't match its checksum for inspection or a report.
+export async function quarantineDownload(path: string, version: string, arch: string) {
+  const entry = `${Date.now()}-v${version}-${arch}.checksum`;
+  await move(path, join(QUARANTINE_DIR, entry));
+  logger.warn(`Quarantined the download of Node.js v${version} (${arch}), checksum mismatch`);
+  return parseEntry(entry)!;
+}
+
+export async function getQuarantined() {
+  if (!(await pathExists(QUARANTINE_DIR))) return [];
+
+  const entries = (await readdir(QUARANTINE_DIR)).map(parseEntry);
+  return entries.filter((entry): entry is Nvmd.QuarantinedEntry => !!entry);
+}
+
+// Only a listed entry, never a path built from what the renderer sent.
+export async function deleteQuarantined(entry: string) {
+  if (!(await getQuarantined()).some((item) => item.entry === entry)) {
+    return Promise.reject(`${entry} is not in the quarantine`);
+  }
+
+  await remove(join(QUARANTINE_DIR, entry));
+  logger.info(`Deleted ${entry} from the quarantine`);
+  return getQuarantined();
+}
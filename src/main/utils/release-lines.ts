@@ -0,0 +1,31 @@
+// Release lines (majors) of the index, newest first. A line is named after the
+// codename its LTS releases carry (`Iron` for 20), odd majors never get one.
+export function getReleaseLines(versions: Nvmd.Versions): Nvmd.ReleaseLine[] {
+  const lines = new Map<number, Nvmd.ReleaseLine>();
+
+  versions.forEach(({ version: tag, lts }) => {
+    const version = tag.replace(/^v/, "");
+    const major = Number(version.split(".")[0]);
+    if (!Number.isInteger(major)) return;
+
+    const line = lines.get(major) ?? { major, latest: version, versions: [] };
+    line.versions.push(version);
+    if (lts && !line.latestLts) {
+      line.codename = lts;
+      line.latestLts = version;
+    }
+    lines.set(major, line);
+  });
+
+  // the index is sorted newest first, so is each line and its `latest`
+  return [...lines.values()].sort((line1, line2) => line2.major - line1.major);
+}
+
+// Newest version of a line given by its major (`20`, `v20`) or codename (`iron`).
+export function getLatestOfLine(versions: Nvmd.Versions, line: string | number) {
+  const name = `${line}`.trim().toLowerCase().replace(/^v(?=\d)/, "");
+  const found = getReleaseLines(versions).find(
+    ({ major, codename }) => `${major}` === name || codename?.toLowerCase() === name
+  );
+  return found?.latest;
+}
@@ -0,0 +1,89 @@
+import { json } from "node:stream/consumers";
+import { pathExists, readJson } from "fs-extra";
+import { SCHEDULE_JSONFILE } from "../constants";
+import { fetchNodeWebsite } from "../deps/fetch-node-website";
+import { writeJsonAtomic } from "./file";
+import { logger } from "./logger";
+
+import type { Options as FetchOptions } from "../deps/fetch-node-website";
+
+// Maintained by the Node.js release team, `schedule.json` at the root
+const SCHEDULE_URL = "https://raw.githubusercontent.com/nodejs/Release/main";
+// the dates of a line rarely change once announced
+const MAX_AGE = 24 * 60 * 60 * 1000;
+
+type ScheduleFile = Record<
+  string,
+  { start: string; lts?: string; maintenance?: string; end: string; codename?: string }
+>;
+
+let cached: Nvmd.ReleaseSchedule | undefined;
+
+// Phase of a line at `date`, from its dates.
+function getStatus(line: Omit<Nvmd.ReleaseLineSchedule, "status">, date: string) {
+  if (date >= line.end) return "end-of-life";
+  if (line.maintenance && date >= line.maintenance) return "maintenance";
+  if (line.lts && date >= line.lts) return "active";
+  if (date >= line.start) return "current";
+  return "pending";
+}
+
+function parseSchedule(file: ScheduleFile, date: string): Nvmd.ReleaseLineSchedule[] {
+  return Object.entries(file)
+    .flatMap(([name, { start, lts, maintenance, end, codename }]) => {
+      const major = Number(name.replace(/^v/, ""));
+      // `v0.10`, `v0.12` aren't majors of their own anymore
+      if (!Number.isInteger(major) || major < 1) return [];
+
+      const line = { major, start, end, ...(lts && { lts }), ...(maintenance && { maintenance }) };
+      return [{ ...line, ...(codename && { codename }), status: getStatus(line, date) }];
+    })
+    .sort((line1, line2) => line2.major - line1.major);
+}
+
+async function readCache(): Promise<Nvmd.ReleaseSchedule | undefined> {
+  if (!(await pathExists(SCHEDULE_JSONFILE))) return;
+  return (await readJson(SCHEDULE_JSONFILE, { throws: false })) || undefined;
+}
+
+// Dates of every release line, newest first. The schedule is fetched once a day (or
+// when `fetch`), the copy on disk is used offline, its statuses computed again.
+export async function getReleaseSchedule(
+  fetchOpts: FetchOptions,
+  fetch: boolean = false
+): Promise<Nvmd.ReleaseSchedule> {
+  cached ??= await readCache();
+  const today = new Date().toISOString().slice(0, 10);
+  const fresh = cached && Date.now() - Date.parse(cached.fetchedAt) < MAX_AGE;
+
+  if (!fresh || fetch) {
+    try {
+      const response = await fetchNodeWebsite("schedule.json", {
+        ...fetchOpts,
+        mirror: SCHEDULE_URL
+      });
+      const file = (await json(response as NodeJS.ReadableStream)) as ScheduleFile;
+      cached = { fetchedAt: new Date().toISOString(), lines: parseSchedule(file, today) };
+      await writeJsonAtomic(SCHEDULE_JSONFILE, cached);
+    } catch (err) {
+      if (!cached) throw err;
+      logger.warn(`Could not fetch the release schedule, using the cached one: ${err.message}`);
+    }
+  }
+
+  return {
+    ...cached!,
+    lines: cached!.lines.map(({ status: _status, ...line }) => ({
+      ...line,
+      status: getStatus(line, today)
+    }))
+  };
+}
+
+// The schedule of the line of `version`, e.g. to warn about its end of life. Unknown
+// majors (nightlies, lines not announced yet) have none.
+export const getLineSchedule = (schedule: Nvmd.ReleaseSchedule, version: string) =>
+  schedule.lines.find(({ major }) => `${major}` === version.replace(/^v/, "").split(".")[0]);
+
+// The schedule last fetched, without going to the network.
+export const getCachedSchedule = async () => (cached ??= await readCache());
@@ -0,0 +1,84 @@
+import { platform } from "node:process";
+import { join } from "node:path";
+import { symlink } from "node:fs/promises";
+import { ensureDir, move, pathExists, readdir } from "fs-extra";
+import { valid } from "semver";
+import { getCurrentVersion } from "./version";
+import { recordInstallation, verifyInstallation } from "./verify";
+import { syncShims } from "./shims";
+import { samePath } from "./winpath";
+import { logger } from "./logger";
+
+// Junctions don't require the symlink privilege on Windows.
+const LINK_TYPE = platform === "win32" ? "junction" : "dir";
+
+// Versions installed in `directory`, anything not named like a version is left alone.
+export async function getVersionDirs(directory: string) {
+  if (!(await pathExists(directory))) return [];
+  return (await readdir(directory)).filter((name) => valid(name));
+}
+
+// Bring the versions installed in `from` to `to` when the install directory changes,
+// either moving them or leaving them in place behind a link. Versions already present
+// in `to` are skipped. The shims are regenerated for the new directory and the
+// default version is checked to still resolve there.
+export async function migrateVersions({
+  from,
+  to,
+  mode,
+  signal
+}: {
+  from: string;
+  to: string;
+  mode: Nvmd.MigrateMode;
+  signal?: AbortSignal;
+}): Promise<Nvmd.CommandResult<Nvmd.MigrateResult>> {
+  const result: Nvmd.MigrateResult = { migrated: [], skipped: [], failed: [] };
+  const warnings: Nvmd.CommandWarning[] = [];
+  if (samePath(from, to)) return { data: result, warnings };
+
+  await ensureDir(to);
+  const versions = await getVersionDirs(from);
+  for (const [index, version] of versions.entries()) {
+    // the versions migrated so far stay where they are, the others are left behind
+    if (signal?.aborted) {
+      warnings.push({ message: `Canceled, ${versions.length - index} versions not migrated` });
+      break;
+    }
+
+    const source = join(from, version);
+    const target = join(to, version);
+    if (await pathExists(target)) {
+      result.skipped.push(version);
+      warnings.push({ message: `Already in ${to}, skipped`, subject: version });
+      continue;
+    }
+
+    try {
+      if (mode === "link") {
+        await symlink(source, target, LINK_TYPE);
+        // where its files really are, for their size and to remove them on uninstall
+        await recordInstallation({ path: to, version });
+      } else {
+        await move(source, target);
+      }
+      result.migrated.push(version);
+    } catch (err) {
+      logger.error(`Migrating Node.js v${version} to ${to} failed: ${err.message}`);
+      result.failed.push({ version, message: err.message });
+      warnings.push({ message: err.message, subject: version });
+    }
+  }
+  logger.info(`Migrated ${result.migrated.length} versions from ${from} to ${to} (${mode})`);
+
+  await syncShims(to).catch((err) =>
+    warnings.push({ message: `Could not update the shims: ${err.message}` })
+  );
+
+  const current = await getCurrentVersion();
+  if (current) {
+    const { valid } = await verifyInstallation({ path: to, version: current });
+    result.current = { version: current, valid };
+  }
+  return { data: result, warnings };
+}
@@ -0,0 +1,150 @@
+import { basename, join } from "node:path";
+import { copy, ensureDir, pathExists, readJson, readdir, realpath, remove } from "fs-extra";
+import {
+  GROUPS_JSONFILE,
+  INSTALL_DIR,
+  LOCKED_JSONFILE,
+  PROJECTS_JSONFILE,
+  ROLLBACK_DIR,
+  SETTING_JSONFILE
+} from "../constants";
+import { saveConfig } from "./backup";
+import { withConfigLock } from "./config-lock";
+import { writeJsonAtomic } from "./file";
+import { fromPortableConfig } from "./portable-paths";
+import { linkVersion } from "./locations";
+import { getVersionDirs } from "./relocate";
+import { getCurrentVersion, setCurrentVersion } from "./version";
+import { logger } from "./logger";
+
+// Rollback points kept, older ones are removed as new ones are taken.
+const MAX_POINTS = 5;
+
+const CONFIG_FILES = [SETTING_JSONFILE, PROJECTS_JSONFILE, GROUPS_JSONFILE, LOCKED_JSONFILE];
+
+type Meta = Omit<Nvmd.RollbackPoint, "id">;
+
+export async function getRollbackPoints(): Promise<Nvmd.RollbackPoint[]> {
+  if (!(await pathExists(ROLLBACK_DIR))) return [];
+
+  const points = await Promise.all(
+    (await readdir(ROLLBACK_DIR)).map(async (id) => {
+      const meta: Meta | null = await readJson(join(ROLLBACK_DIR, id, "meta.json"), {
+        throws: false
+      }).catch(() => null);
+      return meta && { id, ...meta };
+    })
+  );
+  // newest first, ids are timestamps
+  return points
+    .filter((point): point is Nvmd.RollbackPoint => !!point)
+    .sort((point1, point2) => point2.id.localeCompare(point1.id));
+}
+
+// Copy the config files and list the installed versions with where their files
+// really are, before `operation` changes them. The versions are those of `from`, the
+// install directory of the setting file by default.
+export async function createRollbackPoint(operation: Nvmd.RollbackOperation, from?: string) {
+  try {
+    const setting = from ? null : await readJson(SETTING_JSONFILE, { throws: false });
+    const directory: string = from || setting?.directory || INSTALL_DIR;
+
+    const id = `${Date.now()}`;
+    const dir = join(ROLLBACK_DIR, id);
+    await ensureDir(join(dir, "config"));
+
+    const files: string[] = [];
+    for (const file of CONFIG_FILES) {
+      if (!(await pathExists(file))) continue;
+      await copy(file, join(dir, "config", basename(file)));
+      files.push(basename(file));
+    }
+
+    const versions = await Promise.all(
+      (await getVersionDirs(directory)).map(async (version) => ({
+        version,
+        location: await realpath(join(directory, version)).catch(() => join(directory, version))
+      }))
+    );
+    const meta: Meta = {
+      operation,
+      createAt: new Date().toISOString(),
+      directory,
+      files,
+      versions,
+      current: (await getCurrentVersion(true)) || void 0
+    };
+    await writeJsonAtomic(join(dir, "meta.json"), meta);
+
+    const points = await getRollbackPoints();
+    await Promise.all(points.slice(MAX_POINTS).map(({ id }) => remove(join(ROLLBACK_DIR, id))));
+    logger.info(`Took rollback point ${id} before ${operation}`);
+    return id;
+  } catch (err) {
+    // the operation goes on, it just can't be rolled back
+    logger.warn(`Could not take a rollback point before ${operation}: ${err.message}`);
+    return;
+  }
+}
+
+// Put back the config files of the last rollback point, saved like any other change so
+// they're backed up and locked, and link the versions it
+// listed that are missing from the restored install directory: from where their
+// files were then, or from `directory` (the install directory before the rollback)
+// when they were moved there. The point is removed, the next rollback goes further back.
+export async function rollbackLastOperation(directory: string): Promise<Nvmd.RollbackResult> {
+  const [point] = await getRollbackPoints();
+  if (!point) return Promise.reject("There is no operation to roll back");
+
+  const dir = join(ROLLBACK_DIR, point.id);
+  const restored: string[] = [];
+  for (const file of CONFIG_FILES) {
+    const name = basename(file);
+    if (point.files.includes(name)) {
+      const data = await readJson(join(dir, "config", name));
+      await saveConfig(file, fromPortableConfig(file, data));
+      restored.push(name);
+    } else if (await pathExists(file)) {
+      // created by the operation
+      await withConfigLock(file, () => remove(file));
+      restored.push(name);
+    }
+  }
+
+  const relinked: string[] = [];
+  const missing: string[] = [];
+  for (const { version, location } of point.versions) {
+    if (await pathExists(join(point.directory, version))) continue;
+
+    let target: string | undefined;
+    for (const candidate of [location, join(directory, version)]) {
+      if (await pathExists(candidate)) {
+        target = candidate;
+        break;
+      }
+    }
+    if (!target) {
+      missing.push(version);
+      continue;
+    }
+
+    try {
+      await linkVersion(point.directory, version, target);
+      relinked.push(version);
+    } catch (err) {
+      logger.warn(`Could not link Node.js v${version} back: ${err.message}`);
+      missing.push(version);
+    }
+  }
+
+  if (point.current && point.current !== (await getCurrentVersion(true))) {
+    await setCurrentVersion(point.current, false);
+  }
+
+  await remove(dir);
+  logger.info(
+    `Rolled back ${point.operation} of ${point.createAt}: ${restored.length} files restored, ` +
+      `${relinked.length} versions relinked, ${missing.length} missing`
+  );
+  return { operation: point.operation, createAt: point.createAt, restored, relinked, missing };
+}
@@ -0,0 +1,346 @@
+import { createHash } from "node:crypto";
+import { chmod, lstat, readdir } from "node:fs/promises";
+import { join } from "node:path";
+import { arch, platform } from "node:process";
+import { buffer, json, text } from "node:stream/consumers";
+import { ensureDir, move, pathExists, readFile, remove, writeFile } from "fs-extra";
+import { BIN_DIR, RUNTIMES_DIR } from "../constants";
+import { fetchNodeWebsite } from "../deps/fetch-node-website";
+import { countZipEntries, getZipStream } from "../deps/get-node/archive/zip";
+import { trackExtraction, trackProgress } from "../deps/get-node/progress";
+import { bun } from "./bun";
+import { deno } from "./deno";
+import { writeFileAtomic } from "./file";
+import { logger } from "./logger";
+import { pnpm } from "./pnpm";
+import { getProjects, updateProjects } from "./projects";
+import { samePath } from "./winpath";
+
+import type { Options as FetchOptions } from "../deps/fetch-node-website";
+
+// A runtime managed next to Node.js: its releases are published on GitHub as a
+// single executable, possibly zipped. Versions are installed to `RUNTIMES_DIR/<name>/<version>`,
+// the shims in `BIN_DIR` run the version pinned by the closest `versionFile`, or the
+// default one.
+export interface RuntimeDefinition {
+  name: Nvmd.RuntimeName;
+  title: string;
+  // GitHub repository publishing the releases, e.g. `denoland/deno`
+  repository: string;
+  getTag(version: string): string;
+  // undefined for tags that aren't releases
+  parseTag(tag: string): string | undefined;
+  // release asset for this platform, undefined when there is no build for it
+  getAsset(version: string): string | undefined;
+  // asset listing the sha256 of `asset`, when the releases publish one
+  getChecksumAsset?(asset: string): string;
+  // path of the executable inside the zip, the asset is the executable itself without it
+  getArchivePath?(asset: string): string;
+  // shim name -> arguments passed before the user's ones
+  shims: Record<string, string[]>;
+  versionFile: string;
+}
+
+const RUNTIMES: RuntimeDefinition[] = [deno, bun, pnpm];
+
+const GITHUB = "https://github.com";
+const GITHUB_API = "https://api.github.com";
+
+const releases = new Map<Nvmd.RuntimeName, Nvmd.RuntimeRelease[]>();
+
+export function getRuntime(name: Nvmd.RuntimeName) {
+  const runtime = RUNTIMES.find((runtime) => runtime.name === name);
+  if (!runtime) throw new Error(`Unknown runtime ${name}`);
+  return runtime;
+}
+
+const runtimeDir = (name: Nvmd.RuntimeName) => join(RUNTIMES_DIR, name);
+
+const executableName = ({ name }: RuntimeDefinition) =>
+  platform === "win32" ? `${name}.exe` : name;
+
+// Latest releases first, only the first page: older ones are rarely wanted and the
+// unauthenticated GitHub API is limited to 60 requests an hour.
+export async function getRuntimeReleases(
+  name: Nvmd.RuntimeName,
+  fetchOpts: FetchOptions,
+  fetch: boolean = false
+): Promise<Nvmd.RuntimeRelease[]> {
+  const cached = releases.get(name);
+  if (cached && !fetch) return cached;
+
+  const runtime = getRuntime(name);
+  const response = await fetchNodeWebsite(`repos/${runtime.repository}/releases?per_page=100`, {
+    ...fetchOpts,
+    mirror: GITHUB_API
+  });
+  const list = (await json(response as NodeJS.ReadableStream)) as {
+    tag_name: string;
+    published_at: string;
+    prerelease: boolean;
+    assets: { name: string }[];
+  }[];
+
+  const result = list.flatMap(({ tag_name, published_at, prerelease, assets }) => {
+    const version = runtime.parseTag(tag_name);
+    if (!version) return [];
+
+    const asset = runtime.getAsset(version);
+    const available = !!asset && assets.some(({ name }) => name === asset);
+    return [{ version, date: published_at, prerelease, available }];
+  });
+  releases.set(name, result);
+  return result;
+}
+
+export async function getInstalledRuntimeVersions(name: Nvmd.RuntimeName) {
+  const dir = runtimeDir(name);
+  if (!(await pathExists(dir))) return [];
+
+  const executable = executableName(getRuntime(name));
+  const versions = await Promise.all(
+    (await readdir(dir)).map(async (version) =>
+      (await pathExists(join(dir, version, executable))) ? version : ""
+    )
+  );
+  return versions.filter(Boolean);
+}
+
+export async function getRuntimeDefault(name: Nvmd.RuntimeName) {
+  const file = join(runtimeDir(name), "default");
+  if (!(await pathExists(file))) return "";
+  return (await readFile(file)).toString().trim();
+}
+
+export async function setRuntimeDefault(name: Nvmd.RuntimeName, version: string) {
+  if (version && !(await getInstalledRuntimeVersions(name)).includes(version)) {
+    return Promise.reject(`${getRuntime(name).title} v${version} is not installed`);
+  }
+  await writeFileAtomic(join(runtimeDir(name), "default"), version);
+}
+
+export async function getRuntimeInfo(name: Nvmd.RuntimeName): Promise<Nvmd.RuntimeInfo> {
+  const [installed, current] = await Promise.all([
+    getInstalledRuntimeVersions(name),
+    getRuntimeDefault(name)
+  ]);
+  return { name, installed, default: current || undefined };
+}
+
+// Expected sha256 of `asset`, from a `sha256sum` or `SHASUMS256.txt` style file.
+async function getExpectedChecksum(
+  runtime: RuntimeDefinition,
+  version: string,
+  asset: string,
+  fetchOpts: FetchOptions
+) {
+  if (!runtime.getChecksumAsset) return;
+
+  const response = await fetchNodeWebsite(
+    `${runtime.getTag(version)}/${runtime.getChecksumAsset(asset)}`,
+    { ...fetchOpts, mirror: `${GITHUB}/${runtime.repository}/releases/download` }
+  );
+  const content = await text(response as NodeJS.ReadableStream);
+  const line = content.split("\n").find((line) => line.includes(asset)) ?? content;
+  return /[a-f0-9]{64}/i.exec(line)?.[0].toLowerCase();
+}
+
+export async function installRuntime(
+  name: Nvmd.RuntimeName,
+  version: string,
+  {
+    fetchOpts,
+    onProgress,
+    requireChecksum
+  }: {
+    fetchOpts: FetchOptions;
+    onProgress?: (data: Nvmd.ProgressData) => void;
+    // refuse the download when no checksum is published for it
+    requireChecksum?: boolean;
+  }
+) {
+  const runtime = getRuntime(name);
+  const asset = runtime.getAsset(version);
+  if (!asset) {
+    return Promise.reject(`No ${runtime.title} builds are available for ${platform} ${arch}`);
+  }
+
+  const response = await fetchNodeWebsite(`${runtime.getTag(version)}/${asset}`, {
+    ...fetchOpts,
+    mirror: `${GITHUB}/${runtime.repository}/releases/download`
+  });
+  trackProgress(response, onProgress);
+  const content = await buffer(response as NodeJS.ReadableStream);
+
+  const expected = await getExpectedChecksum(runtime, version, asset, fetchOpts).catch(
+    (err) => {
+      logger.warn(`No checksum for ${asset} ${version}: ${err.message}`);
+      return undefined;
+    }
+  );
+  if (!expected && requireChecksum) {
+    return Promise.reject(`No checksum is published for ${asset} ${version}`);
+  }
+  if (expected && createHash("sha256").update(content).digest("hex") !== expected) {
+    return Promise.reject(
+      `Could not download ${runtime.title} ${version}: checksum did not match`
+    );
+  }
+
+  const dir = runtimeDir(name);
+  const tmpDir = join(dir, `.${version}.${Date.now()}.tmp`);
+  const target = join(dir, version, executableName(runtime));
+  try {
+    if (runtime.getArchivePath) {
+      const onEntry = trackExtraction(onProgress, { totalEntries: countZipEntries(content) });
+      await getZipStream(content, tmpDir, onEntry);
+    } else {
+      await ensureDir(tmpDir);
+      await writeFile(join(tmpDir, asset), content);
+    }
+    await ensureDir(join(dir, version));
+    await move(join(tmpDir, runtime.getArchivePath?.(asset) ?? asset), target, {
+      overwrite: true
+    });
+    platform !== "win32" && (await chmod(target, 0o755));
+  } finally {
+    await remove(tmpDir).catch(() => {});
+  }
+  logger.info(`Installed ${runtime.title} v${version} to ${join(dir, version)}`);
+
+  if (!(await getRuntimeDefault(name))) await setRuntimeDefault(name, version);
+  await syncRuntimeShims();
+  return { version, path: target };
+}
+
+export async function uninstallRuntime(name: Nvmd.RuntimeName, version: string) {
+  await remove(join(runtimeDir(name), version));
+  if ((await getRuntimeDefault(name)) === version) await setRuntimeDefault(name, "");
+  logger.info(`Uninstalled ${getRuntime(name).title} v${version}`);
+  await syncRuntimeShims();
+}
+
+// Pin the project at `path` to `version`, or unpin it with an empty version. The
+// version file is what the shims read, the projects config keeps it for display.
+export async function setProjectRuntime(path: string, name: Nvmd.RuntimeName, version: string) {
+  const runtime = getRuntime(name);
+  if (!(await pathExists(path))) return Promise.reject(`${path} does not exist`);
+
+  const file = join(path, runtime.versionFile);
+  version ? await writeFile(file, `${version}\n`) : await remove(file);
+
+  const projects = await getProjects();
+  const newProjects = projects.map((project) => {
+    if (!samePath(project.path, path)) return project;
+
+    const { [name]: _previous, ...runtimes } = project.runtimes ?? {};
+    return { ...project, runtimes: version ? { ...runtimes, [name]: version } : runtimes };
+  });
+  await updateProjects(newProjects);
+  return newProjects;
+}
+
+const SHIM_MARKER = "Generated by nvm-desktop";
+
+// `sh` script looking for the version file from the working directory up, then
+// falling back to the default version.
+const posixShim = (runtime: RuntimeDefinition, args: string[]) => {
+  const dir = runtimeDir(runtime.name);
+  const env = `NVMD_${runtime.name.toUpperCase()}_VERSION`;
+  return `#!/bin/sh
+# ${SHIM_MARKER}, runs the ${runtime.title} version of the project
+root='${dir}'
+version="$${env}"
+dir="$PWD"
+while [ -z "$version" ]; do
+  [ -f "$dir/${runtime.versionFile}" ] && version=$(tr -d ' \\r\\n' < "$dir/${runtime.versionFile}")
+  [ "$dir" = "/" ] && break
+  dir=$(dirname "$dir")
+done
+[ -z "$version" ] && [ -f "$root/default" ] && version=$(tr -d ' \\r\\n' < "$root/default")
+version=\${version#v}
+if [ -z "$version" ]; then
+  echo "nvmd: no ${runtime.title} version is selected" >&2
+  exit 1
+fi
+exec "$root/$version/${runtime.name}" ${[...args, '"$@"'].join(" ")}
+`;
+};
+
+const windowsShim = (runtime: RuntimeDefinition, args: string[]) => {
+  const dir = runtimeDir(runtime.name);
+  const env = `NVMD_${runtime.name.toUpperCase()}_VERSION`;
+  return [
+    "@echo off",
+    `rem ${SHIM_MARKER}, runs the ${runtime.title} version of the project`,
+    "setlocal",
+    `set "root=${dir}"`,
+    `set "version=%${env}%"`,
+    'set "dir=%CD%"',
+    ":search",
+    "if defined version goto found",
+    `if exist "%dir%\\${runtime.versionFile}" set /p version=<"%dir%\\${runtime.versionFile}"`,
+    'for %%I in ("%dir%\\..") do set "parent=%%~fI"',
+    'if /i "%parent%"=="%dir%" goto found',
+    'set "dir=%parent%"',
+    "goto search",
+    ":found",
+    'if not defined version if exist "%root%\\default" set /p version=<"%root%\\default"',
+    `if not defined version (echo nvmd: no ${runtime.title} version is selected 1>&2 & exit /b 1)`,
+    'set "version=%version: =%"',
+    'if "%version:~0,1%"=="v" set "version=%version:~1%"',
+    `"%root%\\%version%\\${runtime.name}.exe" ${[...args, "%*"].join(" ")}`,
+    "exit /b %ERRORLEVEL%",
+    ""
+  ].join("\r\n");
+};
+
+// Scripts written here, as opposed to the links to `nvmd` made for the executables
+// of the Node.js versions (`npm i -g pnpm`), which are left alone.
+const isRuntimeShim = async (file: string) => {
+  try {
+    if ((await lstat(file)).isSymbolicLink()) return false;
+    return (await readFile(file)).toString().includes(SHIM_MARKER);
+  } catch {
+    return false;
+  }
+};
+
+// Shims of the runtimes with installed versions, they take over the executables of
+// the same name provided by the Node.js versions.
+export async function getRuntimeShimNames() {
+  const names = await Promise.all(
+    RUNTIMES.map(async ({ name, shims }) =>
+      (await getInstalledRuntimeVersions(name)).length ? Object.keys(shims) : []
+    )
+  );
+  return new Set(names.flat());
+}
+
+// Write the shims of the runtimes with installed versions, remove the others.
+export async function syncRuntimeShims() {
+  await ensureDir(BIN_DIR);
+
+  for (const runtime of RUNTIMES) {
+    const installed = (await getInstalledRuntimeVersions(runtime.name)).length > 0;
+    for (const [name, args] of Object.entries(runtime.shims)) {
+      const file = join(BIN_DIR, platform === "win32" ? `${name}.cmd` : name);
+      try {
+        if (!installed) {
+          (await isRuntimeShim(file)) && (await remove(file));
+          continue;
+        }
+
+        // never write through a link, it points to the `nvmd` binary; on Windows
+        // the `.exe` shim would win over the script
+        await remove(file);
+        platform === "win32" && (await remove(join(BIN_DIR, `${name}.exe`)));
+        const content =
+          platform === "win32" ? windowsShim(runtime, args) : posixShim(runtime, args);
+        await writeFile(file, content, { mode: 0o755 });
+      } catch (err) {
+        logger.warn(`Could not update the ${name} shim: ${err.message}`);
+      }
+    }
+  }
+}
@@ -0,0 +1,76 @@
+import { join } from "node:path";
+import { move, pathExists, readJson, remove } from "fs-extra";
+import { valid } from "semver";
+import { SANDBOXES_JSONFILE } from "../constants";
+import { writeJsonAtomic } from "./file";
+import { writeNpmCache } from "./npm-cache";
+import { logger } from "./logger";
+
+// Appended to the version as a prerelease tag, so `22.0.0-experiment` sorts with 22.0.0.
+const NAME = /^[a-z][a-z0-9]*(?:-[a-z0-9]+)*$/i;
+
+export async function getSandboxes(): Promise<Nvmd.Sandbox[]> {
+  if (!(await pathExists(SANDBOXES_JSONFILE))) return [];
+  return (await readJson(SANDBOXES_JSONFILE, { throws: false })) || [];
+}
+
+export const withoutSandboxes = async (versions: string[]) => {
+  const sandboxes = await getSandboxes();
+  return versions.filter((version) => !sandboxes.some((sandbox) => sandbox.version === version));
+};
+
+export async function getSandboxVersion(version: string, name: string) {
+  if (!valid(version) || version.includes("-")) {
+    return Promise.reject(`${version} is not a release`);
+  }
+  if (!NAME.test(name)) return Promise.reject(`${name} is not a valid sandbox name`);
+  return `${version}-${name}`;
+}
+
+// Install `version` a second time as `<version>-<name>`, a version folder of its own.
+// Global packages already go to the folder of the version running npm, the sandbox
+// gets its npm cache inside that folder too, so removing it leaves nothing behind.
+// `fetchNode` downloads the release into the folder it's given.
+export async function createSandbox({
+  directory,
+  version,
+  name,
+  fetchNode
+}: {
+  directory: string;
+  version: string;
+  name: string;
+  fetchNode: (output: string) => Promise<{ path: string }>;
+}): Promise<Nvmd.Sandbox> {
+  const sandbox = await getSandboxVersion(version, name);
+  const target = join(directory, sandbox);
+  if (await pathExists(target)) return Promise.reject(`${sandbox} is already installed`);
+
+  // not a version name, left out of the installed versions while downloading
+  const staging = join(directory, `.${sandbox}`);
+  try {
+    const { path } = await fetchNode(staging);
+    await move(path, target);
+  } finally {
+    await remove(staging).catch(() => {});
+  }
+  await writeNpmCache(directory, sandbox, join(target, "npm-cache"));
+
+  const entry = { version: sandbox, base: version, name, createdAt: new Date().toISOString() };
+  const sandboxes = (await getSandboxes()).filter((item) => item.version !== sandbox);
+  await writeJsonAtomic(SANDBOXES_JSONFILE, [...sandboxes, entry]);
+  logger.info(`Created the sandbox ${sandbox} in ${target}`);
+  return entry;
+}
+
+// Drop the record of a sandbox, its folder is removed like any installed version.
+export async function forgetSandbox(version: string) {
+  const sandboxes = await getSandboxes();
+  if (!sandboxes.some((sandbox) => sandbox.version === version)) return false;
+
+  await writeJsonAtomic(
+    SANDBOXES_JSONFILE,
+    sandboxes.filter((sandbox) => sandbox.version !== version)
+  );
+  return true;
+}
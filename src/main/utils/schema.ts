@@ -0,0 +1,102 @@
+import { pathExists, readJson } from "fs-extra";
+import {
+  GROUPS_JSONFILE,
+  PROJECTS_JSONFILE,
+  SCHEMA_JSONFILE,
+  SETTING_JSONFILE
+} from "../constants";
+import { writeJsonAtomic } from "./file";
+import { createRollbackPoint } from "./rollback";
+import { logger } from "./logger";
+
+type Migration = {
+  version: number;
+  migrate: (data: any) => any;
+};
+
+type ConfigName = "setting" | "projects" | "groups";
+
+// Upgrades applied in order to each config file, append new entries with an
+// incremented `version` whenever the shape of `Setting`, `Project` or `Group` changes.
+// `projects.json` and `groups.json` stay plain arrays (the nvmd shim reads them),
+// so the versions of every file are tracked in `schema.json`.
+const MIGRATIONS: Record<ConfigName, Migration[]> = {
+  setting: [
+    {
+      version: 1,
+      migrate: (setting) => ({
+        ...setting,
+        proxy: setting.proxy ?? { enabled: false, ip: "127.0.0.1", port: "8080" }
+      })
+    }
+  ],
+  projects: [
+    {
+      version: 1,
+      migrate: (projects: Nvmd.Project[]) =>
+        projects
+          .filter((project) => project && project.path)
+          .map((project) => ({
+            ...project,
+            name: project.name ?? project.path,
+            active: project.active ?? true,
+            createAt: project.createAt ?? new Date().toISOString(),
+            updateAt: project.updateAt ?? project.createAt ?? new Date().toISOString()
+          }))
+    }
+  ],
+  groups: [
+    {
+      version: 1,
+      migrate: (groups: Nvmd.Group[]) =>
+        groups
+          .filter((group) => group && group.name)
+          .map((group) => ({ ...group, projects: group.projects ?? [] }))
+    }
+  ]
+};
+
+const FILES: Record<ConfigName, string> = {
+  setting: SETTING_JSONFILE,
+  projects: PROJECTS_JSONFILE,
+  groups: GROUPS_JSONFILE
+};
+
+export const latestSchemaVersion = (name: ConfigName) =>
+  MIGRATIONS[name].reduce((latest, { version }) => Math.max(latest, version), 0);
+
+async function getSchemaVersions(): Promise<Partial<Record<ConfigName, number>>> {
+  if (!(await pathExists(SCHEMA_JSONFILE))) return {};
+
+  return (await readJson(SCHEMA_JSONFILE, { throws: false })) || {};
+}
+
+// Bring every config file up to its latest schema, must run before any of them is read.
+// A file that fails to migrate is left untouched, it'll be retried on the next launch.
+export async function migrateConfigs() {
+  const versions = await getSchemaVersions();
+  const outdated = (Object.keys(MIGRATIONS) as ConfigName[]).some((name) =>
+    MIGRATIONS[name].some(({ version }) => version > (versions[name] ?? 0))
+  );
+  outdated && (await createRollbackPoint("migration"));
+
+  for (const name of Object.keys(MIGRATIONS) as ConfigName[]) {
+    const current = versions[name] ?? 0;
+    const pending = MIGRATIONS[name].filter(({ version }) => version > current);
+    if (!pending.length) continue;
+
+    try {
+      if (await pathExists(FILES[name])) {
+        let data = await readJson(FILES[name]);
+        for (const { migrate } of pending) data = migrate(data);
+        await writeJsonAtomic(FILES[name], data);
+      }
+      versions[name] = latestSchemaVersion(name);
+      logger.info(`Migrated ${name} config from schema ${current} to ${versions[name]}`);
+    } catch (err) {
+      logger.error(`Could not migrate ${name} config: ${err.message}`);
+    }
+  }
+
+  await writeJsonAtomic(SCHEMA_JSONFILE, versions);
+}
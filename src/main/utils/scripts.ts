@@ -0,0 +1,183 @@
+import { execFile, spawn } from "node:child_process";
+import { join } from "node:path";
+import { platform } from "node:process";
+import { pathExists, readJson } from "fs-extra";
+import { PACKAGE_JSON_NAME } from "../constants";
+import { logger } from "./logger";
+import { readPackageManager } from "./package-manager";
+
+// Lock file of each package manager, for projects without a `packageManager` field.
+const LOCK_FILES: [string, string][] = [
+  ["pnpm-lock.yaml", "pnpm"],
+  ["yarn.lock", "yarn"],
+  ["bun.lockb", "bun"],
+  ["bun.lock", "bun"]
+];
+
+// Scripts of the project's `package.json`, in the order they are written.
+export async function listScripts(path: string): Promise<Nvmd.ProjectScript[]> {
+  const file = join(path, PACKAGE_JSON_NAME);
+  if (!(await pathExists(file))) return Promise.reject(`${path} has no ${PACKAGE_JSON_NAME}`);
+
+  const { scripts } = (await readJson(file, { throws: false })) || {};
+  if (!scripts || typeof scripts !== "object") return [];
+
+  return Object.entries(scripts)
+    .filter(([, command]) => typeof command === "string")
+    .map(([name, command]) => ({ name, command: command as string }));
+}
+
+// Scripts run by the app under their operation id. They are the only project processes
+// it can restart: their command, environment and working directory are known.
+const runningScripts = new Map<string, { path: string; name: string; restart: AbortController }>();
+
+export const getRunningScripts = (path: string) =>
+  [...runningScripts.values()].filter((script) => script.path === path).map(({ name }) => name);
+
+// Stop the running scripts of the project, `restart` runs them again with the project's
+// current version, otherwise their operation is canceled.
+export function stopRunningScripts(
+  path: string,
+  action: Nvmd.ProcessAction,
+  cancel: (id: string) => void
+) {
+  const ids = [...runningScripts].filter(([, script]) => script.path === path);
+  ids.forEach(([id, { restart }]) => (action === "restart" ? restart.abort() : cancel(id)));
+  return ids.length;
+}
+
+// Run the script under the operation `id` until it exits or the operation is canceled.
+// Restarting it calls `prepare` again, so it picks up a version switched meanwhile.
+export async function runTrackedScript(
+  id: string,
+  path: string,
+  name: string,
+  {
+    prepare,
+    signal,
+    onOutput
+  }: {
+    prepare: () => Promise<{ env?: NodeJS.ProcessEnv; npmCli?: string }>;
+    signal: AbortSignal;
+    onOutput: (line: string, stream: "stdout" | "stderr") => void;
+  }
+) {
+  for (;;) {
+    const { env, npmCli } = await prepare();
+    const restart = new AbortController();
+    runningScripts.set(id, { path, name, restart });
+    try {
+      const result = await runScript(path, name, {
+        env,
+        npmCli,
+        signal: AbortSignal.any([signal, restart.signal]),
+        onOutput
+      });
+      if (!restart.signal.aborted || signal.aborted) return result;
+    } finally {
+      runningScripts.delete(id);
+    }
+    logger.info(`Restarting ${name} in ${path}`);
+  }
+}
+
+async function detectPackageManager(path: string) {
+  const packageManager = await readPackageManager(path);
+  if (packageManager) return packageManager.name;
+
+  for (const [file, name] of LOCK_FILES) {
+    if (await pathExists(join(path, file))) return name;
+  }
+  return "npm";
+}
+
+// npm starts the script in a process of its own, the whole tree is stopped.
+function killTree(pid: number) {
+  if (platform === "win32") {
+    execFile("taskkill", ["/pid", `${pid}`, "/T", "/F"], { windowsHide: true }, () => {});
+    return;
+  }
+
+  try {
+    process.kill(-pid);
+  } catch (err) {
+    logger.warn(`Could not stop the script ${pid}: ${err.message}`);
+  }
+}
+
+// Run the script `name` with the package manager of the project and `env` (the
+// project's version first in PATH), `npmCli` is the npm pinned by the project. Each
+// line of output is passed to `onOutput`, stderr included, and aborting `signal`
+// stops the script.
+export async function runScript(
+  path: string,
+  name: string,
+  {
+    env,
+    npmCli,
+    signal,
+    onOutput
+  }: {
+    env?: NodeJS.ProcessEnv;
+    npmCli?: string;
+    signal?: AbortSignal;
+    onOutput: (line: string, stream: "stdout" | "stderr") => void;
+  }
+): Promise<Nvmd.ScriptResult> {
+  const scripts = await listScripts(path);
+  if (!scripts.some((script) => script.name === name)) {
+    return Promise.reject(`${name} is not a script of ${path}`);
+  }
+  signal?.throwIfAborted();
+
+  const packageManager = await detectPackageManager(path);
+  const start = Date.now();
+  logger.info(`Running ${packageManager} run ${name} in ${path}`);
+
+  const pinned = packageManager === "npm" ? npmCli : undefined;
+  const command = pinned ? "node" : packageManager;
+  const args = pinned ? [pinned, "run", name] : ["run", name];
+
+  return new Promise((resolve, reject) => {
+    const child = spawn(command, args, {
+      cwd: path,
+      env,
+      // in its own process group to stop it with its children
+      detached: platform !== "win32",
+      // npm, yarn and pnpm are `.cmd` scripts on Windows, a pinned npm runs on node itself
+      shell: !pinned && platform === "win32",
+      windowsHide: true
+    });
+
+    const abort = () => child.pid && killTree(child.pid);
+    signal?.addEventListener("abort", abort, { once: true });
+
+    for (const stream of ["stdout", "stderr"] as const) {
+      let rest = "";
+      child[stream].on("data", (chunk: Buffer) => {
+        const lines = (rest + chunk.toString()).split(/\r?\n/);
+        rest = lines.pop()!;
+        lines.forEach((line) => onOutput(line, stream));
+      });
+      child[stream].on("end", () => rest && onOutput(rest, stream));
+    }
+
+    child.once("error", (err) => {
+      signal?.removeEventListener("abort", abort);
+      reject(err.message);
+    });
+    child.once("close", (code, exitSignal) => {
+      signal?.removeEventListener("abort", abort);
+      const duration = Date.now() - start;
+      logger.info(`${packageManager} run ${name} in ${path} exited with ${code ?? exitSignal}`);
+      resolve({
+        name,
+        packageManager,
+        code,
+        ...(exitSignal && { signal: exitSignal }),
+        aborted: !!signal?.aborted,
+        duration
+      });
+    });
+  });
+}
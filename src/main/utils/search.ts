@@ -0,0 +1,80 @@
+// Rank `target` against `query`: every query character must appear in order.
+// Consecutive matches and matches at the start of a word score higher, `0` means no match.
+export function fuzzyScore(query: string, target: string): number {
+  const q = query.toLowerCase().trim(),
+    t = target.toLowerCase();
+  if (!q) return 1;
+
+  let score = 0,
+    streak = 0,
+    from = 0;
+  for (const char of q) {
+    const index = t.indexOf(char, from);
+    if (index === -1) return 0;
+
+    streak = index === from ? streak + 1 : 1;
+    score += streak * 2;
+    if (index === 0 || /[\s\-_./\\v]/.test(t[index - 1])) score += 3;
+    from = index + 1;
+  }
+
+  // Prefer shorter targets, `20` should find `v20.0.0` before `v20.11.1`.
+  return score + q.length / t.length;
+}
+
+type SearchSources = {
+  installed: string[];
+  versions: Nvmd.Versions;
+  projects: Nvmd.Project[];
+  groups: Nvmd.Group[];
+  current: string;
+};
+
+export function searchEverything(
+  query: string,
+  { installed, versions, projects, groups, current }: SearchSources,
+  limit: number = 20
+): Nvmd.SearchResult[] {
+  const candidates: Array<Omit<Nvmd.SearchResult, "score"> & { text: string }> = [
+    ...installed.map((version) => ({
+      type: "installed" as const,
+      id: version,
+      label: `v${version}`,
+      description: version === current ? "Installed, current global version" : "Installed",
+      action: { name: "use-version" as const, payload: version },
+      text: `v${version}`
+    })),
+    ...versions
+      .filter(({ version }) => !installed.includes(version.slice(1)))
+      .map(({ version, lts, date }) => ({
+        type: "remote" as const,
+        id: version.slice(1),
+        label: version,
+        description: `${lts ? `LTS ${lts}` : "Current"}, released ${date}`,
+        action: { name: "install" as const, payload: version.slice(1) },
+        text: `${version} ${lts || ""}`
+      })),
+    ...projects.map(({ name, path, version }) => ({
+      type: "project" as const,
+      id: path,
+      label: name,
+      description: version ? `${path} (${version})` : path,
+      action: { name: "open-project" as const, payload: path },
+      text: `${name} ${path}`
+    })),
+    ...groups.map(({ name, desc, version }) => ({
+      type: "group" as const,
+      id: name,
+      label: name,
+      description: desc ? `${desc} (v${version})` : `v${version}`,
+      action: { name: "open-group" as const, payload: name },
+      text: `${name} ${desc || ""}`
+    }))
+  ];
+
+  return candidates
+    .map(({ text, ...result }) => ({ ...result, score: fuzzyScore(query, text) }))
+    .filter(({ score }) => score > 0)
+    .sort((a, b) => b.score - a.score)
+    .slice(0, limit);
+}
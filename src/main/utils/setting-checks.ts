@@ -0,0 +1,94 @@
+import { connect } from "node:net";
+import { join } from "node:path";
+import { ensureDir, remove, writeFile } from "fs-extra";
+import { fetchNodeWebsite } from "../deps/fetch-node-website";
+import { formatSize, getFreeSpace } from "./disk";
+
+import type { Options as FetchOptions } from "../deps/fetch-node-website";
+
+const TIMEOUT = 10 * 1000;
+// room to download and extract a recent release next to the installed ones
+const MIN_FREE_SPACE = 500 * 1024 * 1024;
+
+const check = async (
+  field: Nvmd.SettingCheck["field"],
+  run: () => Promise<string>
+): Promise<Nvmd.SettingCheck> => {
+  try {
+    return { field, ok: true, message: await run() };
+  } catch (err) {
+    return { field, ok: false, message: `${err.message || err}` };
+  }
+};
+
+// Request the headers of the release index, through the proxy when one is set.
+// Resolves with the time the mirror took to answer, in milliseconds.
+export async function pingMirror(fetchOpts: FetchOptions) {
+  const start = Date.now();
+  const request = await fetchNodeWebsite("index.json", {
+    ...fetchOpts,
+    method: "HEAD",
+    timeout: { request: TIMEOUT }
+  });
+  return new Promise<number>((resolve, reject) => {
+    request.once("response", () => {
+      resolve(Date.now() - start);
+      request.destroy();
+    });
+    request.once("error", reject);
+    request.resume();
+  });
+}
+
+// Only tells whether the proxy accepts connections, a proxy refusing to forward
+// the requests shows up in the mirror check.
+const connectProxy = ({ ip = "127.0.0.1", port = "8080" }: Nvmd.Proxy) =>
+  new Promise<void>((resolve, reject) => {
+    const socket = connect({ host: ip, port: Number(port), timeout: TIMEOUT });
+    socket.once("connect", () => {
+      socket.destroy();
+      resolve();
+    });
+    socket.once("timeout", () => {
+      socket.destroy();
+      reject(`${ip}:${port} did not answer within ${TIMEOUT / 1000}s`);
+    });
+    socket.once("error", reject);
+  });
+
+// Check the settings an install depends on, each one on its own so users can tell
+// which of them to fix before an install fails.
+export function validateSetting(
+  { directory, proxy }: { directory: string; proxy?: Nvmd.Proxy },
+  fetchOpts: FetchOptions
+): Promise<Nvmd.SettingCheck[]> {
+  return Promise.all([
+    check("mirror", async () => {
+      const time = await pingMirror(fetchOpts);
+      return `${fetchOpts.indexUrl || fetchOpts.mirror} answered in ${time}ms`;
+    }),
+    check("proxy", async () => {
+      if (!proxy?.enabled) return "No proxy is used";
+      await connectProxy(proxy);
+      return `${proxy.ip}:${proxy.port} accepts connections`;
+    }),
+    check("directory", async () => {
+      // permissions don't tell the whole story on Windows, write a file instead
+      const file = join(directory, `.nvmd-write-check-${process.pid}`);
+      await ensureDir(directory);
+      await writeFile(file, "");
+      await remove(file);
+      return `${directory} is writable`;
+    }),
+    check("disk-space", async () => {
+      const free = await getFreeSpace(directory);
+      if (free < MIN_FREE_SPACE) {
+        throw new Error(
+          `${formatSize(free)} available in ${directory}, ` +
+            `an install needs about ${formatSize(MIN_FREE_SPACE)}`
+        );
+      }
+      return `${formatSize(free)} available in ${directory}`;
+    })
+  ]);
+}
@@ -1,7 +1,9 @@
-import { pathExists, readJson, writeJson } from "fs-extra";
 import { app } from "electron";
 import { INSTALL_DIR, SETTING_JSONFILE } from "../constants";
 import { Closer, Themes } from "@src/types";
+import { readConfig, saveConfig } from "./backup";
+import { CommandError } from "./errors";
+import { enforcePolicy } from "./policy";
 
 export async function getSetting(): Promise<Nvmd.Setting> {
   const defaultSetting = {
@@ -14,12 +16,28 @@ export async function getSetting(): Promise<Nvmd.Setting> {
       enabled: false,
       ip: "127.0.0.1",
       port: "8080"
-    }
+    },
+    logLevel: "info" as Nvmd.LogLevel,
+    crashReport: false,
+    telemetry: false,
+    trash: false,
+    updateChannel: "stable" as Nvmd.UpdateChannel,
+    concurrency: 3,
+    editor: "code",
+    activation: "shim" as Nvmd.ActivationMode,
+    installScope: "user" as Nvmd.Setting["installScope"],
+    buildFromSource: false,
+    toolVersions: true,
+    syncEngines: false,
+    launchAtLogin: false,
+    startHidden: false,
+    prefetchLts: false,
+    pauseOnMetered: false,
+    cacheCleanup: { enabled: true, maxAgeDays: 7 } as Nvmd.CacheCleanup,
+    freshness: { enabled: true, notify: false } as Nvmd.FreshnessOptions
   };
 
-  if (!(await pathExists(SETTING_JSONFILE))) return defaultSetting;
-
-  const setting = await readJson(SETTING_JSONFILE, { throws: false });
+  const setting = { ...defaultSetting, ...(await readConfig(SETTING_JSONFILE, defaultSetting)) };
   if (!setting.directory) setting.directory = INSTALL_DIR;
   if (!setting.closer) setting.closer = Closer.Minimize;
   if (!setting.proxy)
@@ -28,12 +46,15 @@ export async function getSetting(): Promise<Nvmd.Setting> {
       ip: "127.0.0.1",
       port: "8080"
     };
-  return setting || defaultSetting;
+  return enforcePolicy(setting);
 }
 
 export async function setSetting(setting: Nvmd.Setting): Promise<void> {
   try {
-    await writeJson(SETTING_JSONFILE, setting);
-  } catch (err) {}
+    await saveConfig(SETTING_JSONFILE, setting);
+  } catch (err) {
+    // the form has to be reloaded with the other program's changes
+    if (err instanceof CommandError && err.kind === "conflict") throw err;
+  }
   return;
 }
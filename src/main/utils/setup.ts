@@ -0,0 +1,156 @@
+import { homedir } from "node:os";
+import { join } from "node:path";
+import { env, platform } from "node:process";
+import { appendFile, ensureFile, pathExists, readFile } from "fs-extra";
+import { BIN_DIR } from "../constants";
+import { MIRROR_PRESETS, testMirror } from "./mirrors";
+import { getMirrorStats } from "./mirror-health";
+import { logger } from "./logger";
+import { readPath, writeUserPath } from "./path-repair";
+
+import type { Options as FetchOptions } from "../deps/fetch-node-website";
+
+// Where each manager keeps its versions by default, its environment variable first.
+const MANAGERS: { name: string; env?: string; paths: string[] }[] =
+  platform === "win32"
+    ? [
+        { name: "nvm-windows", env: "NVM_HOME", paths: [join(env.APPDATA || "", "nvm")] },
+        { name: "fnm", env: "FNM_DIR", paths: [join(env.APPDATA || "", "fnm")] },
+        { name: "volta", env: "VOLTA_HOME", paths: [join(env.LOCALAPPDATA || "", "Volta")] },
+        { name: "nodist", env: "NODIST_PREFIX", paths: [] }
+      ]
+    : [
+        { name: "nvm", env: "NVM_DIR", paths: [join(homedir(), ".nvm")] },
+        {
+          name: "fnm",
+          env: "FNM_DIR",
+          paths: [join(homedir(), ".local", "share", "fnm"), join(homedir(), ".fnm")]
+        },
+        { name: "volta", env: "VOLTA_HOME", paths: [join(homedir(), ".volta")] },
+        { name: "n", env: "N_PREFIX", paths: ["/usr/local/n"] },
+        { name: "asdf", env: "ASDF_DATA_DIR", paths: [join(homedir(), ".asdf")] },
+        { name: "nodenv", env: "NODENV_ROOT", paths: [join(homedir(), ".nodenv")] }
+      ];
+
+// Other Node.js version managers installed, they may put their node ahead of the shims.
+export async function detectNodeManagers(): Promise<Nvmd.NodeManager[]> {
+  const found = await Promise.all(
+    MANAGERS.map(async ({ name, env: variable, paths }) => {
+      const candidates = [...(variable && env[variable] ? [env[variable]!] : []), ...paths];
+      for (const path of candidates) {
+        if (path && (await pathExists(path))) return { name, path };
+      }
+      return;
+    })
+  );
+  return found.filter((manager): manager is Nvmd.NodeManager => !!manager);
+}
+
+// The presets from the fastest to answer, unreachable ones last. Those that have
+// been flaky for the past week come after the reliable ones, however fast they are now.
+export async function rankMirrors(fetchOpts: FetchOptions) {
+  const tests = await Promise.all(MIRROR_PRESETS.map(({ url }) => testMirror(url, fetchOpts)));
+  const flaky = (await getMirrorStats()).filter(({ flaky }) => flaky).map(({ url }) => url);
+  const isFlaky = ({ url }: Nvmd.MirrorTest) => Number(flaky.includes(url));
+  return tests.sort(
+    (test1, test2) =>
+      Number(test2.ok) - Number(test1.ok) ||
+      isFlaky(test1) - isFlaky(test2) ||
+      (test1.time ?? Infinity) - (test2.time ?? Infinity)
+  );
+}
+
+const MARKER = "# added by nvmd";
+
+// Startup files of the shells in use: those that exist, and the one of `$SHELL`.
+async function getShellFiles() {
+  const files = [
+    { shell: "bash", file: join(homedir(), ".bashrc") },
+    { shell: "zsh", file: join(homedir(), ".zshrc") },
+    { shell: "fish", file: join(homedir(), ".config", "fish", "config.fish") }
+  ];
+  const exists = await Promise.all(files.map(({ file }) => pathExists(file)));
+  return files.filter(({ shell }, index) => exists[index] || env.SHELL?.endsWith(`/${shell}`));
+}
+
+const getPathLine = (shell: string) =>
+  shell === "fish"
+    ? `set -gx PATH ${JSON.stringify(BIN_DIR)} $PATH ${MARKER}`
+    : `export PATH=${JSON.stringify(BIN_DIR)}:"$PATH" ${MARKER}`;
+
+// Whether the shims directory is on the PATH of new shells.
+export async function isShellConfigured() {
+  if (platform === "win32") {
+    const paths = await readPath("user");
+    return paths.some((path) => path.toLowerCase() === BIN_DIR.toLowerCase());
+  }
+
+  const files = await getShellFiles();
+  const contents = await Promise.all(
+    files.map(({ file }) => readFile(file, "utf-8").catch(() => ""))
+  );
+  return !!files.length && contents.every((content) => content.includes(MARKER));
+}
+
+// Put the shims directory first on the PATH of new shells: in the user PATH on
+// Windows, with a marked line in each shell startup file elsewhere. Files already
+// holding the line are left alone. Resolves to the files (or `PATH`) changed.
+export async function configureShell(): Promise<string[]> {
+  if (platform === "win32") {
+    if (await isShellConfigured()) return [];
+
+    // read and written as stored, so `%VAR%` entries stay unexpanded
+    await writeUserPath([BIN_DIR, ...(await readPath("user")).filter(Boolean)]);
+    logger.info(`Added ${BIN_DIR} to the user PATH`);
+    return ["PATH"];
+  }
+
+  const changed: string[] = [];
+  for (const { shell, file } of await getShellFiles()) {
+    const content = await readFile(file, "utf-8").catch(() => "");
+    if (content.includes(MARKER)) continue;
+
+    await ensureFile(file);
+    const separator = content && !content.endsWith("\n") ? "\n" : "";
+    await appendFile(file, `${separator}${getPathLine(shell)}\n`);
+    logger.info(`Added ${BIN_DIR} to the PATH in ${file}`);
+    changed.push(file);
+  }
+  return changed;
+}
+
+export type SetupStep = {
+  id: Nvmd.SetupStepId;
+  // resolves to a description of what was done, `skipped` when there was nothing to do
+  run: () => Promise<{ skipped?: boolean; detail?: string }>;
+};
+
+// Run the steps in order, each reporting when it starts and how it ended. A failed
+// step stops the ones after it, they may depend on it. Running again is safe, steps
+// check what's there before changing anything.
+export async function runSetupSteps(
+  steps: SetupStep[],
+  {
+    signal,
+    onProgress
+  }: { signal?: AbortSignal; onProgress?: (progress: Nvmd.SetupStepResult) => void } = {}
+) {
+  const results: Nvmd.SetupStepResult[] = [];
+  for (const { id, run } of steps) {
+    signal?.throwIfAborted();
+    onProgress?.({ step: id, status: "running" });
+
+    let result: Nvmd.SetupStepResult;
+    try {
+      const { skipped, detail } = await run();
+      result = { step: id, status: skipped ? "skipped" : "done", ...(detail && { detail }) };
+    } catch (err) {
+      logger.error(`Setup step ${id} failed: ${err.message || err}`);
+      result = { step: id, status: "failed", error: `${err.message || err}` };
+    }
+    results.push(result);
+    onProgress?.(result);
+    if (result.status === "failed") break;
+  }
+  return results;
+}
@@ -0,0 +1,105 @@
+import { platform, arch } from "node:process";
+import { extname, join } from "node:path";
+import { copy, ensureDir, lstat, pathExists, readdir, remove, symlink } from "fs-extra";
+import { app } from "electron";
+import { BIN_DIR } from "../constants";
+import { getNodeBinPath } from "./launcher";
+import { getRuntimeShimNames } from "./runtimes";
+import { logger } from "./logger";
+
+// Always present, whatever the installed versions provide.
+const CORE_SHIMS = ["nvmd", "node", "npm", "npx", "corepack"];
+
+const sourceFile = (name: string) =>
+  app.isPackaged
+    ? join(process.resourcesPath, "assets", "sources", name)
+    : join(__dirname, "../../", "assets", "sources", name);
+
+// Executables shipped with each version and the bins of its global packages,
+// both end up in the version's bin folder (its root folder on Windows).
+async function getProvidedBins(directory: string) {
+  if (!(await pathExists(directory))) return new Set<string>();
+
+  const names = await Promise.all(
+    (await readdir(directory)).map(async (version) => {
+      const dir = getNodeBinPath(directory, version);
+      if (!(await pathExists(dir))) return [];
+
+      const files = await readdir(dir);
+      if (platform !== "win32") return files;
+
+      return files
+        .filter((file) => [".exe", ".cmd"].includes(extname(file).toLowerCase()))
+        .map((file) => file.slice(0, -extname(file).length));
+    })
+  );
+  return new Set(names.flat());
+}
+
+// The shim binary dispatches on the name it's invoked with, so each executable
+// is a link (a copy on Windows) of `nvmd`.
+async function createShim(name: string) {
+  if (platform !== "win32") {
+    await symlink(join(BIN_DIR, "nvmd"), join(BIN_DIR, name));
+    return;
+  }
+
+  await copy(sourceFile(`${arch}.exe`), join(BIN_DIR, `${name}.exe`));
+  name !== "node" && (await copy(sourceFile("temp.cmd"), join(BIN_DIR, `${name}.cmd`)));
+}
+
+async function getShims() {
+  if (!(await pathExists(BIN_DIR))) return new Set<string>();
+
+  const files = await readdir(BIN_DIR);
+  if (platform === "win32") {
+    return new Set(
+      files
+        .filter((file) => extname(file).toLowerCase() === ".exe")
+        .map((file) => file.slice(0, -4))
+    );
+  }
+
+  const links = await Promise.all(
+    files.map(async (file) => ((await lstat(join(BIN_DIR, file))).isSymbolicLink() ? file : ""))
+  );
+  return new Set(links.filter(Boolean));
+}
+
+// Create shims for the executables provided by the installed versions and remove
+// the ones nothing provides anymore. Names taken by a managed runtime (pnpm, bun...)
+// are its own shims, left to `syncRuntimeShims`.
+export async function syncShims(directory: string) {
+  await ensureDir(BIN_DIR);
+
+  const [provided, existing, claimed] = await Promise.all([
+    getProvidedBins(directory),
+    getShims(),
+    getRuntimeShimNames()
+  ]);
+  CORE_SHIMS.forEach((name) => provided.add(name));
+
+  for (const name of provided) {
+    if (existing.has(name) || name === "nvmd" || claimed.has(name)) continue;
+    try {
+      await createShim(name);
+      logger.info(`Created shim for ${name}`);
+    } catch (err) {
+      logger.warn(`Could not create shim for ${name}: ${err.message}`);
+    }
+  }
+
+  for (const name of existing) {
+    if (provided.has(name) || claimed.has(name)) continue;
+    try {
+      await Promise.all(
+        (platform === "win32" ? [`${name}.exe`, `${name}.cmd`] : [name]).map((file) =>
+          remove(join(BIN_DIR, file))
+        )
+      );
+      logger.info(`Removed shim for ${name}`);
+    } catch (err) {
+      logger.warn(`Could not remove shim for ${name}: ${err.message}`);
+    }
+  }
+}
@@ -0,0 +1,98 @@
+import { globalShortcut } from "electron";
+import { logger } from "./logger";
+
+export type ShortcutActions = {
+  toggleWindow: () => void;
+  quickSwitch: () => void;
+  useVersion: (version: string) => void;
+};
+
+const MODIFIERS = new Set([
+  "command",
+  "cmd",
+  "control",
+  "ctrl",
+  "commandorcontrol",
+  "cmdorctrl",
+  "alt",
+  "option",
+  "altgr",
+  "shift",
+  "super",
+  "meta"
+]);
+
+// Same spelling for aliases and any order of modifiers, so conflicts are detected
+// between `Ctrl+Shift+N` and `shift+control+n`.
+const normalize = (accelerator: string) => {
+  const keys = accelerator.split("+").map((key) => key.trim().toLowerCase());
+  const alias: Record<string, string> = {
+    cmd: "command",
+    ctrl: "control",
+    cmdorctrl: "commandorcontrol",
+    option: "alt"
+  };
+  const modifiers = keys.slice(0, -1).map((key) => alias[key] ?? key);
+  return [...new Set(modifiers)].sort().concat(keys.slice(-1)).join("+");
+};
+
+const isValid = (accelerator: string) => {
+  const keys = accelerator.split("+").map((key) => key.trim().toLowerCase());
+  const key = keys[keys.length - 1];
+  return (
+    keys.length > 1 &&
+    keys.slice(0, -1).every((modifier) => MODIFIERS.has(modifier)) &&
+    !!key &&
+    !MODIFIERS.has(key)
+  );
+};
+
+const getBindings = ({ toggleWindow, quickSwitch, versions = [] }: Nvmd.Shortcuts = {}) =>
+  [
+    { accelerator: toggleWindow, name: "toggleWindow" },
+    { accelerator: quickSwitch, name: "quickSwitch" },
+    ...versions.map(({ accelerator, version }) => ({ accelerator, name: `v${version}` }))
+  ].filter((binding): binding is { accelerator: string; name: string } => !!binding.accelerator);
+
+// Throws when an accelerator is malformed or bound to more than one action. Each
+// action needs a modifier: global shortcuts would otherwise swallow plain keys.
+export function validateShortcuts(shortcuts?: Nvmd.Shortcuts) {
+  const seen = new Map<string, string>();
+  for (const { accelerator, name } of getBindings(shortcuts)) {
+    if (!isValid(accelerator)) throw new Error(`Invalid shortcut "${accelerator}" for ${name}`);
+
+    const key = normalize(accelerator);
+    const other = seen.get(key);
+    if (other) throw new Error(`Shortcut "${accelerator}" is used by ${other} and ${name}`);
+    seen.set(key, name);
+  }
+}
+
+// Replaces the registered shortcuts, returns the accelerators taken by another app.
+export function registerShortcuts(shortcuts: Nvmd.Shortcuts | undefined, actions: ShortcutActions) {
+  globalShortcut.unregisterAll();
+
+  const failed: string[] = [];
+  const register = (accelerator: string | undefined, action: () => void) => {
+    if (!accelerator) return;
+    try {
+      if (!globalShortcut.register(accelerator, action)) failed.push(accelerator);
+    } catch (err) {
+      logger.warn(`Could not register the shortcut ${accelerator}: ${err}`);
+      failed.push(accelerator);
+    }
+  };
+
+  register(shortcuts?.toggleWindow, actions.toggleWindow);
+  register(shortcuts?.quickSwitch, actions.quickSwitch);
+  shortcuts?.versions?.forEach(({ accelerator, version }) =>
+    register(accelerator, () => actions.useVersion(version))
+  );
+
+  failed.length && logger.warn(`Shortcuts already in use: ${failed.join(", ")}`);
+  return failed;
+}
+
+export function unregisterShortcuts() {
+  globalShortcut.unregisterAll();
+}
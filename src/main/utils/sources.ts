@@ -0,0 +1,129 @@
+import { json } from "node:stream/consumers";
+import { allNodeVersions } from "../deps/all-node-versions";
+import { fetchNodeWebsite } from "../deps/fetch-node-website";
+import { logger } from "./logger";
+
+import type { Options } from "../deps/all-node-versions/options";
+
+// Where versions are listed and downloaded from. The official index (or the mirror
+// set in the settings) is the default, other sources are declared in the settings
+// by a manifest and can't do anything else than list versions: downloads always go
+// through the regular installer, checksums included.
+export interface VersionSource {
+  name: string;
+  // base URL or `{version}/{filename}` template serving the release files and
+  // their `SHASUMS256.txt`, in the nodejs.org layout
+  mirror: string;
+  index: string;
+  // releases in the format of the nodejs.org `index.json`
+  list(options: Options): Promise<Nvmd.Versions>;
+}
+
+export const OFFICIAL_SOURCE = "official";
+
+const sources = new Map<string, VersionSource>();
+let active: string = OFFICIAL_SOURCE;
+
+const isVersions = (value: unknown): value is Nvmd.Versions =>
+  Array.isArray(value) &&
+  value.every((item) => typeof item?.version === "string" && Array.isArray(item.files));
+
+// Value at the dot separated `path` of `value`, e.g. `data.releases` or `dist.0.tag`.
+const pick = (value: unknown, path?: string) =>
+  path
+    ? path
+        .split(".")
+        .reduce<unknown>((item, key) => (item as Record<string, unknown>)?.[key], value)
+    : value;
+
+// Read an index in another format through the manifest's `mapping`: where the list of
+// releases is, and where each field is in a release. Nothing of the source is run.
+function mapIndex(name: string, mapping: Nvmd.SourceMapping, index: unknown): Nvmd.Versions {
+  const releases = pick(index, mapping.releases);
+  if (!Array.isArray(releases)) {
+    throw new Error(`The ${name} source has no list of releases at ${mapping.releases}`);
+  }
+
+  const field = (release: unknown, key: keyof Nvmd.Version) =>
+    pick(release, mapping.fields?.[key] ?? key);
+  const versions = releases.map((release) => {
+    const version = `${field(release, "version") ?? ""}`;
+    return {
+      version: version && !version.startsWith("v") ? `v${version}` : version,
+      npm: `${field(release, "npm") ?? ""}`,
+      lts: (field(release, "lts") || false) as string | false,
+      date: `${field(release, "date") ?? ""}`,
+      v8: `${field(release, "v8") ?? ""}`,
+      security: !!field(release, "security"),
+      files: field(release, "files") as string[]
+    };
+  });
+  if (!isVersions(versions) || versions.some(({ version }) => version === "v")) {
+    throw new Error(`The ${name} source did not map to a list of versions`);
+  }
+  return versions;
+}
+
+export function createManifestSource(manifest: Nvmd.SourceManifest): VersionSource {
+  const { name, index, mirror, mapping } = manifest;
+  let cached: Nvmd.Versions | undefined;
+
+  return {
+    name,
+    mirror,
+    index,
+    async list({ fetch, force: _force, onProgress: _onProgress, ...options }) {
+      if (cached && !fetch) return cached;
+
+      const response = await fetchNodeWebsite("index.json", {
+        ...options,
+        mirror,
+        indexUrl: index
+      });
+      const content = await json(response as NodeJS.ReadableStream);
+
+      if (mapping) {
+        cached = mapIndex(name, mapping, content);
+      } else if (isVersions(content)) {
+        cached = content;
+      } else {
+        throw new Error(`The index of the ${name} source is not in the nodejs.org format`);
+      }
+      return cached;
+    }
+  };
+}
+
+// Sources of the settings replace the ones registered before. An unknown `source`
+// falls back to the official one.
+export function setSources(manifests: Nvmd.SourceManifest[] = [], source?: string) {
+  sources.clear();
+  manifests.forEach((manifest) => {
+    if (!manifest.name || manifest.name === OFFICIAL_SOURCE || !manifest.index) {
+      logger.warn(`Ignored the version source ${manifest.name || "without a name"}`);
+      return;
+    }
+    registerSource(createManifestSource(manifest));
+  });
+
+  active = source && sources.has(source) ? source : OFFICIAL_SOURCE;
+}
+
+export function registerSource(source: VersionSource) {
+  sources.set(source.name, source);
+}
+
+export const getSources = () => ({ sources: [OFFICIAL_SOURCE, ...sources.keys()], active });
+
+export const getActiveSource = () => sources.get(active);
+
+// Connection options pointing to the active source, to spread over the mirror ones.
+export const getSourceLocation = () => {
+  const source = getActiveSource();
+  return source ? { mirror: source.mirror, indexUrl: source.index } : {};
+};
+
+export function listVersions(options: Options) {
+  const source = getActiveSource();
+  return source ? source.list(options) : allNodeVersions(options);
+}
@@ -0,0 +1,20 @@
+import { pathExists, readJson } from "fs-extra";
+import { STARTUP_CACHE_JSONFILE } from "../constants";
+import { writeJsonAtomic } from "./file";
+import { logger } from "./logger";
+
+// What the last run found in the install `directory`, listed right away on launch
+// while the folders are scanned again in the background.
+type StartupCache = { directory?: string; installed?: string[] };
+
+export async function readStartupCache(directory: string): Promise<StartupCache> {
+  if (!(await pathExists(STARTUP_CACHE_JSONFILE))) return {};
+
+  const cache: StartupCache = (await readJson(STARTUP_CACHE_JSONFILE, { throws: false })) || {};
+  return cache.directory === directory ? cache : {};
+}
+
+export const writeStartupCache = (cache: StartupCache) =>
+  writeJsonAtomic(STARTUP_CACHE_JSONFILE, cache).catch((err) =>
+    logger.warn(`Could not write the startup cache: ${err.message}`)
+  );
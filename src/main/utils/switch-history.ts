@@ -0,0 +1,93 @@
+import { pathExists, readFile, writeFile } from "fs-extra";
+import { getGroups, updateGroups } from "./groups";
+import { logger } from "./logger";
+import { getProjects, updateProjects } from "./projects";
+import { removePath } from "./trash";
+import { samePath } from "./winpath";
+import { setCurrentVersion } from "./version";
+
+// Content of a file before a switch, undefined when it didn't exist.
+export type FileState = [file: string, content: string | undefined];
+
+const MAX_ENTRIES = 20;
+
+// Newest first. The files are the `.nvmdrc` and `package.json` of the switched
+// projects, as they were before.
+let history: { change: Nvmd.VersionSwitch; files: FileState[] }[] = [];
+
+const listeners: (() => void)[] = [];
+
+export function onSwitchHistoryChanged(listener: () => void) {
+  listeners.push(listener);
+}
+
+export const readFileStates = (files: string[]) =>
+  Promise.all(
+    files.map(
+      async (file): Promise<FileState> => [
+        file,
+        (await pathExists(file)) ? (await readFile(file)).toString() : void 0
+      ]
+    )
+  );
+
+export async function restoreFileStates(states: FileState[]) {
+  for (const [file, content] of states) {
+    if (content !== void 0) await writeFile(file, content);
+    else if (await pathExists(file)) await removePath(file);
+  }
+}
+
+export function recordSwitch(change: Omit<Nvmd.VersionSwitch, "at">, files: FileState[] = []) {
+  const entry = { change: { ...change, at: new Date().toISOString() }, files };
+  history = [entry, ...history].slice(0, MAX_ENTRIES);
+  listeners.forEach((listener) => listener());
+}
+
+export const getSwitchHistory = () => history.map(({ change }) => change);
+
+// Put the projects back on the version (or group) they had.
+async function restoreProjects(previous: NonNullable<Nvmd.VersionSwitch["projects"]>) {
+  const [projects, groups] = await Promise.all([getProjects(), getGroups()]);
+  const find = (path: string) => previous.find((project) => samePath(project.path, path));
+
+  await updateProjects(
+    projects.map((project) => {
+      const version = find(project.path)?.version;
+      return version === void 0 ? project : { ...project, version };
+    })
+  );
+
+  // switching to a version takes the project out of its group
+  let regrouped = false;
+  const newGroups = groups.map((group) => {
+    const paths = previous
+      .filter(({ path, version }) => version === group.name && !group.projects.includes(path))
+      .map(({ path }) => path);
+    if (!paths.length) return group;
+
+    regrouped = true;
+    return { ...group, projects: [...group.projects, ...paths] };
+  });
+  regrouped && (await updateGroups(newGroups));
+}
+
+// Restore the state before the last switch, including the `.nvmdrc` files. Changes
+// made to those files since are lost. Resolves with the undone switch.
+export async function undoLastSwitch(): Promise<Nvmd.VersionSwitch | undefined> {
+  const [entry] = history;
+  if (!entry) return;
+
+  const { change, files } = entry;
+  if (change.scope === "global") {
+    await setCurrentVersion(change.previous!, false);
+  } else {
+    await restoreFileStates(files);
+    await restoreProjects(change.projects ?? []);
+  }
+
+  history = history.slice(1);
+  listeners.forEach((listener) => listener());
+  logger.info(`Undid the switch to v${change.version}`);
+  return change;
+}
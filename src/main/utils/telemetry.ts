@@ -0,0 +1,55 @@
+import { arch, platform } from "node:process";
+import { pathExists, readJson, remove } from "fs-extra";
+import { app } from "electron";
+import { TELEMETRY_JSONFILE } from "../constants";
+import { writeJsonAtomic } from "./file";
+
+// Keep the local queue small, older events are dropped first.
+const MAX_EVENTS = 500;
+
+let enabled: boolean = false,
+  queue: Nvmd.TelemetryEvent[];
+
+async function getQueue() {
+  if (queue !== void 0) return queue;
+
+  queue = (await pathExists(TELEMETRY_JSONFILE))
+    ? (await readJson(TELEMETRY_JSONFILE, { throws: false })) || []
+    : [];
+  return queue;
+}
+
+export function setTelemetryEnabled(value: boolean = false) {
+  enabled = value;
+}
+
+// Events are coarse on purpose: no paths, project names or mirror urls are recorded.
+// Nothing is sent anywhere, the queue only lives on disk so the user can review it.
+export async function recordEvent(
+  name: Nvmd.TelemetryEvent["name"],
+  data: Nvmd.TelemetryEvent["data"] = {}
+) {
+  if (!enabled) return;
+
+  try {
+    const events = await getQueue();
+    events.push({ name, data, platform, arch, createAt: new Date().toISOString() });
+    queue = events.slice(-MAX_EVENTS);
+    await writeJsonAtomic(TELEMETRY_JSONFILE, queue);
+  } catch (err) {}
+  return;
+}
+
+export function recordLaunch() {
+  return recordEvent("launch", { appVersion: app.getVersion() });
+}
+
+export async function getTelemetryEvents() {
+  return [...(await getQueue())];
+}
+
+export async function purgeTelemetry() {
+  queue = [];
+  await remove(TELEMETRY_JSONFILE);
+  return;
+}
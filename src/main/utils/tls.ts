@@ -0,0 +1,109 @@
+import { execFile } from "node:child_process";
+import { rootCertificates } from "node:tls";
+import { platform } from "node:process";
+import { pathExists, readFile } from "fs-extra";
+import { logger } from "./logger";
+
+const PEM_REGEXP = /-----BEGIN CERTIFICATE-----[\s\S]+?-----END CERTIFICATE-----/gu;
+
+// Bundles shipped by the common Linux distributions.
+const LINUX_BUNDLES = [
+  "/etc/ssl/certs/ca-certificates.crt",
+  "/etc/pki/tls/certs/ca-bundle.crt",
+  "/etc/ssl/ca-bundle.pem",
+  "/etc/ssl/cert.pem"
+];
+
+const TLS_ERROR_CODES = new Set([
+  "UNABLE_TO_VERIFY_LEAF_SIGNATURE",
+  "UNABLE_TO_GET_ISSUER_CERT",
+  "UNABLE_TO_GET_ISSUER_CERT_LOCALLY",
+  "SELF_SIGNED_CERT_IN_CHAIN",
+  "DEPTH_ZERO_SELF_SIGNED_CERT",
+  "CERT_HAS_EXPIRED",
+  "CERT_UNTRUSTED",
+  "ERR_TLS_CERT_ALTNAME_INVALID"
+]);
+
+// `undefined` keeps Node's bundled root certificates.
+let certificates: string[] | undefined;
+
+const run = (file: string, args: string[]) =>
+  new Promise<string>((resolve, reject) => {
+    execFile(file, args, { maxBuffer: 32 * 1024 * 1024, windowsHide: true }, (err, stdout) =>
+      err ? reject(err) : resolve(stdout)
+    );
+  });
+
+// Root certificates trusted by the OS, which include the ones deployed by IT
+// for TLS interception. The fetch client otherwise only trusts Node's bundle.
+async function getSystemCertificates() {
+  if (platform === "darwin") {
+    return run("security", [
+      "find-certificate",
+      "-a",
+      "-p",
+      "/System/Library/Keychains/SystemRootCertificates.keychain",
+      "/Library/Keychains/System.keychain"
+    ]);
+  }
+
+  if (platform === "win32") {
+    const script = [
+      "Get-ChildItem Cert:\\LocalMachine\\Root, Cert:\\CurrentUser\\Root | ForEach-Object {",
+      "'-----BEGIN CERTIFICATE-----';",
+      "[Convert]::ToBase64String($_.RawData, 'InsertLineBreaks');",
+      "'-----END CERTIFICATE-----' }"
+    ].join(" ");
+    return run("powershell.exe", ["-NoProfile", "-Command", script]);
+  }
+
+  for (const bundle of LINUX_BUNDLES) {
+    if (await pathExists(bundle)) return (await readFile(bundle)).toString();
+  }
+  return "";
+}
+
+export async function loadCertificates(tls: Nvmd.Setting["tls"] = {}) {
+  const extra: string[] = [];
+
+  try {
+    if (tls.systemStore) extra.push(...((await getSystemCertificates()).match(PEM_REGEXP) || []));
+  } catch (err) {
+    logger.error(`Could not read the OS trust store: ${err.message}`);
+  }
+
+  try {
+    if (tls.caFile) {
+      const pem = (await readFile(tls.caFile)).toString().match(PEM_REGEXP) || [];
+      if (!pem.length) logger.warn(`${tls.caFile} contains no PEM certificate`);
+      extra.push(...pem);
+    }
+  } catch (err) {
+    logger.error(`Could not read the CA certificates at ${tls.caFile}: ${err.message}`);
+  }
+
+  certificates = extra.length ? [...rootCertificates, ...extra] : undefined;
+  extra.length && logger.info(`Trusting ${extra.length} additional CA certificates`);
+}
+
+export const getCertificates = () => certificates;
+
+// Certificates of a PEM bundle, files containing none are rejected.
+export async function readPemBundle(file: string) {
+  const pem = (await readFile(file)).toString().match(PEM_REGEXP) || [];
+  if (!pem.length) return Promise.reject(`${file} contains no PEM certificate`);
+  return pem;
+}
+
+// Explain certificate failures, which usually mean a proxy intercepts TLS.
+export function describeFetchError(err: Error & { code?: string; options?: { url?: URL } }) {
+  if (!err.code || !TLS_ERROR_CODES.has(err.code)) return `${err.name}: ${err.message}`;
+
+  const host = err.options?.url?.host;
+  return [
+    `TLS verification failed${host ? ` for ${host}` : ""} (${err.code}).`,
+    "If your network inspects TLS traffic, trust its CA certificate",
+    "(a PEM file or the OS trust store) in the settings."
+  ].join(" ");
+}
@@ -0,0 +1,47 @@
+import { join } from "node:path";
+import { platform } from "node:process";
+import { pathExists, readJson } from "fs-extra";
+import { PACKAGE_JSON_NAME } from "../constants";
+import { getChecksums } from "./verify";
+
+const getNpmPackage = (directory: string, version: string) =>
+  platform === "win32"
+    ? join(directory, version, "node_modules", "npm", PACKAGE_JSON_NAME)
+    : join(directory, version, "lib", "node_modules", "npm", PACKAGE_JSON_NAME);
+
+async function readNpmVersion(directory: string, version: string) {
+  const file = getNpmPackage(directory, version);
+  if (!(await pathExists(file))) return;
+
+  const { version: npm } = (await readJson(file, { throws: false })) || {};
+  return typeof npm === "string" ? npm : undefined;
+}
+
+// npm, V8 and OpenSSL of the installed versions, as listed by the release `index`.
+// Versions the index doesn't know (another source, offline) still get their npm.
+export async function getInstalledDetails(
+  directory: string,
+  versions: string[],
+  index: Nvmd.Versions
+): Promise<Nvmd.InstalledVersion[]> {
+  const releases = new Map(index.map((release) => [release.version.replace(/^v/, ""), release]));
+  const checksums = await getChecksums();
+
+  return Promise.all(
+    versions.map(async (version) => {
+      const release = releases.get(version);
+      const npm = await readNpmVersion(directory, version).catch(() => undefined);
+      return {
+        version,
+        npm: npm ?? release?.npm,
+        bundledNpm: release?.npm,
+        v8: release?.v8,
+        openssl: release?.openssl,
+        lts: release?.lts,
+        security: release?.security,
+        date: release?.date,
+        ...(checksums[version]?.source === "manual" && { source: "manual" as const })
+      };
+    })
+  );
+}
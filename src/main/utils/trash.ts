@@ -0,0 +1,39 @@
+import { pathExists, remove } from "fs-extra";
+import { shell } from "electron";
+import { logger } from "./logger";
+
+let useTrash: boolean = false;
+
+export function setTrashEnabled(value: boolean = false) {
+  useTrash = value;
+}
+
+// Delete a file or directory, moving it to the OS trash/recycle bin instead when
+// the `trash` setting is on (or `trash` says so). Falls back to a permanent delete if
+// the platform has no trash available (e.g. some Linux setups without a desktop environment).
+export async function removePath(path: string, trash: boolean = useTrash) {
+  if (trash) {
+    try {
+      await shell.trashItem(path);
+      return;
+    } catch (err) {
+      logger.warn(`Could not move ${path} to trash, deleting it instead: ${err.message}`);
+    }
+  }
+
+  await remove(path);
+}
+
+// Like `removePath`, for a path that may already be gone (its folder deleted or
+// unmounted). Resolves to whether there was something to remove.
+export async function removeIfExists(path: string, trash?: boolean) {
+  if (!(await pathExists(path))) return false;
+
+  try {
+    await removePath(path, trash);
+  } catch (err) {
+    if (err.code === "ENOENT") return false;
+    throw err;
+  }
+  return true;
+}
@@ -0,0 +1,113 @@
+import { stat } from "node:fs/promises";
+import { join } from "node:path";
+import { platform } from "node:process";
+import { getProjects } from "./projects";
+import { getGroups } from "./groups";
+import { getDiskUsage } from "./locations";
+import { getNetworkOverride } from "./project-env";
+import { getLockedVersions } from "./locks";
+import { getCurrentVersion } from "./version";
+import { canEmulateX64, toEmulatedVersion } from "./emulation";
+
+const normalize = (version: string) => version.replace(/^v/, "");
+
+// For each installed version, the projects and groups referencing it. A project in
+// a group stores the group name as its version and uses the group's version.
+export async function getVersionUsage(installed: string[]): Promise<Nvmd.VersionUsage[]> {
+  const [projects, groups] = await Promise.all([getProjects(), getGroups()]);
+  const groupVersions = new Map(groups.map(({ name, version }) => [name, normalize(version)]));
+
+  return installed.map((version) => ({
+    version,
+    projects: projects
+      .filter((project) => {
+        if (!project.version) return false;
+        const resolved = groupVersions.get(project.version) ?? normalize(project.version);
+        return resolved === version;
+      })
+      .map(({ name, path, version: group }) => ({
+        name,
+        path,
+        ...(group && groupVersions.has(group) && { group })
+      })),
+    groups: groups.filter((group) => normalize(group.version) === version).map(({ name }) => name)
+  }));
+}
+
+const NODE_FILE = platform === "win32" ? "node.exe" : join("bin", "node");
+
+// Last time the version's `node` executable was run, from its access time. File
+// systems mounted with `noatime` only report when it was installed.
+async function getLastUsedAt(path: string) {
+  try {
+    const { atime, mtime } = await stat(join(path, NODE_FILE));
+    return (atime > mtime ? atime : mtime).toISOString();
+  } catch {
+    return undefined;
+  }
+}
+
+// Installed versions no project, group or the global default refers to, the
+// candidates for a cleanup. Locked versions are reported but flagged.
+export async function getOrphanedVersions(
+  directory: string,
+  installed: string[]
+): Promise<Nvmd.OrphanedVersion[]> {
+  const [usages, disk, locked, current] = await Promise.all([
+    getVersionUsage(installed),
+    getDiskUsage(directory),
+    getLockedVersions(),
+    getCurrentVersion()
+  ]);
+  const sizes = new Map(disk.map((usage) => [usage.version, usage]));
+
+  const orphans = usages.filter(
+    ({ version, projects, groups }) =>
+      !projects.length && !groups.length && normalize(current) !== version
+  );
+  return Promise.all(
+    orphans.map(async ({ version }) => {
+      const { path = join(directory, version), size = 0 } = sizes.get(version) || {};
+      return {
+        version,
+        path,
+        size,
+        lastUsedAt: await getLastUsedAt(path),
+        locked: locked.includes(version)
+      };
+    })
+  );
+}
+
+// Distinct versions the `groups` and the projects at `paths` need, e.g. to install
+// them all after importing a project list on a new machine. Each one comes with the
+// mirror and proxy of the first group or project needing it. A project running emulated
+// needs the `<version>-x64` build as well.
+export async function getRequiredVersions({
+  groups: names = [],
+  paths = []
+}: {
+  groups?: string[];
+  paths?: string[];
+}) {
+  const [projects, groups] = await Promise.all([getProjects(), getGroups()]);
+  const groupVersions = new Map(groups.map(({ name, version }) => [name, normalize(version)]));
+  const required = new Map<string, { network?: Nvmd.NetworkOverride }>();
+  const add = (version: string, network?: Nvmd.NetworkOverride) => {
+    required.has(version) || required.set(version, { network });
+  };
+
+  names.forEach((name) => {
+    const group = groups.find((group) => group.name === name);
+    group && add(normalize(group.version), group.network);
+  });
+  projects
+    .filter((project) => paths.includes(project.path) && !!project.version)
+    .forEach((project) => {
+      const version = groupVersions.get(project.version!) ?? normalize(project.version!);
+      const network = getNetworkOverride(project, groups);
+      add(version, network);
+      project.emulateX64 && canEmulateX64() && add(toEmulatedVersion(version), network);
+    });
+  return required;
+}
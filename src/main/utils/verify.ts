@@ -0,0 +1,210 @@
+import { createHash } from "node:crypto";
+import { createReadStream } from "node:fs";
+import { tmpdir } from "node:os";
+import { join, sep } from "node:path";
+import { platform } from "node:process";
+import { Transform } from "node:stream";
+import { text } from "node:stream/consumers";
+import { pipeline } from "node:stream/promises";
+import { mkdtemp, realpath } from "node:fs/promises";
+import { createGunzip } from "node:zlib";
+import { pathExists, readJson, move, remove } from "fs-extra";
+import { extract as tarExtract } from "tar-fs";
+import { CHECKSUMS_JSONFILE, INSTALL_DIR } from "../constants";
+import fetchNodeWebsite from "../deps/fetch-node-website";
+import { getExpectedChecksum } from "../deps/get-node/checksum";
+import { writeJsonAtomic } from "./file";
+
+import type { Options as FetchNodeOptions } from "../deps/fetch-node-website";
+
+// Files that every usable installation is expected to ship.
+const EXPECTED_FILES =
+  platform === "win32"
+    ? ["node.exe", "npm.cmd", "npx.cmd", join("node_modules", "npm", "package.json")]
+    : [
+        join("bin", "node"),
+        join("bin", "npm"),
+        join("bin", "npx"),
+        join("lib", "node_modules", "npm", "package.json")
+      ];
+
+// Only the node executable is hashed, the rest of the tree is too large to hash on every check.
+export const HASHED_FILE = EXPECTED_FILES[0];
+
+// Per version: the checksum of `HASHED_FILE`, the platform string of the build,
+// when it lives outside of the install directory the `location` it's linked from,
+// and `source: "manual"` when it was unpacked by hand.
+type Checksums = Record<string, Record<string, string>>;
+
+export const getChecksums = async (): Promise<Checksums> => {
+  if (!(await pathExists(CHECKSUMS_JSONFILE))) return {};
+
+  return (await readJson(CHECKSUMS_JSONFILE, { throws: false })) || {};
+};
+
+const hashFile = (file: string) =>
+  text(createReadStream(file).pipe(createHash("sha256").setEncoding("hex")));
+
+// Where the version really is, a relocated one is checked at its recorded location.
+const getVersionPath = async (path: string, version: string) =>
+  (await getChecksums())[version]?.location ?? join(path, version);
+
+// Cache the checksum of a freshly installed version, so later checks can detect corruption.
+// A version linked into the install directory from elsewhere records where it really is.
+export async function recordInstallation({
+  path = INSTALL_DIR,
+  version,
+  arch,
+  source
+}: {
+  path?: string;
+  version: string;
+  arch?: string;
+  source?: "manual";
+}) {
+  try {
+    const checksums = await getChecksums();
+    const location = await realpath(join(path, version));
+    // what isn't known again is kept, e.g. the platform when a version is relinked
+    const previous = { ...checksums[version] };
+    delete previous.location;
+    checksums[version] = {
+      ...previous,
+      [HASHED_FILE]: await hashFile(join(path, version, HASHED_FILE)),
+      // platform string of the installed build, e.g. `win-arm64`
+      ...(arch && { platform: `${platform === "win32" ? "win" : platform}-${arch}` }),
+      ...(location !== (await realpath(path)) + sep + version && { location }),
+      ...(source && { source })
+    };
+    await writeJsonAtomic(CHECKSUMS_JSONFILE, checksums);
+  } catch (err) {}
+  return;
+}
+
+// Drop what's known about an uninstalled version.
+export async function forgetInstallation(version: string) {
+  const checksums = await getChecksums();
+  if (!checksums[version]) return;
+
+  delete checksums[version];
+  await writeJsonAtomic(CHECKSUMS_JSONFILE, checksums);
+}
+
+export async function verifyInstallation({
+  path = INSTALL_DIR,
+  version
+}: {
+  path?: string;
+  version: string;
+}): Promise<Nvmd.VerifyResult> {
+  const versionPath = await getVersionPath(path, version);
+  if (!(await pathExists(versionPath))) {
+    return { version, valid: false, missing: EXPECTED_FILES, mismatched: [], checked: false };
+  }
+
+  const exists = await Promise.all(
+    EXPECTED_FILES.map((file) => pathExists(join(versionPath, file)))
+  );
+  const missing = EXPECTED_FILES.filter((_, index) => !exists[index]);
+
+  const expected = (await getChecksums())[version];
+  const mismatched: string[] = [];
+  if (expected) {
+    for (const file of EXPECTED_FILES) {
+      if (!expected[file] || missing.includes(file)) continue;
+      if ((await hashFile(join(versionPath, file))) !== expected[file]) mismatched.push(file);
+    }
+  }
+
+  return {
+    version,
+    valid: !missing.length && !mismatched.length,
+    missing,
+    mismatched,
+    checked: !!expected
+  };
+}
+
+// Hash the node executable of the release's `.tar.gz`, the other platforms only publish
+// the checksum of archives. The archive is checked against it while it downloads, and
+// only its node is extracted. Undefined when the mirror has no checksum for it.
+const hashReleaseNode = async (version: string, arch: string, fetchOpts: FetchNodeOptions) => {
+  const name = `node-v${version}-${platform}-${arch}`;
+  const expected = await getExpectedChecksum(version, `${name}.tar.gz`, fetchOpts).catch(
+    () => undefined
+  );
+  if (!expected) return;
+
+  const staging = await mkdtemp(join(tmpdir(), "nvmd-verify-"));
+  try {
+    const archive = createHash("sha256");
+    await pipeline(
+      await fetchNodeWebsite(`v${version}/${name}.tar.gz`, fetchOpts),
+      new Transform({
+        transform(chunk: Buffer, _encoding, callback) {
+          archive.update(chunk);
+          callback(null, chunk);
+        }
+      }),
+      createGunzip(),
+      tarExtract(staging, { ignore: (_file, header) => header?.name !== `${name}/bin/node` })
+    );
+    if (archive.digest("hex") !== expected) {
+      return Promise.reject(`The archive of Node.js v${version} doesn't match its checksum`);
+    }
+    return await hashFile(join(staging, name, "bin", "node"));
+  } finally {
+    await remove(staging).catch(() => {});
+  }
+};
+
+// Compare the node executable with the one of the release. Windows releases publish
+// its checksum on its own (`win-x64/node.exe`), elsewhere it's hashed from the archive.
+// Undefined when the mirror has no checksum to compare with.
+export async function matchesReleaseChecksum({
+  path = INSTALL_DIR,
+  version,
+  arch,
+  fetchOpts
+}: {
+  path?: string;
+  version: string;
+  arch: string;
+  fetchOpts: FetchNodeOptions;
+}) {
+  const expected =
+    platform === "win32"
+      ? await getExpectedChecksum(version, `win-${arch}/node.exe`, fetchOpts).catch(
+          () => undefined
+        )
+      : await hashReleaseNode(version, arch, fetchOpts);
+  if (!expected) return;
+  return (await hashFile(join(await getVersionPath(path, version), HASHED_FILE))) === expected;
+}
+
+// Reinstall a version in place. The broken installation is kept aside until the
+// new one is in place, so a failed download leaves things as they were.
+// Projects pinning the version are not touched, their `.nvmdrc` stays the same.
+export async function repairInstallation(
+  { path = INSTALL_DIR, version, arch }: { path?: string; version: string; arch?: string },
+  install: () => Promise<unknown>
+) {
+  // a version installed elsewhere is repaired where it lives, behind its link
+  const linkPath = join(path, version);
+  const exists = await pathExists(linkPath);
+  const versionPath = exists ? await realpath(linkPath) : linkPath;
+  const backupPath = `${versionPath}.nvmd-repair`;
+
+  exists && (await move(versionPath, backupPath, { overwrite: true }));
+
+  try {
+    await install();
+  } catch (err) {
+    exists && (await move(backupPath, versionPath, { overwrite: true }));
+    return Promise.reject(err);
+  }
+
+  exists && (await remove(backupPath));
+  await recordInstallation({ path, version, arch });
+  return verifyInstallation({ path, version });
+}
@@ -0,0 +1,48 @@
+import { compare } from "semver";
+
+const DEFAULT_PAGE_SIZE = 50;
+const MAX_PAGE_SIZE = 500;
+
+const getTime = (date: string) => new Date(date).getTime() || 0;
+
+// Filter, sort and slice the release index, so the renderer gets one page of it
+// instead of the 700+ releases. `search` matches the version or the LTS codename.
+export function queryVersions(
+  versions: Nvmd.Versions,
+  {
+    search,
+    lts,
+    sort = "version",
+    order = "desc",
+    page = 1,
+    pageSize = DEFAULT_PAGE_SIZE
+  }: Nvmd.VersionQuery = {}
+): Nvmd.VersionPage {
+  const text = search?.trim().toLowerCase().replace(/^v(?=\d)/, "");
+  const filtered = versions.filter(
+    (version) =>
+      (!lts || !!version.lts) &&
+      (!text ||
+        version.version.slice(1).startsWith(text) ||
+        (!!version.lts && version.lts.toLowerCase().includes(text)))
+  );
+
+  const direction = order === "asc" ? 1 : -1;
+  const sorted =
+    sort === "date"
+      ? [...filtered].sort((version1, version2) =>
+          direction * (getTime(version1.date) - getTime(version2.date))
+        )
+      : [...filtered].sort(
+          (version1, version2) => direction * compare(version1.version, version2.version)
+        );
+
+  const size = Math.min(Math.max(1, Math.floor(pageSize) || DEFAULT_PAGE_SIZE), MAX_PAGE_SIZE);
+  const current = Math.max(1, Math.floor(page) || 1);
+  return {
+    items: sorted.slice((current - 1) * size, current * size),
+    total: sorted.length,
+    page: current,
+    pageSize: size
+  };
+}
@@ -0,0 +1,80 @@
+import { maxSatisfying, valid } from "semver";
+import { getReleaseLines } from "./release-lines";
+
+// nvm's names for the newest release
+const LATEST_ALIASES = ["node", "stable", "latest", "current"];
+const PARTIAL = /^v?\d+(\.\d+)?$/i;
+
+// The spelling written to `.nvmdrc`: exact versions without their `v`, partial ones
+// (`v20` -> `20`) and `lts/` specifiers kept as such but lowercased. Anything else is
+// returned trimmed, to be reported by `resolveVersionSpec`.
+export function normalizeVersionSpec(spec: string) {
+  const value = spec.trim();
+  const exact = valid(value);
+  if (exact) return exact;
+  if (PARTIAL.test(value)) return value.replace(/^v/i, "");
+
+  const lower = value.toLowerCase();
+  return lower.startsWith("lts/") || LATEST_ALIASES.includes(lower) ? lower : value;
+}
+
+const newest = (versions: string[], range: string) =>
+  maxSatisfying(versions, range) ?? undefined;
+
+// The version a specifier stands for, installed ones preferred for the partial and
+// `lts/` forms like `nvm use` does:
+//  - `20.11.1`, `v20.11.1`: that version
+//  - `20`, `20.11`: the newest of those, installed first
+//  - `lts/*`, `lts/iron`, `lts/-1`: the newest LTS line, the line of that codename,
+//    or the one before the newest
+//  - `node`, `latest`, `stable`, `current`: the newest release
+// `versions` is the release index, needed for the `lts/` and latest forms.
+export function resolveVersionSpec(
+  spec: string,
+  { installed, versions }: { installed: string[]; versions: Nvmd.Versions }
+): Nvmd.ResolvedVersionSpec {
+  const normalized = normalizeVersionSpec(spec);
+  const released = versions.map(({ version }) => version.replace(/^v/, ""));
+  const result = (kind: Nvmd.VersionSpecKind, version?: string, error?: string) => ({
+    spec,
+    normalized,
+    kind,
+    ...(version ? { version } : { error: error || `No release matches ${spec}` })
+  });
+
+  if (valid(normalized)) return result("exact", normalized);
+
+  if (PARTIAL.test(normalized)) {
+    return result("partial", newest(installed, normalized) ?? newest(released, normalized));
+  }
+
+  if (LATEST_ALIASES.includes(normalized)) {
+    const latest = released[0] ?? newest(installed, "*");
+    return result("latest", latest, `${spec} needs the release index, check the mirror`);
+  }
+
+  if (normalized.startsWith("lts/")) {
+    if (!versions.length) {
+      return result("lts", undefined, `${spec} needs the release index, check the mirror`);
+    }
+
+    const name = normalized.slice(4);
+    const lines = getReleaseLines(versions).filter(({ codename }) => !!codename);
+    const back = /^-\d+$/.test(name) ? Number(name.slice(1)) : undefined;
+    const line =
+      name === "*"
+        ? lines[0]
+        : back !== undefined
+          ? lines[back]
+          : lines.find(({ codename }) => codename!.toLowerCase() === name);
+    if (!line) return result("lts", undefined, `${spec} is not a known LTS line`);
+
+    return result("lts", newest(installed, `${line.major}`) ?? line.latestLts);
+  }
+
+  return result(
+    "unknown",
+    undefined,
+    `${spec} is not a version, a major like 20, lts/* or an LTS codename like lts/iron`
+  );
+}
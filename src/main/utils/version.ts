@@ -1,6 +1,10 @@
 import { join } from 'node:path';
-import { pathExists, readFile, remove, writeFile } from 'fs-extra';
+import { pathExists, readFile, remove } from 'fs-extra';
 import { APPDIR, INSTALL_DIR } from '../constants';
+import { removePath } from './trash';
+import { writeFileAtomic } from './file';
+import { linkCurrent, unlinkCurrent } from './activation';
+import { recordSwitch } from './switch-history';
 
 let version: string;
 
@@ -16,11 +20,21 @@ export async function getCurrentVersion(
   return version;
 }
 
-export async function setCurrentVersion(newVersion: string): Promise<void> {
+// `record` keeps the previous version in the switch history, to undo the switch.
+export async function setCurrentVersion(
+  newVersion: string,
+  record: boolean = true,
+): Promise<void> {
+  const previous = await getCurrentVersion();
   const file = join(APPDIR, 'default');
-  await writeFile(file, newVersion);
+  await writeFileAtomic(file, newVersion);
+  await linkCurrent(newVersion);
 
   version = newVersion;
+  record &&
+    previous &&
+    previous !== newVersion &&
+    recordSwitch({ scope: 'global', version: newVersion, previous });
 
   return;
 }
@@ -36,9 +50,10 @@ export async function uninstallVersion({
 }) {
   try {
     const versionPath = join(path, version);
-    await remove(versionPath);
+    await removePath(versionPath);
 
     current && (await remove(join(APPDIR, 'default')));
+    current && (await unlinkCurrent());
     return;
   } catch (err) {
     return Promise.reject(err.message);
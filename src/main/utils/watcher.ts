@@ -0,0 +1,71 @@
+import { watch } from "node:fs";
+import { basename, dirname, join } from "node:path";
+import { ensureDirSync, pathExists, readFile } from "fs-extra";
+import { debounce } from "lodash";
+import { GROUPS_JSONFILE, PROJECTS_JSONFILE, SETTING_JSONFILE } from "../constants";
+import { isOwnWrite } from "./file";
+import { logger } from "./logger";
+
+import type { FSWatcher } from "node:fs";
+
+export type ConfigName = "setting" | "projects" | "groups";
+
+const WATCHED: Record<string, ConfigName> = {
+  [SETTING_JSONFILE]: "setting",
+  [PROJECTS_JSONFILE]: "projects",
+  [GROUPS_JSONFILE]: "groups"
+};
+
+let watchers: FSWatcher[] = [];
+
+// Watch the config files for changes made outside of the app (hand edits, sync tools).
+// The directory is watched rather than the files, since atomic writes replace them.
+// Writes performed by the app itself are recognized and ignored.
+export function watchConfigs(onChange: (name: ConfigName) => Promise<void>) {
+  if (watchers.length) return;
+
+  const handlers = new Map<ConfigName, () => void>();
+  const getHandler = (name: ConfigName, file: string) => {
+    if (!handlers.has(name)) {
+      handlers.set(
+        name,
+        debounce(async () => {
+          try {
+            if (!(await pathExists(file))) return;
+
+            const content = (await readFile(file)).toString();
+            if (isOwnWrite(file, content)) return;
+
+            logger.info(`${basename(file)} was modified externally, reloading`);
+            await onChange(name);
+          } catch (err) {
+            logger.warn(`Could not reload ${file}: ${err.message}`);
+          }
+        }, 300)
+      );
+    }
+    return handlers.get(name)!;
+  };
+
+  // The config files may be spread over several directories (XDG on Linux)
+  const dirs = new Set(Object.keys(WATCHED).map((file) => dirname(file)));
+  for (const dir of dirs) {
+    try {
+      ensureDirSync(dir);
+      watchers.push(
+        watch(dir, (_event, filename) => {
+          const file = filename && join(dir, filename.toString());
+          const name = file && WATCHED[file];
+          name && getHandler(name, file)();
+        })
+      );
+    } catch (err) {
+      logger.warn(`Could not watch ${dir}: ${err.message}`);
+    }
+  }
+}
+
+export function unwatchConfigs() {
+  watchers.forEach((watcher) => watcher.close());
+  watchers = [];
+}
@@ -0,0 +1,86 @@
+import { createHmac } from "node:crypto";
+import { hostname } from "node:os";
+import { app, safeStorage } from "electron";
+import { got } from "got";
+import { pathExists, readFile, remove } from "fs-extra";
+import { WEBHOOK_SECRET_FILE } from "../constants";
+import { writeFileAtomic } from "./file";
+import { logger } from "./logger";
+
+// Each payload is signed with HMAC-SHA256 of the raw body, like GitHub webhooks:
+// `X-Nvmd-Signature: sha256=<hex>`. The secret is encrypted with the OS keychain
+// and never written to `setting.json`.
+let webhook: Nvmd.Webhook = {},
+  secret: string = "";
+
+export function setWebhook(value: Nvmd.Webhook = {}) {
+  webhook = value;
+}
+
+export const hasWebhookSecret = () => !!secret;
+
+export async function loadWebhookSecret() {
+  if (!(await pathExists(WEBHOOK_SECRET_FILE))) return;
+
+  try {
+    secret = safeStorage.decryptString(await readFile(WEBHOOK_SECRET_FILE));
+  } catch (err) {
+    logger.error(`Could not read the webhook secret: ${err.message}`);
+  }
+}
+
+export async function setWebhookSecret(value: string) {
+  if (!value.trim()) {
+    secret = "";
+    await remove(WEBHOOK_SECRET_FILE);
+    return;
+  }
+
+  if (!safeStorage.isEncryptionAvailable()) {
+    return Promise.reject("No OS keychain is available to store the secret securely");
+  }
+
+  await writeFileAtomic(WEBHOOK_SECRET_FILE, safeStorage.encryptString(value.trim()));
+  secret = value.trim();
+}
+
+async function post(event: Nvmd.WebhookEvent, data: Record<string, string>) {
+  if (!webhook.url) return Promise.reject("No webhook URL is set");
+
+  const body = JSON.stringify({
+    event,
+    data,
+    host: hostname(),
+    appVersion: app.getVersion(),
+    createAt: new Date().toISOString()
+  });
+  const headers: Record<string, string> = {
+    "content-type": "application/json",
+    "user-agent": `nvm-desktop/${app.getVersion()}`,
+    "x-nvmd-event": event
+  };
+  if (secret) {
+    const signature = createHmac("sha256", secret).update(body).digest("hex");
+    headers["x-nvmd-signature"] = `sha256=${signature}`;
+  }
+
+  await got.post(webhook.url, {
+    body,
+    headers,
+    timeout: { request: 10 * 1000 },
+    retry: { limit: 2, methods: ["POST"] }
+  });
+}
+
+// Fire and forget, a webhook that is down must never fail the operation itself.
+export function notifyWebhook(event: Nvmd.WebhookEvent, data: Record<string, string>) {
+  if (!webhook.enabled || !webhook.url) return;
+  if (webhook.events?.length && !webhook.events.includes(event)) return;
+
+  post(event, data).catch((err) =>
+    logger.warn(`Could not deliver the ${event} webhook: ${err.message || err}`)
+  );
+}
+
+// Send a `ping` right away so the receiving end can be checked from the settings.
+export const testWebhook = () => post("ping", {});
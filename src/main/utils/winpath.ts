@@ -0,0 +1,53 @@
+import { resolve, sep } from "node:path";
+import { platform } from "node:process";
+
+// Windows paths come in several spellings for the same folder: `C:\dev\app`,
+// `\\?\C:\dev\app` (long-path form), `\\server\share\app` (UNC) and
+// `\\?\UNC\server\share\app`. Node's fs handles long paths by itself, these
+// helpers are for comparing paths and for handing them to external commands.
+
+const LONG_PREFIX = "\\\\?\\";
+const LONG_UNC_PREFIX = "\\\\?\\UNC\\";
+
+export const isUncPath = (path: string) =>
+  platform === "win32" && (path.startsWith(LONG_UNC_PREFIX) || /^\\\\[^?.\\]/.test(path));
+
+// Regular form of `path`: prefixes stripped, resolved, without trailing separator.
+// Unicode names are composed (NFC), macOS hands out decomposed ones for the same folder.
+export function normalizePath(path: string) {
+  const composed = path.normalize("NFC");
+  if (platform !== "win32") return composed.length > 1 ? composed.replace(/\/+$/, "") : composed;
+
+  let normalized = composed;
+  if (normalized.startsWith(LONG_UNC_PREFIX)) {
+    normalized = `\\\\${normalized.slice(LONG_UNC_PREFIX.length)}`;
+  } else if (normalized.startsWith(LONG_PREFIX)) {
+    normalized = normalized.slice(LONG_PREFIX.length);
+  }
+
+  normalized = resolve(normalized);
+  // keep the separator of drive roots (`C:\`) and share roots
+  return /^[a-zA-Z]:\\$/.test(normalized) ? normalized : normalized.replace(/\\+$/, "");
+}
+
+// Long-path form for commands that are limited to MAX_PATH (`rmdir`, `del`).
+export function toLongPath(path: string) {
+  if (platform !== "win32") return path;
+
+  const normalized = normalizePath(path);
+  return isUncPath(normalized)
+    ? `${LONG_UNC_PREFIX}${normalized.slice(2)}`
+    : `${LONG_PREFIX}${normalized}`;
+}
+
+export const samePath = (a: string, b: string) =>
+  platform === "win32"
+    ? normalizePath(a).toLowerCase() === normalizePath(b).toLowerCase()
+    : normalizePath(a) === normalizePath(b);
+
+export const isInside = (path: string, parent: string) => {
+  const [child, root] = [normalizePath(path), normalizePath(parent)].map((value) =>
+    platform === "win32" ? value.toLowerCase() : value
+  );
+  return child === root || child.startsWith(root.endsWith(sep) ? root : `${root}${sep}`);
+};
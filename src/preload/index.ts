@@ -16,6 +16,32 @@ type OnProjectUpdate = (args: {
   version?: string;
 }) => void;
 type OnMigrationError = () => void;
+type OnBuildOutputCallback = (id: string, line: string) => void;
+type OnScriptOutputCallback = (id: string, line: string, stream: "stdout" | "stderr") => void;
+type OnInstallEventCallback = (event: Nvmd.InstallEvent) => void;
+type OnInterruptedCallback = (installs: Nvmd.InterruptedInstall[]) => void;
+type OnQuarantinedCallback = (entries: Nvmd.QuarantinedEntry[]) => void;
+type OnAdoptedCallback = (installs: Nvmd.AdoptedInstalls) => void;
+type OnLowDiskCallback = (status: Nvmd.DiskStatus) => void;
+type OnNetworkChangedCallback = (state: Nvmd.NetworkState) => void;
+type OnChecksumMismatchCallback = (mismatch: Nvmd.ChecksumMismatch) => void;
+type OnInstalledVersionsUpdatedCallback = (versions: string[]) => void;
+type OnVersionsUpdatedCallback = () => void;
+type OnRefreshProgressCallback = (id: string, progress: Nvmd.RefreshProgress) => void;
+type OnSetupProgressCallback = (id: string, progress: Nvmd.SetupStepResult) => void;
+type OnSettingsChangedCallback = (
+  setting: Nvmd.Setting & { locked: (keyof Nvmd.Setting)[]; localeMessages: I18n.Message }
+) => void;
+
+// Commands resolve with their `CommandError` under this key (see `withCommandErrors`),
+// it's turned back into a rejection with the error itself, `kind` included.
+const COMMAND_ERROR_KEY = "nvmdCommandError";
+
+const invoke = async (channel: string, ...args: unknown[]) => {
+  const result = await ipcRenderer.invoke(channel, ...args);
+  if (result?.[COMMAND_ERROR_KEY]) return Promise.reject(result[COMMAND_ERROR_KEY]);
+  return result;
+};
 
 let onCheckUpdateResult: OnCheckUpdateResultCallback | null = null,
   onUpdateProgress: OnUpdateProgressCallback | null = null,
@@ -23,7 +49,21 @@ let onCheckUpdateResult: OnCheckUpdateResultCallback | null = null,
   onThemeChanged: OnThemeChangedCallback | null = null,
   onCurVersionChange: OnCurVersionChange | null = null,
   onProjectUpdate: OnProjectUpdate | null = null,
-  onMigrationError: OnMigrationError | null = null;
+  onMigrationError: OnMigrationError | null = null,
+  onBuildOutput: OnBuildOutputCallback | null = null,
+  onInstallEvent: OnInstallEventCallback | null = null,
+  onQuarantined: OnQuarantinedCallback | null = null,
+  onAdopted: OnAdoptedCallback | null = null,
+  onInterrupted: OnInterruptedCallback | null = null,
+  onSettingsChanged: OnSettingsChangedCallback | null = null,
+  onLowDisk: OnLowDiskCallback | null = null,
+  onNetworkChanged: OnNetworkChangedCallback | null = null,
+  onChecksumMismatch: OnChecksumMismatchCallback | null = null,
+  onInstalledVersionsUpdated: OnInstalledVersionsUpdatedCallback | null = null,
+  onVersionsUpdated: OnVersionsUpdatedCallback | null = null,
+  onRefreshProgress: OnRefreshProgressCallback | null = null,
+  onSetupProgress: OnSetupProgressCallback | null = null,
+  onScriptOutput: OnScriptOutputCallback | null = null;
 
 ipcRenderer.on("update-available", (_event, info: UpdateInfo) => {
   onCheckUpdateResult?.(info);
@@ -45,6 +85,21 @@ ipcRenderer.on("get-node:progress", (_event, id: string, progress: Nvmd.Progress
   onProgress?.(id, progress);
 });
 
+ipcRenderer.on("install:lifecycle", (_event, event: Nvmd.InstallEvent) => {
+  onInstallEvent?.(event);
+});
+
+ipcRenderer.on("get-node:output", (_event, id: string, line: string) => {
+  onBuildOutput?.(id, line);
+});
+
+ipcRenderer.on(
+  "project-script:output",
+  (_event, id: string, line: string, stream: "stdout" | "stderr") => {
+    onScriptOutput?.(id, line, stream);
+  }
+);
+
 ipcRenderer.on("native-theme:changed", (_event, theme: string) => {
   onThemeChanged?.(theme);
 });
@@ -67,15 +122,75 @@ ipcRenderer.on(
   }
 );
 
+ipcRenderer.on("installed-versions-updated", (_event, versions: string[]) => {
+  onInstalledVersionsUpdated?.(versions);
+});
+
+ipcRenderer.on("versions-updated", () => {
+  onVersionsUpdated?.();
+});
+
 ipcRenderer.on("migration-error", (_evnet) => {
   onMigrationError?.();
 });
 
+ipcRenderer.on("installs-interrupted", (_event, installs: Nvmd.InterruptedInstall[]) => {
+  onInterrupted?.(installs);
+});
+
+ipcRenderer.on("versions-quarantined", (_event, entries: Nvmd.QuarantinedEntry[]) => {
+  onQuarantined?.(entries);
+});
+
+ipcRenderer.on("versions-adopted", (_event, installs: Nvmd.AdoptedInstalls) => {
+  onAdopted?.(installs);
+});
+
+ipcRenderer.on("disk-low", (_event, status: Nvmd.DiskStatus) => {
+  onLowDisk?.(status);
+});
+
+ipcRenderer.on("network-changed", (_event, state: Nvmd.NetworkState) => {
+  onNetworkChanged?.(state);
+});
+
+ipcRenderer.on("install:checksum-mismatch", (_event, mismatch: Nvmd.ChecksumMismatch) => {
+  onChecksumMismatch?.(mismatch);
+});
+
+ipcRenderer.on(
+  "versions-refresh:progress",
+  (_event, id: string, progress: Nvmd.RefreshProgress) => {
+    onRefreshProgress?.(id, progress);
+  }
+);
+
+ipcRenderer.on("setup-wizard:progress", (_event, id: string, progress: Nvmd.SetupStepResult) => {
+  onSetupProgress?.(id, progress);
+});
+
+ipcRenderer.on(
+  "settings-changed",
+  (_event, setting: Nvmd.Setting & { localeMessages: I18n.Message }) => {
+    onSettingsChanged?.(setting);
+  }
+);
+
 const electronHandler = {
   platform: process.platform,
-  arch: process.arch,
+  // kind, code and context of an error thrown by a command, for localized messages
+  describeError: (err: unknown): Nvmd.CommandError => {
+    if ((err as Nvmd.CommandError)?.kind) return err as Nvmd.CommandError;
+    return { kind: "failed", message: err instanceof Error ? err.message : String(err) };
+  },
+  // native architecture, also when the app itself runs emulated
+  arch: ipcRenderer.sendSync("get-native-arch") as string,
   version: ipcRenderer.sendSync("get-app-version") as string,
 
+  // from the quick switch window
+  openMainWindow: () => {
+    ipcRenderer.send("window:open-main");
+  },
   windowClose: () => {
     ipcRenderer.send("window:close");
   },
@@ -83,8 +198,9 @@ const electronHandler = {
     ipcRenderer.send("window:minimize");
   },
 
-  checkForUpdates: () => ipcRenderer.invoke("check-for-updates") as Promise<UpdateInfo | null>,
-  comfirmUpdate: () => ipcRenderer.invoke("confirm-update") as Promise<string[]>,
+  checkForUpdates: () => invoke("check-for-updates") as Promise<UpdateInfo | null>,
+  // tells whether only the changed blocks of the installer were downloaded
+  comfirmUpdate: () => invoke("confirm-update") as Promise<Nvmd.UpdateDownload>,
   makeUpdateNow() {
     ipcRenderer.send("make-update-now");
   },
@@ -97,24 +213,231 @@ const electronHandler = {
 
   getSettingData: () =>
     ipcRenderer.sendSync("setting-data-get") as Nvmd.Setting & {
+      // fields set by the policy, read-only
+      locked: (keyof Nvmd.Setting)[];
       localeMessages: I18n.Message;
     },
+  // resolves to what couldn't be applied to every version, e.g. the npm cache
   updateSettingData: (setting: Nvmd.Setting) =>
-    ipcRenderer.invoke("setting-data-set", setting) as Promise<void>,
+    invoke("setting-data-set", setting) as Promise<Nvmd.CommandWarning[]>,
+  // pass the unsaved values of the form to check them before saving
+  validateSetting: (setting?: Partial<Nvmd.Setting>) =>
+    invoke("setting-validate", setting) as Promise<Nvmd.SettingCheck[]>,
+  getPolicy: () =>
+    invoke("policy-get") as Promise<{
+      path: string;
+      policy: Nvmd.Policy;
+      locked: (keyof Nvmd.Setting)[];
+    }>,
+  // `mirror` is the id of a preset or a URL
+  getMirrorPresets: () => invoke("mirror-presets") as Promise<Nvmd.MirrorPreset[]>,
+  testMirror: (mirror: string) =>
+    invoke("mirror-test", mirror) as Promise<Nvmd.MirrorTest>,
+  // from the tests and downloads of the last `days` (7 by default)
+  getMirrorStats: (days?: number) =>
+    invoke("mirror-stats", days) as Promise<Nvmd.MirrorStats[]>,
+  applyMirror: (mirror: string) =>
+    invoke("mirror-apply", mirror) as Promise<Nvmd.MirrorPreset[]>,
+  // the setting changed outside of the settings form, e.g. settings.json edited by hand
+  onRegistSettingsChanged: (callback: OnSettingsChangedCallback | null) => {
+    onSettingsChanged = callback;
+  },
   getLocaleData: () => ipcRenderer.sendSync("locale-data") as I18n.Message,
+  getLocales: () =>
+    invoke("locales-get") as Promise<Array<{ locale: string; name: string }>>,
+  setLocale: (locale: string) => invoke("locale-set", locale) as Promise<I18n.Message>,
+
+  tailLogs: (lines?: number) => invoke("logs-tail", lines) as Promise<string[]>,
+  exportLogs: (args: { title: string }) =>
+    invoke("logs-export", args) as Promise<{ canceled: boolean; path?: string }>,
+  exportDiagnostics: (args: { title: string }) =>
+    invoke("diagnostics-export", args) as Promise<{ canceled: boolean; path?: string }>,
+  // the shims ahead of other node.exe, asks for elevation when the machine PATH changes
+  repairPath: (dryRun: boolean = false) =>
+    invoke("path-repair", dryRun) as Promise<Nvmd.PathRepair>,
+
+  getCrashReports: () =>
+    invoke("crash-reports-get") as Promise<{
+      reports: Nvmd.CrashReport[];
+      minidumps: string[];
+    }>,
+  submitCrashReport: (id: string) => invoke("crash-report-submit", id) as Promise<void>,
+  clearCrashReports: () => invoke("crash-reports-clear") as Promise<void>,
+
+  getTelemetryEvents: () =>
+    invoke("telemetry-get") as Promise<Nvmd.TelemetryEvent[]>,
+  purgeTelemetry: () => invoke("telemetry-purge") as Promise<void>,
+
+  // `force` refreshes even if the index was just fetched
+  getAllNodeVersions: async (arg?: { id?: string; fetch?: boolean; force?: boolean }) =>
+    invoke("all-node-versions", arg) as Promise<Nvmd.Versions>,
+  queryNodeVersions: (
+    arg?: Nvmd.VersionQuery & { id?: string; fetch?: boolean; force?: boolean }
+  ) => invoke("node-versions-query", arg) as Promise<Nvmd.VersionPage>,
+  // `official` and the sources declared in the settings
+  getVersionSources: () =>
+    invoke("version-sources") as Promise<{ sources: string[]; active: string }>,
 
-  getAllNodeVersions: async (arg?: { id?: string; fetch?: boolean }) =>
-    ipcRenderer.invoke("all-node-versions", arg) as Promise<Nvmd.Versions>,
+  // majors with their LTS codename, `line` is either of them e.g. `20` or `iron`
+  getReleaseLines: () => invoke("release-lines") as Promise<Nvmd.ReleaseLine[]>,
+  getLatestOfLine: (line: string | number) =>
+    invoke("latest-of-line", line) as Promise<string>,
 
   getInstalledNodeVersions: async (refresh: boolean = false): Promise<string[]> =>
-    ipcRenderer.invoke("installed-node-versions", refresh),
+    invoke("installed-node-versions", refresh),
+  getInstalledNodeVersionsDetails: () =>
+    invoke("installed-node-versions-details") as Promise<Nvmd.InstalledVersion[]>,
 
-  getNode: async (args: { id: string; arch: string; version: string }) =>
-    ipcRenderer.invoke("get-node", args),
-  controllerAbort: (id: string) => ipcRenderer.invoke("controller:abort", id),
+  // `source` builds the version from source, defaults to the `buildFromSource` setting
+  // `location` installs the version in another folder, e.g. on a secondary drive
+  getNode: async (args: {
+    id: string;
+    arch: string;
+    version: string;
+    source?: boolean;
+    location?: string;
+  }) =>
+    invoke("get-node", args) as Promise<{
+      version: string;
+      path: string;
+      arch: string;
+      warning?: string;
+    }>,
+  searchEverything: (query: string, limit?: number) =>
+    invoke("search-everything", query, limit) as Promise<Nvmd.SearchResult[]>,
+  controllerAbort: (id: string) => invoke("controller:abort", id),
+  // any operation started with an `id`: installs, scans, migrations, cleanups, benchmarks
+  cancelOperation: (id: string) => invoke("operation-cancel", id) as Promise<boolean>,
+  getRunningOperations: () => invoke("operations-running") as Promise<string[]>,
+  getActiveInstalls: () => invoke("active-installs") as Promise<Nvmd.ActiveInstall[]>,
+  // to restore the progress of an install after the window was reloaded
+  getInstallState: (version: string) =>
+    invoke("install-state", version) as Promise<Nvmd.InstallState | undefined>,
+  getDiskUsage: () => invoke("versions-disk-usage") as Promise<Nvmd.DiskUsage[]>,
+  // a terminal running the `node` REPL of the version, without switching to it
+  launchRepl: (version: string) => invoke("launch-repl", version) as Promise<void>,
+  // `script` is a file run by each version, `runs` times after a warm-up run
+  benchmarkVersions: (args: { id?: string; versions: string[]; script?: string; runs?: number }) =>
+    invoke("versions-benchmark", args) as Promise<Nvmd.BenchmarkResult[]>,
+  // when leftover download folders are next removed from the cache, and the last run
+  getCacheCleanupStatus: () =>
+    invoke("cache-cleanup-status") as Promise<Nvmd.CacheCleanupStatus>,
+  getPrefetchStatus: () => invoke("prefetch-status") as Promise<Nvmd.PrefetchStatus>,
+  // installs wait while offline, or on a metered connection with `pauseOnMetered`
+  getNetworkState: () => invoke("network-state") as Promise<Nvmd.NetworkState>,
+  onRegistNetworkChanged: (callback: OnNetworkChangedCallback | null) => {
+    onNetworkChanged = callback;
+  },
+  getGroupsFreshness: (refresh: boolean = false) =>
+    invoke("groups-freshness", refresh) as Promise<Nvmd.FreshnessReport>,
+  cleanupCache: (id?: string) =>
+    invoke("cache-cleanup-run", id) as Promise<
+      Nvmd.CommandResult<Nvmd.CacheCleanupRun>
+    >,
+  // installs the app quit in the middle of, reported at launch
+  onRegistInterrupted: (callback: OnInterruptedCallback | null) => {
+    onInterrupted = callback;
+  },
+  getInterruptedInstalls: () =>
+    invoke("installs-interrupted") as Promise<Nvmd.InterruptedInstall[]>,
+  // downloads the release again from the start
+  retryInstall: (args: { id: string; version: string }) =>
+    invoke("install-retry", args) as Promise<{ path: string; arch: string }>,
+  dismissInstall: (version: string) =>
+    invoke("install-dismiss", version) as Promise<Nvmd.InterruptedInstall[]>,
+  // broken installs the launch scan moved away, to reinstall or delete
+  onRegistQuarantined: (callback: OnQuarantinedCallback | null) => {
+    onQuarantined = callback;
+  },
+  getQuarantined: () => invoke("quarantine-list") as Promise<Nvmd.QuarantinedEntry[]>,
+  // versions unpacked by hand into the install directory, adopted or not by a scan
+  onRegistAdopted: (callback: OnAdoptedCallback | null) => {
+    onAdopted = callback;
+  },
+  deleteQuarantined: (entry: string) =>
+    invoke("quarantine-delete", entry) as Promise<Nvmd.QuarantinedEntry[]>,
+  repairQuarantined: (args: { id: string; entry: string }) =>
+    invoke("quarantine-repair", args) as Promise<Nvmd.QuarantinedEntry[]>,
+  // the versions listed from the startup cache differed from those on disk
+  onRegistInstalledVersionsUpdated: (callback: OnInstalledVersionsUpdatedCallback | null) => {
+    onInstalledVersionsUpdated = callback;
+  },
+  // the version index was fetched again, query it anew
+  onRegistVersionsUpdated: (callback: OnVersionsUpdatedCallback | null) => {
+    onVersionsUpdated = callback;
+  },
+  // a download was quarantined, and retried from another mirror if `retryMirror` is set
+  onRegistChecksumMismatch: (callback: OnChecksumMismatchCallback | null) => {
+    onChecksumMismatch = callback;
+  },
+  // each version of a `refreshVersions` run, under its operation id
+  onRegistRefreshProgress: (callback: OnRefreshProgressCallback | null) => {
+    onRefreshProgress = callback;
+  },
+  // each step of a `runSetupWizard`, when it starts and how it ended
+  onRegistSetupProgress: (callback: OnSetupProgressCallback | null) => {
+    onSetupProgress = callback;
+  },
+  // free space of the install volume, counting what the running installs will take
+  onRegistLowDisk: (callback: OnLowDiskCallback | null) => {
+    onLowDisk = callback;
+  },
+  getDiskStatus: () => invoke("disk-status") as Promise<Nvmd.DiskStatus>,
+  getNpmCacheInfo: () => invoke("npm-cache-info") as Promise<Nvmd.NpmCacheInfo>,
+  clearNpmCache: () => invoke("npm-cache-clear") as Promise<Nvmd.NpmCacheInfo>,
+  // projects and groups referencing each installed version
+  getVersionUsage: () => invoke("version-usage") as Promise<Nvmd.VersionUsage[]>,
+  // installed versions nothing refers to, with their size and last use
+  getOrphanedVersions: () =>
+    invoke("versions-orphaned") as Promise<Nvmd.OrphanedVersion[]>,
+  // locked versions can't be uninstalled until unlocked
+  getLockedVersions: () => invoke("locked-versions") as Promise<string[]>,
+  lockVersion: (version: string, locked: boolean) =>
+    invoke("version-lock", version, locked) as Promise<void>,
+  // values are masked, the headers are stored in the OS keychain
+  getDownloadHeaders: () =>
+    invoke("download-headers-get") as Promise<Record<string, string>>,
+  setDownloadHeaders: (headers: Record<string, string>) =>
+    invoke("download-headers-set", headers) as Promise<void>,
+  // the secret is write-only, only whether one is stored can be read back
+  hasWebhookSecret: () => invoke("webhook-secret-get") as Promise<boolean>,
+  setWebhookSecret: (secret: string) =>
+    invoke("webhook-secret-set", secret) as Promise<void>,
+  testWebhook: () => invoke("webhook-test") as Promise<void>,
+  getVersionMetadata: (version: string, arch: string) =>
+    invoke("version-metadata", version, arch) as Promise<Nvmd.DownloadMetadata>,
+  verifyInstallation: (version: string) =>
+    invoke("verify-installation", version) as Promise<Nvmd.VerifyResult>,
+  repairInstallation: (args: { id: string; arch: string; version: string }) =>
+    invoke("repair-installation", args) as Promise<Nvmd.VerifyResult>,
+  getSetupPlan: () => invoke("setup-wizard-plan") as Promise<Nvmd.SetupPlan>,
+  runSetupWizard: (args: { id: string; mirror?: string; steps?: Nvmd.SetupStepId[] }) =>
+    invoke("setup-wizard-run", args) as Promise<Nvmd.SetupStepResult[]>,
+  // every installed version, against its recorded and its published checksum
+  refreshVersions: (id: string) =>
+    invoke("versions-refresh", id) as Promise<
+      Nvmd.CommandResult<Nvmd.RefreshedVersion[]>
+    >,
 
-  useNodeVersion: (version: string) => ipcRenderer.invoke("use-version", version),
-  getCurrentVersion: (fetch: boolean = false) => ipcRenderer.invoke("current-version", fetch),
+  useNodeVersion: (version: string) => invoke("use-version", version),
+  getCurrentVersion: (fetch: boolean = false) => invoke("current-version", fetch),
+  getActivationInfo: () => invoke("activation-info") as Promise<Nvmd.ActivationInfo>,
+  // metadata of the IPC commands, for the command palette
+  listCommands: () => invoke("list-commands") as Promise<Nvmd.Command[]>,
+  getAppPaths: () => invoke("app-paths") as Promise<Nvmd.AppPaths>,
+  getAppInfo: () => invoke("app-info") as Promise<Nvmd.AppInfo>,
+  syncShims: () => invoke("shims-sync") as Promise<void>,
+  // bring the installed versions to `directory` before switching the setting to it
+  migrateVersions: (directory: string, mode: Nvmd.MigrateMode, id?: string) =>
+    invoke("versions-migrate", directory, mode, id) as Promise<
+      Nvmd.CommandResult<Nvmd.MigrateResult>
+    >,
+  // after a `conflict`, before reloading the window
+  reloadConfigs: () => invoke("configs-reload") as Promise<void>,
+  // taken before cache cleanups, config migrations and version migrations, newest first
+  getRollbackPoints: () => invoke("rollback-points") as Promise<Nvmd.RollbackPoint[]>,
+  rollbackLastOperation: () =>
+    invoke("rollback-last-operation") as Promise<Nvmd.RollbackResult>,
   onRegistCurVersionChange: (callback: OnCurVersionChange) => {
     onCurVersionChange = callback;
   },
@@ -122,9 +445,63 @@ const electronHandler = {
   onRegistProgress: (onProgressSource: OnProgressCallback) => {
     onProgress = onProgressSource;
   },
+  onRegistBuildOutput: (onBuildOutputSource: OnBuildOutputCallback) => {
+    onBuildOutput = onBuildOutputSource;
+  },
+  // queued → downloading → extracting → verifying → linking → done (or failed/aborted)
+  onRegistInstallEvent: (onInstallEventSource: OnInstallEventCallback | null) => {
+    onInstallEvent = onInstallEventSource;
+  },
 
   uninstallVersion: (version: string, current: boolean = false) =>
-    ipcRenderer.invoke("uninstall-node-version", version, current),
+    invoke("uninstall-node-version", version, current),
+  getSandboxes: () => invoke("sandboxes") as Promise<Nvmd.Sandbox[]>,
+  // installed as `<version>-<name>`, uninstalled like any version; the download progress
+  // comes through `onRegistProgress` under `id`
+  createSandbox: (options: { id: string; version: string; name: string }) =>
+    invoke("sandbox-create", options) as Promise<Nvmd.Sandbox>,
+  // dates of each release line, cached for a day unless `fetch`
+  getReleaseSchedule: (fetch: boolean = false) =>
+    invoke("release-schedule", fetch) as Promise<Nvmd.ReleaseSchedule>,
+
+  // other runtimes, the install progress comes through `onRegistProgress`
+  getRuntimeReleases: (name: Nvmd.RuntimeName, fetch?: boolean) =>
+    invoke("runtime-releases", name, fetch) as Promise<Nvmd.RuntimeRelease[]>,
+  getRuntimeInfo: (name: Nvmd.RuntimeName) =>
+    invoke("runtime-info", name) as Promise<Nvmd.RuntimeInfo>,
+  installRuntime: (args: { id: string; name: Nvmd.RuntimeName; version: string }) =>
+    invoke("runtime-install", args) as Promise<{ version: string; path: string }>,
+  uninstallRuntime: (name: Nvmd.RuntimeName, version: string) =>
+    invoke("runtime-uninstall", name, version) as Promise<void>,
+  setRuntimeDefault: (name: Nvmd.RuntimeName, version: string) =>
+    invoke("runtime-default-set", name, version) as Promise<void>,
+  // an empty version unpins the project
+  setProjectRuntime: (path: string, name: Nvmd.RuntimeName, version: string) =>
+    invoke("project-runtime-set", path, name, version) as Promise<Nvmd.Project[]>,
+  // npm `version` instead of the bundled one, downloaded when needed; empty to unpin
+  pinProjectNpm: (path: string, version: string) =>
+    invoke("project-npm-pin", path, version) as Promise<Nvmd.Project[]>,
+  // mirror and proxy for the installs of the project and its npm, cleared without `network`
+  setProjectNetwork: (path: string, network?: Nvmd.NetworkOverride) =>
+    invoke("project-network-set", path, network) as Promise<Nvmd.Project[]>,
+  // registries over those of the project's group, scope by scope, cleared without `npm`
+  setProjectNpm: (path: string, npm?: Nvmd.NpmRegistries) =>
+    invoke("project-npm-set", path, npm) as Promise<Nvmd.Project[]>,
+  // variables over those of the project's group, an empty map clears them
+  setProjectEnv: (path: string, env?: Record<string, string>) =>
+    invoke("project-env-set", path, env) as Promise<Nvmd.Project[]>,
+  // PEM bundle exported as NODE_EXTRA_CA_CERTS in the project, cleared without `file`
+  setProjectCaFile: (path: string, file?: string) =>
+    invoke("project-ca-set", path, file) as Promise<Nvmd.Project[]>,
+  // left out of bulk switches, moves to the LTS and its group's version changes
+  setProjectFrozen: (path: string, frozen: boolean) =>
+    invoke("project-frozen-set", path, frozen) as Promise<Nvmd.Project[]>,
+
+  // Electron given by its version or detected from the project at `path`
+  getElectronNode: (options: { version?: string; path?: string }) =>
+    invoke("electron-node", options) as Promise<Nvmd.ElectronMapping>,
+  installElectronNode: (args: { id: string; version?: string; path?: string }) =>
+    invoke("electron-node-install", args) as Promise<Nvmd.ElectronMapping>,
 
   getSystemTheme: () => ipcRenderer.sendSync("get-system-theme") as string,
   onRegistThemeCallback: (callback: OnThemeChangedCallback) => {
@@ -141,21 +518,119 @@ const electronHandler = {
     multiple?: boolean;
     project?: boolean;
   }) =>
-    ipcRenderer.invoke("open-folder-selecter", { title, multiple, project }) as Promise<
-      OpenDialogReturnValue & { versions?: string[] }
+    invoke("open-folder-selecter", { title, multiple, project }) as Promise<
+      OpenDialogReturnValue & { versions?: string[]; tracked?: (string | undefined)[] }
     >,
-  getProjects: (load: boolean = false) =>
-    ipcRenderer.invoke("get-projects", load) as Promise<Nvmd.Project[]>,
-  updateProjects: (projects: Nvmd.Project[], path?: string) =>
-    ipcRenderer.invoke("update-projects", projects, path) as Promise<void>,
+  // install the missing versions the groups and projects need, one operation per version
+  installRequired: (args: { id: string; groups?: string[]; paths?: string[] }) =>
+    invoke("install-required", args) as Promise<Nvmd.RequiredInstallResult>,
+  // projects whose `.nvmdrc` disagrees with the stored version
+  detectProjectDrift: (id?: string) =>
+    invoke("project-drift", id) as Promise<Nvmd.ProjectDrift[]>,
+  resolveProjectDrift: (path: string, action: Nvmd.DriftAction) =>
+    invoke("project-drift-resolve", path, action) as Promise<{
+      projects: Nvmd.Project[];
+      groups: Nvmd.Group[];
+    }>,
+  // share the project list, paths missing on this machine are skipped on import
+  exportProjectList: (args: { title: string; format: Nvmd.ProjectListFormat }) =>
+    invoke("projects-export", args) as Promise<{ canceled: boolean; count?: number }>,
+  importProjectList: (args: { title: string }) =>
+    invoke("projects-import", args) as Promise<
+      { canceled: boolean } & Partial<Nvmd.ProjectListImport>
+    >,
+  // `nvmd-lock.json` in `directory`, resolves to its path
+  exportEnvironmentLock: (directory: string) =>
+    invoke("environment-lock-export", directory) as Promise<string>,
+  applyEnvironmentLock: (file: string, id: string) =>
+    invoke("environment-lock-apply", file, id) as Promise<
+      Nvmd.CommandResult<Nvmd.EnvironmentLockApply>
+    >,
+  // node processes started from a project, e.g. dev servers still on the old version
+  getProjectProcesses: (path: string) =>
+    invoke("project-processes", path) as Promise<Nvmd.ProjectProcesses>,
+  restartProjectProcesses: (path: string, action: Nvmd.ProcessAction) =>
+    invoke("project-processes-restart", path, action) as Promise<number>,
+  // switch projects (all of them by default) to the newest LTS, installing it if needed
+  moveProjectsToLts: (args: { id: string; paths?: string[] }) =>
+    invoke("projects-move-to-lts", args) as Promise<Nvmd.BulkVersionResult>,
+  getProjects: (load: boolean = false, sort?: "recent") =>
+    invoke("get-projects", load, sort) as Promise<Nvmd.Project[]>,
+  // the `.nvmdrc` of `path` is removed, `removed` lists it when it was there
+  updateProjects: (projects: Nvmd.Project[], path?: string, options?: { trash?: boolean }) =>
+    invoke("update-projects", projects, path, options) as Promise<
+      Nvmd.CommandResult<{ removed: string[] }>
+    >,
+  relocateProject: (oldPath: string, newPath: string) =>
+    invoke("relocate-project", oldPath, newPath) as Promise<{
+      projects: Nvmd.Project[];
+      groups: Nvmd.Group[];
+    }>,
+  findDuplicateProjects: () =>
+    invoke("project-duplicates") as Promise<Nvmd.ProjectDuplicates[]>,
+  // keeps the first project of each folder, with the groups of all
+  mergeDuplicateProjects: () =>
+    invoke("projects-merge") as Promise<{
+      merged: Nvmd.ProjectDuplicates[];
+      projects: Nvmd.Project[];
+      groups: Nvmd.Group[];
+    }>,
+  revealProject: (path: string) => invoke("project-reveal", path) as Promise<void>,
+  openProjectInEditor: (path: string) =>
+    invoke("project-open-editor", path) as Promise<void>,
+  openProjectInTerminal: (path: string) =>
+    invoke("project-open-terminal", path) as Promise<void>,
+  copyProjectBinPath: (path: string) =>
+    invoke("project-copy-bin-path", path) as Promise<string>,
   syncProjectVersion: (path: string, version: string) =>
-    ipcRenderer.invoke("sync-project-version", path, version) as Promise<404 | 200>,
+    invoke("sync-project-version", path, version) as Promise<404 | 200>,
+  getProjectVersion: (path: string) =>
+    invoke("project-version", path) as Promise<string>,
+  getProjectDetails: (path: string) =>
+    invoke("project-details", path) as Promise<Nvmd.ProjectDetails>,
+  // download the Yarn of `packageManager` with Corepack and shim it
+  provisionProjectYarn: (path: string) =>
+    invoke("project-yarn-provision", path) as Promise<Nvmd.YarnStatus>,
+  // version -> `x64`, `arm64`... of the installed build
+  getVersionArchs: () => invoke("version-archs") as Promise<Record<string, string>>,
+  // x64 builds run under Rosetta or the Windows on ARM emulation
+  setVersionEmulation: (options: { id: string; version: string; enabled: boolean }) =>
+    invoke("version-emulation-set", options) as Promise<Nvmd.VerifyResult>,
+  setProjectEmulation: (options: { id: string; path: string; enabled: boolean }) =>
+    invoke("project-emulation-set", options) as Promise<Nvmd.Project[]>,
+  getSwitchHistory: () => invoke("switch-history") as Promise<Nvmd.VersionSwitch[]>,
+  // resolves with the undone switch, undefined when there was none
+  undoSwitch: () => invoke("switch-undo") as Promise<Nvmd.VersionSwitch | undefined>,
+  // the script to source in the shell profile
+  getCompletionScript: (shell: Nvmd.CompletionShell) =>
+    invoke("shell-completion", shell) as Promise<string>,
+  getPromptInfo: (cwd: string) =>
+    invoke("prompt-info", cwd) as Promise<Nvmd.PromptInfo>,
+  // the starship or oh-my-posh config showing the version in effect
+  getPromptSegment: (tool: Nvmd.PromptTool) =>
+    invoke("prompt-segment", tool) as Promise<string>,
+  queryAuditLog: (filter?: Nvmd.AuditFilter) =>
+    invoke("audit-log-query", filter) as Promise<Nvmd.AuditEntry[]>,
+  getProjectScripts: (path: string) =>
+    invoke("project-scripts", path) as Promise<Nvmd.ProjectScript[]>,
+  // the output of the script is streamed to `onRegistScriptOutput`, cancel `id` to stop it
+  runProjectScript: (args: { id: string; path: string; name: string }) =>
+    invoke("project-script-run", args) as Promise<Nvmd.ScriptResult>,
+  onRegistScriptOutput: (callback: OnScriptOutputCallback | null) => {
+    onScriptOutput = callback;
+  },
+  // `20`, `lts/iron`, `v20.11.1`... resolved against the installed versions first
+  resolveVersionSpec: (spec: string) =>
+    invoke("version-spec-resolve", spec) as Promise<Nvmd.ResolvedVersionSpec>,
+  // vulnerabilities reported by the npm of the project's version, needs a package-lock.json
+  auditProject: (path: string, id?: string) =>
+    invoke("project-audit", path, id) as Promise<Nvmd.ProjectAudit>,
   updateProjectsWhenRemoveGroup: (
     projectsPath: string[],
     groupName: string = "",
     version: string = ""
   ) =>
-    ipcRenderer.invoke("update-project-remove-group", projectsPath, groupName, version) as Promise<
+    invoke("update-project-remove-group", projectsPath, groupName, version) as Promise<
       Nvmd.Project[]
     >,
   onRegistProjectUpdate: (callback: OnProjectUpdate | null) => {
@@ -168,20 +643,43 @@ const electronHandler = {
 
   // Configration Export
   onConfigrationExport: (args: Nvmd.ConfigrationExport) =>
-    ipcRenderer.invoke("configration-export", args) as Promise<string>,
+    invoke("configration-export", args) as Promise<string>,
   // Configration import
   onConfigrationImport: (args: { sync: boolean; title: string }) =>
-    ipcRenderer.invoke("configration-import", args) as Promise<
-      OpenDialogReturnValue & { color?: string; mirrors?: string; setting?: Nvmd.Setting }
+    invoke("configration-import", args) as Promise<
+      Nvmd.CommandResult<
+        Pick<OpenDialogReturnValue, "canceled"> & {
+          color?: string;
+          mirrors?: string;
+          setting?: Nvmd.Setting;
+        }
+      >
     >,
 
+  // * Config backups
+  getConfigBackups: () =>
+    invoke("config-backups-get") as Promise<{
+      backups: Nvmd.ConfigBackup[];
+      recovered: Nvmd.ConfigBackup[];
+    }>,
+  restoreConfigBackup: (name: string, id: string) =>
+    invoke("config-backup-restore", name, id) as Promise<void>,
+
   // * Groups
   getGroups: (load: boolean = false) =>
-    ipcRenderer.invoke("group-get", load) as Promise<Nvmd.Group[]>,
-  onGroupCreate: (group: Nvmd.Group) => ipcRenderer.invoke("group-create", group),
-  onGroupUpdate: (groups: Nvmd.Group[]) => ipcRenderer.invoke("group-update", groups),
+    invoke("group-get", load) as Promise<Nvmd.Group[]>,
+  onGroupCreate: (group: Nvmd.Group) => invoke("group-create", group),
+  onGroupUpdate: (groups: Nvmd.Group[]) => invoke("group-update", groups),
   onGroupUpdateVersion: (group: Nvmd.Group, version: string) =>
-    ipcRenderer.invoke("group-update-version", group, version) as Promise<Nvmd.Group[]>
+    invoke("group-update-version", group, version) as Promise<Nvmd.Group[]>,
+  setGroupNetwork: (name: string, network?: Nvmd.NetworkOverride) =>
+    invoke("group-network-set", name, network) as Promise<Nvmd.Group[]>,
+  // inherited by the projects of the group, an empty map clears them
+  setGroupEnv: (name: string, env?: Record<string, string>) =>
+    invoke("group-env-set", name, env) as Promise<Nvmd.Group[]>,
+  // registry and `@scope` registries of the group's projects, cleared without `npm`
+  setGroupNpm: (name: string, npm?: Nvmd.NpmRegistries) =>
+    invoke("group-npm-set", name, npm) as Promise<Nvmd.Group[]>
 };
 
 contextBridge.exposeInMainWorld("Context", electronHandler);
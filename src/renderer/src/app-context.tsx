@@ -19,9 +19,10 @@ type AppContextType = {
   directory: string;
   mirror: string;
   proxy: Nvmd.Proxy;
+  pauseOnMetered: boolean;
   setColor: (color: string) => void;
   getMessage: I18nFn;
-  onUpdateSetting: (setting: Nvmd.Setting) => Promise<void>;
+  onUpdateSetting: (setting: Nvmd.Setting) => Promise<Nvmd.CommandWarning[]>;
 };
 
 type StateType = {
@@ -32,6 +33,8 @@ type StateType = {
   sysTheme: Themes; // system real theme
   mirror: string;
   proxy: Nvmd.Proxy;
+  // downloads wait while the connection is metered
+  pauseOnMetered: boolean;
   messages: I18n.Message;
 };
 
@@ -57,8 +60,16 @@ export type I18nFn = (
 export const AppProviderContext = createContext<AppContextType | null>(null);
 
 export function AppProvider({ defaultColor = "orange", storageKey = "nvmd-ui-theme", children }) {
-  const { locale, theme, closer, directory, mirror, proxy, localeMessages } =
-    window.Context.getSettingData();
+  const {
+    locale,
+    theme,
+    closer,
+    directory,
+    mirror,
+    proxy,
+    pauseOnMetered = false,
+    localeMessages
+  } = window.Context.getSettingData();
 
   const [color, setColor] = useState<string>(
     () => localStorage.getItem(storageKey) || defaultColor
@@ -85,6 +96,7 @@ export function AppProvider({ defaultColor = "orange", storageKey = "nvmd-ui-the
       sysTheme: window.Context.getSystemTheme() as Themes,
       mirror,
       proxy,
+      pauseOnMetered,
       messages: localeMessages
     }
   );
@@ -121,7 +133,7 @@ export function AppProvider({ defaultColor = "orange", storageKey = "nvmd-ui-the
   // update setting
   const onUpdateSetting = useMemo(
     () => async (setting: Nvmd.Setting) => {
-      await window.Context.updateSettingData(setting);
+      const warnings = await window.Context.updateSettingData(setting);
 
       if (setting.theme !== state.theme) {
         // for theme changed
@@ -142,10 +154,38 @@ export function AppProvider({ defaultColor = "orange", storageKey = "nvmd-ui-the
         type: Actions.UpdateSetting,
         payload: { ...state, ...setting, messages }
       });
+      return warnings;
     },
-    [state.locale, state.theme, state.directory, state.mirror, state.proxy]
+    [
+      state.locale,
+      state.theme,
+      state.directory,
+      state.mirror,
+      state.proxy,
+      state.pauseOnMetered
+    ]
   );
 
+  // settings.json edited outside of the app
+  useEffect(() => {
+    window.Context.onRegistSettingsChanged(({ localeMessages, ...setting }) => {
+      if (setting.theme !== state.theme) {
+        applyTheme(
+          setting.theme === Themes.System
+            ? (window.Context.getSystemTheme() as Themes)
+            : setting.theme
+        );
+      }
+
+      dispatch({
+        type: Actions.UpdateSetting,
+        payload: { ...setting, messages: localeMessages }
+      });
+    });
+
+    return () => window.Context.onRegistSettingsChanged(null);
+  }, [state.theme]);
+
   const setColorHandler = useMemo(
     () => (newColor: string) => {
       localStorage.setItem(storageKey, newColor);
@@ -217,6 +257,7 @@ export function AppProvider({ defaultColor = "orange", storageKey = "nvmd-ui-the
         directory: state.directory,
         mirror: state.mirror,
         proxy: state.proxy,
+        pauseOnMetered: state.pauseOnMetered,
         color,
         setColor: setColorHandler,
         getMessage,
@@ -4,12 +4,16 @@ import { RouterProvider } from "react-router-dom";
 import { router } from "./routes";
 import { AppProvider } from "./app-context";
 import { Toaster, TooltipProvider } from "@renderer/components/ui";
+import { QuickSwitch } from "./pages/quick";
+
+// The tray's quick switch window loads the same page with a `#quick` hash.
+const isQuickWindow = window.location.hash === "#quick";
 
 export default function App() {
   return (
     <AppProvider>
       <TooltipProvider delayDuration={200}>
-        <RouterProvider router={router} />
+        {isQuickWindow ? <QuickSwitch /> : <RouterProvider router={router} />}
       </TooltipProvider>
       <Toaster />
     </AppProvider>
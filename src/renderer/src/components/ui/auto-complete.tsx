@@ -19,7 +19,13 @@ type AutoCompleteProps = {
   onChange?: (value: string) => void;
 };
 
-const DefMirrors = ["https://nodejs.org/dist", "https://npmmirror.com/mirrors/node"];
+// the mirror presets of the main process
+const DefMirrors = [
+  "https://nodejs.org/dist",
+  "https://npmmirror.com/mirrors/node",
+  "https://mirrors.tuna.tsinghua.edu.cn/nodejs-release",
+  "https://mirrors.ustc.edu.cn/node"
+];
 
 const AutoComplete: React.FC<AutoCompleteProps> = memo(
   ({ value: valueProp = "", options = [], placeholder = "", shouldFilter = true, onChange }) => {
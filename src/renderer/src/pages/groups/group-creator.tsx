@@ -34,7 +34,7 @@ import { z } from "zod";
 import { useForm } from "react-hook-form";
 import { zodResolver } from "@hookform/resolvers/zod";
 import { useI18n } from "@renderer/app-context";
-import { toast } from "sonner";
+import { toastError } from "@renderer/util";
 
 type Props = {
   projects?: Nvmd.Project[];
@@ -90,11 +90,7 @@ export const GroupCreator: React.FC<Props> = ({
       await onSubmitProp?.(values);
       setOpen(false);
     } catch (err) {
-      toast.error(
-        err.message
-          ? err.message.split("Error invoking remote method 'group-create': ").slice(-1)
-          : "Something went wrong"
-      );
+      toastError(err, i18n);
     } finally {
       setLoading(false);
     }
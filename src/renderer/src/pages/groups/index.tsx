@@ -31,7 +31,9 @@ import {
 import { DndProvider } from "react-dnd";
 import { HTML5Backend } from "react-dnd-html5-backend";
 import { toast } from "sonner";
-import { MagnifyingGlassIcon, ReloadIcon, TrashIcon } from "@radix-ui/react-icons";
+import { toastError } from "@renderer/util";
+import { DownloadIcon, MagnifyingGlassIcon, ReloadIcon, TrashIcon } from "@radix-ui/react-icons";
+import { v4 as uuidv4 } from "uuid";
 
 import { useAppContext, useI18n } from "@renderer/app-context";
 import { cn } from "@renderer/lib/utils";
@@ -130,13 +132,7 @@ export const Component: React.FC = () => {
                   setGroups(newGroups);
                   toast.success(i18n("Restart-Terminal", [`v${newVersion}`]));
                 } catch (err) {
-                  toast.error(
-                    err.message
-                      ? err.message
-                          .split("Error invoking remote method 'group-update-version': ")
-                          .slice(-1)
-                      : "Something went wrong"
-                  );
+                  toastError(err, i18n);
                 }
               }}
             >
@@ -296,6 +292,36 @@ export const Component: React.FC = () => {
     });
   };
 
+  // install whatever the listed groups need, each version reports its stages in the toast
+  const onInstallRequired = async (names: string[]) => {
+    const id = uuidv4();
+    const toastId = toast.loading(i18n("Install-Required"));
+    window.Context.onRegistInstallEvent(({ id: installId, version, stage, pausedFor }) => {
+      installId.startsWith(`${id}:`) &&
+        toast.loading(i18n("Install-Required"), {
+          id: toastId,
+          description: `v${version} ${pausedFor ? i18n(`Install-Paused-${pausedFor}`) : stage}`
+        });
+    });
+
+    setLoading(true);
+    try {
+      const { installed, failed } = await window.Context.installRequired({ id, groups: names });
+      setInstalledVersions(await window.Context.getInstalledNodeVersions());
+      toast.success(i18n("Install-Required-Success", [String(installed.length)]), {
+        id: toastId,
+        description: failed.length
+          ? failed.map(({ version, message }) => `v${version}: ${message}`).join("\n")
+          : void 0
+      });
+    } catch (err) {
+      toastError(err, i18n, { id: toastId });
+    } finally {
+      window.Context.onRegistInstallEvent(null);
+      setLoading(false);
+    }
+  };
+
   const onPageReload = async () => {
     setLoading(true);
     try {
@@ -345,6 +371,21 @@ export const Component: React.FC = () => {
                 >
                   {i18n("Page-Reload")}
                 </Button>
+                <Button
+                  size="sm"
+                  className="h-7 text-sm"
+                  loading={loading}
+                  disabled={!groups.length}
+                  title={i18n("Install-Required-Tip")}
+                  icon={<DownloadIcon />}
+                  onClick={() =>
+                    onInstallRequired(
+                      table.getFilteredRowModel().rows.map(({ original }) => original.name)
+                    )
+                  }
+                >
+                  {i18n("Install-Required")}
+                </Button>
                 <GroupCreator
                   projects={projects}
                   groups={groups}
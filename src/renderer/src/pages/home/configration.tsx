@@ -28,6 +28,7 @@ import { Share1Icon } from "@radix-ui/react-icons";
 
 import { z } from "zod";
 import { toast } from "sonner";
+import { toastError } from "@renderer/util";
 import { useForm } from "react-hook-form";
 import { useAppContext, useI18n } from "@renderer/app-context";
 import { zodResolver } from "@hookform/resolvers/zod";
@@ -172,11 +173,7 @@ const ConfigrationExport = forwardRef<Exporter, {}>(({}, ref) => {
 
       setOpen(false);
     } catch (err) {
-      toast.error(
-        err.message
-          ? err.message.split("Error invoking remote method 'configration-export': ").slice(-1)
-          : "Something went wrong"
-      );
+      toastError(err, i18n);
     } finally {
       setLoading(false);
     }
@@ -273,7 +270,10 @@ const ConfigrationImport = forwardRef<Importer, {}>(({}, ref) => {
 
   const onConfigrationImport = async (sync: boolean) => {
     try {
-      const { canceled, color, mirrors, setting } = await window.Context.onConfigrationImport({
+      const {
+        data: { canceled, color, mirrors, setting },
+        warnings
+      } = await window.Context.onConfigrationImport({
         sync,
         title: i18n("File-Select")
       });
@@ -281,17 +281,19 @@ const ConfigrationImport = forwardRef<Importer, {}>(({}, ref) => {
       if (canceled) return;
 
       toast.success(i18n("Configration-import-success"), { duration: 5000 });
+      warnings.length &&
+        toast.warning(i18n("Completed-with-warnings", [String(warnings.length)]), {
+          description: warnings
+            .map(({ message, subject }) => (subject ? `${subject}: ${message}` : message))
+            .join("\n")
+        });
 
       color && setColor(color);
       mirrors && localStorage.setItem("nvmd-mirror", mirrors);
       setting && onUpdateSetting(setting);
       setOpen(false);
     } catch (err) {
-      toast.error(
-        err.message
-          ? err.message.split("Error invoking remote method 'configration-import': ").slice(-1)
-          : "Something went wrong"
-      );
+      toastError(err, i18n);
     }
   };
 
@@ -14,6 +14,7 @@ import { ThemeCustomizer } from "@renderer/components/theme-customizer";
 import { RocketIcon, HeartIcon, FaceIcon, MinusIcon, Cross2Icon } from "@radix-ui/react-icons";
 import { Configration } from "./configration";
 import { Updater } from "./updater";
+import { ScanNotices } from "./scan-notices";
 import { Setting } from "./setting";
 import { Tip } from "./tip";
 import { toast } from "sonner";
@@ -469,6 +470,7 @@ const Home: React.FC = () => {
       <main className="px-6 py-4 flex-1 overflow-hidden">
         <Outlet />
       </main>
+      <ScanNotices />
       <Steps
         enabled={enabled}
         initialStep={0}
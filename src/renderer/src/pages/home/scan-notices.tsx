@@ -0,0 +1,83 @@
+import { useEffect } from "react";
+import { toast } from "sonner";
+import { v4 as uuidv4 } from "uuid";
+import { toastError } from "@renderer/util";
+
+import { useI18n } from "@src/renderer/src/app-context";
+
+// What the scan of the install directory did on launch, each with its follow-up.
+export const ScanNotices: React.FC = () => {
+  const i18n = useI18n();
+
+  useEffect(() => {
+    const onRepair = async (entry: Nvmd.QuarantinedEntry) => {
+      try {
+        await window.Context.repairQuarantined({ id: uuidv4(), entry: entry.entry });
+        toast.success(i18n("Quarantine-Repaired", { version: `${entry.version}` }));
+      } catch (err) {
+        toastError(err, i18n);
+      }
+    };
+    const onDelete = async (entry: Nvmd.QuarantinedEntry) => {
+      try {
+        await window.Context.deleteQuarantined(entry.entry);
+      } catch (err) {
+        toastError(err, i18n);
+      }
+    };
+
+    const onRetry = async ({ version }: Nvmd.InterruptedInstall) => {
+      try {
+        await window.Context.retryInstall({ id: uuidv4(), version });
+        toast.success(i18n("Install-Retried", { version }));
+      } catch (err) {
+        toastError(err, i18n);
+      }
+    };
+
+    window.Context.onRegistInterrupted((installs) => {
+      installs.forEach((install) =>
+        toast(i18n("Install-Interrupted", { version: install.version }), {
+          description: i18n("Install-Interrupted-Tip"),
+          duration: Infinity,
+          action: { label: i18n("Install-Retry"), onClick: () => onRetry(install) },
+          cancel: {
+            label: i18n("Install-Dismiss"),
+            onClick: () => window.Context.dismissInstall(install.version)
+          }
+        })
+      );
+    });
+
+    window.Context.onRegistAdopted(({ adopted, failed }) => {
+      adopted.length &&
+        toast.success(
+          i18n("Adopted", { versions: adopted.map(({ version }) => `v${version}`).join(", ") })
+        );
+      failed.forEach(({ name, message }) =>
+        toast.warning(i18n("Adopt-Failed", { name }), { description: message, duration: Infinity })
+      );
+    });
+
+    window.Context.onRegistQuarantined((entries) => {
+      entries.forEach((entry) =>
+        toast.warning(i18n("Quarantined", { name: entry.name }), {
+          description: i18n(`Quarantine-${entry.reason}`),
+          duration: Infinity,
+          ...(entry.version && {
+            action: { label: i18n("Quarantine-Reinstall"), onClick: () => onRepair(entry) }
+          }),
+          cancel: { label: i18n("Quarantine-Delete"), onClick: () => onDelete(entry) }
+        })
+      );
+    });
+
+    return () => {
+      window.Context.onRegistInterrupted(null);
+      window.Context.onRegistAdopted(null);
+      window.Context.onRegistQuarantined(null);
+    };
+  }, []);
+
+  return null;
+};
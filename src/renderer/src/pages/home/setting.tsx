@@ -1,5 +1,12 @@
 import { memo, useState } from "react";
 import {
+  AlertDialog,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
   DefMirrors,
   AutoComplete,
   AutoCompleteProps,
@@ -39,11 +46,12 @@ import {
 } from "@renderer/components/ui";
 
 import { z } from "zod";
+import { toast } from "sonner";
 import { useForm } from "react-hook-form";
 import { zodResolver } from "@hookform/resolvers/zod";
 import { useAppContext, useI18n } from "@src/renderer/src/app-context";
 import { Closer, Themes } from "@src/types";
-import { compareObject } from "../../util";
+import { compareObject, toastError } from "../../util";
 
 type Options = NonNullable<AutoCompleteProps["options"]>;
 
@@ -58,8 +66,10 @@ const formSchema = z.object({
   proxy: z
     .object({
       enabled: z.boolean().default(false),
+      protocol: z.enum(["http", "socks5", "socks5h"]).optional(),
       ip: z.string().ip({ message: "Invalid ip" }).optional().or(z.literal("")),
-      port: z.string().regex(/^\d+$/, "Invalid port").optional().or(z.literal(""))
+      port: z.string().regex(/^\d+$/, "Invalid port").optional().or(z.literal("")),
+      noProxy: z.string().optional()
     })
     .superRefine((val, ctx) => {
       if (val.enabled && (val.ip === "" || val.ip === void 0)) {
@@ -77,19 +87,23 @@ const formSchema = z.object({
           message: "Invalid port"
         });
       }
-    })
+    }),
+  pauseOnMetered: z.boolean()
 });
 
 export const Setting: React.FC<Props> = memo(() => {
   const [open, setOpen] = useState<boolean>(false);
   const [loading, setLoading] = useState<boolean>(false);
+  // values waiting for the user to choose what happens to the installed versions
+  const [pending, setPending] = useState<z.infer<typeof formSchema>>();
 
   const [options, setOptions] = useState<Options>(() => {
     const optStr = localStorage.getItem("nvmd-mirror");
     return optStr ? optStr.split("__") : [];
   });
 
-  const { locale, theme, closer, directory, mirror, proxy, onUpdateSetting } = useAppContext();
+  const { locale, theme, closer, directory, mirror, proxy, pauseOnMetered, onUpdateSetting } =
+    useAppContext();
 
   const form = useForm<z.infer<typeof formSchema>>({
     resolver: zodResolver(formSchema),
@@ -99,7 +113,8 @@ export const Setting: React.FC<Props> = memo(() => {
       closer,
       directory,
       mirror,
-      proxy
+      proxy,
+      pauseOnMetered
     }
   });
 
@@ -113,7 +128,8 @@ export const Setting: React.FC<Props> = memo(() => {
       closer: newCloser,
       directory: newDirectory,
       mirror: newMirror,
-      proxy: newProxy
+      proxy: newProxy,
+      pauseOnMetered: newPauseOnMetered
     } = values;
     if (
       locale === newLocale &&
@@ -121,13 +137,64 @@ export const Setting: React.FC<Props> = memo(() => {
       closer === newCloser &&
       directory === newDirectory &&
       mirror === newMirror &&
-      compareObject(proxy, newProxy)
+      compareObject(proxy, newProxy) &&
+      pauseOnMetered === newPauseOnMetered
     ) {
       setLoading(false);
       setOpen(false);
       return;
     }
 
+    if (directory !== newDirectory) {
+      setPending(values);
+      return;
+    }
+
+    await save(values);
+  };
+
+  // the versions installed in the previous directory are moved or linked first,
+  // otherwise they'd no longer be listed
+  const onMigrate = async (mode?: Nvmd.MigrateMode) => {
+    const values = pending!;
+    setPending(undefined);
+    if (mode) {
+      try {
+        const {
+          data: { migrated, current },
+          warnings
+        } = await window.Context.migrateVersions(values.directory, mode);
+        toast.success(i18n("Migrate-success", [String(migrated.length)]));
+        warnings.length &&
+          toast.warning(i18n("Completed-with-warnings", [String(warnings.length)]), {
+            description: warnings
+              .map(({ message, subject }) => (subject ? `${subject}: ${message}` : message))
+              .join("\n")
+          });
+        current &&
+          !current.valid &&
+          toast.warning(i18n("Migrate-current-invalid", [current.version]));
+      } catch (err) {
+        setLoading(false);
+        toastError(err, i18n);
+        return;
+      }
+    }
+
+    await save(values);
+  };
+
+  const save = async (values: z.infer<typeof formSchema>) => {
+    const {
+      locale: newLocale,
+      theme: newTheme,
+      closer: newCloser,
+      directory: newDirectory,
+      mirror: newMirror,
+      proxy: newProxy,
+      pauseOnMetered: newPauseOnMetered
+    } = values;
+
     // for custom mirror url
     // if not exesit need to cache
     if (![...DefMirrors, ...options].includes(newMirror)) {
@@ -143,14 +210,21 @@ export const Setting: React.FC<Props> = memo(() => {
     }
 
     try {
-      await onUpdateSetting({
+      const warnings = await onUpdateSetting({
         locale: newLocale,
         theme: newTheme,
         closer: newCloser,
         directory: newDirectory,
         mirror: newMirror,
-        proxy: newProxy
+        proxy: newProxy,
+        pauseOnMetered: newPauseOnMetered
       });
+      warnings.length &&
+        toast.warning(i18n("Completed-with-warnings", [String(warnings.length)]), {
+          description: warnings
+            .map(({ message, subject }) => (subject ? `${subject}: ${message}` : message))
+            .join("\n")
+        });
     } finally {
       setLoading(false);
       setOpen(false);
@@ -167,7 +241,8 @@ export const Setting: React.FC<Props> = memo(() => {
           closer,
           directory,
           mirror,
-          proxy
+          proxy,
+          pauseOnMetered
         });
         setOpen(open);
       }}
@@ -378,6 +453,32 @@ export const Setting: React.FC<Props> = memo(() => {
                         )}
                       />
                       <div className="flex items-center gap-2">
+                        <FormField
+                          control={form.control}
+                          name="proxy.protocol"
+                          render={({ field }) => (
+                            <FormItem>
+                              <Select
+                                disabled={!enabled}
+                                onValueChange={field.onChange}
+                                defaultValue={field.value || "http"}
+                              >
+                                <FormControl>
+                                  <SelectTrigger className="w-24 h-8">
+                                    <SelectValue />
+                                  </SelectTrigger>
+                                </FormControl>
+                                <SelectContent>
+                                  {["http", "socks5", "socks5h"].map((protocol) => (
+                                    <SelectItem key={protocol} value={protocol}>
+                                      {protocol}
+                                    </SelectItem>
+                                  ))}
+                                </SelectContent>
+                              </Select>
+                            </FormItem>
+                          )}
+                        />
                         <FormField
                           control={form.control}
                           name="proxy.ip"
@@ -408,11 +509,46 @@ export const Setting: React.FC<Props> = memo(() => {
                           )}
                         />
                       </div>
+                      <FormField
+                        control={form.control}
+                        name="proxy.noProxy"
+                        render={({ field }) => (
+                          <FormItem>
+                            <FormControl>
+                              <Input
+                                className="h-8"
+                                disabled={!enabled}
+                                placeholder={i18n("No-Proxy-Tip")}
+                                {...field}
+                              />
+                            </FormControl>
+                          </FormItem>
+                        )}
+                      />
                     </div>
                   </FormItem>
                 );
               }}
             />
+            <FormField
+              control={form.control}
+              name="pauseOnMetered"
+              render={({ field }) => (
+                <FormItem>
+                  <FormLabel className="text-muted-foreground">
+                    {i18n("Pause-On-Metered")}
+                  </FormLabel>
+                  <div className="flex items-center gap-3">
+                    <FormControl>
+                      <Switch checked={field.value} onCheckedChange={field.onChange} />
+                    </FormControl>
+                    <FormDescription className="!mt-0">
+                      {i18n(field.value ? "Enabled" : "Disabled")}
+                    </FormDescription>
+                  </div>
+                </FormItem>
+              )}
+            />
           </Form>
         </div>
         <SheetFooter>
@@ -428,6 +564,31 @@ export const Setting: React.FC<Props> = memo(() => {
           </Button>
         </SheetFooter>
       </SheetContent>
+      <AlertDialog
+        open={!!pending}
+        onOpenChange={(open) => {
+          if (open) return;
+          setPending(undefined);
+          setLoading(false);
+        }}
+      >
+        <AlertDialogContent className="top-1/3">
+          <AlertDialogHeader>
+            <AlertDialogTitle>{i18n("Migrate-versions")}</AlertDialogTitle>
+            <AlertDialogDescription>{i18n("Migrate-versions-tip")}</AlertDialogDescription>
+          </AlertDialogHeader>
+          <AlertDialogFooter>
+            <AlertDialogCancel>{i18n("Cancel")}</AlertDialogCancel>
+            <Button variant="tag" onClick={() => onMigrate()}>
+              {i18n("Migrate-skip")}
+            </Button>
+            <Button variant="tag" onClick={() => onMigrate("link")}>
+              {i18n("Migrate-link")}
+            </Button>
+            <Button onClick={() => onMigrate("move")}>{i18n("Migrate-move")}</Button>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
     </Sheet>
   );
 });
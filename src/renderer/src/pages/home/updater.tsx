@@ -39,6 +39,7 @@ export const Updater: React.FC = () => {
   const [pop, setPop] = useState<boolean>(false);
   const [loading, setLoading] = useState<boolean>(false);
   const [progress, setProgress] = useState<ProgressInfo>();
+  const [download, setDownload] = useState<Nvmd.UpdateDownload>();
 
   const i18n = useI18n();
   const updateInfo = useRef<UpdateInfo>();
@@ -83,7 +84,7 @@ export const Updater: React.FC = () => {
       case ModalType.Check: {
         setOpen({ visible: false, type: ModalType.Check });
         try {
-          await window.Context.comfirmUpdate();
+          setDownload(await window.Context.comfirmUpdate());
           // download completed
           setOpen({ visible: true, type: ModalType.Complete });
         } catch (err) {
@@ -167,7 +168,19 @@ export const Updater: React.FC = () => {
                   </p>
                 </div>
               ) : (
-                <p>{i18n("Upgrade-Tip")}</p>
+                <>
+                  <p>{i18n("Upgrade-Tip")}</p>
+                  {download?.differential ? (
+                    <p className="mt-2 text-xs">
+                      {i18n("Delta-Update", {
+                        transferred: (download.transferred / 1024 / 1024).toFixed(1),
+                        size: (download.size / 1024 / 1024).toFixed(1)
+                      })}
+                    </p>
+                  ) : download?.fallback ? (
+                    <p className="mt-2 text-xs">{i18n("Delta-Update-Failed")}</p>
+                  ) : null}
+                </>
               )}
             </AlertDialogDescription>
           </AlertDialogHeader>
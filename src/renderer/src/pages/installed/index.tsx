@@ -1,6 +1,7 @@
 import { useEffect, useMemo, useState } from "react";
 import { useLoaderData } from "react-router-dom";
 import { toast } from "sonner";
+import { toastError } from "@renderer/util";
 
 import {
   Button,
@@ -23,6 +24,8 @@ import {
   CheckCircledIcon,
   ChevronDownIcon,
   CrossCircledIcon,
+  LockClosedIcon,
+  LockOpen1Icon,
   MinusCircledIcon,
   ReloadIcon
 } from "@radix-ui/react-icons";
@@ -55,6 +58,8 @@ export const Component: React.FC = () => {
   );
   const [installedVersions, setInstalledVersions] = useState<string[]>(() => allInstalledVersions);
   const [loading, setLoading] = useState<boolean>(false);
+  const [locked, setLocked] = useState<string[]>([]);
+  const [usage, setUsage] = useState<Record<string, Nvmd.VersionUsage>>({});
 
   const { directory, locale } = useAppContext();
   const i18n = useI18n();
@@ -66,6 +71,16 @@ export const Component: React.FC = () => {
     });
   }, []);
 
+  useEffect(() => {
+    window.Context.getLockedVersions().then(setLocked);
+  }, []);
+
+  useEffect(() => {
+    window.Context.getVersionUsage().then((usages) =>
+      setUsage(Object.fromEntries(usages.map((usage) => [usage.version, usage])))
+    );
+  }, [installedVersions.length]);
+
   useEffect(() => {
     const fetcher = async () => {
       const iVersions = await window.Context.getInstalledNodeVersions(true);
@@ -76,6 +91,16 @@ export const Component: React.FC = () => {
     fetcher();
   }, [directory]);
 
+  // the versions of the startup cache were scanned again
+  useEffect(() => {
+    window.Context.onRegistInstalledVersionsUpdated((iVersions) => {
+      setVersions(allVersions.filter(({ version }) => iVersions.includes(version.slice(1))));
+      setInstalledVersions(iVersions);
+    });
+
+    return () => window.Context.onRegistInstalledVersionsUpdated(null);
+  }, []);
+
   const columns: ColumnDef<Nvmd.Version>[] = useMemo(() => {
     const { version: latest } = versions[0] || { version: "" };
     return [
@@ -123,6 +148,9 @@ export const Component: React.FC = () => {
               ) : latest === version ? (
                 <span className="text-foreground-foreground">({i18n("latest")})</span>
               ) : null}
+              {locked.includes(version.slice(1)) ? (
+                <LockClosedIcon className="text-muted-foreground" aria-label={i18n("Locked")} />
+              ) : null}
             </div>
           );
         }
@@ -148,6 +176,30 @@ export const Component: React.FC = () => {
         ),
         cell: ({ row }) => dayjs(row.original.date).format("ll")
       },
+      {
+        id: "usage",
+        header: i18n("Used-By"),
+        enableSorting: false,
+        cell: ({ row }) => {
+          const { projects = [], groups = [] } = usage[row.original.version.slice(1)] || {};
+          if (!projects.length && !groups.length) return "-";
+
+          return (
+            <Tooltip>
+              <TooltipTrigger asChild>
+                <span className="cursor-default">
+                  {i18n("Used-By-Projects", [String(projects.length)])}
+                </span>
+              </TooltipTrigger>
+              <TooltipPortal>
+                <TooltipContent className="max-w-96">
+                  {[...projects.map(({ name }) => name), ...groups].join(", ")}
+                </TooltipContent>
+              </TooltipPortal>
+            </Tooltip>
+          );
+        }
+      },
       {
         accessorKey: "status",
         header: i18n("Status"),
@@ -189,6 +241,7 @@ export const Component: React.FC = () => {
         enableSorting: false,
         cell: ({ row }) => {
           const { version } = row.original;
+          const isLocked = locked.includes(version.slice(1));
           return (
             <DropdownMenu>
               <DropdownMenuTrigger asChild>
@@ -214,8 +267,20 @@ export const Component: React.FC = () => {
                   <CheckCircledIcon />
                   {i18n("Apply")}
                 </DropdownMenuItem>
+                <DropdownMenuItem
+                  className="flex gap-2 cursor-pointer"
+                  onSelect={async () => {
+                    await window.Context.lockVersion(version.slice(1), !isLocked);
+                    setLocked(await window.Context.getLockedVersions());
+                  }}
+                >
+                  {isLocked ? <LockOpen1Icon /> : <LockClosedIcon />}
+                  {isLocked ? i18n("Unlock") : i18n("Lock")}
+                </DropdownMenuItem>
                 <DropdownMenuItem
                   className="flex gap-2 text-red-600 focus:text-red-500 cursor-pointer"
+                  disabled={isLocked}
+                  title={isLocked ? i18n("Locked-Tip") : undefined}
                   onSelect={async () => {
                     try {
                       await window.Context.uninstallVersion(
@@ -234,15 +299,7 @@ export const Component: React.FC = () => {
                       );
                       toast.success(i18n("Tip-Uninstall", [version]));
                     } catch (err) {
-                      toast.error(
-                        err.message
-                          ? err.message
-                              .split(
-                                "Error: Error invoking remote method 'uninstall-node-version': "
-                              )
-                              .slice(-1)
-                          : "Something went wrong"
-                      );
+                      toastError(err, i18n);
                     }
                   }}
                 >
@@ -255,7 +312,7 @@ export const Component: React.FC = () => {
         }
       }
     ];
-  }, [locale, current, installedVersions.length]);
+  }, [locale, current, installedVersions.length, locked, usage]);
 
   const statuses = useMemo(
     () => [
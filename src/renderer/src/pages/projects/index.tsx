@@ -14,6 +14,10 @@ import {
   Button,
   DataDndTable,
   DataTableToolbar,
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuTrigger,
   LabelCopyable,
   Select,
   SelectContent,
@@ -26,7 +30,17 @@ import {
 import { DndProvider } from "react-dnd";
 import { HTML5Backend } from "react-dnd-html5-backend";
 import { toast } from "sonner";
-import { FilePlusIcon, ReloadIcon, TrashIcon } from "@radix-ui/react-icons";
+import { toastError } from "@renderer/util";
+import {
+  CubeIcon,
+  DoubleArrowUpIcon,
+  ExclamationTriangleIcon,
+  FilePlusIcon,
+  ReloadIcon,
+  TrashIcon,
+  UpdateIcon
+} from "@radix-ui/react-icons";
+import { v4 as uuidv4 } from "uuid";
 
 import { useAppContext, useI18n } from "@src/renderer/src/app-context";
 import { cn } from "@renderer/lib/utils";
@@ -53,6 +67,8 @@ export const Component: React.FC = () => {
   const [projects, setProjects] = useState<Nvmd.Project[]>(() => allProjects);
   const [groups, setGroups] = useState<Nvmd.Group[]>(() => allGroups);
   const [loading, setLoading] = useState<boolean>(false);
+  // projects whose `.nvmdrc` was changed outside of the app, by path
+  const [drifts, setDrifts] = useState<Record<string, Nvmd.ProjectDrift>>({});
 
   const i18n = useI18n();
   const { directory, locale } = useAppContext();
@@ -69,6 +85,55 @@ export const Component: React.FC = () => {
     };
   }, []);
 
+  useEffect(() => {
+    window.Context.detectProjectDrift().then((drifts) =>
+      setDrifts(Object.fromEntries(drifts.map((drift) => [drift.path, drift])))
+    );
+  }, [projects]);
+
+  // dev servers started before the switch keep running the previous version
+  const onOfferRestart = async ({ name, path }: Nvmd.Project) => {
+    const { scripts, processes } = await window.Context.getProjectProcesses(path);
+    if (!processes.length) return;
+
+    const description = processes.map(({ pid, command }) => `${pid}: ${command}`).join("\n");
+    // processes started outside the app can't be restarted as they were
+    if (!scripts.length) {
+      toast(i18n("Processes-Running", { count: `${processes.length}`, name }), {
+        description,
+        duration: Infinity
+      });
+      return;
+    }
+
+    const onAction = async (action: Nvmd.ProcessAction) => {
+      try {
+        const count = await window.Context.restartProjectProcesses(path, action);
+        toast.success(
+          i18n(action === "restart" ? "Processes-Restarted" : "Processes-Stopped", [`${count}`])
+        );
+      } catch (err) {
+        toastError(err, i18n);
+      }
+    };
+    toast(i18n("Scripts-Running", { scripts: scripts.join(", "), name }), {
+      description,
+      duration: Infinity,
+      action: { label: i18n("Processes-Restart"), onClick: () => onAction("restart") },
+      cancel: { label: i18n("Processes-Stop"), onClick: () => onAction("stop") }
+    });
+  };
+
+  const onResolveDrift = async (path: string, action: Nvmd.DriftAction) => {
+    try {
+      const { projects, groups } = await window.Context.resolveProjectDrift(path, action);
+      setProjects(projects);
+      setGroups(groups);
+    } catch (err) {
+      toastError(err, i18n);
+    }
+  };
+
   useEffect(() => {
     const fetcher = async () => {
       const iVersions = await window.Context.getInstalledNodeVersions(true);
@@ -118,6 +183,7 @@ export const Component: React.FC = () => {
           const { version, path } = row.original;
           return (
             <Select
+              key={version}
               defaultValue={version}
               onValueChange={async (newVersion) => {
                 // fromGroup: whether to switch from group, need to remove
@@ -171,8 +237,9 @@ export const Component: React.FC = () => {
                   code === 200
                     ? toast.success(i18n("Restart-Terminal", [`v${targetVersion}`]))
                     : toast.error(`Project not found, please check it`);
+                  code === 200 && row.original.restartProcesses && onOfferRestart(row.original);
                 } catch (err) {
-                  toast.error("Something went wrong");
+                  toastError(err, i18n);
                 }
               }}
             >
@@ -205,67 +272,134 @@ export const Component: React.FC = () => {
         header: i18n("Operation"),
         maxSize: 120,
         cell: ({ row }) => {
-          const { name, path, version } = row.original;
+          const { name, path, version, syncEngines, restartProcesses } = row.original;
+          const drift = drifts[path];
           return (
-            <AlertDialog>
-              <AlertDialogTrigger asChild>
-                <Button size="sm" variant="tag">
-                  <TrashIcon />
-                  {i18n("Remove")}
-                </Button>
-              </AlertDialogTrigger>
-              <AlertDialogContent>
-                <AlertDialogHeader>
-                  <AlertDialogTitle>{name}</AlertDialogTitle>
-                  <AlertDialogDescription>{i18n("Project-Delete")}</AlertDialogDescription>
-                </AlertDialogHeader>
-                <AlertDialogFooter>
-                  <AlertDialogCancel>{i18n("Cancel")}</AlertDialogCancel>
-                  <AlertDialogAction
-                    onClick={async () => {
-                      const [newProjects, newGroups] = await Promise.all([
-                        (async () => {
-                          const newProjects = projects.filter(
-                            ({ path: source }) => source !== path
-                          );
-                          await window.Context.updateProjects(newProjects, path);
-                          return newProjects;
-                        })(),
-                        (async () => {
-                          const newGroups = [...groups];
-                          let needUpdate: boolean = false;
-                          newGroups.forEach((group) => {
-                            if (group.name === version) {
-                              needUpdate = true;
-                              const projects = [...group.projects];
-                              group.projects = projects.filter((proPath) => proPath !== path);
-                            }
-                          });
-                          needUpdate && (await window.Context.onGroupUpdate(newGroups));
-                          return needUpdate ? newGroups : undefined;
-                        })()
-                      ]);
-                      setProjects(newProjects);
-                      newGroups && setGroups(newGroups);
-                    }}
-                  >
-                    {i18n("OK")}
-                  </AlertDialogAction>
-                </AlertDialogFooter>
-              </AlertDialogContent>
-            </AlertDialog>
+            <div className="flex items-center gap-1">
+              {drift ? (
+                <DropdownMenu>
+                  <DropdownMenuTrigger asChild>
+                    <Button
+                      size="sm"
+                      variant="tag"
+                      className="text-amber-500 border-amber-500"
+                      title={i18n("Drift-Tip", {
+                        actual: drift.actual || "-",
+                        expected: drift.expected
+                      })}
+                      icon={<ExclamationTriangleIcon />}
+                    />
+                  </DropdownMenuTrigger>
+                  <DropdownMenuContent className="min-w-8">
+                    <DropdownMenuItem
+                      className="cursor-pointer"
+                      disabled={!drift.actual}
+                      onSelect={() => onResolveDrift(path, "adopt")}
+                    >
+                      {i18n("Drift-Adopt", [drift.actual || "-"])}
+                    </DropdownMenuItem>
+                    <DropdownMenuItem
+                      className="cursor-pointer"
+                      onSelect={() => onResolveDrift(path, "rewrite")}
+                    >
+                      {i18n("Drift-Rewrite", [drift.expected])}
+                    </DropdownMenuItem>
+                  </DropdownMenuContent>
+                </DropdownMenu>
+              ) : null}
+              <Button
+                size="sm"
+                variant={syncEngines ? "default" : "tag"}
+                title={i18n("Sync-Engines-Tip")}
+                icon={<CubeIcon />}
+                onClick={async () => {
+                  const newProjects = projects.map((project) =>
+                    project.path === path ? { ...project, syncEngines: !syncEngines } : project
+                  );
+                  await window.Context.updateProjects(newProjects);
+                  setProjects(newProjects);
+                }}
+              />
+              <Button
+                size="sm"
+                variant={restartProcesses ? "default" : "tag"}
+                title={i18n("Restart-Processes-Tip")}
+                icon={<UpdateIcon />}
+                onClick={async () => {
+                  const newProjects = projects.map((project) =>
+                    project.path === path
+                      ? { ...project, restartProcesses: !restartProcesses }
+                      : project
+                  );
+                  await window.Context.updateProjects(newProjects);
+                  setProjects(newProjects);
+                }}
+              />
+              <AlertDialog>
+                <AlertDialogTrigger asChild>
+                  <Button size="sm" variant="tag">
+                    <TrashIcon />
+                    {i18n("Remove")}
+                  </Button>
+                </AlertDialogTrigger>
+                <AlertDialogContent>
+                  <AlertDialogHeader>
+                    <AlertDialogTitle>{name}</AlertDialogTitle>
+                    <AlertDialogDescription>{i18n("Project-Delete")}</AlertDialogDescription>
+                  </AlertDialogHeader>
+                  <AlertDialogFooter>
+                    <AlertDialogCancel>{i18n("Cancel")}</AlertDialogCancel>
+                    <AlertDialogAction
+                      onClick={async () => {
+                        const [newProjects, newGroups] = await Promise.all([
+                          (async () => {
+                            const newProjects = projects.filter(
+                              ({ path: source }) => source !== path
+                            );
+                            const { warnings } = await window.Context.updateProjects(
+                              newProjects,
+                              path
+                            );
+                            warnings.forEach(({ message }) => toast.warning(message));
+                            return newProjects;
+                          })(),
+                          (async () => {
+                            const newGroups = [...groups];
+                            let needUpdate: boolean = false;
+                            newGroups.forEach((group) => {
+                              if (group.name === version) {
+                                needUpdate = true;
+                                const projects = [...group.projects];
+                                group.projects = projects.filter((proPath) => proPath !== path);
+                              }
+                            });
+                            needUpdate && (await window.Context.onGroupUpdate(newGroups));
+                            return needUpdate ? newGroups : undefined;
+                          })()
+                        ]);
+                        setProjects(newProjects);
+                        newGroups && setGroups(newGroups);
+                      }}
+                    >
+                      {i18n("OK")}
+                    </AlertDialogAction>
+                  </AlertDialogFooter>
+                </AlertDialogContent>
+              </AlertDialog>
+            </div>
           );
         }
       }
     ],
-    [locale, projects, installedVersions.length, groups.length]
+    [locale, projects, installedVersions.length, groups.length, drifts]
   );
 
   const onAddProject = async () => {
     const {
       canceled,
       filePaths,
-      versions = []
+      versions = [],
+      tracked = []
     } = await window.Context.openFolderSelecter({
       title: i18n("Project-Select"),
       multiple: true,
@@ -280,7 +414,7 @@ export const Component: React.FC = () => {
         name = pathArr[pathArr.length - 1],
         now = new Date().toISOString();
 
-      if (!projects.find(({ path: source }) => source === path)) {
+      if (!tracked[index] && !projects.find(({ path: source }) => source === path)) {
         addProjects.push({
           name,
           path,
@@ -329,6 +463,39 @@ export const Component: React.FC = () => {
     }
   };
 
+  // the projects listed by the current filter are moved
+  const onMoveToLts = async (paths: string[]) => {
+    setLoading(true);
+    try {
+      const { version, installed, projects, groups, missing } =
+        await window.Context.moveProjectsToLts({ id: uuidv4(), paths });
+
+      const [allProjects, allGroups, installedVersions] = await Promise.all([
+        window.Context.getProjects(),
+        window.Context.getGroups(),
+        window.Context.getInstalledNodeVersions(installed)
+      ]);
+      setProjects(allProjects);
+      setGroups(allGroups);
+      setInstalledVersions(installedVersions);
+
+      toast.success(
+        i18n("Move-To-LTS-Success", {
+          count: String(projects.length),
+          groups: String(groups.length),
+          version: `v${version}`
+        }),
+        {
+          description: missing.length ? `${i18n("Project-Missing")}: ${missing.join(", ")}` : void 0
+        }
+      );
+    } catch (err) {
+      toastError(err, i18n);
+    } finally {
+      setLoading(false);
+    }
+  };
+
   return (
     <DndProvider backend={HTML5Backend}>
       <div className="h-full flex flex-col space-y-2">
@@ -349,6 +516,21 @@ export const Component: React.FC = () => {
                 >
                   {i18n("Page-Reload")}
                 </Button>
+                <Button
+                  size="sm"
+                  className="h-7 text-sm"
+                  loading={loading}
+                  disabled={!projects.length}
+                  title={i18n("Move-To-LTS-Tip")}
+                  icon={<DoubleArrowUpIcon />}
+                  onClick={() =>
+                    onMoveToLts(
+                      table.getFilteredRowModel().rows.map(({ original }) => original.path)
+                    )
+                  }
+                >
+                  {i18n("Move-To-LTS")}
+                </Button>
                 <Button
                   size="sm"
                   className="h-7 text-sm"
@@ -0,0 +1,113 @@
+import { useEffect, useState } from "react";
+import {
+  Button,
+  Label,
+  RadioGroup,
+  RadioGroupItem,
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue
+} from "@renderer/components/ui";
+import { CheckIcon, ExternalLinkIcon } from "@radix-ui/react-icons";
+import { toast } from "sonner";
+
+import { useAppContext, useI18n } from "@src/renderer/src/app-context";
+import { Themes } from "@src/types";
+
+// Content of the quick switch window opened from the tray: the global version and
+// the settings that are changed the most, nothing heavier is loaded.
+export const QuickSwitch: React.FC = () => {
+  const [versions, setVersions] = useState<string[]>([]);
+  const [current, setCurrent] = useState<string>("");
+
+  const { locale, theme, closer, directory, mirror, proxy, onUpdateSetting } = useAppContext();
+  const i18n = useI18n();
+
+  const load = async () => {
+    const [versions, current] = await Promise.all([
+      window.Context.getInstalledNodeVersions(),
+      window.Context.getCurrentVersion(true)
+    ]);
+    setVersions(versions);
+    setCurrent(current);
+  };
+
+  useEffect(() => {
+    load();
+    // the window is kept when hidden, refresh whenever it's shown again
+    window.addEventListener("focus", load);
+    window.Context.onRegistCurVersionChange(setCurrent);
+    return () => window.removeEventListener("focus", load);
+  }, []);
+
+  const onSwitch = async (version: string) => {
+    await window.Context.useNodeVersion(version);
+    setCurrent(version);
+    toast.success(i18n("Restart-Terminal", [`v${version}`]));
+  };
+
+  const onSettingChange = (data: Partial<Nvmd.Setting>) =>
+    onUpdateSetting({ locale, theme, closer, directory, mirror, proxy, ...data } as Nvmd.Setting);
+
+  return (
+    <div className="h-screen flex flex-col gap-3 p-3 select-none">
+      <div className="flex items-center justify-between">
+        <Label className="font-medium">NVM-Desktop</Label>
+        <Button
+          size="sm"
+          variant="ghost"
+          title={i18n("Open-Main-Window")}
+          icon={<ExternalLinkIcon />}
+          onClick={() => window.Context.openMainWindow()}
+        />
+      </div>
+      <div className="flex-1 flex flex-col gap-1 overflow-hidden">
+        <Label className="text-muted-foreground">{i18n("Global-Default")}</Label>
+        <div className="flex-1 overflow-y-auto">
+          {versions.map((version) => (
+            <Button
+              key={version}
+              size="sm"
+              variant="ghost"
+              className="w-full justify-between"
+              onClick={() => onSwitch(version)}
+            >
+              {`v${version}`}
+              {current === version ? <CheckIcon className="text-primary" /> : null}
+            </Button>
+          ))}
+        </div>
+      </div>
+      <div className="flex flex-col gap-2">
+        <Label className="text-muted-foreground">{i18n("Themes")}</Label>
+        <RadioGroup
+          value={theme}
+          onValueChange={(theme) => onSettingChange({ theme: theme as Themes })}
+          className="flex space-x-1"
+        >
+          {[
+            [Themes.System, "System-Default"],
+            [Themes.Light, "Light"],
+            [Themes.Dark, "Dark"]
+          ].map(([value, label]) => (
+            <Label key={value} className="flex items-center gap-1 font-normal">
+              <RadioGroupItem value={value} />
+              {i18n(label)}
+            </Label>
+          ))}
+        </RadioGroup>
+        <Select value={locale} onValueChange={(locale) => onSettingChange({ locale })}>
+          <SelectTrigger className="h-7">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            <SelectItem value="zh-CN">简体中文</SelectItem>
+            <SelectItem value="en">English</SelectItem>
+          </SelectContent>
+        </Select>
+      </div>
+    </div>
+  );
+};
@@ -33,7 +33,7 @@ import { InfoModal } from "./modal";
 
 import dayjs from "dayjs";
 import localizedFormat from "dayjs/plugin/localizedFormat";
-import { checkSupportive } from "@renderer/util";
+import { checkSupportive, toastError } from "@renderer/util";
 import { useI18n, useAppContext } from "@src/renderer/src/app-context";
 
 import type { Ref as InfoRef } from "./modal";
@@ -120,6 +120,19 @@ const Versions: React.FC = () => {
     fetcher();
   }, [directory]);
 
+  // the lists of the startup cache were refreshed in the background
+  useEffect(() => {
+    window.Context.onRegistInstalledVersionsUpdated(setInstalledVersions);
+    window.Context.onRegistVersionsUpdated(async () =>
+      setVersions(await window.Context.getAllNodeVersions())
+    );
+
+    return () => {
+      window.Context.onRegistInstalledVersionsUpdated(null);
+      window.Context.onRegistVersionsUpdated(null);
+    };
+  }, []);
+
   const columns: ColumnDef<Nvmd.Version>[] = useMemo(() => {
     const { version: latest } = versions[0] || { version: "" };
     return [
@@ -270,18 +283,22 @@ const Versions: React.FC = () => {
                   <DropdownMenuItem
                     className="flex gap-2 text-red-600 focus:text-red-500 cursor-pointer"
                     onSelect={async () => {
-                      await window.Context.uninstallVersion(
-                        version.slice(1),
-                        version.includes(current)
-                      );
-
-                      const [currentVersion, versions] = await Promise.all([
-                        window.Context.getCurrentVersion(),
-                        window.Context.getInstalledNodeVersions(true)
-                      ]);
-                      setCurrent(currentVersion);
-                      setInstalledVersions(versions);
-                      toast.success(i18n("Tip-Uninstall", [version]));
+                      try {
+                        await window.Context.uninstallVersion(
+                          version.slice(1),
+                          version.includes(current)
+                        );
+
+                        const [currentVersion, versions] = await Promise.all([
+                          window.Context.getCurrentVersion(),
+                          window.Context.getInstalledNodeVersions(true)
+                        ]);
+                        setCurrent(currentVersion);
+                        setInstalledVersions(versions);
+                        toast.success(i18n("Tip-Uninstall", [version]));
+                      } catch (err) {
+                        toastError(err, i18n);
+                      }
                     }}
                   >
                     <CrossCircledIcon />
@@ -403,11 +420,7 @@ const Versions: React.FC = () => {
 
       toast.success(i18n("Refresh-successful"));
     } catch (err) {
-      toast.error(
-        err.message
-          ? err.message.split("Error invoking remote method 'all-node-versions': ").slice(-1)
-          : "Something went wrong"
-      );
+      toastError(err, i18n);
     } finally {
       setLoading(false);
     }
@@ -18,6 +18,7 @@ import {
   SelectValue
 } from "@renderer/components/ui";
 import { toast } from "sonner";
+import { toastError } from "@renderer/util";
 
 import { v4 as uuidv4 } from "uuid";
 import { useI18n } from "@src/renderer/src/app-context";
@@ -31,17 +32,27 @@ type Props = {
 };
 
 const archs = ["arm64", "x64", "x86"];
+// lines of the build output kept when building from source
+const MAX_OUTPUT_LINES = 200;
 
 export const InfoModal = forwardRef<Ref, Props>(({ onRefrresh }, ref) => {
   const [open, setOpen] = useState<boolean>(false);
   const [loading, setLoading] = useState<boolean>(false);
   const [path, setPath] = useState<string>();
   const [progress, setProgress] = useState<Nvmd.ProgressData>();
+  // why the download waits for the network, it resumes on its own
+  const [pausedFor, setPausedFor] = useState<Nvmd.NetworkPauseReason>();
+  // `configure` and `make` (`vcbuild`) output of a build from source
+  const [output, setOutput] = useState<string[]>([]);
+  const [metadata, setMetadata] = useState<Nvmd.DownloadMetadata>();
+  // folder to install this version in instead of the install directory
+  const [location, setLocation] = useState<string>();
 
   const record = useRef<Nvmd.Version>();
   const arch = useRef<HTMLSpanElement>(null);
   const archOption = useRef<string[]>(archs);
   const uuid = useRef<string>();
+  const outputRef = useRef<HTMLPreElement>(null);
 
   const i18n = useI18n();
 
@@ -58,8 +69,21 @@ export const InfoModal = forwardRef<Ref, Props>(({ onRefrresh }, ref) => {
       if (!uuid.current || uuid.current !== id) return;
       setProgress(progress);
     });
+
+    window.Context.onRegistBuildOutput((id, line) => {
+      if (!uuid.current || uuid.current !== id) return;
+      setOutput((output) => [...output, line].slice(-MAX_OUTPUT_LINES));
+    });
   }, []);
 
+  // follow the build output unless it was scrolled up
+  useEffect(() => {
+    const pre = outputRef.current;
+    if (pre && pre.scrollHeight - pre.scrollTop - pre.clientHeight < 40) {
+      pre.scrollTop = pre.scrollHeight;
+    }
+  }, [output]);
+
   const onShow: Ref["show"] = (data) => {
     const { files } = data,
       platform = window.Context.platform;
@@ -74,7 +98,25 @@ export const InfoModal = forwardRef<Ref, Props>(({ onRefrresh }, ref) => {
     });
     record.current = data;
     archOption.current = newArchs;
+    setLocation(undefined);
     setOpen(true);
+    loadMetadata(systemArch);
+  };
+
+  // Size of the download before committing to it, e.g. on a metered connection
+  const loadMetadata = async (arch: string) => {
+    setMetadata(undefined);
+    try {
+      setMetadata(await window.Context.getVersionMetadata(record.current!.version.slice(1), arch));
+    } catch {}
+  };
+
+  const onSelectLocation = async () => {
+    const { canceled, filePaths } = await window.Context.openFolderSelecter({
+      title: i18n("Install-Location")
+    });
+    if (canceled || !filePaths.length) return;
+    setLocation(filePaths[0]);
   };
 
   const onStart = async () => {
@@ -82,27 +124,44 @@ export const InfoModal = forwardRef<Ref, Props>(({ onRefrresh }, ref) => {
     setLoading(true);
     setPath(undefined);
     setProgress(undefined);
+    setPausedFor(undefined);
+    setOutput([]);
+    window.Context.onRegistInstallEvent(({ id, pausedFor }) => {
+      uuid.current === id && setPausedFor(pausedFor);
+    });
     try {
-      const { path } = await window.Context.getNode({
+      const { path, warning } = await window.Context.getNode({
         id: uuid.current!,
         arch: arch.current?.innerText || systemArch,
-        version: record.current!.version.slice(1)
+        version: record.current!.version.slice(1),
+        location
       });
+      warning && toast.warning(warning);
       setPath(path);
     } catch (err) {
-      if (!err.message.includes("This operation was aborted")) {
-        toast.error(
-          err.message
-            ? err.message.split("Error invoking remote method 'get-node':").slice(-1)
-            : "Something went wrong"
-        );
+      if (window.Context.describeError(err).kind !== "aborted") {
+        toastError(err, i18n);
         setPath("error");
       }
     } finally {
+      window.Context.onRegistInstallEvent(null);
+      setPausedFor(undefined);
       setLoading(false);
     }
   };
 
+  const getProgressText = ({
+    phase,
+    entries,
+    totalEntries,
+    transferred,
+    total
+  }: Nvmd.ProgressData) => {
+    if (phase !== "extracting" || entries === void 0) return `${transferred} / ${total} B`;
+
+    return `${i18n("Extracting")} ${totalEntries ? `${entries} / ${totalEntries}` : entries}`;
+  };
+
   const onAbort = async () => {
     await window.Context.controllerAbort(uuid.current!);
     uuid.current = undefined;
@@ -126,16 +185,43 @@ export const InfoModal = forwardRef<Ref, Props>(({ onRefrresh }, ref) => {
                   <Label className="text-foreground">{record.current?.npm}</Label>
                 </p>
               </div>
+              {metadata?.size ? (
+                <p className="space-x-2">
+                  <Label>{i18n("Download-Size")}</Label>
+                  <Label
+                    className="text-foreground"
+                    title={metadata.checksum ? `sha256: ${metadata.checksum}` : undefined}
+                  >
+                    {`${(metadata.size / 1024 / 1024).toFixed(1)} MB`}
+                  </Label>
+                </p>
+              ) : null}
+              {location ? (
+                <p className="space-x-2">
+                  <Label>{i18n("Install-Location")}</Label>
+                  <Label className="text-foreground">{location}</Label>
+                </p>
+              ) : null}
               <div className="flex items-center h-5">
-                {progress ? (
+                {pausedFor ? (
+                  <p className="flex-1">{i18n(`Install-Paused-${pausedFor}`)}</p>
+                ) : progress ? (
                   <div className="flex flex-1 items-center space-x-2">
                     <Progress value={progress.percent * 100} className="max-w-60" />
-                    <Label>{`${progress.transferred} / ${progress.total} B`}</Label>
+                    <Label>{getProgressText(progress)}</Label>
                   </div>
                 ) : (
                   <p className="flex-1">{i18n("Install-Tip")}</p>
                 )}
               </div>
+              {output.length ? (
+                <pre
+                  ref={outputRef}
+                  className="max-h-40 overflow-auto rounded-md bg-muted p-2 text-xs text-foreground"
+                >
+                  {output.join("\n")}
+                </pre>
+              ) : null}
               {path && path !== "error" ? (
                 <div className="flex items-center gap-2">
                   <Label>Installation Directory</Label>
@@ -147,7 +233,7 @@ export const InfoModal = forwardRef<Ref, Props>(({ onRefrresh }, ref) => {
         </AlertDialogHeader>
         <AlertDialogFooter className="sm:justify-between">
           <p className="flex items-center space-x-2">
-            <Select disabled={loading} defaultValue={systemArch}>
+            <Select disabled={loading} defaultValue={systemArch} onValueChange={loadMetadata}>
               <SelectTrigger className="w-24 h-6">
                 <SelectValue ref={arch} />
               </SelectTrigger>
@@ -161,6 +247,15 @@ export const InfoModal = forwardRef<Ref, Props>(({ onRefrresh }, ref) => {
                 </SelectGroup>
               </SelectContent>
             </Select>
+            <Button
+              variant="tag"
+              size="sm"
+              disabled={loading || !!path}
+              title={i18n("Install-Location-Tip")}
+              onClick={onSelectLocation}
+            >
+              {i18n("Install-Location")}
+            </Button>
           </p>
           <div className="flex items-center space-x-2">
             {path && path !== "error" ? null : loading ? (
@@ -188,6 +283,7 @@ export const InfoModal = forwardRef<Ref, Props>(({ onRefrresh }, ref) => {
                     uuid.current = undefined;
                     setPath(undefined);
                     setProgress(undefined);
+                    setOutput([]);
                   }, 0);
                 }}
               >
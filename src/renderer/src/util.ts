@@ -1,6 +1,9 @@
 import semver from "semver";
+import { toast } from "sonner";
 import { Themes } from "@src/types";
 
+import type { I18nFn } from "./app-context";
+
 export function applyTheme(theme: Themes) {
   const root = window.document.documentElement;
 
@@ -40,3 +43,26 @@ export function compareObject(obj1: Obj, obj2: Obj) {
   }
   return ret;
 }
+
+// Message of a command's rejection for a toast, localized from its `kind`. Failures
+// without a more precise kind keep the message of the command.
+export function getErrorMessage(err: unknown, i18n: I18nFn) {
+  const { kind, message } = window.Context.describeError(err);
+  return kind === "failed" ? message || i18n("Error-failed") : i18n(`Error-${kind}`);
+}
+
+// Toast the rejection of a command. On a `conflict` another program saved the config in
+// between, the toast offers to read it again and reload the window.
+export function toastError(err: unknown, i18n: I18nFn, options?: { id?: string | number }) {
+  if (window.Context.describeError(err).kind !== "conflict") {
+    return toast.error(getErrorMessage(err, i18n), options);
+  }
+  return toast.error(getErrorMessage(err, i18n), {
+    ...options,
+    duration: Infinity,
+    action: {
+      label: i18n("Reload"),
+      onClick: () => window.Context.reloadConfigs().finally(() => window.location.reload())
+    }
+  });
+}